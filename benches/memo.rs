@@ -0,0 +1,102 @@
+//  MEMO.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:40:36
+//  Last edited:
+//    18 Oct 2026, 21:40:36
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Benchmarks packrat memoisation (`#[parse(memo)]`) on a grammar that
+//!   backtracks between variants sharing their whole prefix, which takes
+//!   exponential time without it.
+//!
+//!   Run with `cargo bench --features parser`.
+//
+
+use std::time::{Duration, Instant};
+
+use ast_toolkit_2::parser::Parse;
+
+
+/***** GRAMMARS *****/
+/// The grammar without memoisation.
+mod plain {
+    use ast_toolkit_2::parser::chumsky::text;
+    use ast_toolkit_2::parser::{Parse, Span};
+
+    #[derive(Clone, Debug, Eq, Parse, PartialEq)]
+    pub enum Atom {
+        Paren(#[parse(delim("(", ")"))] Box<Term>),
+        Var(#[parse(with = text::ident)] String),
+    }
+
+    #[derive(Clone, Debug, Eq, Parse, PartialEq)]
+    pub enum Term {
+        Call(Atom, #[parse(punct = "!")] Span),
+        Plain(Atom),
+    }
+}
+
+/// The same grammar, but with memoisation.
+mod memo {
+    use ast_toolkit_2::parser::chumsky::text;
+    use ast_toolkit_2::parser::{Parse, Span};
+
+    #[derive(Clone, Debug, Eq, Parse, PartialEq)]
+    #[parse(memo)]
+    pub enum Atom {
+        Paren(#[parse(delim("(", ")"))] Box<Term>),
+        Var(#[parse(with = text::ident)] String),
+    }
+
+    #[derive(Clone, Debug, Eq, Parse, PartialEq)]
+    pub enum Term {
+        Call(Atom, #[parse(punct = "!")] Span),
+        Plain(Atom),
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Times how long parsing some text takes.
+///
+/// # Arguments
+/// - `text`: The text to parse.
+///
+/// # Returns
+/// The parsed node and the fastest of a few runs.
+fn time<T: Parse>(text: &str) -> (T, Duration) {
+    let mut best: Option<(T, Duration)> = None;
+    for _ in 0..3 {
+        let start: Instant = Instant::now();
+        let node: T = T::parse_str("<bench>", text).unwrap_or_else(|errs| panic!("Failed to parse benchmark input: {errs:?}"));
+        let elapsed: Duration = start.elapsed();
+        if best.as_ref().is_none_or(|(_, best)| elapsed < *best) {
+            best = Some((node, elapsed));
+        }
+    }
+    best.unwrap_or_else(|| unreachable!())
+}
+
+
+
+
+
+/***** ENTRYPOINT *****/
+fn main() {
+    // Every `(` makes `Term` try `Call` before `Plain`, both parsing the same `Atom`
+    println!("{:>5} {:>14} {:>14}", "depth", "plain", "memo");
+    for depth in (2..=18).step_by(2) {
+        let text: String = format!("{}x{}", "(".repeat(depth), ")".repeat(depth));
+        let (plain, plain_time): (plain::Term, Duration) = time(&text);
+        let (memo, memo_time): (memo::Term, Duration) = time(&text);
+        assert_eq!(format!("{plain:?}"), format!("{memo:?}"));
+        println!("{depth:>5} {:>14?} {:>14?}", plain_time, memo_time);
+    }
+}
@@ -4,7 +4,7 @@
 //  Created:
 //    06 Mar 2025, 10:53:08
 //  Last edited:
-//    06 Mar 2025, 11:01:09
+//    18 Oct 2026, 23:52:17
 //  Auto updated?
 //    Yes
 //
@@ -13,8 +13,12 @@
 //
 
 // Define the libaries
+#[cfg(feature = "parser")]
+mod parser;
 #[cfg(feature = "railroad")]
 mod railroad;
+#[cfg(feature = "report")]
+mod report;
 
 // Imports
 #[allow(unused_imports)]
@@ -22,6 +26,439 @@ use proc_macro::TokenStream;
 
 
 /***** MACROS *****/
+/// Derive macro to automatically implement [`Parse`] on an AST node.
+///
+/// Structs are parsed as the sequence of their fields, each parsed with the `Parse`-implementation
+/// of its type and with whitespace before it skipped. Enums are parsed as a choice between their
+/// variants, which are tried in order and again parsed as sequences.
+///
+/// The generated parser is built once per thread, such that nodes may contain themselves (e.g.,
+/// through a `Box<Self>`). Note that left-recursive nodes still recurse forever.
+///
+/// # Expressions
+/// Variants of enums can be marked as prefix, infix or postfix operators instead. Then, the other
+/// variants are the operands, and the enum is parsed by precedence (see `parser::pratt`). This
+/// also implements `Pratt`, which can be used to draw the expression's railroad diagram and
+/// precedence table under the `railroad`-feature. For example:
+/// ```
+/// # use ast_toolkit_2::parser::Parse;
+/// # use ast_toolkit_2::parser::chumsky::text;
+/// #[derive(Parse)]
+/// enum Expr {
+///     #[parse(infix, punct = "+", prec = 1)]
+///     Add(Box<Expr>, Box<Expr>),
+///     #[parse(infix, punct = "^", prec = 2, assoc = right)]
+///     Pow(Box<Expr>, Box<Expr>),
+///     #[parse(prefix, punct = "-", prec = 3)]
+///     Neg(Box<Expr>),
+///     Paren(#[parse(delim("(", ")"))] Box<Expr>),
+///     Var(#[parse(with = text::ident)] String),
+/// }
+///
+/// let Ok(Expr::Add(_, rhs)) = Expr::parse_str("<example>", "a + -b ^ c") else { panic!() };
+/// assert!(matches!(*rhs, Expr::Pow(..)));
+/// ```
+///
+/// # Recovery
+/// To find more than one syntax error per parse (see `Parse::parse_str_recovery()`), nodes can
+/// recover from them by skipping input. This produces their error node, which is the variant
+/// marked `#[parse(error)]` (or a manual `Recover`-implementation). For example:
+/// ```
+/// # use ast_toolkit_2::parser::{Parse, Span};
+/// # use ast_toolkit_2::parser::chumsky::text;
+/// # #[derive(Parse)]
+/// # enum Expr {
+/// #     Var(#[parse(with = text::ident)] String),
+/// # }
+/// #[derive(Parse)]
+/// #[parse(sync = ";", sync_before = "}")]
+/// enum Stmt {
+///     #[parse(keyword = "let")]
+///     Let(#[parse(with = text::ident)] String, #[parse(punct = "=")] Span, Expr, #[parse(punct = ";")] Span),
+///     #[parse(error)]
+///     Error(#[parse(span)] Span),
+/// }
+///
+/// #[derive(Parse)]
+/// struct Block(#[parse(delim("{", "}"), recover)] Vec<Stmt>);
+///
+/// let (block, errs) = Block::parse_str_recovery("<example>", "{ let x = ; let y = z; }");
+/// assert!(matches!(block.unwrap().0.as_slice(), [Stmt::Error(_), Stmt::Let(..)]));
+/// assert_eq!(errs.len(), 1);
+/// ```
+///
+/// # Tokens
+/// By default, nodes are parsed from characters. Alternatively, they can be parsed from the tokens
+/// produced by a [`Lex`](macro@Lex)-enum with `#[parse(input = ...)]`. Then, keywords and
+/// punctuation match the token with that literal text (given with `#[token("...")]`), and
+/// whitespace is left to the lexer. For example:
+/// ```
+/// # use ast_toolkit_2::parser::{Error, Lex, Parse, Span};
+/// # use ast_toolkit_2::parser::chumsky::prelude::*;
+/// # #[derive(Clone, Debug, Eq, Hash, Lex, PartialEq)]
+/// # #[lex(skip = r"\s+")]
+/// # enum Token {
+/// #     #[token("let")]
+/// #     Let,
+/// #     #[token("=")]
+/// #     Eq,
+/// #     #[token(";")]
+/// #     Semi,
+/// #     #[regex("[a-z]+")]
+/// #     Ident(String),
+/// # }
+/// # #[derive(Parse)]
+/// # #[parse(input = Token)]
+/// # struct Expr(#[parse(with = ident)] String);
+/// # #[allow(clippy::result_large_err)]
+/// fn ident() -> impl Parser<Token, String, Error = Error<Token>> + Clone {
+///     select! { Token::Ident(name) => name }
+/// }
+///
+/// #[derive(Parse)]
+/// #[parse(input = Token)]
+/// enum Stmt {
+///     #[parse(keyword = "let")]
+///     Let(#[parse(with = ident)] String, #[parse(punct = "=")] Span, Expr, #[parse(punct = ";")] Span),
+/// }
+///
+/// let Ok(Stmt::Let(name, _, Expr(value), semi)) = Stmt::parse_str("<example>", "let x =\n  y;") else { panic!() };
+/// assert_eq!((name.as_str(), value.as_str(), semi), ("x", "y", Span::new("<example>", 11..12)));
+/// ```
+/// All nodes parsed together must use the same input. Recovery is only supported on characters.
+/// Keywords and punctuation that aren't tokens of the input are reported when compiling:
+/// ```compile_fail
+/// # use ast_toolkit_2::parser::{Lex, Parse, Span};
+/// #[derive(Clone, Debug, Eq, Hash, Lex, PartialEq)]
+/// enum Token {
+///     #[token("let")]
+///     Let,
+/// }
+///
+/// #[derive(Parse)]
+/// #[parse(input = Token)]
+/// struct Stmt(#[parse(keyword = "let")] Span, #[parse(punct = ";")] Span);
+/// ```
+///
+/// # Tracing
+/// With the `trace`-feature, the parsers of every node and variant (e.g., `Stmt` and `Stmt::Let`)
+/// are trace points. Then, `trace::record()` records where each was attempted and whether it
+/// matched, backtracked or failed.
+///
+/// # Memoisation
+/// Enums whose variants share long prefixes may parse those prefixes again for every variant
+/// tried, which can take exponential time if they nest. Nodes marked `#[parse(memo)]` remember
+/// what they parsed at every position during a parse, and reuse that instead (see the `memo`
+/// module). For example:
+/// ```
+/// # use ast_toolkit_2::parser::chumsky::text;
+/// # use ast_toolkit_2::parser::{Parse, Span};
+/// #[derive(Clone, Debug, Parse)]
+/// #[parse(memo)]
+/// enum Atom {
+///     Paren(#[parse(delim("(", ")"))] Box<Term>),
+///     Var(#[parse(with = text::ident)] String),
+/// }
+///
+/// #[derive(Clone, Debug, Parse)]
+/// enum Term {
+///     Call(Atom, #[parse(punct = "!")] Span),
+///     Plain(Atom),
+/// }
+///
+/// // Without memoisation, this would parse `x` 2^31 times
+/// let text: String = format!("{}x{}", "(".repeat(30), ")".repeat(30));
+/// assert!(matches!(Term::parse_str("<example>", &text), Ok(Term::Plain(Atom::Paren(_)))));
+/// ```
+/// Memoised nodes must implement [`Clone`], and cannot recover themselves.
+///
+/// # Attributes
+/// ## Toplevel
+/// At the toplevel, you can give the following attributes:
+/// - `#[parse(prefix = ...)]`: Defines the library path to the `parser`-library. If omitted,
+///   defaults to `::ast_toolkit_2::parser`.
+/// - `#[parse(input = ...)]`: Defines the type of the input to parse, which must implement `Input`
+///   (e.g., a [`Lex`](macro@Lex)-enum). If omitted, defaults to `char`.
+/// - `#[parse(sync = "...")]` and/or `#[parse(sync_before = "...")]`: If the node fails to parse,
+///   skips input until any of the given characters instead. Characters in `sync` are skipped too,
+///   those in `sync_before` are not. The node must implement `Recover`.
+/// - `#[parse(memo)]`: Remembers what the node parsed at every position, such that backtracking
+///   doesn't parse it again. The node must implement [`Clone`].
+/// - On structs, any of the attributes in the variant-level section.
+///
+/// ## Variant-level
+/// - `#[parse(keyword = "...")]`: Parses a keyword (e.g., `let`) before the fields. Keywords
+///   don't match if directly followed by identifier characters.
+/// - `#[parse(punct = "...")]`: Parses punctuation (e.g., `->`) before the fields.
+/// - `#[parse(delim("...", "..."))]`: Parses the fields in between delimiters (e.g.,
+///   `delim("(", ")")`).
+/// - `#[parse(with = ...)]`: Uses the function at the given path instead. It must take no
+///   arguments and return a chumsky parser producing `Self`.
+/// - `#[parse(prefix, prec = ...)]`, `#[parse(infix, prec = ...)]` or
+///   `#[parse(postfix, prec = ...)]`: Marks the variant as an operator with the given precedence,
+///   where higher binds tighter. Its token is given with `keyword` or `punct`. Infix operators
+///   have two operand fields, the others one. Operand fields are the fields without attributes,
+///   and their types must implement `From<Self>` (e.g., `Box<Self>`).
+/// - `#[parse(assoc = left|right|none)]`: Defines how infix operators with the same precedence
+///   group. Defaults to `left`. All infix operators with the same precedence must agree.
+/// - `#[parse(error)]`: Marks the variant as the error node. It isn't parsed, but constructed when
+///   recovering instead. Its fields must be marked `span` (which is set to the skipped input) or
+///   `skip`. This implements `Recover` for the enum.
+///
+/// ## Field-level
+/// - `#[parse(keyword = "...")]` or `#[parse(punct = "...")]`: Parses the field as a keyword or
+///   punctuation. The field's type must implement `From<Span>`.
+/// - `#[parse(with = ...)]`: Parses the field with the function at the given path. It must take no
+///   arguments and return a chumsky parser producing the field's type.
+/// - `#[parse(sep = "...")]` or `#[parse(sep = "...", trailing)]`: Parses the field as a list of
+///   nodes separated by the given punctuation, optionally allowing a trailing one. The field must
+///   be a `Vec` of something implementing `Parse`.
+/// - `#[parse(delim("...", "..."))]`: Parses the field in between delimiters. Can be combined with
+///   the above.
+/// - `#[parse(delim("...", "..."), recover)]`: Like the above, but if the field fails to parse,
+///   skips to the matching closing delimiter. Then the field becomes its type's `Recover`-error
+///   node. The delimiters must be single characters.
+/// - `#[parse(span)]`: Doesn't parse the field, but sets it to the span of the whole node. The
+///   field's type must implement `From<Span>`.
+/// - `#[parse(skip)]`: Doesn't parse the field, but sets it to its default value.
+/// - `#[parse(op)]`: Only for operators. Sets the field to the span of the operator's token. The
+///   field's type must implement `From<Span>`.
+#[inline]
+#[cfg(feature = "parser")]
+#[proc_macro_derive(Parse, attributes(parse))]
+pub fn parse(input: TokenStream) -> TokenStream {
+    match parser::parse(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+/// Derive macro to automatically implement [`Lex`] on a token enum.
+///
+/// Every variant is a kind of token, given by one or more rules. The generated lexer splits text
+/// into the longest token that any rule matches. If multiple rules match equally long, literal
+/// tokens win over patterns, and earlier rules over later ones (so `let` is a keyword rather than
+/// an identifier). Text that no rule matches is reported as an error and skipped.
+///
+/// Variants are either units, or have a single field that is parsed from the matched text with
+/// its `FromStr`-implementation (e.g., a `String` or a `u64`). Failing to parse it is reported as
+/// an error too.
+///
+/// The derive also implements `Input`, such that nodes can be parsed from the tokens (see
+/// [`Parse`](macro@Parse)), and `Display`, which writes the token as it would appear in the
+/// source (the literal, the field, or the variant's name for patterns without a field). For
+/// example:
+/// ```
+/// # use ast_toolkit_2::parser::Lex;
+/// # use ast_toolkit_2::parser::lex::tokenize;
+/// #[derive(Clone, Debug, Eq, Hash, Lex, PartialEq)]
+/// #[lex(skip = r"\s+", skip = "//[^\n]*")]
+/// enum Token {
+///     #[token("let")]
+///     Let,
+///     #[token("=")]
+///     Eq,
+///     #[regex("[a-zA-Z_][a-zA-Z0-9_]*")]
+///     Ident(String),
+///     #[regex("[0-9]+")]
+///     Int(u64),
+/// }
+///
+/// let (tokens, errors) = tokenize::<Token>("<example>".into(), "let lets = 42; // The answer");
+/// let tokens: Vec<Token> = tokens.into_iter().map(|(token, _)| token).collect();
+/// assert_eq!(tokens, [Token::Let, Token::Ident("lets".into()), Token::Eq, Token::Int(42)]);
+/// assert_eq!(errors.len(), 1);
+/// ```
+///
+/// # Patterns
+/// Patterns are a subset of regular expressions: literal characters, `.`, classes (`[a-z_]`,
+/// `[^"]`), the escapes `\d`, `\w`, `\s` (and their negations `\D`, `\W`, `\S`), `\n`, `\r`
+/// and `\t`, escaped punctuation (`\*`), groups, alternatives (`|`) and the repetitions `*`, `+`
+/// and `?`. Malformed patterns are reported when deriving, including other escapes (e.g., `\b`)
+/// and reversed ranges (e.g., `[z-a]`):
+/// ```compile_fail
+/// # use ast_toolkit_2::parser::Lex;
+/// #[derive(Clone, Debug, Eq, Hash, Lex, PartialEq)]
+/// enum Token {
+///     #[regex("[a-z")]
+///     Ident(String),
+/// }
+/// ```
+/// ```compile_fail
+/// # use ast_toolkit_2::parser::Lex;
+/// #[derive(Clone, Debug, Eq, Hash, Lex, PartialEq)]
+/// enum Token {
+///     #[regex(r"\bword")]
+///     Word,
+/// }
+/// ```
+/// ```compile_fail
+/// # use ast_toolkit_2::parser::Lex;
+/// #[derive(Clone, Debug, Eq, Hash, Lex, PartialEq)]
+/// enum Token {
+///     #[regex("[z-a]+")]
+///     Ident(String),
+/// }
+/// ```
+///
+/// # Attributes
+/// ## Toplevel
+/// - `#[lex(prefix = ...)]`: Defines the library path to the `parser`-library. If omitted,
+///   defaults to `::ast_toolkit_2::parser`.
+/// - `#[lex(skip = "...")]`: Defines a pattern for text that is skipped (e.g., whitespace or
+///   comments). Can be given multiple times.
+///
+/// ## Variant-level
+/// - `#[token("...")]`: Matches the given text literally. Only on variants without fields.
+/// - `#[regex("...")]`: Matches the given pattern.
+#[inline]
+#[cfg(feature = "parser")]
+#[proc_macro_derive(Lex, attributes(lex, token, regex))]
+pub fn lex(input: TokenStream) -> TokenStream {
+    match parser::lex(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+/// Derive macro to automatically implement [`Unparse`] on an AST node.
+///
+/// It prints the same syntax that the [`Parse`](macro@Parse) derive macro parses, and hence reads
+/// the same `#[parse(...)]`-attributes. Fields are printed in order, with the keywords,
+/// punctuation and delimiters given in the attributes. Fields that aren't parsed (`span`, `skip`
+/// and `op`) and error nodes aren't printed.
+///
+/// The result is a `Doc` that can be laid out within any line width. Nodes are kept on one line if
+/// they fit, and delimited fields and separated lists are broken over indented lines otherwise.
+/// The operands of operators are parenthesized with `(` and `)` if needed, so the expression type
+/// should be able to parse those. The variant that parses them (one that isn't an operator and
+/// only has a field delimited by parentheses) is returned by `Unparse::paren()`.
+///
+/// Nodes with custom parsers (`#[parse(with = ...)]` on the node) cannot be derived. Fields with
+/// custom parsers are printed with their type's `Unparse`-implementation.
+#[inline]
+#[cfg(feature = "parser")]
+#[proc_macro_derive(Unparse, attributes(parse))]
+pub fn unparse(input: TokenStream) -> TokenStream {
+    match parser::unparse(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+/// Derive macro to automatically implement [`Generate`] on an AST node.
+///
+/// It generates random nodes that the [`Parse`](macro@Parse) derive macro parses, and hence reads
+/// the same `#[parse(...)]`-attributes. Structs are generated by generating all their fields, and
+/// enums by picking one of their variants at random. Fields that are keywords, punctuation, spans
+/// or operator tokens get an empty span, and `skip`ped fields their default value. Error nodes are
+/// never generated.
+///
+/// Every struct and variant counts towards the `Generator`'s budget. Once it is exhausted, only
+/// variants are picked whose fields don't mention the enum itself (if there are any), such that
+/// generation ends.
+///
+/// Operands of operators are wrapped in the enum's parenthesizing variant wherever they bind looser
+/// than their operator, exactly where the [`Unparse`](macro@Unparse) derive macro would print
+/// parentheses. This is the first variant with a single parsed field that mentions the enum and is
+/// delimited by `(` and `)`. Without one, such operands are generated again until they bind tightly
+/// enough. Either way, generated nodes print to text that parses back to themselves.
+///
+/// Nodes with custom parsers (`#[parse(with = ...)]` on the node) cannot be derived. Fields with
+/// custom parsers are generated with their type's `Generate`-implementation, unless given a
+/// custom generator (see below).
+///
+/// # Attributes
+/// On top of those of [`Parse`](macro@Parse), the following attributes are read:
+/// - `#[parse(weight = ...)]`: Only on variants. Defines how often the variant is picked relative
+///   to the others. Defaults to `1`, and `0` means it's never picked.
+/// - `#[parse(generate = ...)]`: Only on fields. Generates the field with the function at the
+///   given path instead. It must take a `&mut Generator` and return the field's type.
+///
+/// # Example
+/// ```
+/// # use ast_toolkit_2::parser::{Error, Generate, Parse, Unparse};
+/// # use ast_toolkit_2::parser::chumsky::prelude::*;
+/// # use ast_toolkit_2::parser::chumsky::text;
+/// # use ast_toolkit_2::parser::generate::Generator;
+/// # fn int() -> impl Parser<char, u64, Error = Error> + Clone { text::int(10).from_str().unwrapped() }
+/// fn int_value(generator: &mut Generator) -> u64 { generator.below(1000) as u64 }
+///
+/// #[derive(Debug, Generate, Parse, PartialEq, Unparse)]
+/// enum Expr {
+///     #[parse(infix, punct = "+", prec = 1)]
+///     Add(Box<Expr>, Box<Expr>),
+///     #[parse(infix, punct = "*", prec = 2)]
+///     Mul(Box<Expr>, Box<Expr>),
+///     Paren(#[parse(delim("(", ")"))] Box<Expr>),
+///     #[parse(weight = 4)]
+///     Var(#[parse(with = text::ident)] String),
+///     #[parse(weight = 2)]
+///     Int(#[parse(with = int, generate = int_value)] u64),
+/// }
+///
+/// for seed in 0..100 {
+///     let expr: Expr = Generator::new(seed).generate();
+///     assert_eq!(Expr::parse_str("<generated>", &expr.unparse_to_string(80)).ok(), Some(expr));
+/// }
+/// ```
+#[inline]
+#[cfg(feature = "parser")]
+#[proc_macro_derive(Generate, attributes(parse))]
+pub fn generate(input: TokenStream) -> TokenStream {
+    match parser::generate(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+/// Derive macro to automatically implement [`Reparse`] on an AST node.
+///
+/// It reparses nodes that the [`Parse`](macro@Parse) derive macro parses, and hence reads the same
+/// `#[parse(...)]`-attributes (including `input`). An edit is first offered to the fields in order,
+/// such that the smallest node containing it is reparsed. If none absorbs it, the node reparses
+/// itself if it has a field marked `#[parse(span)]`, which must implement `Spanned`. Nodes without
+/// one leave the edit to their parent.
+///
+/// Operands of operators are only replaced by nodes that aren't operators themselves, as those
+/// might bind differently in context (e.g., reparsing `b` in `a * b` as `b + c`).
+///
+/// Nodes with custom parsers (`#[parse(with = ...)]` on the node) cannot be derived. Fields with
+/// custom parsers are reparsed and shifted with their type's `Reparse`-implementation, which is
+/// implemented for common leaves like `String` and integers. Skipped fields are left alone.
+///
+/// # Example
+/// ```
+/// # use ast_toolkit_2::parser::{Parse, Reparse, Span};
+/// # use ast_toolkit_2::parser::chumsky::text;
+/// # use ast_toolkit_2::parser::reparse::{Edit, Incremental};
+/// # #[derive(Parse, Reparse)]
+/// # enum Expr {
+/// #     #[parse(infix, punct = "*", prec = 1)]
+/// #     Mul(Box<Expr>, Box<Expr>, #[parse(span)] Span),
+/// #     Var(#[parse(with = text::ident)] String, #[parse(span)] Span),
+/// # }
+/// #[derive(Parse, Reparse)]
+/// enum Stmt {
+///     #[parse(keyword = "let")]
+///     Let(#[parse(with = text::ident)] String, #[parse(punct = "=")] Span, Expr, #[parse(punct = ";")] Span, #[parse(span)] Span),
+///     Expr(Expr, #[parse(punct = ";")] Span, #[parse(span)] Span),
+/// }
+///
+/// // Reparsing `abc` as `a * c` would bind differently, so the whole product is reparsed instead
+/// let mut doc = Incremental::<Vec<Stmt>>::new("<example>", "x * abc; y;").unwrap();
+/// assert_eq!(doc.edit(Edit::new(5..6, " * ")), Ok(Span::new("<example>", 0..9)));
+/// ```
+#[inline]
+#[cfg(feature = "parser")]
+#[proc_macro_derive(Reparse, attributes(parse))]
+pub fn reparse(input: TokenStream) -> TokenStream {
+    match parser::reparse(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
 /// Derive macro to automatically implement [`Railroad`] on an AST node.
 ///
 /// By default, it will interpret structs as sequences of nodes to parse. You can use attributes to
@@ -39,6 +476,7 @@ use proc_macro::TokenStream;
 ///
 /// ## Field-level
 #[inline]
+#[cfg(feature = "railroad")]
 #[proc_macro_derive(Railroad, attributes(railroad))]
 pub fn railroad(input: TokenStream) -> TokenStream {
     match railroad::railroad(input.into()) {
@@ -46,3 +484,79 @@ pub fn railroad(input: TokenStream) -> TokenStream {
         Err(err) => err.into_compile_error().into(),
     }
 }
+
+/// Derive macro to automatically implement [`Report`] on an error type.
+///
+/// Structs are interpreted as a single report. Enums are interpreted as one report per variant,
+/// where every variant is given its own message, labels, notes and help.
+///
+/// Messages can refer to the fields of the struct or variant as if they were variables, e.g.,
+/// `"Unknown variable '{name}'"`, or, for tuple fields, `"Unknown variable '{0}'"`.
+///
+/// Alternatively, reports can be localized by giving a `message_id` instead of a `message`. Then,
+/// all messages of labels, notes, helps and suggestions are Fluent message IDs too (e.g.,
+/// `"dup-def"` or `"dup-def.first"`), resolved at render time with the fields marked `#[arg]` as
+/// arguments. The derive also implements `Localized`, such that a test can check that a bundle
+/// defines all IDs.
+///
+/// # Attributes
+/// ## Toplevel
+/// At the toplevel, you can give the following attributes:
+/// - `#[report(prefix = ...)]`: Defines the library path to the `report`-library. If omitted,
+///   defaults to `::ast_toolkit_2::report`.
+/// - `#[report(message = "...")]`: Defines the main message of the report. Required on structs.
+///   On enums, acts as a default for all variants.
+/// - `#[report(message_id = "...")]`: Defines the message ID of the main message, making the
+///   report localized. Overrides any `message`, and vice versa. On enums, acts as a default for
+///   all variants.
+/// - `#[report(code = ...)]`: Defines a code identifying the kind of error. Either the path to a
+///   `Code` declared with `codes!()` (e.g., `E0042`), in which case unknown codes are compile
+///   errors; or a string literal (e.g., `"E0042"`), which isn't checked. On enums, acts as a
+///   default for all variants.
+/// - `#[report(lint = ...)]`: Defines the path to a `Lint` that users can configure the severity
+///   of the report with. If no severity is given, it defaults to that of the lint's default level.
+///   On enums, acts as a default for all variants.
+/// - `#[report(severity = ...)]`: Defines the severity of the report. Can be `error` (default),
+///   `warning` or `note`. On enums, acts as a default for all variants.
+/// - `#[note("...")]`: Adds a note to the report. Can be given multiple times. On enums, is added
+///   to all variants.
+/// - `#[help("...")]`: Adds a help message to the report. Can be given multiple times. On enums,
+///   is added to all variants.
+///
+/// ## Variant-level
+/// Variants accept the same attributes as the toplevel, except for `#[report(prefix = ...)]`.
+/// Every variant needs a message, either from itself or from the toplevel.
+///
+/// ## Field-level
+/// - `#[label(primary)]` or `#[label(primary, "...")]`: Marks the field as the primary location
+///   of the error, optionally with a message. The field must implement `Spanned`. Exactly one
+///   field must be marked as such.
+/// - `#[label]` or `#[label("...")]`: Marks the field as a secondary location relevant to the
+///   error, optionally with a message. The field must implement `Spanned`. If the field is an
+///   [`Option`], the label is only added if it is [`Some`]. The span may live in another source
+///   file than the primary label.
+/// - `#[label(each)]` or `#[label(each, "...")]`: Like `#[label]`, but for a collection of
+///   `Spanned` values (e.g., a `Vec<Span>`) with one label per element.
+/// - `#[note]`: Adds the field's value as a note. The field must implement
+///   [`Display`](std::fmt::Display). If the field is an [`Option`], the note is only added if it
+///   is [`Some`].
+/// - `#[help]`: Adds the field's value as a help message. The field must implement
+///   [`Display`](std::fmt::Display). If the field is an [`Option`], the help is only added if it
+///   is [`Some`].
+/// - `#[suggestion("...", code = "...", applicability = "...")]`: Suggests to replace the field's
+///   span with the text given as `code`, explained by the first message. The applicability can be
+///   `machine-applicable`, `maybe-incorrect`, `has-placeholders` or `unspecified` (default). The
+///   field must implement `Spanned`. Can be given multiple times. If the field is an [`Option`],
+///   the suggestion is only added if it is [`Some`].
+/// - `#[arg]` or `#[arg("...")]`: Passes the field's value as an argument to the messages of a
+///   localized report, named after the field or the given name. The field must implement
+///   [`Display`](std::fmt::Display). Unnamed fields must be given a name.
+#[inline]
+#[cfg(feature = "report")]
+#[proc_macro_derive(Report, attributes(report, label, note, help, suggestion, arg))]
+pub fn report(input: TokenStream) -> TokenStream {
+    match report::report(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
@@ -0,0 +1,353 @@
+//  GENERATE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:08:57
+//  Last edited:
+//    18 Oct 2026, 23:42:55
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements the `Generate` derive-macro, which generates the nodes
+//!   that the `Parse` derive-macro parses.
+//
+
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{ToTokens as _, format_ident, quote};
+use syn::spanned::Spanned as _;
+use syn::{Data, DeriveInput, Error, Field, Fields, Ident, LitStr, Path, Type, Variant};
+
+use super::{FieldAttrs, FieldKind, NodeAttrs, default_prefix};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Checks whether a type mentions the node itself (e.g., `Box<Expr>` in `Expr`).
+///
+/// # Arguments
+/// - `tokens`: The tokens of the type.
+/// - `ident`: The name of the node.
+///
+/// # Returns
+/// True if either `ident` or `Self` occurs anywhere in the type.
+fn mentions(tokens: TokenStream2, ident: &Ident) -> bool {
+    tokens.into_iter().any(|tree| match tree {
+        TokenTree::Ident(i) => i == *ident || i == "Self",
+        TokenTree::Group(g) => mentions(g.stream(), ident),
+        _ => false,
+    })
+}
+
+/// Checks whether a variant only wraps the node in parentheses (e.g., `Paren(#[parse(delim("(", ")"))] Box<Expr>)`).
+///
+/// # Arguments
+/// - `attrs`: The [`NodeAttrs`] given to the variant.
+/// - `fields`: Its [`Fields`].
+/// - `ident`: The name of the node.
+///
+/// # Returns
+/// True if the variant isn't an operator, and has exactly one parsed field, which mentions the
+/// node and is delimited by parentheses (either by itself or by the variant).
+///
+/// # Errors
+/// This function errors if any of the fields' attributes was malformed.
+pub(super) fn is_paren(attrs: &NodeAttrs, fields: &Fields, ident: &Ident) -> Result<bool, Error> {
+    if attrs.fixity.is_some() || attrs.error.is_some() || attrs.leading.is_some() || attrs.with.is_some() {
+        return Ok(false);
+    }
+    let parens = |delim: &Option<(LitStr, LitStr)>| matches!(delim, Some((open, close)) if open.value() == "(" && close.value() == ")");
+    let mut inner: Option<(&Field, FieldAttrs)> = None;
+    for field in fields {
+        let fattrs = FieldAttrs::try_from(field.attrs.as_slice())?;
+        match fattrs.kind {
+            FieldKind::Parse | FieldKind::With(_) | FieldKind::Sep(_) if inner.is_some() => return Ok(false),
+            FieldKind::Parse | FieldKind::With(_) | FieldKind::Sep(_) => inner = Some((field, fattrs)),
+            FieldKind::Keyword(_) | FieldKind::Punct(_) => return Ok(false),
+            FieldKind::Span | FieldKind::Op | FieldKind::Skip => {},
+        }
+    }
+    let Some((field, fattrs)) = inner else { return Ok(false) };
+    let delimited: bool = (parens(&fattrs.delim) && attrs.delim.is_none()) || (parens(&attrs.delim) && fattrs.delim.is_none());
+    Ok(matches!(fattrs.kind, FieldKind::Parse) && fattrs.generate.is_none() && delimited && mentions(field.ty.to_token_stream(), ident))
+}
+
+/// Generates the generator of a single field.
+///
+/// # Arguments
+/// - `prefix`: The path to the `parser`-library.
+/// - `field`: The [`Field`] to generate.
+/// - `fattrs`: The [`FieldAttrs`] given to it.
+///
+/// # Returns
+/// A [`TokenStream2`] encoding an expression that evaluates to the field, given a `__generator`.
+fn generate_field(prefix: &Path, field: &Field, fattrs: &FieldAttrs) -> TokenStream2 {
+    let ty: &Type = &field.ty;
+    match (&fattrs.generate, &fattrs.kind) {
+        (Some(generate), _) => quote! { #generate(__generator) },
+        (None, FieldKind::Parse | FieldKind::With(_) | FieldKind::Sep(_)) => quote! { <#ty as #prefix::generate::Generate>::generate(__generator) },
+        (None, FieldKind::Keyword(_) | FieldKind::Punct(_) | FieldKind::Span | FieldKind::Op) => {
+            quote! { ::std::convert::From::from(#prefix::generate::Generator::span(__generator)) }
+        },
+        (None, FieldKind::Skip) => quote! { ::std::default::Default::default() },
+    }
+}
+
+/// Generates the constructor of a struct or variant from its fields' expressions.
+///
+/// # Arguments
+/// - `path`: The path to the struct or variant (e.g., `Self` or `Self::Foo`).
+/// - `fields`: Its [`Fields`].
+/// - `exprs`: The expressions evaluating to every field, in order.
+///
+/// # Returns
+/// A [`TokenStream2`] encoding an expression that evaluates to the node.
+fn generate_ctor(path: TokenStream2, fields: &Fields, exprs: &[TokenStream2]) -> TokenStream2 {
+    match fields {
+        Fields::Named(_) => {
+            let names = fields.iter().map(|field| &field.ident);
+            quote! { #path { #(#names: #exprs),* } }
+        },
+        Fields::Unnamed(_) => quote! { #path ( #(#exprs),* ) },
+        Fields::Unit => quote! { #path },
+    }
+}
+
+/// Generates the generator of a single struct or variant.
+///
+/// # Arguments
+/// - `prefix`: The path to the `parser`-library.
+/// - `path`: The path to the struct or variant (e.g., `Self` or `Self::Foo`).
+/// - `attrs`: The [`NodeAttrs`] given to it.
+/// - `fields`: Its [`Fields`].
+/// - `inner`: If given, the expression to use for the node's only parsed field instead of
+///   generating it (used to wrap operands in parentheses).
+///
+/// # Returns
+/// A [`TokenStream2`] encoding an expression that evaluates to the node, given a
+/// `__generator`.
+///
+/// # Errors
+/// This function errors if any of the fields' attributes was malformed.
+fn generate_node(prefix: &Path, path: TokenStream2, attrs: &NodeAttrs, fields: &Fields, inner: Option<&TokenStream2>) -> Result<TokenStream2, Error> {
+    if let Some(with) = &attrs.with {
+        return Err(Error::new(with.span(), "Cannot derive Generate for nodes with custom parsers; implement it manually instead"));
+    }
+
+    // Generate the fields in order
+    let mut exprs: Vec<TokenStream2> = Vec::with_capacity(fields.len());
+    for field in fields {
+        let fattrs = FieldAttrs::try_from(field.attrs.as_slice())?;
+        exprs.push(match (inner, &fattrs.kind) {
+            (Some(inner), FieldKind::Parse) => quote! { ::std::convert::From::from(#inner) },
+            _ => generate_field(prefix, field, &fattrs),
+        });
+    }
+    let ctor: TokenStream2 = generate_ctor(path, fields, &exprs);
+    Ok(quote! { #prefix::generate::Generator::nest(__generator, |__generator| #ctor) })
+}
+
+/// Generates the generator of an operator variant.
+///
+/// Its operands are wrapped in parentheses wherever they bind looser than the operator, exactly
+/// like the `Unparse` derive-macro would print them (see the `__strength`-closure generated by
+/// [`generate()`]). That way, generated nodes parse back to themselves.
+///
+/// # Arguments
+/// - `prefix`: The path to the `parser`-library.
+/// - `path`: The path to the variant (e.g., `Self::Add`).
+/// - `attrs`: The [`NodeAttrs`] given to it. Its `fixity` must be set.
+/// - `fields`: Its [`Fields`].
+/// - `strength`: The strength of the operator itself.
+/// - `paren`: The name, [`NodeAttrs`] and [`Fields`] of the variant that parenthesizes (see
+///   [`is_paren()`]). If [`None`], operands that bind too loosely are generated again instead.
+///
+/// # Returns
+/// A [`TokenStream2`] encoding an expression that evaluates to the node, given a
+/// `__generator`.
+///
+/// # Errors
+/// This function errors if any of the fields' attributes was malformed.
+fn generate_operator(
+    prefix: &Path,
+    path: TokenStream2,
+    attrs: &NodeAttrs,
+    fields: &Fields,
+    strength: &TokenStream2,
+    paren: Option<(&Ident, &NodeAttrs, &Fields)>,
+) -> Result<TokenStream2, Error> {
+    let fixity: &Ident = attrs.fixity.as_ref().unwrap();
+    if let Some(with) = &attrs.with {
+        return Err(Error::new(with.span(), "Cannot derive Generate for nodes with custom parsers; implement it manually instead"));
+    }
+
+    // Generate every field into a binding first, such that the operands can be wrapped
+    let mut lets: Vec<TokenStream2> = Vec::with_capacity(fields.len());
+    let mut bindings: Vec<TokenStream2> = Vec::with_capacity(fields.len());
+    let mut operands: Vec<(Ident, TokenStream2)> = Vec::with_capacity(2);
+    for (i, field) in fields.iter().enumerate() {
+        let fattrs = FieldAttrs::try_from(field.attrs.as_slice())?;
+        let binding: Ident = format_ident!("__field{i}");
+        let expr: TokenStream2 = generate_field(prefix, field, &fattrs);
+        if matches!(fattrs.kind, FieldKind::Parse) && fattrs.delim.is_none() {
+            operands.push((binding.clone(), expr.clone()));
+        }
+        let ty: &Type = &field.ty;
+        lets.push(quote! { let #binding: #ty = #expr; });
+        bindings.push(binding.into_token_stream());
+    }
+
+    // Find which operands must bind strictly tighter (`left`) or at least as tight
+    let lefts: Vec<bool> = match (fixity.to_string().as_str(), operands.len()) {
+        ("infix", 2) => {
+            let assoc: Option<String> = attrs.assoc.as_ref().map(Ident::to_string);
+            vec![assoc.is_none() || assoc.as_deref() == Some("left"), assoc.as_deref() == Some("right")]
+        },
+        ("prefix" | "postfix", 1) => vec![true],
+        _ => return Err(Error::new(fixity.span(), "Infix operators must have exactly two operands, prefix and postfix operators exactly one")),
+    };
+    for ((binding, expr), left) in operands.into_iter().zip(lefts) {
+        let cmp: TokenStream2 = if left {
+            quote! { < }
+        } else {
+            quote! { <= }
+        };
+        let loose: TokenStream2 = quote! { __strength(::std::borrow::Borrow::<Self>::borrow(&#binding)) #cmp #strength };
+        lets.push(match paren {
+            Some((vident, vattrs, vfields)) => {
+                let wrapped: TokenStream2 = generate_node(prefix, quote! { Self::#vident }, vattrs, vfields, Some(&binding.to_token_stream()))?;
+                quote! { let #binding = if #loose { ::std::convert::From::from(#wrapped) } else { #binding }; }
+            },
+            None => quote! {
+                let mut #binding = #binding;
+                while #loose {
+                    #binding = #expr;
+                }
+            },
+        });
+    }
+    let ctor: TokenStream2 = generate_ctor(path, fields, &bindings);
+    Ok(quote! {
+        #prefix::generate::Generator::nest(__generator, |__generator| {
+            #(#lets)*
+            #ctor
+        })
+    })
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Implements the `Generate` derive-macro.
+///
+/// See the [actual macro](crate::generate()) for more information.
+///
+/// # Arguments
+/// - `input`: A [`TokenStream2`] encoding the input to parse and derive from.
+///
+/// # Returns
+/// A stream that encodes the generated impl.
+///
+/// # Errors
+/// This function may error if something about the input was malformed (probably attributes).
+pub fn generate(input: TokenStream2) -> Result<TokenStream2, Error> {
+    // Parse as derive macro input
+    let DeriveInput { attrs, vis: _, ident, generics, data } = syn::parse2(input)?;
+    let mut tattrs = NodeAttrs::try_from(attrs.as_slice())?;
+    let prefix: Path = tattrs.prefix.take().unwrap_or_else(default_prefix);
+
+    // Generate the node expression
+    let body: TokenStream2 = match data {
+        Data::Struct(s) => generate_node(&prefix, quote! { Self }, &tattrs, &s.fields, None)?,
+        Data::Enum(e) => {
+            // Find the strength of every operator like the `Unparse` derive-macro does, and the
+            // variant to parenthesize operands with
+            let mut strengths: Vec<TokenStream2> = Vec::new();
+            let mut paren: Option<(&Ident, NodeAttrs, &Fields)> = None;
+            for Variant { attrs, ident: vident, fields, .. } in &e.variants {
+                let vattrs = NodeAttrs::try_from(attrs.as_slice())?;
+                if let (Some(fixity), Some(prec)) = (&vattrs.fixity, &vattrs.prec) {
+                    let rank: u8 = match fixity.to_string().as_str() {
+                        "infix" => 0,
+                        "prefix" => 1,
+                        _ => 2,
+                    };
+                    strengths.push(quote! { Self::#vident { .. } => (#prec, #rank) });
+                } else if paren.is_none() && is_paren(&vattrs, fields, &ident)? {
+                    paren = Some((vident, vattrs, fields));
+                }
+            }
+
+            // Collect the variants that may be generated, with their weights, and whether they
+            // contain the node itself
+            let mut arms: Vec<TokenStream2> = Vec::with_capacity(e.variants.len());
+            let mut weights: Vec<u32> = Vec::with_capacity(e.variants.len());
+            let mut leaves: Vec<u32> = Vec::with_capacity(e.variants.len());
+            for Variant { attrs, ident: vident, fields, .. } in &e.variants {
+                let vattrs = NodeAttrs::try_from(attrs.as_slice())?;
+                if vattrs.error.is_some() {
+                    continue;
+                }
+                let weight: u32 = vattrs.weight.as_ref().map(|w| w.base10_parse()).transpose()?.unwrap_or(1);
+                let i: usize = arms.len();
+                let node: TokenStream2 = if let (Some(fixity), Some(prec)) = (&vattrs.fixity, &vattrs.prec) {
+                    let rank: u8 = match fixity.to_string().as_str() {
+                        "infix" => 0,
+                        "prefix" => 1,
+                        _ => 2,
+                    };
+                    let paren: Option<(&Ident, &NodeAttrs, &Fields)> = paren.as_ref().map(|(vident, vattrs, fields)| (*vident, vattrs, *fields));
+                    generate_operator(&prefix, quote! { Self::#vident }, &vattrs, fields, &quote! { (#prec, #rank) }, paren)?
+                } else {
+                    generate_node(&prefix, quote! { Self::#vident }, &vattrs, fields, None)?
+                };
+                arms.push(quote! { ::std::option::Option::Some(#i) => #node });
+                weights.push(weight);
+                leaves.push(if fields.iter().any(|field| mentions(field.ty.to_token_stream(), &ident)) { 0 } else { weight });
+            }
+            if weights.iter().all(|w| *w == 0) {
+                return Err(Error::new(ident.span(), "Cannot derive Generate on enums without any non-error variants of non-zero weight"));
+            }
+            let strength: TokenStream2 = if strengths.is_empty() {
+                quote! {}
+            } else {
+                quote! {
+                    let __strength = |node: &Self| -> (u32, u8) {
+                        match node {
+                            #(#strengths,)*
+                            _ => (u32::MAX, u8::MAX),
+                        }
+                    };
+                }
+            };
+
+            // Once the budget is exhausted, only pick variants that don't recurse (if any)
+            quote! {
+                #strength
+                let __weights: &[u32] = if #prefix::generate::Generator::is_exhausted(__generator) && [#(#leaves),*].iter().any(|w| *w > 0) {
+                    &[#(#leaves),*]
+                } else {
+                    &[#(#weights),*]
+                };
+                match #prefix::generate::Generator::weighted(__generator, __weights) {
+                    #(#arms,)*
+                    _ => ::std::unreachable!(),
+                }
+            }
+        },
+        Data::Union(u) => return Err(Error::new(u.union_token.span(), "Cannot derive Generate on unions")),
+    };
+
+    // Generate the impl
+    let (impl_gen, ty_gen, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_gen #prefix::generate::Generate for #ident #ty_gen #where_clause {
+            #[inline]
+            fn generate(__generator: &mut #prefix::generate::Generator) -> Self {
+                #body
+            }
+        }
+    })
+}
@@ -0,0 +1,292 @@
+//  LEX.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:18:33
+//  Last edited:
+//    18 Oct 2026, 23:52:17
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements the `Lex` derive-macro, which generates a lexer for a
+//!   token enum.
+//
+
+// Module definitions
+/// The compiler of the runtime `parser::lex::Pattern`, such that patterns are checked exactly like
+/// they're compiled (only the matching isn't used here).
+#[allow(dead_code)]
+#[path = "../../../src/parser/lex/pattern.rs"]
+mod pattern;
+
+// Imports
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{ToTokens as _, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned as _;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, Ident, LitStr, Meta, Path, Token, Variant};
+
+use super::default_prefix;
+
+
+/***** ATTRIBUTES *****/
+/// Defines possible `#[lex(...)]`-attributes on the toplevel.
+enum LexAttr {
+    /// Defines the prefix.
+    Prefix(Path),
+    /// Defines a pattern to skip.
+    Skip(LitStr),
+}
+impl Parse for LexAttr {
+    #[inline]
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        if ident == "prefix" {
+            Ok(Self::Prefix(input.parse()?))
+        } else if ident == "skip" {
+            Ok(Self::Skip(input.parse()?))
+        } else {
+            Err(Error::new(ident.span(), format!("Unknown attribute {:?}", ident.to_token_stream().to_string())))
+        }
+    }
+}
+
+
+
+/// Defines the rules given to a variant.
+enum RuleAttr {
+    /// A literal token, given by `#[token("...")]`.
+    Token(LitStr),
+    /// A pattern, given by `#[regex("...")]`.
+    Regex(LitStr),
+}
+
+/// Parses the rules of a variant.
+///
+/// # Arguments
+/// - `attrs`: The [`Attribute`]s of the variant.
+///
+/// # Returns
+/// The [`RuleAttr`]s given, in order.
+///
+/// # Errors
+/// This function errors if any of the attributes was malformed.
+fn parse_rules(attrs: &[Attribute]) -> Result<Vec<RuleAttr>, Error> {
+    let mut res: Vec<RuleAttr> = Vec::new();
+    for attr in attrs {
+        match &attr.meta {
+            Meta::List(l) if l.path.is_ident("token") => res.push(RuleAttr::Token(l.parse_args()?)),
+            Meta::List(l) if l.path.is_ident("regex") => res.push(RuleAttr::Regex(l.parse_args()?)),
+            Meta::List(l) if l.path.is_ident("lex") => return Err(Error::new(l.span(), "The \"lex\"-attribute can only be given on the toplevel")),
+            _ => continue,
+        }
+    }
+    Ok(res)
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Checks a pattern given to an attribute.
+///
+/// # Arguments
+/// - `lit`: The [`LitStr`] with the pattern.
+///
+/// # Errors
+/// This function errors, spanned on `lit`, if the pattern was malformed.
+#[inline]
+fn check_pattern(lit: &LitStr) -> Result<(), Error> { pattern::compile(&lit.value()).map(|_| ()).map_err(|err| Error::new(lit.span(), err)) }
+
+
+
+
+
+/***** LIBRARY *****/
+/// Implements the `Lex` derive-macro.
+///
+/// See the [actual macro](crate::lex()) for more information.
+///
+/// # Arguments
+/// - `input`: A [`TokenStream2`] encoding the input to parse and derive from.
+///
+/// # Returns
+/// A stream that encodes the generated impls.
+///
+/// # Errors
+/// This function may error if something about the input was malformed (probably attributes).
+pub fn lex(input: TokenStream2) -> Result<TokenStream2, Error> {
+    // Parse as derive macro input
+    let DeriveInput { attrs, vis: _, ident, generics, data } = syn::parse2(input)?;
+    let mut prefix: Path = default_prefix();
+    let mut skips: Vec<LitStr> = Vec::new();
+    for attr in &attrs {
+        if let Meta::List(l) = &attr.meta
+            && l.path.is_ident("lex")
+        {
+            for attr in l.parse_args_with(Punctuated::<LexAttr, Token![,]>::parse_terminated)? {
+                match attr {
+                    LexAttr::Prefix(path) => prefix = path,
+                    LexAttr::Skip(lit) => {
+                        check_pattern(&lit)?;
+                        skips.push(lit);
+                    },
+                }
+            }
+        }
+    }
+    let mut rules: Vec<TokenStream2> = skips.iter().map(|lit| quote! { #prefix::lex::Rule::skip(#lit) }).collect();
+    let Data::Enum(e) = data else {
+        return Err(Error::new(ident.span(), "Can only derive Lex on enums"));
+    };
+    if let Some(param) = generics.params.first() {
+        return Err(Error::new(param.span(), "Cannot derive Lex on generic enums"));
+    }
+
+    // Collect the rules of every variant
+    let mut tokens: Vec<TokenStream2> = Vec::new();
+    let mut literals: Vec<TokenStream2> = Vec::new();
+    let mut from_literals: Vec<TokenStream2> = Vec::new();
+    let mut all_literals: Vec<LitStr> = Vec::new();
+    let mut displays: Vec<TokenStream2> = Vec::with_capacity(e.variants.len());
+    for Variant { attrs, ident: vident, fields, .. } in &e.variants {
+        let vrules: Vec<RuleAttr> = parse_rules(attrs)?;
+        if vrules.is_empty() {
+            return Err(Error::new(vident.span(), "Every variant must be given at least one #[token(\"...\")] or #[regex(\"...\")]"));
+        }
+        let name: String = vident.to_string();
+
+        // Find how to construct the variant from the matched text
+        let (ctor, display): (TokenStream2, TokenStream2) = match fields {
+            Fields::Unit => (quote! { ::std::result::Result::Ok(Self::#vident) }, quote! { Self::#vident => f.write_str(#name) }),
+            Fields::Unnamed(f) if f.unnamed.len() == 1 => (
+                quote! {
+                    ::std::result::Result::map_err(
+                        ::std::result::Result::map(::std::str::FromStr::from_str(__text), Self::#vident),
+                        |err| ::std::format!("Invalid {} {:?}: {}", #name, __text, err),
+                    )
+                },
+                quote! { Self::#vident(value) => ::std::fmt::Display::fmt(value, f) },
+            ),
+            Fields::Named(f) if f.named.len() == 1 => {
+                let field: &Ident = f.named[0].ident.as_ref().unwrap();
+                (
+                    quote! {
+                        ::std::result::Result::map_err(
+                            ::std::result::Result::map(::std::str::FromStr::from_str(__text), |#field| Self::#vident { #field }),
+                            |err| ::std::format!("Invalid {} {:?}: {}", #name, __text, err),
+                        )
+                    },
+                    quote! { Self::#vident { #field: value } => ::std::fmt::Display::fmt(value, f) },
+                )
+            },
+            _ => return Err(Error::new(fields.span(), "Tokens can have at most one field, which is parsed from the matched text")),
+        };
+
+        // Add a lexer rule for every attribute
+        let mut literal: Option<&LitStr> = None;
+        for rule in &vrules {
+            let i: usize = rules.len();
+            match rule {
+                RuleAttr::Token(lit) => {
+                    if !matches!(fields, Fields::Unit) {
+                        return Err(Error::new(lit.span(), "Only tokens without fields can be given a #[token(\"...\")]"));
+                    }
+                    rules.push(quote! { #prefix::lex::Rule::literal(#lit) });
+                    from_literals.push(quote! { #lit => ::std::option::Option::Some(Self::#vident) });
+                    all_literals.push(lit.clone());
+                    literal.get_or_insert(lit);
+                },
+                RuleAttr::Regex(lit) => {
+                    check_pattern(lit)?;
+                    rules.push(quote! { #prefix::lex::Rule::pattern(#lit) });
+                },
+            }
+            tokens.push(quote! { #i => #ctor });
+        }
+        match literal {
+            Some(lit) => {
+                literals.push(quote! { Self::#vident => ::std::option::Option::Some(#lit) });
+                displays.push(quote! { Self::#vident => f.write_str(#lit) });
+            },
+            None => displays.push(display),
+        }
+    }
+
+    // Generate the impls
+    Ok(quote! {
+        #[automatically_derived]
+        impl #prefix::lex::Lex for #ident {
+            #[inline]
+            fn lexer() -> &'static #prefix::lex::Lexer {
+                static LEXER: ::std::sync::OnceLock<#prefix::lex::Lexer> = ::std::sync::OnceLock::new();
+                LEXER.get_or_init(|| #prefix::lex::Lexer::new(::std::vec![#(#rules),*]))
+            }
+
+            #[inline]
+            fn token(__rule: usize, __text: &str) -> ::std::result::Result<Self, ::std::string::String> {
+                match __rule {
+                    #(#tokens,)*
+                    _ => ::std::unreachable!(),
+                }
+            }
+
+            #[inline]
+            fn literal(&self) -> ::std::option::Option<&'static str> {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#literals,)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            #[inline]
+            fn from_literal(__literal: &str) -> ::std::option::Option<Self> {
+                #[allow(unreachable_patterns)]
+                match __literal {
+                    #(#from_literals,)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl #prefix::Input for #ident {
+            const LITERALS: ::std::option::Option<&'static [&'static str]> = ::std::option::Option::Some(&[#(#all_literals),*]);
+
+            #[inline]
+            fn stream(__source: ::std::sync::Arc<str>, __text: &str) -> (#prefix::InputStream<'_, Self>, ::std::vec::Vec<#prefix::Error<Self>>) {
+                #prefix::lex::stream(__source, __text)
+            }
+
+            #[inline]
+            fn keyword(__keyword: &'static str) -> impl #prefix::chumsky::Parser<Self, #prefix::Span, Error = #prefix::Error<Self>> + ::std::clone::Clone {
+                #prefix::lex::literal(__keyword)
+            }
+
+            #[inline]
+            fn punct(__punct: &'static str) -> impl #prefix::chumsky::Parser<Self, #prefix::Span, Error = #prefix::Error<Self>> + ::std::clone::Clone {
+                #prefix::lex::literal(__punct)
+            }
+
+            #[inline]
+            fn whitespace() -> impl #prefix::chumsky::Parser<Self, (), Error = #prefix::Error<Self>> + ::std::clone::Clone {
+                #prefix::chumsky::primitive::empty()
+            }
+        }
+
+        #[automatically_derived]
+        impl ::std::fmt::Display for #ident {
+            #[inline]
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self {
+                    #(#displays,)*
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,797 @@
+//  MOD.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:44:07
+//  Last edited:
+//    18 Oct 2026, 23:52:17
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements macros for the `parser`-library.
+//
+
+// Module definitions
+mod generate;
+mod lex;
+mod reparse;
+mod unparse;
+
+// Re-exports
+// Imports
+use std::collections::HashMap;
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned as _;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, Ident, LitInt, LitStr, Meta, Path, PathArguments, PathSegment, Token, Type, Variant, parenthesized};
+pub use generate::generate;
+pub use lex::lex;
+pub use reparse::reparse;
+pub use unparse::unparse;
+
+
+/***** ATTRIBUTES *****/
+/// Defines what we may learn from `#[parse(...)]`-attributes on structs or variants.
+#[derive(Default)]
+struct NodeAttrs {
+    /// The prefix path to the library structs. Only allowed on the toplevel.
+    prefix:      Option<Path>,
+    /// The input to parse (e.g., a token type) if not characters. Only allowed on the toplevel.
+    input:       Option<Type>,
+    /// Whether to memoise the parser per input position. Only allowed on the toplevel.
+    memo:        Option<Ident>,
+    /// A keyword or punctuation (in that order) to parse before the fields, if any.
+    leading:     Option<(Ident, LitStr)>,
+    /// Any delimiters around the fields.
+    delim:       Option<(LitStr, LitStr)>,
+    /// A custom parser function to use instead, if any.
+    with:        Option<Path>,
+    /// Whether this is a `prefix`, `infix` or `postfix` operator. Only allowed on variants.
+    fixity:      Option<Ident>,
+    /// The precedence of the operator, if any.
+    prec:        Option<LitInt>,
+    /// The associativity of an infix operator, if any.
+    assoc:       Option<Ident>,
+    /// The tokens to synchronise on when recovering, which are skipped too. Only allowed on the
+    /// toplevel.
+    sync:        Option<LitStr>,
+    /// The tokens to synchronise on when recovering, which are not skipped. Only allowed on the
+    /// toplevel.
+    sync_before: Option<LitStr>,
+    /// Whether this is the error variant.
+    error:       Option<Ident>,
+    /// How often this variant is generated relative to the others, if given.
+    weight:      Option<LitInt>,
+}
+impl TryFrom<&[Attribute]> for NodeAttrs {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &[Attribute]) -> Result<Self, Self::Error> {
+        let mut res: Self = Default::default();
+        for attr in parse_attrs(value)? {
+            match attr {
+                ParseAttr::Prefix(path) => res.prefix = Some(path),
+                ParseAttr::Input(ty) => res.input = Some(ty),
+                ParseAttr::Memo(memo) => res.memo = Some(memo),
+                ParseAttr::Keyword(kw, lit) | ParseAttr::Punct(kw, lit) => res.leading = Some((kw, lit)),
+                ParseAttr::Delim(open, close) => res.delim = Some((open, close)),
+                ParseAttr::With(path) => res.with = Some(path),
+                ParseAttr::Fixity(fixity) => res.fixity = Some(fixity),
+                ParseAttr::Prec(prec) => res.prec = Some(prec),
+                ParseAttr::Assoc(assoc) => res.assoc = Some(assoc),
+                ParseAttr::Sync(ident, lit) if ident == "sync" => res.sync = Some(lit),
+                ParseAttr::Sync(_, lit) => res.sync_before = Some(lit),
+                ParseAttr::Error(error) => res.error = Some(error),
+                ParseAttr::Weight(weight) => res.weight = Some(weight),
+                ParseAttr::Generate(path) => return Err(Error::new(path.span(), "The \"generate\"-attribute can only be given on fields")),
+                ParseAttr::Sep(ident, _)
+                | ParseAttr::Trailing(ident)
+                | ParseAttr::Span(ident)
+                | ParseAttr::Skip(ident)
+                | ParseAttr::Op(ident)
+                | ParseAttr::Recover(ident) => {
+                    return Err(Error::new(ident.span(), format!("The {:?}-attribute can only be given on fields", ident.to_string())));
+                },
+            }
+        }
+        Ok(res)
+    }
+}
+
+
+
+/// Defines what we may learn from `#[parse(...)]`-attributes on fields.
+#[derive(Default)]
+struct FieldAttrs {
+    /// How to parse the field.
+    kind:     FieldKind,
+    /// Whether a separated field allows a trailing separator.
+    trailing: Option<Ident>,
+    /// Any delimiters around the field.
+    delim:    Option<(LitStr, LitStr)>,
+    /// Whether a delimited field recovers by skipping to its closing delimiter.
+    recover:  Option<Ident>,
+    /// A custom generator function, if any.
+    generate: Option<Path>,
+}
+impl TryFrom<&[Attribute]> for FieldAttrs {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &[Attribute]) -> Result<Self, Self::Error> {
+        let mut res: Self = Default::default();
+        for attr in parse_attrs(value)? {
+            let (span, kind): (Span, FieldKind) = match attr {
+                ParseAttr::Prefix(path) => return Err(Error::new(path.span(), "The prefix can only be given on the toplevel")),
+                ParseAttr::Input(ty) => return Err(Error::new(ty.span(), "The input can only be given on the toplevel")),
+                ParseAttr::Keyword(kw, lit) => (kw.span(), FieldKind::Keyword(lit)),
+                ParseAttr::Punct(punct, lit) => (punct.span(), FieldKind::Punct(lit)),
+                ParseAttr::With(path) => (path.span(), FieldKind::With(path)),
+                ParseAttr::Sep(sep, lit) => (sep.span(), FieldKind::Sep(lit)),
+                ParseAttr::Span(span) => (span.span(), FieldKind::Span),
+                ParseAttr::Skip(skip) => (skip.span(), FieldKind::Skip),
+                ParseAttr::Op(op) => (op.span(), FieldKind::Op),
+                ParseAttr::Fixity(ident) | ParseAttr::Error(ident) => {
+                    return Err(Error::new(ident.span(), format!("The {:?}-attribute can only be given on variants", ident.to_string())));
+                },
+                ParseAttr::Sync(ident, _) | ParseAttr::Memo(ident) => {
+                    return Err(Error::new(ident.span(), format!("The {:?}-attribute can only be given on the toplevel", ident.to_string())));
+                },
+                ParseAttr::Prec(prec) => return Err(Error::new(prec.span(), "The \"prec\"-attribute can only be given on variants")),
+                ParseAttr::Assoc(assoc) => return Err(Error::new(assoc.span(), "The \"assoc\"-attribute can only be given on variants")),
+                ParseAttr::Weight(weight) => return Err(Error::new(weight.span(), "The \"weight\"-attribute can only be given on variants")),
+                ParseAttr::Generate(path) => {
+                    res.generate = Some(path);
+                    continue;
+                },
+                ParseAttr::Trailing(trailing) => {
+                    res.trailing = Some(trailing);
+                    continue;
+                },
+                ParseAttr::Delim(open, close) => {
+                    res.delim = Some((open, close));
+                    continue;
+                },
+                ParseAttr::Recover(recover) => {
+                    res.recover = Some(recover);
+                    continue;
+                },
+            };
+            if !matches!(res.kind, FieldKind::Parse) {
+                return Err(Error::new(span, "A field can only be one of 'keyword', 'punct', 'with', 'sep', 'span', 'skip' or 'op'"));
+            }
+            res.kind = kind;
+        }
+
+        // Check the combinations
+        if let Some(trailing) = &res.trailing
+            && !matches!(res.kind, FieldKind::Sep(_))
+        {
+            return Err(Error::new(trailing.span(), "'trailing' can only be given together with 'sep'"));
+        }
+        if let Some((open, _)) = &res.delim
+            && matches!(res.kind, FieldKind::Span | FieldKind::Skip | FieldKind::Op)
+        {
+            return Err(Error::new(open.span(), "Fields that aren't parsed cannot be delimited"));
+        }
+        if let Some(recover) = &res.recover
+            && res.delim.is_none()
+        {
+            return Err(Error::new(recover.span(), "'recover' can only be given together with 'delim'"));
+        }
+        Ok(res)
+    }
+}
+
+/// Defines the ways to parse a field.
+#[derive(Default)]
+enum FieldKind {
+    /// Use the `Parse`-implementation of the field's type.
+    #[default]
+    Parse,
+    /// Parse a keyword, and convert its span to the field's type.
+    Keyword(LitStr),
+    /// Parse punctuation, and convert its span to the field's type.
+    Punct(LitStr),
+    /// Use a custom parser function.
+    With(Path),
+    /// Parse a list separated by punctuation.
+    Sep(LitStr),
+    /// Don't parse, but convert the node's span to the field's type.
+    Span,
+    /// Don't parse, but use the field type's default.
+    Skip,
+    /// Don't parse, but convert the span of the operator of an operator variant to the field's
+    /// type.
+    Op,
+}
+
+
+
+/// Defines possible `#[parse(...)]`-attributes.
+enum ParseAttr {
+    /// Defines the prefix.
+    Prefix(Path),
+    /// Defines the input to parse.
+    Input(Type),
+    /// Marks a node as memoised.
+    Memo(Ident),
+    /// Defines a keyword.
+    Keyword(Ident, LitStr),
+    /// Defines punctuation.
+    Punct(Ident, LitStr),
+    /// Defines delimiters.
+    Delim(LitStr, LitStr),
+    /// Defines a custom parser function.
+    With(Path),
+    /// Defines a separator.
+    Sep(Ident, LitStr),
+    /// Allows a trailing separator.
+    Trailing(Ident),
+    /// Marks a field as the node's span.
+    Span(Ident),
+    /// Marks a field as skipped.
+    Skip(Ident),
+    /// Marks a variant as an operator.
+    Fixity(Ident),
+    /// Defines the precedence of an operator.
+    Prec(LitInt),
+    /// Defines the associativity of an infix operator.
+    Assoc(Ident),
+    /// Marks a field as the span of an operator.
+    Op(Ident),
+    /// Defines the tokens to synchronise on when recovering.
+    Sync(Ident, LitStr),
+    /// Marks a variant as the error node.
+    Error(Ident),
+    /// Marks a delimited field as recovering.
+    Recover(Ident),
+    /// Defines the weight of a variant when generating.
+    Weight(LitInt),
+    /// Defines a custom generator function.
+    Generate(Path),
+}
+impl Parse for ParseAttr {
+    #[inline]
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        if ident == "delim" {
+            let content;
+            parenthesized!(content in input);
+            let open: LitStr = content.parse()?;
+            content.parse::<Token![,]>()?;
+            let close: LitStr = content.parse()?;
+            return Ok(Self::Delim(open, close));
+        } else if ident == "trailing" {
+            return Ok(Self::Trailing(ident));
+        } else if ident == "span" {
+            return Ok(Self::Span(ident));
+        } else if ident == "skip" {
+            return Ok(Self::Skip(ident));
+        } else if ident == "op" {
+            return Ok(Self::Op(ident));
+        } else if ident == "error" {
+            return Ok(Self::Error(ident));
+        } else if ident == "recover" {
+            return Ok(Self::Recover(ident));
+        } else if ident == "memo" {
+            return Ok(Self::Memo(ident));
+        } else if ident == "infix" || ident == "postfix" || (ident == "prefix" && !input.peek(Token![=])) {
+            return Ok(Self::Fixity(ident));
+        }
+
+        // The rest are name/value pairs
+        input.parse::<Token![=]>()?;
+        if ident == "prefix" {
+            Ok(Self::Prefix(input.parse()?))
+        } else if ident == "input" {
+            Ok(Self::Input(input.parse()?))
+        } else if ident == "keyword" {
+            Ok(Self::Keyword(ident, input.parse()?))
+        } else if ident == "punct" {
+            Ok(Self::Punct(ident, input.parse()?))
+        } else if ident == "with" {
+            Ok(Self::With(input.parse()?))
+        } else if ident == "sep" {
+            Ok(Self::Sep(ident, input.parse()?))
+        } else if ident == "sync" || ident == "sync_before" {
+            Ok(Self::Sync(ident, input.parse()?))
+        } else if ident == "prec" {
+            Ok(Self::Prec(input.parse()?))
+        } else if ident == "weight" {
+            Ok(Self::Weight(input.parse()?))
+        } else if ident == "generate" {
+            Ok(Self::Generate(input.parse()?))
+        } else if ident == "assoc" {
+            let assoc: Ident = input.parse()?;
+            if assoc != "left" && assoc != "right" && assoc != "none" {
+                return Err(Error::new(assoc.span(), "The associativity can only be 'left', 'right' or 'none'"));
+            }
+            Ok(Self::Assoc(assoc))
+        } else {
+            Err(Error::new(ident.span(), format!("Unknown attribute {:?}", ident.to_token_stream().to_string())))
+        }
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns the default path to the `parser`-library.
+///
+/// # Returns
+/// A [`Path`] encoding `::ast_toolkit_2::parser`.
+fn default_prefix() -> Path {
+    Path {
+        leading_colon: Some(Default::default()),
+        segments:      {
+            let mut segments = Punctuated::new();
+            segments.push(PathSegment { ident: Ident::new("ast_toolkit_2", Span::call_site()), arguments: PathArguments::None });
+            segments.push(PathSegment { ident: Ident::new("parser", Span::call_site()), arguments: PathArguments::None });
+            segments
+        },
+    }
+}
+
+/// Parses all `#[parse(...)]`-attributes in a list.
+///
+/// # Arguments
+/// - `attrs`: The list of [`Attribute`]s to parse.
+///
+/// # Returns
+/// All [`ParseAttr`]s given, in order.
+///
+/// # Errors
+/// This function errors if any of the attributes was malformed.
+fn parse_attrs(attrs: &[Attribute]) -> Result<Vec<ParseAttr>, Error> {
+    let mut res: Vec<ParseAttr> = Vec::new();
+    for attr in attrs {
+        if let Meta::List(l) = &attr.meta
+            && l.path.is_ident("parse")
+        {
+            let attrs: Punctuated<ParseAttr, Token![,]> = l.parse_args_with(Punctuated::parse_terminated)?;
+            res.extend(attrs);
+        }
+    }
+    Ok(res)
+}
+
+/// Gets the character of a token that must be one character long.
+///
+/// # Arguments
+/// - `lit`: The [`LitStr`] encoding the token.
+///
+/// # Returns
+/// The token's character.
+///
+/// # Errors
+/// This function errors if the token isn't exactly one character.
+fn single_char(lit: &LitStr) -> Result<char, Error> {
+    let value: String = lit.value();
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(Error::new(lit.span(), "Recovery can only synchronise on single characters")),
+    }
+}
+
+/// Collects the keywords and punctuation parsed by a single struct or variant.
+///
+/// # Arguments
+/// - `attrs`: The [`NodeAttrs`] given to it.
+/// - `fields`: Its [`Fields`].
+/// - `lits`: The list to push the literals to.
+///
+/// # Errors
+/// This function errors if any of the fields' attributes was malformed.
+fn collect_literals(attrs: &NodeAttrs, fields: &Fields, lits: &mut Vec<LitStr>) -> Result<(), Error> {
+    lits.extend(attrs.leading.iter().map(|(_, lit)| lit.clone()));
+    lits.extend(attrs.delim.iter().flat_map(|(open, close)| [open.clone(), close.clone()]));
+    for field in fields {
+        let fattrs = FieldAttrs::try_from(field.attrs.as_slice())?;
+        if let FieldKind::Keyword(lit) | FieldKind::Punct(lit) | FieldKind::Sep(lit) = fattrs.kind {
+            lits.push(lit);
+        }
+        lits.extend(fattrs.delim.into_iter().flat_map(|(open, close)| [open, close]));
+    }
+    Ok(())
+}
+
+/// Generates the parser of a single struct or variant.
+///
+/// # Arguments
+/// - `prefix`: The path to the `parser`-library.
+/// - `input`: The input parsed if not characters (e.g., a token type).
+/// - `path`: The path to the struct or variant (e.g., `Self` or `Self::Foo`).
+/// - `attrs`: The [`NodeAttrs`] given to it.
+/// - `fields`: Its [`Fields`].
+///
+/// # Returns
+/// A [`TokenStream2`] encoding an expression that evaluates to the parser.
+///
+/// # Errors
+/// This function errors if any of the fields' attributes was malformed.
+fn generate_node(prefix: &Path, input: Option<&Type>, path: TokenStream2, attrs: &NodeAttrs, fields: &Fields) -> Result<TokenStream2, Error> {
+    if let Some(with) = &attrs.with {
+        return Ok(quote! { #with() });
+    }
+    let chars: bool = input.is_none();
+    let input: TokenStream2 = input.map(Type::to_token_stream).unwrap_or_else(|| quote! { ::std::primitive::char });
+
+    // Build the sequence of field parsers and the constructor
+    let mut seq: TokenStream2 = quote! { #prefix::chumsky::primitive::empty() };
+    let mut pat: TokenStream2 = quote! { () };
+    let mut inits: Vec<(Ident, TokenStream2)> = Vec::with_capacity(fields.len());
+    for (i, field) in fields.iter().enumerate() {
+        let binding: Ident = match &field.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("_{}", i),
+        };
+        let fattrs = FieldAttrs::try_from(field.attrs.as_slice())?;
+
+        // Find the field's parser
+        let ty = &field.ty;
+        let parser: TokenStream2 = match fattrs.kind {
+            FieldKind::Parse => quote! { <#ty as #prefix::Parse<#input>>::parser() },
+            FieldKind::Keyword(kw) => quote! { #prefix::chumsky::Parser::map(#prefix::combinators::keyword::<#input>(#kw), ::std::convert::From::from) },
+            FieldKind::Punct(punct) => quote! { #prefix::chumsky::Parser::map(#prefix::combinators::punct::<#input>(#punct), ::std::convert::From::from) },
+            FieldKind::With(with) => quote! { #with() },
+            FieldKind::Sep(sep) => {
+                let trailing: bool = fattrs.trailing.is_some();
+                quote! { #prefix::combinators::separated::<#input, _>(#sep, #trailing) }
+            },
+            FieldKind::Span => {
+                inits.push((binding, quote! { ::std::convert::From::from(::std::clone::Clone::clone(&__span)) }));
+                continue;
+            },
+            FieldKind::Skip => {
+                inits.push((binding, quote! { ::std::default::Default::default() }));
+                continue;
+            },
+            FieldKind::Op => return Err(Error::new(field.span(), "Only fields of operator variants can be marked as 'op'")),
+        };
+        let parser: TokenStream2 = match &fattrs.delim {
+            Some((open, close)) if fattrs.recover.is_some() => {
+                if !chars {
+                    return Err(Error::new(open.span(), "Recovery is only supported when parsing characters"));
+                }
+                let (open_char, close_char): (char, char) = (single_char(open)?, single_char(close)?);
+                quote! {
+                    #prefix::combinators::padded_left(#prefix::recover::nested(#open_char, #close_char, #prefix::combinators::delimited(#open, #close, #parser)))
+                }
+            },
+            Some((open, close)) => quote! { #prefix::combinators::delimited::<#input, _, _>(#open, #close, #parser) },
+            None => quote! { #prefix::combinators::padded_left::<#input, _, _>(#parser) },
+        };
+        seq = quote! { #prefix::chumsky::Parser::then(#seq, #parser) };
+        pat = quote! { (#pat, #binding) };
+        inits.push((binding.clone(), quote! { #binding }));
+    }
+
+    // Wrap it in the node's delimiters and leading token
+    if let Some((open, close)) = &attrs.delim {
+        seq = quote! { #prefix::combinators::delimited::<#input, _, _>(#open, #close, #seq) };
+    }
+    if let Some((kind, lit)) = &attrs.leading {
+        seq = quote! { #prefix::chumsky::Parser::ignore_then(#prefix::combinators::#kind::<#input>(#lit), #seq) };
+    }
+    let (names, exprs): (Vec<Ident>, Vec<TokenStream2>) = inits.into_iter().unzip();
+    let ctor: TokenStream2 = match fields {
+        Fields::Named(_) => quote! { #path { #(#names: #exprs),* } },
+        Fields::Unnamed(_) => quote! { #path ( #(#exprs),* ) },
+        Fields::Unit => quote! { #path },
+    };
+    Ok(quote! { #prefix::chumsky::Parser::map_with_span(#seq, |#pat, __span: #prefix::Span| #ctor) })
+}
+
+/// Generates the constructor of the error variant.
+///
+/// # Arguments
+/// - `ident`: The name of the variant.
+/// - `attrs`: The [`NodeAttrs`] given to it. Its `error` must be set.
+/// - `fields`: Its [`Fields`].
+///
+/// # Returns
+/// A [`TokenStream2`] encoding an expression that constructs the variant from a `__span`.
+///
+/// # Errors
+/// This function errors if the variant is also given attributes on how to parse it, or if any of
+/// its fields are parsed.
+fn generate_error(ident: &Ident, attrs: &NodeAttrs, fields: &Fields) -> Result<TokenStream2, Error> {
+    let error: &Ident = attrs.error.as_ref().unwrap();
+    if attrs.leading.is_some() || attrs.delim.is_some() || attrs.with.is_some() || attrs.fixity.is_some() || attrs.prec.is_some() {
+        return Err(Error::new(error.span(), "The error node isn't parsed, so cannot be given attributes on how to parse it"));
+    }
+
+    // Every field is either the span or skipped
+    let mut exprs: Vec<TokenStream2> = Vec::with_capacity(fields.len());
+    for field in fields {
+        let fattrs = FieldAttrs::try_from(field.attrs.as_slice())?;
+        exprs.push(match fattrs.kind {
+            FieldKind::Span => quote! { ::std::convert::From::from(::std::clone::Clone::clone(&__span)) },
+            FieldKind::Skip => quote! { ::std::default::Default::default() },
+            _ => return Err(Error::new(field.span(), "Fields of the error node can only be 'span' or 'skip'")),
+        });
+    }
+    Ok(match fields {
+        Fields::Named(_) => {
+            let names = fields.iter().map(|field| &field.ident);
+            quote! { Self::#ident { #(#names: #exprs),* } }
+        },
+        Fields::Unnamed(_) => quote! { Self::#ident ( #(#exprs),* ) },
+        Fields::Unit => quote! { Self::#ident },
+    })
+}
+
+/// Generates the `Operator` of an operator variant.
+///
+/// # Arguments
+/// - `prefix`: The path to the `parser`-library.
+/// - `ident`: The name of the variant.
+/// - `attrs`: The [`NodeAttrs`] given to it. Its `fixity` must be set.
+/// - `fields`: Its [`Fields`].
+///
+/// # Returns
+/// A [`TokenStream2`] encoding an expression that evaluates to the `Operator`, and the variant's
+/// precedence.
+///
+/// # Errors
+/// This function errors if the variant's or any of the fields' attributes were malformed.
+fn generate_operator(prefix: &Path, ident: &Ident, attrs: &NodeAttrs, fields: &Fields) -> Result<(TokenStream2, u32), Error> {
+    let fixity: &Ident = attrs.fixity.as_ref().unwrap();
+    let infix: bool = fixity == "infix";
+    if let Some(with) = &attrs.with {
+        return Err(Error::new(with.span(), "Operators cannot be given custom parsers"));
+    } else if let Some((open, _)) = &attrs.delim {
+        return Err(Error::new(open.span(), "Operators cannot be given delimiters"));
+    } else if let (Some(assoc), false) = (&attrs.assoc, infix) {
+        return Err(Error::new(assoc.span(), "Only infix operators can be given an associativity"));
+    }
+    let Some((kind, lit)) = &attrs.leading else {
+        return Err(Error::new(fixity.span(), "Operators must be given their token with 'keyword' or 'punct'"));
+    };
+    let Some(prec) = &attrs.prec else {
+        return Err(Error::new(fixity.span(), "Operators must be given their precedence with 'prec'"));
+    };
+    let prec_value: u32 = prec.base10_parse()?;
+
+    // Assign the operands, operator span and node span to the fields
+    let operands: &[Ident] = if infix { &[format_ident!("__lhs"), format_ident!("__rhs")] } else { &[format_ident!("__operand")] };
+    let arity: &str = if infix { "Infix operators must have exactly two operands" } else { "Prefix and postfix operators must have exactly one operand" };
+    let mut n_operands: usize = 0;
+    let mut exprs: Vec<TokenStream2> = Vec::with_capacity(fields.len());
+    for field in fields {
+        let fattrs = FieldAttrs::try_from(field.attrs.as_slice())?;
+        exprs.push(match fattrs.kind {
+            FieldKind::Parse if fattrs.delim.is_none() => {
+                let Some(operand) = operands.get(n_operands) else {
+                    return Err(Error::new(field.span(), arity));
+                };
+                n_operands += 1;
+                quote! { ::std::convert::From::from(#operand) }
+            },
+            FieldKind::Op => quote! { ::std::convert::From::from(::std::clone::Clone::clone(&__op)) },
+            FieldKind::Span => quote! { ::std::convert::From::from(::std::clone::Clone::clone(&__span)) },
+            FieldKind::Skip => quote! { ::std::default::Default::default() },
+            _ => return Err(Error::new(field.span(), "Fields of operators can only be operands, 'op', 'span' or 'skip'")),
+        });
+    }
+    if n_operands < operands.len() {
+        return Err(Error::new(ident.span(), arity));
+    }
+    let ctor: TokenStream2 = match fields {
+        Fields::Named(_) => {
+            let names = fields.iter().map(|field| &field.ident);
+            quote! { Self::#ident { #(#names: #exprs),* } }
+        },
+        Fields::Unnamed(_) => quote! { Self::#ident ( #(#exprs),* ) },
+        Fields::Unit => quote! { Self::#ident },
+    };
+
+    // Generate the operator
+    let name: String = ident.to_string();
+    let token: Ident = if kind == "keyword" { format_ident!("Keyword") } else { format_ident!("Punct") };
+    let token: TokenStream2 = quote! { #prefix::pratt::Token::#token(#lit) };
+    let op: TokenStream2 = if infix {
+        let assoc: Ident = match &attrs.assoc {
+            Some(assoc) if assoc == "right" => format_ident!("Right"),
+            Some(assoc) if assoc == "none" => format_ident!("None"),
+            _ => format_ident!("Left"),
+        };
+        quote! {
+            #prefix::pratt::Operator::infix(#name, #prec, #prefix::pratt::Assoc::#assoc, #token, |__lhs, __op: #prefix::Span, __rhs, __span: #prefix::Span| #ctor)
+        }
+    } else {
+        quote! { #prefix::pratt::Operator::#fixity(#name, #prec, #token, |__operand, __op: #prefix::Span, __span: #prefix::Span| #ctor) }
+    };
+    Ok((op, prec_value))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Implements the `Parse` derive-macro.
+///
+/// See the [actual macro](super::parse()) for more information.
+///
+/// # Arguments
+/// - `input`: A [`TokenStream2`] encoding the input to parse and derive from.
+///
+/// # Returns
+/// A stream that encodes the generated impl.
+///
+/// # Errors
+/// This function may error if something about the input was malformed (probably attributes).
+pub fn parse(input: TokenStream2) -> Result<TokenStream2, Error> {
+    // Parse as derive macro input
+    let DeriveInput { attrs, vis: _, ident, generics, data } = syn::parse2(input)?;
+    let mut tattrs = NodeAttrs::try_from(attrs.as_slice())?;
+    let prefix: Path = tattrs.prefix.take().unwrap_or_else(default_prefix);
+    let tokens: Option<Type> = tattrs.input.take();
+    let input: TokenStream2 = tokens.as_ref().map(Type::to_token_stream).unwrap_or_else(|| quote! { ::std::primitive::char });
+
+    if let Some(fixity) = &tattrs.fixity {
+        return Err(Error::new(fixity.span(), format!("The {:?}-attribute can only be given on variants", fixity.to_string())));
+    } else if let Some(prec) = &tattrs.prec {
+        return Err(Error::new(prec.span(), "The \"prec\"-attribute can only be given on variants"));
+    } else if let Some(assoc) = &tattrs.assoc {
+        return Err(Error::new(assoc.span(), "The \"assoc\"-attribute can only be given on variants"));
+    } else if let Some(error) = &tattrs.error {
+        return Err(Error::new(error.span(), "The \"error\"-attribute can only be given on variants"));
+    } else if let (Some(sync), Some(_)) = (tattrs.sync.as_ref().or(tattrs.sync_before.as_ref()), &tokens) {
+        return Err(Error::new(sync.span(), "Recovery is only supported when parsing characters"));
+    } else if let (Some(memo), Some(_)) = (&tattrs.memo, tattrs.sync.as_ref().or(tattrs.sync_before.as_ref())) {
+        return Err(Error::new(memo.span(), "Memoised nodes cannot recover, as recovered errors aren't remembered"));
+    }
+    let (impl_gen, ty_gen, where_clause) = generics.split_for_impl();
+
+    // Generate the parser expression
+    let mut pratt: Option<TokenStream2> = None;
+    let mut recover: Option<TokenStream2> = None;
+    let mut lits: Vec<LitStr> = Vec::new();
+    let mut body: TokenStream2 = match data {
+        Data::Struct(s) => {
+            collect_literals(&tattrs, &s.fields, &mut lits)?;
+            generate_node(&prefix, tokens.as_ref(), quote! { Self }, &tattrs, &s.fields)?
+        },
+        Data::Enum(e) => {
+            if let Some((kind, _)) = &tattrs.leading {
+                return Err(Error::new(kind.span(), "Enums can only be given tokens per variant"));
+            } else if let Some((open, _)) = &tattrs.delim {
+                return Err(Error::new(open.span(), "Enums can only be given delimiters per variant"));
+            } else if let Some(with) = &tattrs.with {
+                return Err(Error::new(with.span(), "Enums can only be given custom parsers per variant"));
+            }
+            if e.variants.is_empty() {
+                return Err(Error::new(ident.span(), "Cannot derive Parse on enums without variants"));
+            }
+
+            // Try every variant in order, except operators and the error node
+            let mut variants: Vec<TokenStream2> = Vec::with_capacity(e.variants.len());
+            let mut ops: Vec<TokenStream2> = Vec::new();
+            let mut error: Option<TokenStream2> = None;
+            let mut assocs: HashMap<u32, String> = HashMap::new();
+            for Variant { attrs, ident: vident, fields, .. } in &e.variants {
+                let vattrs = NodeAttrs::try_from(attrs.as_slice())?;
+                if let Some(prefix) = &vattrs.prefix {
+                    return Err(Error::new(prefix.span(), "The prefix can only be given on the toplevel"));
+                } else if let Some(input) = &vattrs.input {
+                    return Err(Error::new(input.span(), "The input can only be given on the toplevel"));
+                } else if let Some(sync) = vattrs.sync.as_ref().or(vattrs.sync_before.as_ref()) {
+                    return Err(Error::new(sync.span(), "Synchronisation tokens can only be given on the toplevel"));
+                } else if let Some(memo) = &vattrs.memo {
+                    return Err(Error::new(memo.span(), "The \"memo\"-attribute can only be given on the toplevel"));
+                }
+                if let Some(err) = &vattrs.error {
+                    if tokens.is_some() {
+                        return Err(Error::new(err.span(), "Recovery is only supported when parsing characters"));
+                    } else if error.is_some() {
+                        return Err(Error::new(err.span(), "Only one variant can be the error node"));
+                    }
+                    error = Some(generate_error(vident, &vattrs, fields)?);
+                    continue;
+                }
+                collect_literals(&vattrs, fields, &mut lits)?;
+                if let Some(fixity) = &vattrs.fixity {
+                    let (op, prec): (TokenStream2, u32) = generate_operator(&prefix, vident, &vattrs, fields)?;
+                    if fixity == "infix" {
+                        let assoc: String = vattrs.assoc.as_ref().map(Ident::to_string).unwrap_or_else(|| "left".into());
+                        if *assocs.entry(prec).or_insert_with(|| assoc.clone()) != assoc {
+                            return Err(Error::new(vident.span(), format!("Infix operators with precedence {prec} must have the same associativity")));
+                        }
+                    }
+                    ops.push(op);
+                    continue;
+                } else if let Some(prec) = &vattrs.prec {
+                    return Err(Error::new(prec.span(), "Only operators (marked 'prefix', 'infix' or 'postfix') can be given a precedence"));
+                } else if let Some(assoc) = &vattrs.assoc {
+                    return Err(Error::new(assoc.span(), "Only infix operators can be given an associativity"));
+                }
+                let parser: TokenStream2 = generate_node(&prefix, tokens.as_ref(), quote! { Self::#vident }, &vattrs, fields)?;
+                let name: String = format!("{ident}::{vident}");
+                variants.push(quote! { #prefix::chumsky::Parser::boxed(#prefix::traced(#name, #parser)) });
+            }
+            if let Some(error) = error {
+                recover = Some(quote! {
+                    #[automatically_derived]
+                    impl #impl_gen #prefix::recover::Recover for #ident #ty_gen #where_clause {
+                        #[inline]
+                        fn error(__span: #prefix::Span) -> Self { #error }
+                    }
+                });
+            }
+            let choice: TokenStream2 = quote! { #prefix::chumsky::primitive::choice(::std::vec![#(#variants),*]) };
+            if variants.is_empty() {
+                return Err(Error::new(ident.span(), "Cannot derive Parse on enums with only operators or error nodes"));
+            } else if ops.is_empty() {
+                choice
+            } else {
+                // Generate the Pratt-impl, and build the parser from that
+                pratt = Some(quote! {
+                    #[automatically_derived]
+                    #[allow(unused_variables)]
+                    impl #impl_gen #prefix::pratt::Pratt<#input> for #ident #ty_gen #where_clause {
+                        #[inline]
+                        fn atom() -> impl #prefix::chumsky::Parser<#input, Self, Error = #prefix::Error<#input>> + ::std::clone::Clone { #choice }
+
+                        #[inline]
+                        fn operators() -> ::std::vec::Vec<#prefix::pratt::Operator<Self>> { ::std::vec![#(#ops),*] }
+                    }
+                });
+                quote! { #prefix::pratt::pratt(<Self as #prefix::pratt::Pratt<#input>>::atom(), <Self as #prefix::pratt::Pratt<#input>>::operators()) }
+            }
+        },
+        Data::Union(u) => return Err(Error::new(u.union_token.span(), "Cannot derive Parse on unions")),
+    };
+
+    // Recover from errors in the node, if told to
+    if tattrs.sync.is_some() || tattrs.sync_before.is_some() {
+        let sync: LitStr = tattrs.sync.take().unwrap_or_else(|| LitStr::new("", Span::call_site()));
+        let sync_before: LitStr = tattrs.sync_before.take().unwrap_or_else(|| LitStr::new("", Span::call_site()));
+        body = quote! { #prefix::recover::sync(#sync, #sync_before, #body) };
+    }
+
+    // Remember what the node parsed where, if told to
+    if tattrs.memo.is_some() {
+        body = quote! { #prefix::memo::memo::<#input, Self, _>(#body) };
+    }
+
+    // Check that any keywords and punctuation are tokens when the node is compiled, as parsing them
+    // would panic otherwise
+    let checks: Vec<TokenStream2> = match &tokens {
+        Some(tokens) => lits
+            .iter()
+            .map(|lit| {
+                let msg: String = format!("There is no token {:?} in {}", lit.value(), tokens.to_token_stream());
+                quote_spanned! { lit.span()=> ::std::assert!(#prefix::lex::accepts::<#tokens>(#lit), #msg); }
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    // Generate the impl
+    let name: String = ident.to_string();
+    Ok(quote! {
+        #pratt
+        #recover
+
+        #[automatically_derived]
+        #[allow(unused_variables)]
+        impl #impl_gen #prefix::Parse<#input> for #ident #ty_gen #where_clause {
+            #[inline]
+            fn parser() -> impl #prefix::chumsky::Parser<#input, Self, Error = #prefix::Error<#input>> + ::std::clone::Clone {
+                const { #(#checks)* }
+                #prefix::cached(|| #prefix::traced(#name, #body))
+            }
+        }
+    })
+}
@@ -0,0 +1,179 @@
+//  REPARSE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:29:40
+//  Last edited:
+//    18 Oct 2026, 21:29:40
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements the `Reparse` derive-macro, which reparses the nodes that
+//!   the `Parse` derive-macro parses after edits.
+//
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{ToTokens as _, quote};
+use syn::spanned::Spanned as _;
+use syn::{Data, DeriveInput, Error, Fields, Ident, Path, Variant};
+
+use super::unparse::generate_pattern;
+use super::{FieldAttrs, FieldKind, NodeAttrs, default_prefix};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates the reparsing and shifting of a single struct or variant.
+///
+/// # Arguments
+/// - `prefix`: The path to the `parser`-library.
+/// - `input`: The input parsed (e.g., `char`).
+/// - `attrs`: The [`NodeAttrs`] given to it.
+/// - `fields`: Its [`Fields`].
+/// - `bindings`: The names of the fields' bindings.
+///
+/// # Returns
+/// A pair of [`TokenStream2`]s. The first encodes a block that reparses the children given a
+/// `__ctx`, returning from the function if any absorbed the edit, and otherwise evaluates to the
+/// node's own span (if it has any). The second encodes statements shifting all fields given a
+/// `__from` and `__delta`.
+///
+/// # Errors
+/// This function errors if any of the fields' attributes was malformed.
+fn generate_node(prefix: &Path, input: &TokenStream2, attrs: &NodeAttrs, fields: &Fields, bindings: &[Ident]) -> Result<(TokenStream2, TokenStream2), Error> {
+    if let Some(with) = &attrs.with {
+        return Err(Error::new(with.span(), "Cannot derive Reparse for nodes with custom parsers; implement it manually instead"));
+    }
+    let operator: bool = attrs.fixity.is_some();
+
+    // Find the node's own span, its children, and all fields that are shifted
+    let mut span: Option<&Ident> = None;
+    let mut children: Vec<(usize, bool)> = Vec::with_capacity(fields.len());
+    let mut shifted: Vec<usize> = Vec::with_capacity(fields.len());
+    for (i, field) in fields.iter().enumerate() {
+        let fattrs = FieldAttrs::try_from(field.attrs.as_slice())?;
+        match fattrs.kind {
+            FieldKind::Parse => children.push((i, operator && fattrs.delim.is_none())),
+            FieldKind::With(_) | FieldKind::Sep(_) => children.push((i, false)),
+            FieldKind::Span => {
+                span.get_or_insert(&bindings[i]);
+            },
+            FieldKind::Keyword(_) | FieldKind::Punct(_) | FieldKind::Op => {},
+            FieldKind::Skip => continue,
+        }
+        shifted.push(i);
+    }
+
+    // Try the children in order, shifting all other fields once one absorbs the edit
+    let mut tries: Vec<TokenStream2> = Vec::with_capacity(children.len());
+    for (i, operand) in children {
+        let child: &Ident = &bindings[i];
+        let others = shifted.iter().filter(|j| **j != i).map(|j| &bindings[*j]);
+        tries.push(quote! {
+            if let ::std::option::Option::Some(__reparsed) = #prefix::reparse::Reparse::<#input>::reparse(#child, #prefix::reparse::Context::with_operand(__ctx, #operand)) {
+                #(#prefix::reparse::Context::shift(&__ctx, #others);)*
+                return ::std::option::Option::Some(__reparsed);
+            }
+        });
+    }
+    let reparse: TokenStream2 = match span {
+        Some(span) => quote! {{
+            let __span: #prefix::Span = #prefix::Spanned::span(&*#span);
+            if !#prefix::reparse::Context::overlaps(&__ctx, &__span) {
+                return ::std::option::Option::None;
+            }
+            #(#tries)*
+            ::std::option::Option::Some(__span)
+        }},
+        None => quote! {{
+            #(#tries)*
+            ::std::option::Option::None
+        }},
+    };
+
+    // Shift all fields
+    let shifted = shifted.iter().map(|i| &bindings[*i]);
+    let shift: TokenStream2 = quote! { #(#prefix::reparse::Reparse::<#input>::shift(#shifted, __from, __delta);)* };
+    Ok((reparse, shift))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Implements the `Reparse` derive-macro.
+///
+/// See the [actual macro](crate::reparse()) for more information.
+///
+/// # Arguments
+/// - `input`: A [`TokenStream2`] encoding the input to parse and derive from.
+///
+/// # Returns
+/// A stream that encodes the generated impl.
+///
+/// # Errors
+/// This function may error if something about the input was malformed (probably attributes).
+pub fn reparse(input: TokenStream2) -> Result<TokenStream2, Error> {
+    // Parse as derive macro input
+    let DeriveInput { attrs, vis: _, ident, generics, data } = syn::parse2(input)?;
+    let mut tattrs = NodeAttrs::try_from(attrs.as_slice())?;
+    let prefix: Path = tattrs.prefix.take().unwrap_or_else(default_prefix);
+    let input: TokenStream2 = tattrs.input.take().map(|ty| ty.into_token_stream()).unwrap_or_else(|| quote! { ::std::primitive::char });
+
+    // Generate the match arms reparsing and shifting every struct or variant
+    let mut reparses: Vec<TokenStream2> = Vec::new();
+    let mut shifts: Vec<TokenStream2> = Vec::new();
+    let mut operators: Vec<TokenStream2> = Vec::new();
+    match data {
+        Data::Struct(s) => {
+            let (pat, bindings): (TokenStream2, Vec<Ident>) = generate_pattern(quote! { Self }, &s.fields);
+            let (reparse, shift): (TokenStream2, TokenStream2) = generate_node(&prefix, &input, &tattrs, &s.fields, &bindings)?;
+            reparses.push(quote! { #pat => #reparse });
+            shifts.push(quote! { #pat => { #shift } });
+        },
+        Data::Enum(e) => {
+            for Variant { attrs, ident: vident, fields, .. } in &e.variants {
+                let vattrs = NodeAttrs::try_from(attrs.as_slice())?;
+                let (pat, bindings): (TokenStream2, Vec<Ident>) = generate_pattern(quote! { Self::#vident }, fields);
+                let (reparse, shift): (TokenStream2, TokenStream2) = generate_node(&prefix, &input, &vattrs, fields, &bindings)?;
+                reparses.push(quote! { #pat => #reparse });
+                shifts.push(quote! { #pat => { #shift } });
+                if vattrs.fixity.is_some() {
+                    operators.push(quote! { Self::#vident { .. } });
+                }
+            }
+        },
+        Data::Union(u) => return Err(Error::new(u.union_token.span(), "Cannot derive Reparse on unions")),
+    }
+    let is_operator: Option<TokenStream2> = (!operators.is_empty()).then(|| {
+        quote! {
+            #[inline]
+            fn is_operator(&self) -> bool { ::std::matches!(self, #(#operators)|*) }
+        }
+    });
+
+    // Generate the impl
+    let (impl_gen, ty_gen, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        #[allow(unused_variables)]
+        impl #impl_gen #prefix::reparse::Reparse<#input> for #ident #ty_gen #where_clause {
+            fn reparse(&mut self, __ctx: #prefix::reparse::Context<'_, #input>) -> ::std::option::Option<#prefix::Span> {
+                // Try the children first, falling back to this node if it has a span
+                let __span: #prefix::Span = match self {
+                    #(#reparses,)*
+                }?;
+                #prefix::reparse::Context::reparse(&__ctx, self, &__span)
+            }
+
+            fn shift(&mut self, __from: usize, __delta: isize) {
+                match self {
+                    #(#shifts,)*
+                }
+            }
+
+            #is_operator
+        }
+    })
+}
@@ -0,0 +1,302 @@
+//  UNPARSE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:00:50
+//  Last edited:
+//    18 Oct 2026, 23:42:55
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements the `Unparse` derive-macro, which mirrors the `Parse`
+//!   derive-macro.
+//
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned as _;
+use syn::{Data, DeriveInput, Error, Fields, Ident, LitStr, Path, Variant};
+
+use super::generate::is_paren;
+use super::{FieldAttrs, FieldKind, NodeAttrs, default_prefix};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates the pattern binding all fields of a struct or variant.
+///
+/// # Arguments
+/// - `path`: The path to the struct or variant (e.g., `Self` or `Self::Foo`).
+/// - `fields`: Its [`Fields`].
+///
+/// # Returns
+/// A [`TokenStream2`] encoding the pattern, and the names of the bindings in field order.
+pub(super) fn generate_pattern(path: TokenStream2, fields: &Fields) -> (TokenStream2, Vec<Ident>) {
+    let bindings: Vec<Ident> = fields.iter().enumerate().map(|(i, field)| field.ident.clone().unwrap_or_else(|| format_ident!("_{}", i))).collect();
+    let pat: TokenStream2 = match fields {
+        Fields::Named(_) => quote! { #path { #(#bindings),* } },
+        Fields::Unnamed(_) => quote! { #path ( #(#bindings),* ) },
+        Fields::Unit => quote! { #path },
+    };
+    (pat, bindings)
+}
+
+/// Generates the document of a token.
+///
+/// # Arguments
+/// - `prefix`: The path to the `parser`-library.
+/// - `kind`: Either `keyword` or `punct`.
+/// - `lit`: The token.
+///
+/// # Returns
+/// A [`TokenStream2`] encoding an expression that evaluates to the `Doc`.
+#[inline]
+fn generate_token(prefix: &Path, kind: &Ident, lit: &LitStr) -> TokenStream2 {
+    if kind == "keyword" {
+        quote! { #prefix::doc::Doc::Keyword(#lit) }
+    } else {
+        quote! { #prefix::doc::Doc::Punct(#lit) }
+    }
+}
+
+/// Generates the document of a single struct or variant.
+///
+/// # Arguments
+/// - `prefix`: The path to the `parser`-library.
+/// - `attrs`: The [`NodeAttrs`] given to it.
+/// - `fields`: Its [`Fields`].
+/// - `bindings`: The names of the fields' bindings.
+///
+/// # Returns
+/// A [`TokenStream2`] encoding an expression that evaluates to the `Doc`.
+///
+/// # Errors
+/// This function errors if any of the fields' attributes was malformed.
+fn generate_node(prefix: &Path, attrs: &NodeAttrs, fields: &Fields, bindings: &[Ident]) -> Result<TokenStream2, Error> {
+    if let Some(with) = &attrs.with {
+        return Err(Error::new(with.span(), "Cannot derive Unparse for nodes with custom parsers; implement it manually instead"));
+    }
+
+    // Print the fields in order
+    let mut docs: Vec<TokenStream2> = Vec::with_capacity(fields.len());
+    for (field, binding) in fields.iter().zip(bindings) {
+        let fattrs = FieldAttrs::try_from(field.attrs.as_slice())?;
+        let doc: TokenStream2 = match fattrs.kind {
+            FieldKind::Parse | FieldKind::With(_) => quote! { #prefix::Unparse::unparse(#binding) },
+            FieldKind::Keyword(kw) => quote! { #prefix::doc::Doc::Keyword(#kw) },
+            FieldKind::Punct(punct) => quote! { #prefix::doc::Doc::Punct(#punct) },
+            FieldKind::Sep(sep) => quote! {
+                #prefix::doc::Doc::join(
+                    ::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(#binding), #prefix::Unparse::unparse),
+                    #prefix::doc::Doc::concat([#prefix::doc::Doc::Punct(#sep), #prefix::doc::Doc::line()]),
+                )
+            },
+            FieldKind::Span | FieldKind::Skip | FieldKind::Op => continue,
+        };
+        docs.push(match &fattrs.delim {
+            Some((open, close)) => quote! { #prefix::doc::Doc::delimited(#open, #close, #doc) },
+            None => doc,
+        });
+    }
+
+    // Wrap it in the node's delimiters and leading token
+    let mut doc: TokenStream2 = quote! { #prefix::doc::Doc::join([#(#docs),*], #prefix::doc::Doc::Space) };
+    if let Some((open, close)) = &attrs.delim {
+        doc = quote! { #prefix::doc::Doc::delimited(#open, #close, #doc) };
+    }
+    if let Some((kind, lit)) = &attrs.leading {
+        let token: TokenStream2 = generate_token(prefix, kind, lit);
+        doc = quote! { #prefix::doc::Doc::concat([#token, #prefix::doc::Doc::Space, #doc]) };
+    }
+    Ok(quote! { #prefix::doc::Doc::group(#doc) })
+}
+
+/// Generates the document of an operator variant.
+///
+/// Its operands are wrapped in parentheses if they bind looser than the operator (see the
+/// `__strength`-closure generated by [`unparse()`]).
+///
+/// # Arguments
+/// - `prefix`: The path to the `parser`-library.
+/// - `attrs`: The [`NodeAttrs`] given to it. Its `fixity` must be set.
+/// - `fields`: Its [`Fields`].
+/// - `bindings`: The names of the fields' bindings.
+/// - `strength`: The strength of the operator itself.
+///
+/// # Returns
+/// A [`TokenStream2`] encoding an expression that evaluates to the `Doc`.
+///
+/// # Errors
+/// This function errors if any of the fields' attributes was malformed.
+fn generate_operator(prefix: &Path, attrs: &NodeAttrs, fields: &Fields, bindings: &[Ident], strength: &TokenStream2) -> Result<TokenStream2, Error> {
+    let fixity: &Ident = attrs.fixity.as_ref().unwrap();
+    let Some((kind, lit)) = &attrs.leading else {
+        return Err(Error::new(fixity.span(), "Operators must be given their token with 'keyword' or 'punct'"));
+    };
+    let token: TokenStream2 = generate_token(prefix, kind, lit);
+    // Only keywords are spaced from their operand (e.g., `not a`, but `-a`)
+    let space: TokenStream2 = if kind == "keyword" {
+        quote! { #prefix::doc::Doc::Space }
+    } else {
+        quote! { #prefix::doc::Doc::Nil }
+    };
+
+    // Find the operands
+    let mut operands: Vec<&Ident> = Vec::with_capacity(2);
+    for (field, binding) in fields.iter().zip(bindings) {
+        let fattrs = FieldAttrs::try_from(field.attrs.as_slice())?;
+        if matches!(fattrs.kind, FieldKind::Parse) && fattrs.delim.is_none() {
+            operands.push(binding);
+        }
+    }
+    let operand = |binding: &Ident, left: bool| -> TokenStream2 {
+        let cmp: TokenStream2 = if left {
+            quote! { < }
+        } else {
+            quote! { <= }
+        };
+        quote! {
+            #prefix::unparse::operand(
+                #prefix::Unparse::unparse(#binding),
+                __strength(::std::borrow::Borrow::<Self>::borrow(#binding)) #cmp #strength,
+            )
+        }
+    };
+
+    // Print them around the token
+    match (fixity.to_string().as_str(), operands.as_slice()) {
+        ("infix", [lhs, rhs]) => {
+            let assoc: Option<String> = attrs.assoc.as_ref().map(Ident::to_string);
+            let lhs: TokenStream2 = operand(lhs, assoc.is_none() || assoc.as_deref() == Some("left"));
+            let rhs: TokenStream2 = operand(rhs, assoc.as_deref() == Some("right"));
+            Ok(quote! {
+                #prefix::doc::Doc::group(#prefix::doc::Doc::concat([
+                    #lhs,
+                    #prefix::doc::Doc::Space,
+                    #token,
+                    #prefix::doc::Doc::nest(#prefix::doc::Doc::concat([#prefix::doc::Doc::line(), #rhs]), #prefix::doc::INDENT),
+                ]))
+            })
+        },
+        ("prefix", [operand_]) => {
+            let operand: TokenStream2 = operand(operand_, true);
+            Ok(quote! { #prefix::doc::Doc::concat([#token, #space, #operand]) })
+        },
+        ("postfix", [operand_]) => {
+            let operand: TokenStream2 = operand(operand_, true);
+            Ok(quote! { #prefix::doc::Doc::concat([#operand, #space, #token]) })
+        },
+        _ => Err(Error::new(fixity.span(), "Infix operators must have exactly two operands, prefix and postfix operators exactly one")),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Implements the `Unparse` derive-macro.
+///
+/// See the [actual macro](crate::unparse()) for more information.
+///
+/// # Arguments
+/// - `input`: A [`TokenStream2`] encoding the input to parse and derive from.
+///
+/// # Returns
+/// A stream that encodes the generated impl.
+///
+/// # Errors
+/// This function may error if something about the input was malformed (probably attributes).
+pub fn unparse(input: TokenStream2) -> Result<TokenStream2, Error> {
+    // Parse as derive macro input
+    let DeriveInput { attrs, vis: _, ident, generics, data } = syn::parse2(input)?;
+    let mut tattrs = NodeAttrs::try_from(attrs.as_slice())?;
+    let prefix: Path = tattrs.prefix.take().unwrap_or_else(default_prefix);
+
+    // Generate the document expression
+    let mut paren: Option<String> = None;
+    let body: TokenStream2 = match data {
+        Data::Struct(s) => {
+            let (pat, bindings): (TokenStream2, Vec<Ident>) = generate_pattern(quote! { Self }, &s.fields);
+            let doc: TokenStream2 = generate_node(&prefix, &tattrs, &s.fields, &bindings)?;
+            quote! {
+                let #pat = self;
+                #doc
+            }
+        },
+        Data::Enum(e) => {
+            // Find the strength of every operator, as (precedence, fixity), where postfix binds
+            // tightest and infix loosest, and the variant that parsing parenthesized operands gives
+            let mut strengths: Vec<TokenStream2> = Vec::new();
+            for Variant { attrs, ident: vident, fields, .. } in &e.variants {
+                let vattrs = NodeAttrs::try_from(attrs.as_slice())?;
+                if let (Some(fixity), Some(prec)) = (&vattrs.fixity, &vattrs.prec) {
+                    let rank: u8 = match fixity.to_string().as_str() {
+                        "infix" => 0,
+                        "prefix" => 1,
+                        _ => 2,
+                    };
+                    strengths.push(quote! { Self::#vident { .. } => (#prec, #rank) });
+                } else if paren.is_none() && is_paren(&vattrs, fields, &ident)? {
+                    paren = Some(vident.to_string());
+                }
+            }
+
+            // Print every variant
+            let mut arms: Vec<TokenStream2> = Vec::with_capacity(e.variants.len());
+            for Variant { attrs, ident: vident, fields, .. } in &e.variants {
+                let vattrs = NodeAttrs::try_from(attrs.as_slice())?;
+                let (pat, bindings): (TokenStream2, Vec<Ident>) = generate_pattern(quote! { Self::#vident }, fields);
+                let doc: TokenStream2 = if vattrs.error.is_some() {
+                    quote! { #prefix::doc::Doc::Nil }
+                } else if let (Some(fixity), Some(prec)) = (&vattrs.fixity, &vattrs.prec) {
+                    let rank: u8 = match fixity.to_string().as_str() {
+                        "infix" => 0,
+                        "prefix" => 1,
+                        _ => 2,
+                    };
+                    generate_operator(&prefix, &vattrs, fields, &bindings, &quote! { (#prec, #rank) })?
+                } else {
+                    generate_node(&prefix, &vattrs, fields, &bindings)?
+                };
+                arms.push(quote! { #pat => #doc });
+            }
+            if strengths.is_empty() {
+                paren = None;
+                quote! { match self { #(#arms,)* } }
+            } else {
+                quote! {
+                    let __strength = |node: &Self| -> (u32, u8) {
+                        match node {
+                            #(#strengths,)*
+                            _ => (u32::MAX, u8::MAX),
+                        }
+                    };
+                    match self { #(#arms,)* }
+                }
+            }
+        },
+        Data::Union(u) => return Err(Error::new(u.union_token.span(), "Cannot derive Unparse on unions")),
+    };
+
+    // Generate the impl
+    let (impl_gen, ty_gen, where_clause) = generics.split_for_impl();
+    let paren: Option<TokenStream2> = paren.map(|paren| {
+        quote! {
+            #[inline]
+            fn paren() -> ::std::option::Option<&'static ::std::primitive::str> { ::std::option::Option::Some(#paren) }
+        }
+    });
+    Ok(quote! {
+        #[automatically_derived]
+        #[allow(unused_variables)]
+        impl #impl_gen #prefix::Unparse for #ident #ty_gen #where_clause {
+            #[inline]
+            fn unparse(&self) -> #prefix::doc::Doc {
+                #body
+            }
+
+            #paren
+        }
+    })
+}
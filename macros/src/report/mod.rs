@@ -0,0 +1,876 @@
+//  MOD.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 19:57:10
+//  Last edited:
+//    18 Oct 2026, 23:28:39
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements macros for the `report`-library.
+//
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{ToTokens, format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned as _;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, GenericArgument, Ident, LitStr, Meta, Path, PathArguments, PathSegment, Token, Type, Variant};
+
+
+/***** ATTRIBUTES *****/
+/// Defines what we may learn from toplevel attributes.
+struct ToplevelAttrs {
+    /// The prefix path to the library structs.
+    prefix: Path,
+    /// Any report-attributes that act as defaults for the variants.
+    report: ReportAttrs,
+}
+impl Default for ToplevelAttrs {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            prefix: Path {
+                leading_colon: Some(Default::default()),
+                segments:      {
+                    let mut segments = Punctuated::new();
+                    segments.push(PathSegment { ident: Ident::new("ast_toolkit_2", Span::call_site()), arguments: PathArguments::None });
+                    segments.push(PathSegment { ident: Ident::new("report", Span::call_site()), arguments: PathArguments::None });
+                    segments
+                },
+            },
+            report: ReportAttrs::default(),
+        }
+    }
+}
+impl TryFrom<&[Attribute]> for ToplevelAttrs {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &[Attribute]) -> Result<Self, Self::Error> {
+        let mut res: Self = Default::default();
+        for attr in value {
+            match &attr.meta {
+                Meta::List(l) if l.path.is_ident("report") => {
+                    // Parse the contents as a list of metas
+                    let attrs: Punctuated<ReportAttr, Token![,]> = l.parse_args_with(Punctuated::parse_terminated)?;
+                    for attr in attrs {
+                        match attr {
+                            ReportAttr::Prefix(path) => res.prefix = path,
+                            attr => res.report.apply(attr),
+                        }
+                    }
+                },
+                Meta::List(l) if l.path.is_ident("note") => res.report.notes.push(l.parse_args()?),
+                Meta::List(l) if l.path.is_ident("help") => res.report.helps.push(l.parse_args()?),
+                _ => continue,
+            }
+        }
+        Ok(res)
+    }
+}
+
+
+
+/// Defines what we may learn from `#[report(...)]`, `#[note(...)]` and `#[help(...)]` attributes
+/// on toplevel structs or variants.
+#[derive(Clone, Default)]
+struct ReportAttrs {
+    /// The main message of the report.
+    message:    Option<LitStr>,
+    /// The ID of the main message of the report, if it's localized.
+    message_id: Option<LitStr>,
+    /// The code of the report.
+    code:       Option<CodeAttr>,
+    /// The lint of the report.
+    lint:       Option<Path>,
+    /// The severity of the report.
+    severity:   Option<Ident>,
+    /// Any notes to add.
+    notes:      Vec<LitStr>,
+    /// Any help messages to add.
+    helps:      Vec<LitStr>,
+}
+impl ReportAttrs {
+    /// Updates this ReportAttrs with a parsed attribute.
+    ///
+    /// # Arguments
+    /// - `attr`: The [`ReportAttr`] to apply. Note that this should not be the prefix, as that is
+    ///   only allowed on the toplevel.
+    #[inline]
+    fn apply(&mut self, attr: ReportAttr) {
+        match attr {
+            ReportAttr::Prefix(_) => unreachable!(),
+            ReportAttr::Message(msg) => {
+                self.message = Some(msg);
+                self.message_id = None;
+            },
+            ReportAttr::MessageId(id) => {
+                self.message_id = Some(id);
+                self.message = None;
+            },
+            ReportAttr::Code(code) => self.code = Some(code),
+            ReportAttr::Lint(lint) => self.lint = Some(lint),
+            ReportAttr::Severity(sev) => self.severity = Some(sev),
+        }
+    }
+
+    /// Parses the attributes of a variant on top of toplevel defaults.
+    ///
+    /// # Arguments
+    /// - `defaults`: Some other ReportAttrs (typically from the toplevel) that provide defaults.
+    /// - `attrs`: The list of [`Attribute`]s to parse.
+    ///
+    /// # Returns
+    /// A new ReportAttrs that combines both.
+    ///
+    /// # Errors
+    /// This function errors if any of the attributes was malformed.
+    fn parse_variant(defaults: &Self, attrs: &[Attribute]) -> Result<Self, Error> {
+        let mut res: Self = Self {
+            message: defaults.message.clone(),
+            message_id: defaults.message_id.clone(),
+            code: defaults.code.clone(),
+            lint: defaults.lint.clone(),
+            severity: defaults.severity.clone(),
+            ..Self::default()
+        };
+        for attr in attrs {
+            match &attr.meta {
+                Meta::List(l) if l.path.is_ident("report") => {
+                    let attrs: Punctuated<ReportAttr, Token![,]> = l.parse_args_with(Punctuated::parse_terminated)?;
+                    for attr in attrs {
+                        if let ReportAttr::Prefix(path) = attr {
+                            return Err(Error::new(path.span(), "The prefix can only be given on the toplevel"));
+                        }
+                        res.apply(attr);
+                    }
+                },
+                Meta::List(l) if l.path.is_ident("note") => res.notes.push(l.parse_args()?),
+                Meta::List(l) if l.path.is_ident("help") => res.helps.push(l.parse_args()?),
+                _ => continue,
+            }
+        }
+        // Toplevel notes & helps apply to all variants
+        res.notes.extend(defaults.notes.iter().cloned());
+        res.helps.extend(defaults.helps.iter().cloned());
+        Ok(res)
+    }
+}
+
+/// Defines the possible ways to give the code of a report.
+#[derive(Clone)]
+enum CodeAttr {
+    /// It's given as a string literal, and not checked.
+    Literal(LitStr),
+    /// It's given as a path to a `Code` (e.g., declared with `codes!()`), so it's checked by the
+    /// compiler.
+    Registered(Path),
+}
+
+/// Defines possible `#[report(...)]`-attributes.
+enum ReportAttr {
+    /// Defines the prefix.
+    Prefix(Path),
+    /// Defines the main message.
+    Message(LitStr),
+    /// Defines the ID of the main message.
+    MessageId(LitStr),
+    /// Defines the code.
+    Code(CodeAttr),
+    /// Defines the lint.
+    Lint(Path),
+    /// Defines the severity.
+    Severity(Ident),
+}
+impl Parse for ReportAttr {
+    #[inline]
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Parse the identifier first
+        let ident: Path = input.parse()?;
+        input.parse::<Token![=]>()?;
+        if ident.is_ident("prefix") {
+            Ok(Self::Prefix(input.parse()?))
+        } else if ident.is_ident("message") {
+            Ok(Self::Message(input.parse()?))
+        } else if ident.is_ident("message_id") {
+            Ok(Self::MessageId(input.parse()?))
+        } else if ident.is_ident("code") {
+            if input.peek(LitStr) { Ok(Self::Code(CodeAttr::Literal(input.parse()?))) } else { Ok(Self::Code(CodeAttr::Registered(input.parse()?))) }
+        } else if ident.is_ident("lint") {
+            Ok(Self::Lint(input.parse()?))
+        } else if ident.is_ident("severity") {
+            let sev: Ident = input.parse()?;
+            if sev != "error" && sev != "warning" && sev != "note" {
+                return Err(Error::new(sev.span(), format!("Unknown severity {:?} (expected 'error', 'warning' or 'note')", sev.to_string())));
+            }
+            Ok(Self::Severity(sev))
+        } else {
+            Err(Error::new(ident.span(), format!("Unknown attribute {:?}", ident.to_token_stream().to_string())))
+        }
+    }
+}
+
+
+
+/// Defines what we may learn from field attributes.
+#[derive(Default)]
+struct FieldAttrs {
+    /// Whether this field is labelled, and if so, how.
+    label:       Option<LabelAttr>,
+    /// Whether this field's value is used as a note.
+    note:        bool,
+    /// Whether this field's value is used as a help message.
+    help:        bool,
+    /// Any suggestions to make at this field's span.
+    suggestions: Vec<SuggestionAttr>,
+    /// Whether this field is an argument to localized messages, and if so, under which name.
+    arg:         Option<Option<LitStr>>,
+}
+impl TryFrom<&[Attribute]> for FieldAttrs {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &[Attribute]) -> Result<Self, Self::Error> {
+        let mut res: Self = Default::default();
+        for attr in value {
+            match &attr.meta {
+                Meta::Path(p) if p.is_ident("label") => res.label = Some(LabelAttr { primary: false, each: None, message: None }),
+                Meta::List(l) if l.path.is_ident("label") => res.label = Some(l.parse_args()?),
+                Meta::Path(p) if p.is_ident("note") => res.note = true,
+                Meta::Path(p) if p.is_ident("help") => res.help = true,
+                Meta::List(l) if l.path.is_ident("suggestion") => res.suggestions.push(l.parse_args()?),
+                Meta::Path(p) if p.is_ident("arg") => res.arg = Some(None),
+                Meta::List(l) if l.path.is_ident("arg") => res.arg = Some(Some(l.parse_args()?)),
+                Meta::List(l) if l.path.is_ident("note") || l.path.is_ident("help") => {
+                    return Err(Error::new(l.span(), "Notes and helps on fields cannot have a message; the field's value is used instead"));
+                },
+                _ => continue,
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Defines the contents of a `#[label(...)]`-attribute.
+struct LabelAttr {
+    /// Whether this is the primary label.
+    primary: bool,
+    /// Whether the field is a collection with a label per element.
+    each:    Option<Ident>,
+    /// The message to show, if any.
+    message: Option<LitStr>,
+}
+impl Parse for LabelAttr {
+    #[inline]
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut res = Self { primary: false, each: None, message: None };
+        while input.peek(Ident) {
+            let ident: Ident = input.parse()?;
+            if ident == "primary" {
+                res.primary = true;
+            } else if ident == "each" {
+                res.each = Some(ident);
+            } else {
+                return Err(Error::new(ident.span(), format!("Unknown label attribute {:?}", ident.to_string())));
+            }
+            if input.is_empty() {
+                return Ok(res);
+            }
+            input.parse::<Token![,]>()?;
+        }
+        if !input.is_empty() {
+            res.message = Some(input.parse()?);
+        }
+        Ok(res)
+    }
+}
+
+
+/// Defines the contents of a `#[suggestion(...)]`-attribute.
+struct SuggestionAttr {
+    /// The message explaining the suggestion.
+    message:       LitStr,
+    /// The replacement text.
+    code:          LitStr,
+    /// The name of the applicability variant.
+    applicability: Ident,
+}
+impl Parse for SuggestionAttr {
+    #[inline]
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let message: LitStr = input.parse()?;
+        let mut code: Option<LitStr> = None;
+        let mut applicability: Ident = Ident::new("Unspecified", Span::call_site());
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let ident: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            if ident == "code" {
+                code = Some(input.parse()?);
+            } else if ident == "applicability" {
+                let value: LitStr = input.parse()?;
+                let variant: &str = match value.value().as_str() {
+                    "machine-applicable" => "MachineApplicable",
+                    "maybe-incorrect" => "MaybeIncorrect",
+                    "has-placeholders" => "HasPlaceholders",
+                    "unspecified" => "Unspecified",
+                    other => {
+                        return Err(Error::new(
+                            value.span(),
+                            format!(
+                                "Unknown applicability {other:?} (expected 'machine-applicable', 'maybe-incorrect', 'has-placeholders' or \
+                                 'unspecified')"
+                            ),
+                        ));
+                    },
+                };
+                applicability = Ident::new(variant, value.span());
+            } else {
+                return Err(Error::new(ident.span(), format!("Unknown suggestion attribute {:?}", ident.to_string())));
+            }
+        }
+        let Some(code) = code else {
+            return Err(Error::new(message.span(), "Missing the replacement text; give it with `code = \"...\"`"));
+        };
+        Ok(Self { message, code, applicability })
+    }
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// Defines how many values a field provides.
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Arity {
+    /// The field is a single value.
+    One,
+    /// The field is an [`Option`] of a value.
+    Optional,
+    /// The field is a collection of values.
+    Each,
+}
+
+
+
+/// Collects everything we need to know about a single struct or variant to generate its report.
+struct Case {
+    /// The pattern that matches the struct or variant and binds its fields.
+    pat:         TokenStream2,
+    /// The report-attributes given to it.
+    attrs:       ReportAttrs,
+    /// The binding & message of the primary label.
+    primary:     (Ident, Option<LitStr>),
+    /// The bindings & messages of any secondary labels, plus how many labels they provide.
+    labels:      Vec<(Ident, Option<LitStr>, Arity)>,
+    /// The bindings of fields used as notes, plus whether they're optional.
+    notes:       Vec<(Ident, bool)>,
+    /// The bindings of fields used as help messages, plus whether they're optional.
+    helps:       Vec<(Ident, bool)>,
+    /// The bindings of fields with suggestions, plus whether they're optional.
+    suggestions: Vec<(Ident, SuggestionAttr, bool)>,
+    /// The bindings of fields used as arguments to localized messages, plus their names.
+    args:        Vec<(Ident, LitStr)>,
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Checks whether a type is an [`Option`].
+///
+/// # Arguments
+/// - `ty`: The [`Type`] to check.
+///
+/// # Returns
+/// True if the last segment of the type's path is `Option` with a single generic argument.
+fn is_option(ty: &Type) -> bool {
+    if let Type::Path(p) = ty
+        && let Some(last) = p.path.segments.last()
+        && let PathArguments::AngleBracketed(args) = &last.arguments
+    {
+        return last.ident == "Option" && args.args.len() == 1 && matches!(args.args.first(), Some(GenericArgument::Type(_)));
+    }
+    false
+}
+
+/// Rewrites positional arguments in a format string such that they refer to the bindings of tuple
+/// fields.
+///
+/// Specifically, replaces `{0}` by `{_0}`, such that it can be implicitly captured by `format!()`.
+///
+/// # Arguments
+/// - `lit`: The [`LitStr`] to rewrite.
+///
+/// # Returns
+/// A new [`LitStr`] with the same span but rewritten contents.
+fn rewrite_fmt(lit: &LitStr) -> LitStr {
+    let value: String = lit.value();
+    let mut res: String = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        res.push(c);
+        if c == '{' {
+            match chars.peek() {
+                Some('{') => {
+                    // Escaped brace
+                    res.push('{');
+                    chars.next();
+                },
+                Some(c) if c.is_ascii_digit() => res.push('_'),
+                _ => {},
+            }
+        }
+    }
+    LitStr::new(&res, lit.span())
+}
+
+/// Checks whether a message ID is a valid Fluent reference.
+///
+/// # Arguments
+/// - `id`: The [`LitStr`] with the ID, either `id` or `id.attribute`.
+///
+/// # Errors
+/// This function errors if the ID or its attribute isn't a valid identifier.
+fn check_id(id: &LitStr) -> Result<(), Error> {
+    let value: String = id.value();
+    let valid = |part: &str| {
+        let mut chars = part.chars();
+        chars.next().is_some_and(|c| c.is_ascii_alphabetic()) && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    };
+    let (main, attr): (&str, Option<&str>) = match value.split_once('.') {
+        Some((main, attr)) => (main, Some(attr)),
+        None => (&value, None),
+    };
+    if valid(main) && attr.is_none_or(valid) {
+        Ok(())
+    } else {
+        Err(Error::new(id.span(), format!("Illegal message ID {value:?} (expected 'id' or 'id.attribute', with ASCII letters, digits, '_' and '-')")))
+    }
+}
+
+/// Collects all messages of a case, which are message IDs if it's localized.
+///
+/// Notes and help messages given by fields are not included, as those are texts.
+///
+/// # Arguments
+/// - `case`: The [`Case`] to collect the messages of.
+///
+/// # Returns
+/// A list of [`LitStr`]s with the messages.
+fn case_ids(case: &Case) -> Vec<&LitStr> {
+    let mut res: Vec<&LitStr> = Vec::new();
+    res.extend(case.attrs.message_id.iter().chain(case.attrs.message.iter()));
+    res.extend(case.primary.1.iter());
+    res.extend(case.labels.iter().filter_map(|(_, message, _)| message.as_ref()));
+    res.extend(case.attrs.notes.iter().chain(case.attrs.helps.iter()));
+    res.extend(case.suggestions.iter().map(|(_, sugg, _)| &sugg.message));
+    res
+}
+
+/// Analyses the fields of a struct or variant.
+///
+/// # Arguments
+/// - `path`: The path to the struct or variant (e.g., `Self` or `Self::Foo`).
+/// - `span`: The span to use for errors concerning the struct or variant as a whole.
+/// - `fields`: The [`Fields`] to analyse.
+/// - `attrs`: The [`ReportAttrs`] already parsed for it.
+///
+/// # Returns
+/// A [`Case`] describing how to generate the report.
+///
+/// # Errors
+/// This function errors if any of the fields' attributes was malformed, or if there isn't
+/// exactly one primary label.
+fn analyse_fields(path: TokenStream2, span: Span, fields: &Fields, attrs: ReportAttrs) -> Result<Case, Error> {
+    if attrs.message.is_none() && attrs.message_id.is_none() {
+        return Err(Error::new(span, "Missing a message; give one with `#[report(message = \"...\")]` or `#[report(message_id = \"...\")]`"));
+    }
+
+    let mut bindings: Vec<TokenStream2> = Vec::with_capacity(fields.len());
+    let mut primary: Option<(Ident, Option<LitStr>)> = None;
+    let mut labels: Vec<(Ident, Option<LitStr>, Arity)> = Vec::new();
+    let mut notes: Vec<(Ident, bool)> = Vec::new();
+    let mut helps: Vec<(Ident, bool)> = Vec::new();
+    let mut suggestions: Vec<(Ident, SuggestionAttr, bool)> = Vec::new();
+    let mut args: Vec<(Ident, LitStr)> = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        // Decide the binding
+        let binding: Ident = match &field.ident {
+            Some(ident) => {
+                bindings.push(quote! { #ident });
+                ident.clone()
+            },
+            None => {
+                let ident: Ident = format_ident!("_{}", i);
+                bindings.push(quote! { #ident });
+                ident
+            },
+        };
+
+        // Read the attributes
+        let fattrs = FieldAttrs::try_from(field.attrs.as_slice())?;
+        let optional: bool = is_option(&field.ty);
+        if let Some(LabelAttr { primary: is_primary, each, message }) = fattrs.label {
+            if is_primary {
+                if primary.is_some() {
+                    return Err(Error::new(field.span(), "There can only be one primary label"));
+                }
+                if optional {
+                    return Err(Error::new(field.ty.span(), "The primary label cannot be optional"));
+                }
+                if let Some(each) = each {
+                    return Err(Error::new(each.span(), "The primary label cannot be a collection"));
+                }
+                primary = Some((binding.clone(), message));
+            } else {
+                let arity: Arity = if each.is_some() {
+                    Arity::Each
+                } else if optional {
+                    Arity::Optional
+                } else {
+                    Arity::One
+                };
+                labels.push((binding.clone(), message, arity));
+            }
+        }
+        if fattrs.note {
+            notes.push((binding.clone(), optional));
+        }
+        if fattrs.help {
+            helps.push((binding.clone(), optional));
+        }
+        for sugg in fattrs.suggestions {
+            suggestions.push((binding.clone(), sugg, optional));
+        }
+        if let Some(name) = fattrs.arg {
+            if attrs.message_id.is_none() {
+                return Err(Error::new(field.span(), "Arguments can only be given to localized reports; give a `#[report(message_id = \"...\")]`"));
+            }
+            let name: LitStr = match (name, &field.ident) {
+                (Some(name), _) => name,
+                (None, Some(ident)) => LitStr::new(ident.to_string().trim_start_matches("r#"), ident.span()),
+                (None, None) => return Err(Error::new(field.span(), "Unnamed fields need an explicit argument name; give one with `#[arg(\"...\")]`")),
+            };
+            args.push((binding.clone(), name));
+        }
+    }
+    let Some(primary) = primary else {
+        return Err(Error::new(span, "Missing a primary label; mark one field with `#[label(primary)]`"));
+    };
+
+    // Generate the pattern
+    let pat: TokenStream2 = match fields {
+        Fields::Named(_) => quote! { #path { #(#bindings),* } },
+        Fields::Unnamed(_) => quote! { #path ( #(#bindings),* ) },
+        Fields::Unit => quote! { #path },
+    };
+    let case = Case { pat, attrs, primary, labels, notes, helps, suggestions, args };
+    if case.attrs.message_id.is_some() {
+        for id in case_ids(&case) {
+            check_id(id)?;
+        }
+    }
+    Ok(case)
+}
+
+/// Generates a `match self { ... }` over all cases.
+///
+/// # Arguments
+/// - `cases`: The [`Case`]s to match.
+/// - `body`: A closure generating the body of every match arm.
+///
+/// # Returns
+/// A [`TokenStream2`] encoding the match.
+fn generate_match(cases: &[Case], mut body: impl FnMut(&Case) -> TokenStream2) -> TokenStream2 {
+    if cases.is_empty() {
+        return quote! { match *self {} };
+    }
+    let arms = cases.iter().map(|case| {
+        let pat: &TokenStream2 = &case.pat;
+        let body: TokenStream2 = body(case);
+        quote! { #pat => { #body } }
+    });
+    quote! {
+        match self {
+            #(#arms,)*
+        }
+    }
+}
+
+/// Generates an expression that turns a field into a list of texts (i.e., notes or helps).
+///
+/// # Arguments
+/// - `fields`: The bindings of the fields and whether they're optional.
+///
+/// # Returns
+/// A [`TokenStream2`] encoding statements that push to a `__report_res`-vector.
+fn generate_field_texts(fields: &[(Ident, bool)]) -> TokenStream2 {
+    let stmts = fields.iter().map(|(binding, optional)| {
+        if *optional {
+            quote! { if let ::std::option::Option::Some(#binding) = #binding { __report_res.push(::std::string::ToString::to_string(#binding)); } }
+        } else {
+            quote! { __report_res.push(::std::string::ToString::to_string(#binding)); }
+        }
+    });
+    quote! { #(#stmts)* }
+}
+
+/// Generates an expression that produces a `String` from a message.
+///
+/// # Arguments
+/// - `message`: The message to format.
+/// - `localized`: Whether the message is a message ID, which is used as-is instead of formatted.
+///
+/// # Returns
+/// A [`TokenStream2`] encoding the expression.
+fn generate_message(message: &LitStr, localized: bool) -> TokenStream2 {
+    if localized {
+        quote! { ::std::string::ToString::to_string(#message) }
+    } else {
+        let message: LitStr = rewrite_fmt(message);
+        quote! { ::std::format!(#message) }
+    }
+}
+
+/// Generates an expression that produces an `Option<String>` from a message.
+///
+/// # Arguments
+/// - `message`: The message to format, if any.
+/// - `localized`: Whether the message is a message ID, which is used as-is instead of formatted.
+///
+/// # Returns
+/// A [`TokenStream2`] encoding the expression.
+fn generate_opt_message(message: &Option<LitStr>, localized: bool) -> TokenStream2 {
+    match message {
+        Some(msg) => {
+            let msg: TokenStream2 = generate_message(msg, localized);
+            quote! { ::std::option::Option::Some(#msg) }
+        },
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Implements the `Report` derive-macro.
+///
+/// See the [actual macro](super::report()) for more information.
+///
+/// # Arguments
+/// - `input`: A [`TokenStream2`] encoding the input to parse and derive from.
+///
+/// # Returns
+/// A stream that encodes the generated impl.
+///
+/// # Errors
+/// This function may error if something about the input was malformed (probably attributes).
+pub fn report(input: TokenStream2) -> Result<TokenStream2, Error> {
+    // Parse as derive macro input
+    let DeriveInput { attrs, vis: _, ident, generics, data } = syn::parse2(input)?;
+    let tattrs = ToplevelAttrs::try_from(attrs.as_slice())?;
+
+    // Collect the cases to generate
+    let cases: Vec<Case> = match data {
+        Data::Struct(s) => vec![analyse_fields(quote! { Self }, ident.span(), &s.fields, tattrs.report.clone())?],
+        Data::Enum(e) => {
+            let mut cases: Vec<Case> = Vec::with_capacity(e.variants.len());
+            for Variant { attrs, ident: vident, fields, .. } in e.variants {
+                let vattrs = ReportAttrs::parse_variant(&tattrs.report, &attrs)?;
+                cases.push(analyse_fields(quote! { Self::#vident }, vident.span(), &fields, vattrs)?);
+            }
+            cases
+        },
+        Data::Union(u) => return Err(Error::new(u.union_token.span(), "Cannot derive Report on unions")),
+    };
+    let prefix: &Path = &tattrs.prefix;
+
+    // Generate the methods
+    let severity = generate_match(&cases, |case| match (&case.attrs.severity, &case.attrs.lint) {
+        (Some(sev), _) if sev == "warning" => quote! { #prefix::Severity::Warning },
+        (Some(sev), _) if sev == "note" => quote! { #prefix::Severity::Note },
+        // NOTE: Without explicit severity, lints are as bad as their default level
+        (None, Some(lint)) => quote! {{
+            const LINT: #prefix::Lint = #lint;
+            match LINT.default {
+                #prefix::Level::Deny => #prefix::Severity::Error,
+                #prefix::Level::Warn | #prefix::Level::Allow => #prefix::Severity::Warning,
+            }
+        }},
+        _ => quote! { #prefix::Severity::Error },
+    });
+    let code = generate_match(&cases, |case| match &case.attrs.code {
+        Some(CodeAttr::Literal(code)) => quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#code)) },
+        Some(CodeAttr::Registered(code)) => quote! {{
+            const CODE: #prefix::Code = #code;
+            ::std::option::Option::Some(::std::string::ToString::to_string(CODE.name))
+        }},
+        None => quote! { ::std::option::Option::None },
+    });
+    let lint = generate_match(&cases, |case| match &case.attrs.lint {
+        Some(lint) => quote! {{
+            const LINT: #prefix::Lint = #lint;
+            ::std::option::Option::Some(&LINT)
+        }},
+        None => quote! { ::std::option::Option::None },
+    });
+    let message = generate_match(&cases, |case| match (&case.attrs.message_id, &case.attrs.message) {
+        (Some(id), _) => generate_message(id, true),
+        (None, Some(msg)) => generate_message(msg, false),
+        // NOTE: Always one of both, checked when analysing
+        (None, None) => unreachable!(),
+    });
+    let primary = generate_match(&cases, |case| {
+        let (binding, message) = &case.primary;
+        let message: TokenStream2 = generate_opt_message(message, case.attrs.message_id.is_some());
+        quote! { #prefix::Label { span: #prefix::Spanned::span(#binding), message: #message } }
+    });
+    let labels = generate_match(&cases, |case| {
+        let stmts = case.labels.iter().map(|(binding, message, arity)| {
+            let message: TokenStream2 = generate_opt_message(message, case.attrs.message_id.is_some());
+            match arity {
+                Arity::One => quote! { __report_res.push(#prefix::Label { span: #prefix::Spanned::span(#binding), message: #message }); },
+                Arity::Optional => quote! {
+                    if let ::std::option::Option::Some(#binding) = #binding {
+                        __report_res.push(#prefix::Label { span: #prefix::Spanned::span(#binding), message: #message });
+                    }
+                },
+                Arity::Each => quote! {
+                    for #binding in #binding {
+                        __report_res.push(#prefix::Label { span: #prefix::Spanned::span(#binding), message: #message });
+                    }
+                },
+            }
+        });
+        quote! {
+            let mut __report_res = ::std::vec::Vec::new();
+            #(#stmts)*
+            __report_res
+        }
+    });
+    let notes = generate_match(&cases, |case| {
+        let lits = case.attrs.notes.iter().map(|lit| generate_message(lit, case.attrs.message_id.is_some()));
+        let fields: TokenStream2 = generate_field_texts(&case.notes);
+        quote! {
+            let mut __report_res: ::std::vec::Vec<::std::string::String> = ::std::vec![#(#lits),*];
+            #fields
+            __report_res
+        }
+    });
+    let helps = generate_match(&cases, |case| {
+        let lits = case.attrs.helps.iter().map(|lit| generate_message(lit, case.attrs.message_id.is_some()));
+        let fields: TokenStream2 = generate_field_texts(&case.helps);
+        quote! {
+            let mut __report_res: ::std::vec::Vec<::std::string::String> = ::std::vec![#(#lits),*];
+            #fields
+            __report_res
+        }
+    });
+    let suggestions = generate_match(&cases, |case| {
+        let stmts = case.suggestions.iter().map(|(binding, SuggestionAttr { message, code, applicability }, optional)| {
+            let message: TokenStream2 = generate_message(message, case.attrs.message_id.is_some());
+            let code: LitStr = rewrite_fmt(code);
+            let sugg: TokenStream2 = quote! {
+                #prefix::Suggestion {
+                    message: #message,
+                    span: #prefix::Spanned::span(#binding),
+                    replacement: ::std::format!(#code),
+                    applicability: #prefix::Applicability::#applicability,
+                }
+            };
+            if *optional {
+                quote! { if let ::std::option::Option::Some(#binding) = #binding { __report_res.push(#sugg); } }
+            } else {
+                quote! { __report_res.push(#sugg); }
+            }
+        });
+        quote! {
+            let mut __report_res = ::std::vec::Vec::new();
+            #(#stmts)*
+            __report_res
+        }
+    });
+
+    let args = generate_match(&cases, |case| {
+        if case.attrs.message_id.is_none() {
+            return quote! { ::std::option::Option::None };
+        }
+        let stmts = case.args.iter().map(|(binding, name)| {
+            quote! { __report_res.insert(::std::string::ToString::to_string(#name), ::std::string::ToString::to_string(#binding)); }
+        });
+        quote! {
+            let mut __report_res = ::std::collections::BTreeMap::new();
+            #(#stmts)*
+            ::std::option::Option::Some(__report_res)
+        }
+    });
+
+    // Generate the list of message IDs, if localized
+    let mut ids: Vec<String> = Vec::new();
+    for case in cases.iter().filter(|case| case.attrs.message_id.is_some()) {
+        for id in case_ids(case) {
+            if !ids.contains(&id.value()) {
+                ids.push(id.value());
+            }
+        }
+    }
+    let (impl_gen, ty_gen, where_clause) = generics.split_for_impl();
+    let localized: Option<TokenStream2> = cases.iter().any(|case| case.attrs.message_id.is_some()).then(|| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_gen #prefix::Localized for #ident #ty_gen #where_clause {
+                const MESSAGE_IDS: &'static [&'static str] = &[#(#ids),*];
+            }
+        }
+    });
+
+    // Generate the impl
+    Ok(quote! {
+        #[automatically_derived]
+        #[allow(unused_variables, unused_mut)]
+        impl #impl_gen #prefix::Report for #ident #ty_gen #where_clause {
+            #[inline]
+            fn severity(&self) -> #prefix::Severity { #severity }
+
+            #[inline]
+            fn code(&self) -> ::std::option::Option<::std::string::String> { #code }
+
+            #[inline]
+            fn lint(&self) -> ::std::option::Option<&'static #prefix::Lint> { #lint }
+
+            #[inline]
+            fn message(&self) -> ::std::string::String { #message }
+
+            #[inline]
+            fn primary(&self) -> #prefix::Label { #primary }
+
+            #[inline]
+            fn labels(&self) -> ::std::vec::Vec<#prefix::Label> { #labels }
+
+            #[inline]
+            fn notes(&self) -> ::std::vec::Vec<::std::string::String> { #notes }
+
+            #[inline]
+            fn helps(&self) -> ::std::vec::Vec<::std::string::String> { #helps }
+
+            #[inline]
+            fn suggestions(&self) -> ::std::vec::Vec<#prefix::Suggestion> { #suggestions }
+
+            #[inline]
+            fn args(&self) -> ::std::option::Option<::std::collections::BTreeMap<::std::string::String, ::std::string::String>> { #args }
+        }
+
+        #localized
+    })
+}
@@ -0,0 +1,59 @@
+//  DIFF.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 22:10:41
+//  Last edited:
+//    18 Oct 2026, 22:10:41
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements a line-by-line diff between two texts, shared by the
+//!   snapshot and round-trip testers.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+
+
+/***** LIBRARY *****/
+/// Writes a line-by-line diff between an expected and an actual text.
+///
+/// Lines only in the expected text are prefixed with `-`, lines only in the actual text with `+`.
+/// Where lines were replaced, the removed ones are written first.
+pub(crate) struct DiffFormatter<'t> {
+    /// The text that was expected.
+    pub expected: &'t str,
+    /// The text that was produced.
+    pub actual:   &'t str,
+}
+impl<'t> Display for DiffFormatter<'t> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        let (exp, act): (Vec<&str>, Vec<&str>) = (self.expected.lines().collect(), self.actual.lines().collect());
+
+        // Compute the longest common subsequence from the back
+        let mut lcs: Vec<Vec<usize>> = vec![vec![0; act.len() + 1]; exp.len() + 1];
+        for i in (0..exp.len()).rev() {
+            for j in (0..act.len()).rev() {
+                lcs[i][j] = if exp[i] == act[j] { 1 + lcs[i + 1][j + 1] } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+            }
+        }
+
+        // Then walk it to write the diff
+        let (mut i, mut j): (usize, usize) = (0, 0);
+        while i < exp.len() || j < act.len() {
+            if i < exp.len() && j < act.len() && exp[i] == act[j] {
+                writeln!(f, "  {}", exp[i])?;
+                i += 1;
+                j += 1;
+            } else if i < exp.len() && (j >= act.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+                writeln!(f, "- {}", exp[i])?;
+                i += 1;
+            } else {
+                writeln!(f, "+ {}", act[j])?;
+                j += 1;
+            }
+        }
+        Ok(())
+    }
+}
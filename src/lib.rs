@@ -4,7 +4,7 @@
 //  Created:
 //    05 Mar 2025, 17:29:54
 //  Last edited:
-//    06 Mar 2025, 10:36:24
+//    18 Oct 2026, 22:10:41
 //  Auto updated?
 //    Yes
 //
@@ -13,6 +13,8 @@
 //
 
 // Define the libraries
+#[cfg(any(feature = "parser", feature = "report"))]
+mod diff;
 #[cfg(feature = "parser")]
 pub mod parser;
 #[cfg(feature = "punctuated")]
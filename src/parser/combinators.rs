@@ -0,0 +1,96 @@
+//  COMBINATORS.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:44:07
+//  Last edited:
+//    18 Oct 2026, 21:18:33
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines the parsers for keywords, punctuation, delimiters and
+//!   separators that the [derive macro](derive@super::Parse) builds
+//!   nodes from.
+//
+
+use chumsky::prelude::Parser;
+
+use super::{Error, Input, Parse};
+use crate::span::Span;
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Skips any whitespace before a parser.
+///
+/// Unlike chumsky's `padded()`, whitespace after it is left alone, such that the spans of nodes
+/// don't include trailing whitespace. The derive macro wraps every field in this. What
+/// whitespace is depends on the [`Input`] (see [`Input::whitespace()`]).
+///
+/// # Arguments
+/// - `parser`: The [`Parser`] to skip whitespace before.
+///
+/// # Returns
+/// A [`Parser`] that produces what `parser` produces.
+#[inline]
+pub fn padded_left<I: Input, T, P: Parser<I, T, Error = Error<I>> + Clone>(parser: P) -> impl Parser<I, T, Error = Error<I>> + Clone {
+    I::whitespace().ignore_then(parser)
+}
+
+/// Parses a keyword, such as `let` or `fn`.
+///
+/// Unlike [`punct()`], this doesn't match if the keyword is directly followed by more identifier
+/// characters (e.g., `letter` is not `let`) when parsing characters. Whitespace before it is
+/// skipped.
+///
+/// # Arguments
+/// - `keyword`: The keyword to parse.
+///
+/// # Returns
+/// A [`Parser`] that produces the [`Span`] of the keyword.
+#[inline]
+pub fn keyword<I: Input>(keyword: &'static str) -> impl Parser<I, Span, Error = Error<I>> + Clone { padded_left(I::keyword(keyword)) }
+
+/// Parses a piece of punctuation, such as `=` or `->`.
+///
+/// Whitespace before it is skipped.
+///
+/// # Arguments
+/// - `punct`: The punctuation to parse.
+///
+/// # Returns
+/// A [`Parser`] that produces the [`Span`] of the punctuation.
+#[inline]
+pub fn punct<I: Input>(punct: &'static str) -> impl Parser<I, Span, Error = Error<I>> + Clone { padded_left(I::punct(punct)) }
+
+/// Parses something in between delimiters, such as parentheses.
+///
+/// # Arguments
+/// - `open`: The opening delimiter (e.g., `(`).
+/// - `close`: The closing delimiter (e.g., `)`).
+/// - `inner`: The [`Parser`] of what's in between.
+///
+/// # Returns
+/// A [`Parser`] that produces what `inner` produces.
+#[inline]
+pub fn delimited<I: Input, T, P: Parser<I, T, Error = Error<I>> + Clone>(
+    open: &'static str,
+    close: &'static str,
+    inner: P,
+) -> impl Parser<I, T, Error = Error<I>> + Clone {
+    padded_left(inner).delimited_by(punct(open), punct(close))
+}
+
+/// Parses zero or more nodes separated by punctuation, such as a comma-separated list.
+///
+/// # Arguments
+/// - `sep`: The punctuation separating the nodes (e.g., `,`).
+/// - `trailing`: Whether to allow a separator after the last node.
+///
+/// # Returns
+/// A [`Parser`] that produces the parsed nodes.
+#[inline]
+pub fn separated<I: Input, T: Parse<I>>(sep: &'static str, trailing: bool) -> impl Parser<I, Vec<T>, Error = Error<I>> + Clone {
+    let parser = padded_left(T::parser()).separated_by(punct(sep));
+    if trailing { parser.allow_trailing() } else { parser }
+}
@@ -0,0 +1,520 @@
+//  CST.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:11:14
+//  Last edited:
+//    18 Oct 2026, 23:45:40
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines a lossless [concrete syntax tree](Cst), which keeps the
+//!   whitespace and comments around every token such that a parsed file
+//!   can be printed back exactly.
+//!
+//!   The CST is found by parsing the file with comments blanked out, and
+//!   then matching the tokens that the parsed node [unparses](Unparse) to
+//!   against the original text. Anything in between the tokens is
+//!   [`Trivia`]. Hence, the node must print its tokens as they were
+//!   written; literals printed differently (e.g., `007` as `7`, or
+//!   strings with their escapes resolved) cannot be matched.
+//
+
+use std::error;
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::ops::Range;
+use std::sync::Arc;
+
+use super::doc::{Doc, spaced};
+use super::{Parse, Unparse};
+use crate::span::Span;
+
+
+/***** CONSTANTS *****/
+/// The name of the source that tokens inserted by edits point into.
+pub const EDITED: &str = "<edited>";
+
+
+
+
+
+/***** ERRORS *****/
+/// Defines errors that occur when building a [`Cst`].
+#[derive(Debug)]
+pub enum Error {
+    /// The text did not parse.
+    Parse { errors: Vec<super::Error> },
+    /// The printed node had a token that wasn't in the text, such as a literal that is printed
+    /// differently than it was written.
+    Misaligned { expected: String, span: Span },
+}
+impl Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Parse { errors } => write!(f, "Failed to parse text ({} error(s))", errors.len()),
+            Self::Misaligned { expected, span } => write!(f, "Expected token {expected:?} at {span} (node is unparsed to something else)"),
+        }
+    }
+}
+impl error::Error for Error {}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Checks whether some text occurs at a position.
+///
+/// # Arguments
+/// - `chars`: The characters of the text to search.
+/// - `pos`: The position to check.
+/// - `text`: The text to find.
+///
+/// # Returns
+/// True if `chars` continue with `text` at `pos`.
+#[inline]
+fn occurs_at(chars: &[char], pos: usize, text: &str) -> bool { (pos..).zip(text.chars()).all(|(i, c)| chars.get(i) == Some(&c)) }
+
+/// Scans the trivia at a position, if any.
+///
+/// # Arguments
+/// - `chars`: The characters of the text to scan.
+/// - `pos`: The position to scan at.
+/// - `comments`: The [`Comments`] syntax.
+///
+/// # Returns
+/// The kind of the trivia and where it ends, or [`None`] if there is no trivia at `pos`.
+fn scan(chars: &[char], pos: usize, comments: &Comments) -> Option<(TriviaKind, usize)> {
+    let c: char = *chars.get(pos)?;
+    if c == '\n' {
+        return Some((TriviaKind::Newline, pos + 1));
+    } else if c == '\r' && chars.get(pos + 1) == Some(&'\n') {
+        return Some((TriviaKind::Newline, pos + 2));
+    } else if c.is_whitespace() {
+        let len: usize = chars[pos..].iter().take_while(|c| c.is_whitespace() && **c != '\n' && **c != '\r').count();
+        return Some((TriviaKind::Whitespace, pos + len.max(1)));
+    }
+    for open in &comments.line {
+        if occurs_at(chars, pos, open) {
+            let len: usize = chars[pos..].iter().take_while(|c| **c != '\n').count();
+            return Some((TriviaKind::LineComment, pos + len));
+        }
+    }
+    for (open, close) in &comments.block {
+        if occurs_at(chars, pos, open) {
+            let start: usize = pos + open.chars().count();
+            let end: usize = (start..chars.len()).find(|i| occurs_at(chars, *i, close)).map(|i| i + close.chars().count()).unwrap_or(chars.len());
+            return Some((TriviaKind::BlockComment, end));
+        }
+    }
+    None
+}
+
+/// Replaces all comments in a text with spaces, keeping newlines.
+///
+/// This keeps all characters where they were, such that spans in the result point to the same
+/// text in the original.
+///
+/// # Arguments
+/// - `chars`: The characters of the text.
+/// - `comments`: The [`Comments`] syntax.
+///
+/// # Returns
+/// The text without comments.
+fn blank(chars: &[char], comments: &Comments) -> String {
+    let mut res: String = String::with_capacity(chars.len());
+    let mut pos: usize = 0;
+    while pos < chars.len() {
+        // Copy strings as-is, as they may contain comment tokens
+        let c: char = chars[pos];
+        if comments.quotes.contains(&c) {
+            res.push(c);
+            pos += 1;
+            while pos < chars.len() && chars[pos] != c {
+                if chars[pos] == '\\' && pos + 1 < chars.len() {
+                    res.push(chars[pos]);
+                    pos += 1;
+                }
+                res.push(chars[pos]);
+                pos += 1;
+            }
+            if pos < chars.len() {
+                res.push(c);
+                pos += 1;
+            }
+            continue;
+        }
+
+        // Blank comments
+        match scan(chars, pos, comments) {
+            Some((TriviaKind::LineComment | TriviaKind::BlockComment, end)) => {
+                res.extend(chars[pos..end].iter().map(|c| if *c == '\n' || *c == '\r' { *c } else { ' ' }));
+                pos = end;
+            },
+            _ => {
+                res.push(c);
+                pos += 1;
+            },
+        }
+    }
+    res
+}
+
+/// Collects the kinds and texts of the tokens in a [`Doc`], in order.
+///
+/// # Arguments
+/// - `doc`: The [`Doc`] to collect from.
+/// - `texts`: The list to push the tokens to.
+fn collect<'d>(doc: &'d Doc, texts: &mut Vec<(TokenKind, &'d str)>) {
+    match doc {
+        Doc::Text(text) if !text.is_empty() => texts.push((TokenKind::Text, text)),
+        Doc::Keyword(text) => texts.push((TokenKind::Keyword, text)),
+        Doc::Punct(text) => texts.push((TokenKind::Punct, text)),
+        Doc::Nest(_, doc) | Doc::Group(doc) => collect(doc, texts),
+        Doc::Concat(docs) => docs.iter().for_each(|doc| collect(doc, texts)),
+        Doc::Nil | Doc::Text(_) | Doc::Space | Doc::Line(_) | Doc::Hardline => {},
+    }
+}
+
+/// Matches the tokens of a [`Doc`] against a text.
+///
+/// # Arguments
+/// - `source`: The name of the source text.
+/// - `chars`: The characters of the source text.
+/// - `doc`: The [`Doc`] printing the node parsed from the text.
+/// - `comments`: The [`Comments`] syntax.
+///
+/// # Returns
+/// The tokens found, and the trivia after the last one.
+///
+/// # Errors
+/// This function errors if any of the tokens wasn't found where expected.
+fn align(source: &Arc<str>, chars: &[char], doc: &Doc, comments: &Comments) -> Result<(Vec<Token>, Vec<Trivia>), Error> {
+    let mut texts: Vec<(TokenKind, &str)> = Vec::new();
+    collect(doc, &mut texts);
+
+    let mut tokens: Vec<Token> = Vec::with_capacity(texts.len());
+    let mut pos: usize = 0;
+    for text in texts.into_iter().map(Some).chain([None]) {
+        // Find the trivia in between this token and the previous one
+        let mut trivia: Vec<Trivia> = Vec::new();
+        while let Some((kind, end)) = scan(chars, pos, comments) {
+            trivia.push(Trivia { kind, text: chars[pos..end].iter().collect() });
+            pos = end;
+        }
+        let leading: Vec<Trivia> = match tokens.last_mut() {
+            Some(prev) => {
+                // The previous token gets everything up to and including the first newline
+                let split: usize = trivia.iter().position(|t| t.kind == TriviaKind::Newline).map(|i| i + 1).unwrap_or(trivia.len());
+                let leading: Vec<Trivia> = trivia.split_off(split);
+                prev.trailing = trivia;
+                leading
+            },
+            None => trivia,
+        };
+
+        // Then match the token itself
+        let Some((kind, text)) = text else {
+            if pos < chars.len() {
+                return Err(Error::Misaligned { expected: "end of input".into(), span: Span::new(source.clone(), pos..pos + 1) });
+            }
+            return Ok((tokens, leading));
+        };
+        if !occurs_at(chars, pos, text) {
+            return Err(Error::Misaligned { expected: text.into(), span: Span::new(source.clone(), pos..(pos + 1).min(chars.len())) });
+        }
+        let end: usize = pos + text.chars().count();
+        tokens.push(Token { kind, text: text.into(), span: Span::new(source.clone(), pos..end), leading, trailing: Vec::new() });
+        pos = end;
+    }
+    unreachable!()
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the syntax of comments, which are kept as [`Trivia`].
+#[derive(Clone, Debug)]
+pub struct Comments {
+    /// The tokens that start comments running until the end of the line (e.g., `//`).
+    pub line:   Vec<&'static str>,
+    /// The tokens that start and end block comments (e.g., `/*` and `*/`).
+    pub block:  Vec<(&'static str, &'static str)>,
+    /// The quotes of string literals, in which comment tokens are ignored.
+    pub quotes: Vec<char>,
+}
+impl Default for Comments {
+    /// Defaults to C-like comments and double-quoted strings.
+    #[inline]
+    fn default() -> Self { Self { line: vec!["//"], block: vec![("/*", "*/")], quotes: vec!['"'] } }
+}
+
+
+
+/// Defines the kinds of [`Trivia`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TriviaKind {
+    /// Spaces, tabs or other whitespace, except newlines.
+    Whitespace,
+    /// A single newline (`\n` or `\r\n`).
+    Newline,
+    /// A comment running until the end of the line (excluding the newline).
+    LineComment,
+    /// A block comment, including its delimiters.
+    BlockComment,
+}
+
+/// Defines text in between tokens that doesn't matter to the parser, such as whitespace and
+/// comments.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Trivia {
+    /// What kind of trivia this is.
+    pub kind: TriviaKind,
+    /// Its text.
+    pub text: String,
+}
+impl Display for Trivia {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { f.write_str(&self.text) }
+}
+
+
+
+/// Defines the kinds of [`Token`]s, which decide whether they're spaced from the tokens around
+/// them (see [`spaced()`]).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TokenKind {
+    /// Text, such as an identifier (see [`Doc::Text`]).
+    Text,
+    /// A keyword (see [`Doc::Keyword`]).
+    Keyword,
+    /// Punctuation (see [`Doc::Punct`]).
+    Punct,
+}
+
+
+
+/// Defines a token in a [`Cst`], together with the [`Trivia`] around it.
+///
+/// Trivia after a token up to and including the end of its line is trailing, and everything
+/// after that is leading trivia of the next token. This keeps comments at the end of a line with
+/// the token before them, and comments on their own lines with the token after them.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Token {
+    /// What kind of token this is.
+    pub kind:     TokenKind,
+    /// The text of the token.
+    pub text:     String,
+    /// Where the token was found.
+    pub span:     Span,
+    /// The trivia before the token.
+    pub leading:  Vec<Trivia>,
+    /// The trivia after the token.
+    pub trailing: Vec<Trivia>,
+}
+impl Display for Token {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        self.leading.iter().try_for_each(|t| t.fmt(f))?;
+        f.write_str(&self.text)?;
+        self.trailing.iter().try_for_each(|t| t.fmt(f))
+    }
+}
+
+
+
+/// A lossless concrete syntax tree of a parsed text.
+///
+/// It consists of the tokens of the parsed node, each with the [`Trivia`] around them. Printing
+/// it (with [`Display`]) reproduces the parsed text exactly, and tokens can be edited without
+/// losing the comments around them.
+///
+/// # Example
+/// ```
+/// # use std::ops::Range;
+/// # use ast_toolkit_2::parser::{Parse, Span, Unparse};
+/// # use ast_toolkit_2::parser::chumsky::text;
+/// use ast_toolkit_2::parser::cst::{Comments, Cst};
+///
+/// #[derive(Parse, Unparse)]
+/// struct Program {
+///     stmts: Vec<Stmt>,
+/// }
+///
+/// #[derive(Parse, Unparse)]
+/// #[parse(keyword = "let")]
+/// struct Stmt {
+///     #[parse(with = text::ident)]
+///     name: String,
+///     #[parse(punct = ";")]
+///     semi: Span,
+///     #[parse(span)]
+///     span: Span,
+/// }
+///
+/// let text: &str = "// Defines x\nlet x; // Keep me\nlet y;\n";
+/// let (program, mut cst) = Cst::parse::<Program>("main.lang", text, &Comments::default()).unwrap();
+/// assert_eq!(cst.to_string(), text);
+///
+/// // Replace the first statement, keeping the comments around it
+/// let range: Range<usize> = cst.find(&program.stmts[0].span);
+/// let new_stmt = Stmt { name: "z".into(), semi: Span::empty("new", 0), span: Span::empty("new", 0) };
+/// cst.splice(range, &new_stmt).unwrap();
+/// assert_eq!(cst.to_string(), "// Defines x\nlet z; // Keep me\nlet y;\n");
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Cst {
+    /// The tokens, in order.
+    pub tokens: Vec<Token>,
+    /// The trivia after the last token.
+    pub end:    Vec<Trivia>,
+}
+
+// Constructors
+impl Cst {
+    /// Parses a node and its CST from a text.
+    ///
+    /// # Arguments
+    /// - `source`: The name of the source (see [`Sources`](crate::span::Sources)).
+    /// - `text`: The source text.
+    /// - `comments`: The [`Comments`] syntax, which are skipped by the parser and kept in the CST.
+    ///
+    /// # Returns
+    /// The parsed node and its CST.
+    ///
+    /// # Errors
+    /// This function errors if the text didn't parse, or if the node didn't unparse to the tokens
+    /// in the text. The latter happens for tokens that are printed differently than they were
+    /// written (e.g., `007` for an integer printed as `7`).
+    pub fn parse<T: Parse + Unparse>(source: impl Into<Arc<str>>, text: &str, comments: &Comments) -> Result<(T, Self), Error> {
+        let source: Arc<str> = source.into();
+        let chars: Vec<char> = text.chars().collect();
+        let node: T = T::parse_str(source.clone(), &blank(&chars, comments)).map_err(|errors| Error::Parse { errors })?;
+        let (tokens, end): (Vec<Token>, Vec<Trivia>) = align(&source, &chars, &node.unparse(), comments)?;
+        Ok((node, Self { tokens, end }))
+    }
+
+    /// Builds the CST of a node that wasn't parsed, such as a replacement node.
+    ///
+    /// The tokens are laid out as if printed on one line, and point into the [`EDITED`]-source.
+    ///
+    /// # Arguments
+    /// - `node`: The node to build the CST of.
+    ///
+    /// # Returns
+    /// A new CST that prints the node.
+    ///
+    /// # Errors
+    /// This function errors if printing the node's tokens separately gave another text than
+    /// printing the node at once, which happens if its [`Unparse`]-implementation is inconsistent.
+    pub fn of(node: &impl Unparse) -> Result<Self, Error> {
+        let doc: Doc = node.unparse();
+        let chars: Vec<char> = doc.pretty(usize::MAX).chars().collect();
+        let (tokens, end): (Vec<Token>, Vec<Trivia>) = align(&EDITED.into(), &chars, &doc, &Comments { line: vec![], block: vec![], quotes: vec![] })?;
+        Ok(Self { tokens, end })
+    }
+}
+
+// Editing
+impl Cst {
+    /// Finds the tokens within a span.
+    ///
+    /// # Arguments
+    /// - `span`: The [`Span`] to find the tokens of, typically of a parsed node.
+    ///
+    /// # Returns
+    /// The range of indices of the tokens that are completely covered by `span`. If there are
+    /// none, this is an empty range where such tokens would be.
+    pub fn find(&self, span: &Span) -> Range<usize> {
+        let within = |token: &Token| token.span.source == span.source && token.span.start >= span.start && token.span.end <= span.end;
+        let start: usize = self.tokens.iter().position(within).unwrap_or_else(|| {
+            self.tokens.iter().position(|token| token.span.source == span.source && token.span.start >= span.end).unwrap_or(self.tokens.len())
+        });
+        let len: usize = self.tokens[start..].iter().take_while(|token| within(token)).count();
+        start..start + len
+    }
+
+    /// Replaces a range of tokens with the tokens of a node.
+    ///
+    /// The leading trivia of the first replaced token and the trailing trivia of the last one are
+    /// kept, such that comments around the replaced tokens survive. Where the node's tokens would
+    /// touch the tokens around them, they're separated by a space like a [`Doc::Space`] would be
+    /// (e.g., when inserting a statement right after another).
+    ///
+    /// # Arguments
+    /// - `range`: The indices of the tokens to replace. May be empty to insert the node.
+    /// - `node`: The node to replace them with (see [`Cst::of()`]).
+    ///
+    /// # Errors
+    /// This function errors if we failed to build the CST of the node.
+    pub fn splice(&mut self, range: Range<usize>, node: &impl Unparse) -> Result<(), Error> {
+        let Self { mut tokens, end: _ } = Self::of(node)?;
+        if !range.is_empty() {
+            if let Some(first) = tokens.first_mut() {
+                first.leading = std::mem::take(&mut self.tokens[range.start].leading);
+            }
+            if let Some(last) = tokens.last_mut() {
+                last.trailing = std::mem::take(&mut self.tokens[range.end - 1].trailing);
+            }
+        }
+        let (start, len): (usize, usize) = (range.start, tokens.len());
+        self.tokens.splice(range, tokens);
+
+        // Separate the node from its neighbours where nothing is in between them
+        if len > 0 {
+            if start > 0 {
+                self.separate(start - 1);
+            }
+            self.separate(start + len - 1);
+        }
+        Ok(())
+    }
+
+    /// Puts a space in between a token and the next one if nothing is in between them, but a
+    /// [`Doc::Space`] would be printed there.
+    ///
+    /// # Arguments
+    /// - `i`: The index of the first of the two tokens.
+    fn separate(&mut self, i: usize) {
+        let Some([prev, next]) = self.tokens.get_mut(i..i + 2) else { return };
+        if prev.trailing.is_empty() && next.leading.is_empty() && spaced((prev.kind, &prev.text), (next.kind, &next.text)) {
+            prev.trailing.push(Trivia { kind: TriviaKind::Whitespace, text: " ".into() });
+        }
+    }
+
+    /// Removes a range of tokens.
+    ///
+    /// The leading trivia of the first removed token is moved to the token after them (or the end
+    /// of the CST), such that comments before them survive.
+    ///
+    /// # Arguments
+    /// - `range`: The indices of the tokens to remove.
+    pub fn remove(&mut self, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        let leading: Vec<Trivia> = std::mem::take(&mut self.tokens[range.start].leading);
+        match self.tokens.get_mut(range.end) {
+            Some(next) => {
+                next.leading.splice(0..0, leading);
+            },
+            None => {
+                self.end.splice(0..0, leading);
+            },
+        }
+        self.tokens.drain(range);
+    }
+}
+
+// Formatting
+impl Display for Cst {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        self.tokens.iter().try_for_each(|t| t.fmt(f))?;
+        self.end.iter().try_for_each(|t| t.fmt(f))
+    }
+}
@@ -0,0 +1,420 @@
+//  DOC.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:00:50
+//  Last edited:
+//    18 Oct 2026, 23:45:40
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements a Wadler-style document layout engine, which prints
+//!   [`Doc`]uments within a line width by breaking groups that don't fit.
+//
+
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use super::cst::TokenKind;
+
+
+/***** CONSTANTS *****/
+/// The punctuation that is never preceded by a [`Doc::Space`].
+const GLUE_LEFT: &[&str] = &[",", ";", ":", ".", "?", ")", "]", "::"];
+/// The punctuation that is never followed by a [`Doc::Space`].
+const GLUE_RIGHT: &[&str] = &["(", "[", ".", "::"];
+/// The punctuation that is not preceded by a [`Doc::Space`] if it follows a [`Doc::Text`] (e.g.,
+/// calls or indexing).
+const GLUE_TEXT: &[&str] = &["(", "["];
+
+/// The number of spaces to indent delimited documents with.
+pub const INDENT: usize = 4;
+
+
+
+
+
+/***** HELPERS *****/
+/// Whether a (part of a) document is laid out on one line or not.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Mode {
+    /// Lines are printed as their flat alternative.
+    Flat,
+    /// Lines are printed as newlines.
+    Break,
+}
+
+/// The last thing printed, to decide whether to print a [`Doc::Space`].
+#[derive(Clone, Copy, Debug)]
+enum Prev<'t> {
+    /// Nothing or a newline.
+    Start,
+    /// A [`Doc::Text`].
+    Text,
+    /// A [`Doc::Keyword`].
+    Keyword,
+    /// A [`Doc::Punct`].
+    Punct(&'t str),
+}
+impl<'t> Prev<'t> {
+    /// Checks whether a [`Doc::Space`] should be printed between this and the next token.
+    ///
+    /// # Arguments
+    /// - `next`: The next token, if it's punctuation.
+    ///
+    /// # Returns
+    /// False if they glue together, true otherwise.
+    #[inline]
+    fn spaced(&self, next: Option<&str>) -> bool {
+        match (self, next) {
+            (Self::Start, _) => false,
+            (_, Some(next)) if GLUE_LEFT.contains(&next) => false,
+            (Self::Text, Some(next)) if GLUE_TEXT.contains(&next) => false,
+            (Self::Punct(prev), _) if GLUE_RIGHT.contains(prev) => false,
+            _ => true,
+        }
+    }
+}
+
+
+
+/// Lays out a [`Doc`] by keeping a stack of the parts still to print.
+struct Printer<'d> {
+    /// The maximum line width.
+    width:   usize,
+    /// The output so far.
+    out:     String,
+    /// The column we're at.
+    col:     usize,
+    /// The last thing printed.
+    prev:    Prev<'static>,
+    /// Whether a [`Doc::Space`] is waiting for the next token.
+    pending: bool,
+    /// The parts still to print, in reverse order.
+    stack:   Vec<(usize, Mode, &'d Doc)>,
+}
+impl<'d> Printer<'d> {
+    /// Prints a newline and the given indent.
+    #[inline]
+    fn newline(&mut self, indent: usize) {
+        self.out.push('\n');
+        self.out.extend(std::iter::repeat_n(' ', indent));
+        self.col = indent;
+        self.prev = Prev::Start;
+        self.pending = false;
+    }
+
+    /// Prints a token, preceded by any pending [`Doc::Space`].
+    ///
+    /// # Arguments
+    /// - `text`: The token to print.
+    /// - `prev`: What the token is, to decide the space after it.
+    /// - `punct`: The token if it is punctuation, to decide the space before it.
+    fn token(&mut self, text: &str, prev: Prev<'static>, punct: Option<&'static str>) {
+        if std::mem::take(&mut self.pending) && self.prev.spaced(punct) {
+            self.out.push(' ');
+            self.col += 1;
+        }
+        self.out.push_str(text);
+        match text.rfind('\n') {
+            Some(pos) => self.col = text[pos + 1..].chars().count(),
+            None => self.col += text.chars().count(),
+        }
+        self.prev = prev;
+    }
+
+    /// Checks whether the rest of the current line fits if `doc` is printed flat.
+    ///
+    /// # Arguments
+    /// - `indent`: The indent of `doc`.
+    /// - `doc`: The [`Doc`] to print flat.
+    ///
+    /// # Returns
+    /// True if everything up to the next newline fits in the width, false otherwise.
+    fn fits(&self, indent: usize, doc: &Doc) -> bool {
+        let mut rem: isize = isize::try_from(self.width).unwrap_or(isize::MAX) - self.col as isize;
+        let (mut prev, mut pending): (Prev<'static>, bool) = (self.prev, self.pending);
+        let mut stack: Vec<(usize, Mode, &Doc)> = vec![(indent, Mode::Flat, doc)];
+        let mut rest = self.stack.iter().rev();
+        while rem >= 0 {
+            let Some((indent, mode, doc)) = stack.pop().or_else(|| rest.next().cloned()) else { return true };
+            let (text, next, punct): (&str, Prev<'static>, Option<&'static str>) = match doc {
+                Doc::Nil => continue,
+                Doc::Text(text) => (text, Prev::Text, None),
+                Doc::Keyword(text) => (text, Prev::Keyword, None),
+                Doc::Punct(text) => (text, Prev::Punct(text), Some(text)),
+                Doc::Space => {
+                    pending = !matches!(prev, Prev::Start);
+                    continue;
+                },
+                Doc::Line(flat) => match mode {
+                    Mode::Flat => {
+                        pending = false;
+                        rem -= flat.chars().count() as isize;
+                        if !flat.is_empty() {
+                            prev = Prev::Start;
+                        }
+                        continue;
+                    },
+                    Mode::Break => return true,
+                },
+                Doc::Hardline => return mode == Mode::Break,
+                Doc::Nest(extra, doc) => {
+                    stack.push((indent + extra, mode, doc));
+                    continue;
+                },
+                Doc::Group(doc) => {
+                    stack.push((indent, mode, doc));
+                    continue;
+                },
+                Doc::Concat(docs) => {
+                    stack.extend(docs.iter().rev().map(|doc| (indent, mode, doc)));
+                    continue;
+                },
+            };
+
+            // Spaces only count if they are printed, like in `Printer::token()`
+            if std::mem::take(&mut pending) && prev.spaced(punct) {
+                rem -= 1;
+            }
+            rem -= text.chars().count() as isize;
+            prev = next;
+        }
+        false
+    }
+
+    /// Prints the whole document.
+    ///
+    /// # Returns
+    /// The printed document.
+    fn print(mut self) -> String {
+        while let Some((indent, mode, doc)) = self.stack.pop() {
+            match doc {
+                Doc::Nil => {},
+                Doc::Text(text) => self.token(text, Prev::Text, None),
+                Doc::Keyword(text) => self.token(text, Prev::Keyword, None),
+                Doc::Punct(text) => self.token(text, Prev::Punct(text), Some(text)),
+                Doc::Space => self.pending = !matches!(self.prev, Prev::Start),
+                Doc::Line(flat) => match mode {
+                    Mode::Flat => {
+                        self.pending = false;
+                        self.out.push_str(flat);
+                        self.col += flat.chars().count();
+                        if !flat.is_empty() {
+                            self.prev = Prev::Start;
+                        }
+                    },
+                    Mode::Break => self.newline(indent),
+                },
+                Doc::Hardline => self.newline(indent),
+                Doc::Nest(extra, doc) => self.stack.push((indent + extra, mode, doc)),
+                Doc::Group(doc) => {
+                    let mode: Mode = if mode == Mode::Flat || self.fits(indent, doc) { Mode::Flat } else { Mode::Break };
+                    self.stack.push((indent, mode, doc));
+                },
+                Doc::Concat(docs) => self.stack.extend(docs.iter().rev().map(|doc| (indent, mode, doc))),
+            }
+        }
+        self.out
+    }
+}
+
+
+
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Checks whether a [`Doc::Space`] in between two tokens would be printed.
+///
+/// This lays out tokens that aren't printed as part of the same document, such as those spliced
+/// into a [`Cst`](super::cst::Cst).
+///
+/// # Arguments
+/// - `prev`: The kind and text of the token before the space.
+/// - `next`: The kind and text of the token after it.
+///
+/// # Returns
+/// False if they glue together, true otherwise.
+pub fn spaced(prev: (TokenKind, &str), next: (TokenKind, &str)) -> bool {
+    let punct: Option<&str> = (next.0 == TokenKind::Punct).then_some(next.1);
+    match prev {
+        (TokenKind::Text, _) => Prev::Text.spaced(punct),
+        (TokenKind::Keyword, _) => Prev::Keyword.spaced(punct),
+        (TokenKind::Punct, text) => Prev::Punct(text).spaced(punct),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// A document that can be laid out within a line width.
+///
+/// Documents are built from text and lines, which are printed as newlines only if the
+/// [group](Doc::group()) around them doesn't fit on the current line. This follows Wadler's
+/// "A prettier printer".
+#[derive(Clone, Debug)]
+pub enum Doc {
+    /// Prints nothing.
+    Nil,
+    /// Prints some text, such as an identifier.
+    Text(Cow<'static, str>),
+    /// Prints a keyword. Unlike [`Doc::Text`], this is always spaced from punctuation.
+    Keyword(&'static str),
+    /// Prints punctuation, which glues to its neighbours depending on what it is (e.g., `,` has no
+    /// space before it).
+    Punct(&'static str),
+    /// Prints a space in between the tokens around it, unless they glue together. Unlike
+    /// [`Doc::Line`], this never breaks.
+    Space,
+    /// Prints the given text if its group fits, or a newline otherwise.
+    Line(&'static str),
+    /// Always prints a newline, breaking any groups around it.
+    Hardline,
+    /// Indents any newlines in the nested document by the given number of extra spaces.
+    Nest(usize, Box<Doc>),
+    /// Prints the nested document on one line if it fits, or breaks its lines otherwise.
+    Group(Box<Doc>),
+    /// Prints the documents after each other.
+    Concat(Vec<Doc>),
+}
+
+// Constructors
+impl Default for Doc {
+    #[inline]
+    fn default() -> Self { Self::Nil }
+}
+impl Doc {
+    /// Constructor for a Doc that prints text.
+    ///
+    /// # Arguments
+    /// - `text`: The text to print.
+    ///
+    /// # Returns
+    /// A new [`Doc::Text`].
+    #[inline]
+    pub fn text(text: impl Into<Cow<'static, str>>) -> Self { Self::Text(text.into()) }
+
+    /// Constructor for a Doc that prints a space or a newline.
+    ///
+    /// # Returns
+    /// A new [`Doc::Line`] that prints a space if it fits.
+    #[inline]
+    pub const fn line() -> Self { Self::Line(" ") }
+
+    /// Constructor for a Doc that prints nothing or a newline.
+    ///
+    /// # Returns
+    /// A new [`Doc::Line`] that prints nothing if it fits.
+    #[inline]
+    pub const fn softline() -> Self { Self::Line("") }
+
+    /// Constructor for a Doc that prints documents after each other.
+    ///
+    /// # Arguments
+    /// - `docs`: The [`Doc`]s to print.
+    ///
+    /// # Returns
+    /// A new [`Doc::Concat`].
+    #[inline]
+    pub fn concat(docs: impl IntoIterator<Item = Doc>) -> Self { Self::Concat(docs.into_iter().collect()) }
+
+    /// Constructor for a Doc that prints documents with a separator in between.
+    ///
+    /// # Arguments
+    /// - `docs`: The [`Doc`]s to print.
+    /// - `sep`: The [`Doc`] to print in between them.
+    ///
+    /// # Returns
+    /// A new [`Doc::Concat`].
+    #[inline]
+    pub fn join(docs: impl IntoIterator<Item = Doc>, sep: Doc) -> Self {
+        let mut res: Vec<Doc> = Vec::new();
+        for (i, doc) in docs.into_iter().enumerate() {
+            if i > 0 {
+                res.push(sep.clone());
+            }
+            res.push(doc);
+        }
+        Self::Concat(res)
+    }
+
+    /// Constructor for a Doc that prints a document in between delimiters.
+    ///
+    /// If it doesn't fit, the document is printed indented on its own lines. Braces (`{`) are
+    /// spaced from their contents, other delimiters are not.
+    ///
+    /// # Arguments
+    /// - `open`: The opening delimiter (e.g., `(`).
+    /// - `close`: The closing delimiter (e.g., `)`).
+    /// - `inner`: The [`Doc`] to print in between.
+    ///
+    /// # Returns
+    /// A new [`Doc::Group`].
+    #[inline]
+    pub fn delimited(open: &'static str, close: &'static str, inner: Doc) -> Self {
+        let line: Doc = if open == "{" { Self::line() } else { Self::softline() };
+        Self::concat([Self::Punct(open), Self::concat([line.clone(), inner]).nest(INDENT), line, Self::Punct(close)]).group()
+    }
+}
+
+// Combinators
+impl Doc {
+    /// Prints another document after this one.
+    ///
+    /// # Arguments
+    /// - `other`: The [`Doc`] to print after this one.
+    ///
+    /// # Returns
+    /// A new [`Doc::Concat`].
+    #[inline]
+    pub fn append(self, other: Doc) -> Self {
+        match self {
+            Self::Concat(mut docs) => {
+                docs.push(other);
+                Self::Concat(docs)
+            },
+            this => Self::Concat(vec![this, other]),
+        }
+    }
+
+    /// Indents any newlines in this document.
+    ///
+    /// # Arguments
+    /// - `indent`: The number of spaces to indent with.
+    ///
+    /// # Returns
+    /// A new [`Doc::Nest`].
+    #[inline]
+    pub fn nest(self, indent: usize) -> Self { Self::Nest(indent, Box::new(self)) }
+
+    /// Prints this document on one line if it fits.
+    ///
+    /// # Returns
+    /// A new [`Doc::Group`].
+    #[inline]
+    pub fn group(self) -> Self { Self::Group(Box::new(self)) }
+}
+
+// Rendering
+impl Doc {
+    /// Lays out this document within a line width.
+    ///
+    /// # Arguments
+    /// - `width`: The maximum number of characters per line. Lines may still be longer if some
+    ///   text doesn't fit by itself.
+    ///
+    /// # Returns
+    /// The laid out document.
+    #[inline]
+    pub fn pretty(&self, width: usize) -> String {
+        Printer { width, out: String::new(), col: 0, prev: Prev::Start, pending: false, stack: vec![(0, Mode::Break, self)] }.print()
+    }
+}
+impl Display for Doc {
+    /// Lays out this document within the formatter's width, or 80 if none is given.
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "{}", self.pretty(f.width().unwrap_or(80))) }
+}
@@ -0,0 +1,401 @@
+//  GENERATE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:08:57
+//  Last edited:
+//    18 Oct 2026, 23:31:54
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines the [`Generate`]-trait, which produces random AST nodes
+//!   (e.g., to fuzz passes with).
+//!
+//!   Usually, you would [derive](derive@super::Generate) it next to
+//!   [`Parse`](trait@super::Parse) and [`Unparse`], such that generated
+//!   nodes can be printed to valid source text.
+//
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::Unparse;
+use crate::span::Span;
+
+
+/***** CONSTANTS *****/
+/// The name of the source that generated spans point into.
+pub const GENERATED: &str = "<generated>";
+
+
+
+
+
+/***** HELPER MACROS *****/
+/// Implements [`Generate`] for a pointer-like type that wraps a generated pointee.
+///
+/// # Syntax
+/// Call it with a type to implement it for (including the generic `T`) and the function wrapping
+/// it.
+macro_rules! wrapper_impl {
+    ($type:ty, $wrap:expr) => {
+        impl<T: Generate> Generate for $type {
+            #[inline]
+            fn generate(generator: &mut Generator) -> Self { $wrap(T::generate(generator)) }
+        }
+    };
+}
+
+/// Implements [`Generate`] for integer types, generating small, non-negative values.
+///
+/// # Syntax
+/// Call it with a comma-separated list of types to implement it for.
+macro_rules! int_impl {
+    ($($type:ty),+) => {
+        $(
+            impl Generate for $type {
+                /// Generates a value between 0 and 100 (exclusive).
+                #[inline]
+                fn generate(generator: &mut Generator) -> Self { generator.below(100) as $type }
+            }
+        )+
+    };
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Produces random values deterministically from a seed, within a budget.
+///
+/// The budget limits how deeply nodes are nested and how many nodes are generated in total. Once
+/// it is exhausted, derived nodes only pick variants that don't contain themselves, and lists and
+/// optional nodes are left empty.
+///
+/// # Example
+/// ```
+/// # use ast_toolkit_2::parser::{Generate, Parse, Span, Unparse};
+/// # use ast_toolkit_2::parser::chumsky::text;
+/// use ast_toolkit_2::parser::generate::Generator;
+///
+/// #[derive(Generate, Parse, Unparse)]
+/// enum Stmt {
+///     #[parse(keyword = "let")]
+///     Let(#[parse(with = text::ident)] String, #[parse(punct = ";")] Span),
+///     #[parse(keyword = "while")]
+///     While(#[parse(with = text::ident)] String, #[parse(delim("{", "}"))] Vec<Stmt>),
+/// }
+///
+/// let mut generator = Generator::new(42).with_reserved(["let", "while"]);
+/// let source: String = generator.source::<Stmt>(80);
+/// assert!(Stmt::parse_str("<generated>", &source).is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Generator {
+    /// The state of the random number generator.
+    state:     u64,
+    /// The current nesting depth.
+    depth:     usize,
+    /// The number of nodes generated so far.
+    size:      usize,
+    /// The maximum nesting depth.
+    max_depth: usize,
+    /// The maximum number of nodes.
+    max_size:  usize,
+    /// The maximum number of elements in generated lists.
+    max_len:   usize,
+    /// Words that generated identifiers may not be (e.g., keywords).
+    reserved:  HashSet<&'static str>,
+}
+
+// Constructors
+impl Generator {
+    /// Constructor for the Generator.
+    ///
+    /// # Arguments
+    /// - `seed`: The seed to generate from. The same seed always generates the same values.
+    ///
+    /// # Returns
+    /// A new Generator that nests at most 8 nodes deep, generates at most 256 nodes and lists of at
+    /// most 4 elements.
+    #[inline]
+    pub fn new(seed: u64) -> Self { Self { state: seed, depth: 0, size: 0, max_depth: 8, max_size: 256, max_len: 4, reserved: HashSet::new() } }
+
+    /// Sets the seed to generate from, restarting the random values and the budget of nodes.
+    ///
+    /// # Arguments
+    /// - `seed`: The seed to generate from.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.state = seed;
+        self.depth = 0;
+        self.size = 0;
+        self
+    }
+
+    /// Sets the maximum nesting depth.
+    ///
+    /// # Arguments
+    /// - `depth`: The maximum number of nodes nested in each other.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub const fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Sets the maximum number of nodes generated.
+    ///
+    /// # Arguments
+    /// - `size`: The maximum number of nodes.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub const fn with_max_size(mut self, size: usize) -> Self {
+        self.max_size = size;
+        self
+    }
+
+    /// Sets the maximum number of elements in generated lists.
+    ///
+    /// # Arguments
+    /// - `len`: The maximum length.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub const fn with_max_len(mut self, len: usize) -> Self {
+        self.max_len = len;
+        self
+    }
+
+    /// Adds words that generated identifiers may not be.
+    ///
+    /// # Arguments
+    /// - `words`: The words to reserve, typically the grammar's keywords.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_reserved(mut self, words: impl IntoIterator<Item = &'static str>) -> Self {
+        self.reserved.extend(words);
+        self
+    }
+}
+
+// Randomness
+impl Generator {
+    /// Generates the next random number.
+    ///
+    /// This is a SplitMix64 generator, such that values are the same on every platform and
+    /// version.
+    ///
+    /// # Returns
+    /// A random [`u64`].
+    #[inline]
+    pub const fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z: u64 = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Generates a random number below some bound.
+    ///
+    /// # Arguments
+    /// - `bound`: The (exclusive) upper bound.
+    ///
+    /// # Returns
+    /// A random number in `0..bound`, or 0 if `bound` is 0.
+    #[inline]
+    pub const fn below(&mut self, bound: usize) -> usize { ((self.next_u64() as u128 * bound as u128) >> 64) as usize }
+
+    /// Decides something randomly.
+    ///
+    /// # Arguments
+    /// - `num`: The numerator of the chance to decide `true`.
+    /// - `den`: The denominator of the chance to decide `true`.
+    ///
+    /// # Returns
+    /// `true` with a chance of `num / den`.
+    #[inline]
+    pub const fn chance(&mut self, num: usize, den: usize) -> bool { self.below(den) < num }
+
+    /// Picks an index weighted by the given weights.
+    ///
+    /// # Arguments
+    /// - `weights`: The weight of every index. Indices with weight 0 are never picked.
+    ///
+    /// # Returns
+    /// The picked index, or [`None`] if all weights were 0.
+    pub fn weighted(&mut self, weights: &[u32]) -> Option<usize> {
+        let total: u64 = weights.iter().map(|w| *w as u64).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut pick: u64 = ((self.next_u64() as u128 * total as u128) >> 64) as u64;
+        for (i, weight) in weights.iter().enumerate() {
+            if pick < *weight as u64 {
+                return Some(i);
+            }
+            pick -= *weight as u64;
+        }
+        unreachable!()
+    }
+}
+
+// Budget
+impl Generator {
+    /// Returns the maximum nesting depth.
+    ///
+    /// # Returns
+    /// The maximum number of nodes nested in each other.
+    #[inline]
+    pub const fn max_depth(&self) -> usize { self.max_depth }
+
+    /// Returns the maximum number of nodes generated.
+    ///
+    /// # Returns
+    /// The maximum number of nodes.
+    #[inline]
+    pub const fn max_size(&self) -> usize { self.max_size }
+
+    /// Returns whether the budget is exhausted.
+    ///
+    /// # Returns
+    /// True if nodes are nested as deep as allowed, or if as many nodes were generated as allowed.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool { self.depth >= self.max_depth || self.size >= self.max_size }
+
+    /// Generates a node that counts towards the budget.
+    ///
+    /// This is used by the [derive macro](derive@super::Generate) for every struct or variant.
+    ///
+    /// # Arguments
+    /// - `generate`: Some closure that generates the node's fields, nested one deeper.
+    ///
+    /// # Returns
+    /// The node generated by `generate`.
+    #[inline]
+    pub fn nest<T>(&mut self, generate: impl FnOnce(&mut Self) -> T) -> T {
+        self.depth += 1;
+        self.size += 1;
+        let res: T = generate(self);
+        self.depth -= 1;
+        res
+    }
+
+    /// Generates the length of a list, which is 0 if the budget is exhausted.
+    ///
+    /// # Returns
+    /// A length up to and including the maximum length.
+    #[inline]
+    pub fn list_len(&mut self) -> usize { if self.is_exhausted() { 0 } else { self.below(self.max_len + 1) } }
+}
+
+// Leaves
+impl Generator {
+    /// Generates an identifier that isn't reserved.
+    ///
+    /// # Returns
+    /// A lowercase identifier of one to six characters, optionally followed by a digit.
+    pub fn ident(&mut self) -> String {
+        loop {
+            let mut ident: String = (0..1 + self.below(6)).map(|_| (b'a' + self.below(26) as u8) as char).collect();
+            if self.chance(1, 4) {
+                ident.push((b'0' + self.below(10) as u8) as char);
+            }
+            if !self.reserved.contains(ident.as_str()) {
+                return ident;
+            }
+        }
+    }
+
+    /// Generates a span for a generated node.
+    ///
+    /// # Returns
+    /// An empty [`Span`] in the [`GENERATED`]-source.
+    #[inline]
+    pub fn span(&mut self) -> Span { Span::empty(GENERATED, 0) }
+}
+
+// Generating
+impl Generator {
+    /// Generates a random node.
+    ///
+    /// # Returns
+    /// A new instance of `T`.
+    #[inline]
+    pub fn generate<T: Generate>(&mut self) -> T { T::generate(self) }
+
+    /// Generates a random node and prints it to source text.
+    ///
+    /// # Arguments
+    /// - `width`: The maximum number of characters per line.
+    ///
+    /// # Returns
+    /// The printed node, which parses if `T`'s [`Parse`](trait@super::Parse)-,
+    /// [`Unparse`]- and [`Generate`]-implementations agree.
+    #[inline]
+    pub fn source<T: Generate + Unparse>(&mut self, width: usize) -> String { T::generate(self).unparse_to_string(width) }
+}
+
+
+
+/// Marks an AST node as randomly generatable.
+///
+/// Usually, you would [derive](derive@super::Generate) this trait instead of implementing it
+/// manually. Then, it generates nodes that the derived [`Parse`](trait@super::Parse) accepts.
+pub trait Generate: Sized {
+    /// Generates a random instance of this node.
+    ///
+    /// # Arguments
+    /// - `generator`: The [`Generator`] to draw random values and the budget from.
+    ///
+    /// # Returns
+    /// A new instance of Self.
+    fn generate(generator: &mut Generator) -> Self;
+}
+
+// Std impls
+wrapper_impl!(Box<T>, Box::new);
+wrapper_impl!(Rc<T>, Rc::new);
+wrapper_impl!(Arc<T>, Arc::new);
+impl<T: Generate> Generate for Option<T> {
+    /// Generates [`None`] if the budget is exhausted, or else either with equal chance.
+    #[inline]
+    fn generate(generator: &mut Generator) -> Self { if !generator.is_exhausted() && generator.chance(1, 2) { Some(T::generate(generator)) } else { None } }
+}
+impl<T: Generate> Generate for Vec<T> {
+    /// Generates an empty list if the budget is exhausted, or else up to the maximum length.
+    #[inline]
+    fn generate(generator: &mut Generator) -> Self {
+        let len: usize = generator.list_len();
+        (0..len).map(|_| T::generate(generator)).collect()
+    }
+}
+impl Generate for String {
+    /// Generates an identifier (see [`Generator::ident()`]).
+    #[inline]
+    fn generate(generator: &mut Generator) -> Self { generator.ident() }
+}
+impl Generate for Span {
+    /// Generates an empty span (see [`Generator::span()`]).
+    #[inline]
+    fn generate(generator: &mut Generator) -> Self { generator.span() }
+}
+impl Generate for bool {
+    #[inline]
+    fn generate(generator: &mut Generator) -> Self { generator.chance(1, 2) }
+}
+int_impl!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
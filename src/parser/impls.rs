@@ -0,0 +1,60 @@
+//  IMPLS.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:44:07
+//  Last edited:
+//    18 Oct 2026, 21:18:33
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements the main [`Parse`]-trait for standard library types.
+//
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use chumsky::prelude::Parser;
+
+use super::combinators::padded_left;
+use super::{Error, Input, Parse};
+
+
+/***** HELPER MACROS *****/
+/// Implements [`Parse`] for a wrapper around the parsed type.
+///
+/// # Syntax
+/// Call it with a type to implement it for (including the generic `T`) and the function wrapping
+/// a `T` in it.
+macro_rules! wrapper_impl {
+    ($type:ty, $wrap:path) => {
+        impl<I: Input, T: Parse<I>> Parse<I> for $type {
+            #[inline]
+            fn parser() -> impl Parser<I, Self, Error = Error<I>> + Clone { <T as Parse<I>>::parser().map($wrap) }
+        }
+    };
+}
+
+
+
+
+
+/***** IMPLEMENTATIONS *****/
+wrapper_impl!(Box<T>, Box::new);
+wrapper_impl!(Rc<T>, Rc::new);
+wrapper_impl!(Arc<T>, Arc::new);
+
+impl<I: Input, T: Parse<I>> Parse<I> for Option<T> {
+    /// Parses the node if it's there.
+    #[inline]
+    fn parser() -> impl Parser<I, Self, Error = Error<I>> + Clone { <T as Parse<I>>::parser().or_not() }
+}
+
+impl<I: Input, T: Parse<I>> Parse<I> for Vec<T> {
+    /// Parses zero or more nodes, optionally preceded by whitespace.
+    ///
+    /// Use [`separated()`](super::combinators::separated) for nodes separated by punctuation.
+    #[inline]
+    fn parser() -> impl Parser<I, Self, Error = Error<I>> + Clone { padded_left(<T as Parse<I>>::parser()).repeated() }
+}
@@ -0,0 +1,367 @@
+//  IMPORT.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:42:28
+//  Last edited:
+//    18 Oct 2026, 23:31:37
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements a [`Driver`] that parses a root file and all the files it
+//!   (transitively) imports, for languages with `import "other.lang"`-like
+//!   statements.
+//
+
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::{fs, io};
+
+use super::{Error, Input, Parse};
+use crate::span::{Sources, Span};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Normalizes a path without touching the filesystem, by removing `.`-components and resolving
+/// `..`-components where possible.
+///
+/// # Arguments
+/// - `path`: The path to normalize.
+///
+/// # Returns
+/// The normalized path.
+fn normalize(path: &Path) -> PathBuf {
+    let mut res: PathBuf = PathBuf::new();
+    for comp in path.components() {
+        match comp {
+            Component::CurDir => {},
+            Component::ParentDir if matches!(res.components().next_back(), Some(Component::Normal(_))) => {
+                res.pop();
+            },
+            comp => res.push(comp),
+        }
+    }
+    res
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// A hook that finds the [`Import`]s in a parsed node.
+type ImportsHook<'h, T> = Box<dyn 'h + FnMut(&T) -> Vec<Import>>;
+
+/// A hook that resolves an import to the name of the imported file (see [`resolve()`]).
+type ResolveHook<'h> = Box<dyn 'h + FnMut(Option<&str>, &str) -> String>;
+
+/// A hook that loads the text of a file by name.
+type LoadHook<'h> = Box<dyn 'h + FnMut(&str) -> io::Result<String>>;
+
+/// The imports of a file left to visit, as their spans and the names of the imported files.
+type Imports = std::vec::IntoIter<(Span, Arc<str>)>;
+
+
+
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Resolves an import to the name of the imported file.
+///
+/// This is what the [`Driver`] uses by default. Relative paths are taken relative to the
+/// directory of the importing file, and all paths are normalized (without touching the
+/// filesystem) such that the same file always gets the same name.
+///
+/// # Arguments
+/// - `importer`: The name of the importing file, or [`None`] for the root file.
+/// - `path`: The path as it was written in the import (or given for the root).
+///
+/// # Returns
+/// The name of the imported file.
+pub fn resolve(importer: Option<&str>, path: &str) -> String {
+    let dir: &Path = importer.and_then(|importer| Path::new(importer).parent()).unwrap_or(Path::new(""));
+    normalize(&dir.join(path)).to_string_lossy().into_owned()
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// An import found in a parsed node.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Import {
+    /// The path as it was written (e.g., `other.lang`).
+    pub path: String,
+    /// The span of the import, where errors about it are reported.
+    pub span: Span,
+}
+
+// Constructors
+impl Import {
+    /// Constructor for the Import.
+    ///
+    /// # Arguments
+    /// - `path`: The path as it was written (e.g., `other.lang`).
+    /// - `span`: The [`Span`] of the import.
+    ///
+    /// # Returns
+    /// A new Import.
+    #[inline]
+    pub fn new(path: impl Into<String>, span: Span) -> Self { Self { path: path.into(), span } }
+}
+
+
+
+/// A single file parsed by the [`Driver`].
+#[derive(Clone, Debug)]
+pub struct File<T> {
+    /// The name of the file, which is also its name in the [`Sources`].
+    pub name:    Arc<str>,
+    /// The parsed node, if any could be made (see
+    /// [`Parse::parse_str_recovery()`](super::Parse::parse_str_recovery())).
+    pub node:    Option<T>,
+    /// The names of the files it imports, in order.
+    pub imports: Vec<Arc<str>>,
+}
+
+
+
+/// All files parsed by the [`Driver`], and all [`Error`]s found while doing so.
+#[derive(Clone, Debug)]
+pub struct Files<T, I: Input = char> {
+    /// The parsed files, with the root first and every other file after the first file that
+    /// imported it.
+    files:  Vec<File<T>>,
+    /// The errors found while loading or parsing the files.
+    errors: Vec<Error<I>>,
+}
+
+// Accessors
+impl<T, I: Input> Files<T, I> {
+    /// Returns the parsed files.
+    ///
+    /// # Returns
+    /// A slice of [`File`]s, with the root first. Files that failed to load are absent.
+    #[inline]
+    pub fn files(&self) -> &[File<T>] { &self.files }
+
+    /// Returns the root file.
+    ///
+    /// # Returns
+    /// The root [`File`], or [`None`] if it failed to load.
+    #[inline]
+    pub fn root(&self) -> Option<&File<T>> { self.files.first() }
+
+    /// Returns a file by name.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the file to find.
+    ///
+    /// # Returns
+    /// The [`File`], or [`None`] if no file with that name was parsed.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&File<T>> { self.files.iter().find(|file| &*file.name == name) }
+
+    /// Returns the errors found while loading or parsing the files.
+    ///
+    /// With the `report`-feature, these can be emitted like any other parse error.
+    ///
+    /// # Returns
+    /// A slice of [`Error`]s, including syntax errors, files that failed to load and import
+    /// cycles.
+    #[inline]
+    pub fn errors(&self) -> &[Error<I>] { &self.errors }
+
+    /// Returns whether all files loaded and parsed without errors.
+    #[inline]
+    pub fn is_ok(&self) -> bool { self.errors.is_empty() }
+
+    /// Returns the parsed files and the errors found.
+    ///
+    /// # Returns
+    /// A tuple of the [`File`]s and [`Error`]s, consuming the Files.
+    #[inline]
+    pub fn into_parts(self) -> (Vec<File<T>>, Vec<Error<I>>) { (self.files, self.errors) }
+}
+
+
+
+/// Parses a root file and all the files it (transitively) imports.
+///
+/// Which nodes are imports is decided by a hook given on construction. Every imported file is
+/// resolved to a name (see [`resolve()`]), loaded (from the filesystem by default), added to the
+/// shared [`Sources`] and parsed, recovering from syntax errors where possible. Every file is
+/// loaded only once, even if imported multiple times (so failing to load it is reported once
+/// too), and import cycles are reported at the import closing the cycle.
+///
+/// # Example
+/// ```
+/// use ast_toolkit_2::parser::chumsky::prelude::*;
+/// use ast_toolkit_2::parser::chumsky::text;
+/// use ast_toolkit_2::parser::import::{Driver, Files, Import};
+/// use ast_toolkit_2::parser::{Error, Parse, Span};
+/// use ast_toolkit_2::span::Sources;
+///
+/// fn path() -> impl Parser<char, String, Error = Error> + Clone {
+///     filter(|c: &char| *c != '"').repeated().collect().delimited_by(just('"'), just('"'))
+/// }
+///
+/// #[derive(Parse)]
+/// struct Module(Vec<Item>);
+///
+/// #[derive(Parse)]
+/// enum Item {
+///     #[parse(keyword = "import")]
+///     Import(#[parse(with = path)] String, #[parse(span)] Span),
+///     #[parse(keyword = "let")]
+///     Let(#[parse(with = text::ident)] String, #[parse(punct = ";")] Span),
+/// }
+///
+/// let mut sources = Sources::new();
+/// let files: Files<Module> = Driver::new(|module: &Module| {
+///     module.0.iter().filter_map(|item| match item {
+///         Item::Import(path, span) => Some(Import::new(path, span.clone())),
+///         _ => None,
+///     }).collect()
+/// })
+/// // Load from memory instead of the filesystem
+/// .with_loader(|name: &str| match name {
+///     "main.lang" => Ok("import \"lib/util.lang\" let x;".into()),
+///     "lib/util.lang" => Ok("import \"../main.lang\"".into()),
+///     _ => Err(std::io::ErrorKind::NotFound.into()),
+/// })
+/// .parse(&mut sources, "main.lang");
+///
+/// assert_eq!(files.files().len(), 2);
+/// assert_eq!(files.errors().len(), 1);
+/// assert_eq!(files.errors()[0].span(), Span::new("lib/util.lang", 0..21));
+/// ```
+pub struct Driver<'h, T, I = char> {
+    /// Finds the imports in a parsed node.
+    imports: ImportsHook<'h, T>,
+    /// Resolves an import to the name of the imported file.
+    resolve: ResolveHook<'h>,
+    /// Loads the text of a file by name.
+    load:    LoadHook<'h>,
+    /// The input that the files are parsed from.
+    input:   PhantomData<fn() -> I>,
+}
+
+// Constructors
+impl<'h, T, I> Driver<'h, T, I> {
+    /// Constructor for the Driver that loads files from the filesystem.
+    ///
+    /// # Arguments
+    /// - `imports`: Some hook that finds the [`Import`]s in a parsed node.
+    ///
+    /// # Returns
+    /// A new Driver that resolves imports with [`resolve()`].
+    #[inline]
+    pub fn new(imports: impl 'h + FnMut(&T) -> Vec<Import>) -> Self {
+        Self { imports: Box::new(imports), resolve: Box::new(resolve), load: Box::new(|name: &str| fs::read_to_string(name)), input: PhantomData }
+    }
+
+    /// Sets how imports are resolved to the names of the imported files.
+    ///
+    /// # Arguments
+    /// - `resolve`: Some hook that takes the name of the importing file ([`None`] for the root)
+    ///   and the path as written, and returns the name of the imported file.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_resolver(mut self, resolve: impl 'h + FnMut(Option<&str>, &str) -> String) -> Self {
+        self.resolve = Box::new(resolve);
+        self
+    }
+
+    /// Sets how files are loaded, e.g., to load them from memory instead.
+    ///
+    /// # Arguments
+    /// - `load`: Some hook that takes the name of a file and returns its text.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_loader(mut self, load: impl 'h + FnMut(&str) -> io::Result<String>) -> Self {
+        self.load = Box::new(load);
+        self
+    }
+}
+
+// Parsing
+impl<'h, I: Input, T: Parse<I>> Driver<'h, T, I> {
+    /// Loads and parses a single file.
+    ///
+    /// # Arguments
+    /// - `sources`: The [`Sources`] to add the text of the file to.
+    /// - `name`: The file to load.
+    /// - `at`: The [`Span`] of the import to report failing to load at.
+    /// - `files`: The [`Files`] to add the parsed file and errors to.
+    ///
+    /// # Returns
+    /// The spans of the file's imports and the names they resolve to, or [`None`] if it failed to
+    /// load.
+    fn load_file(&mut self, sources: &mut Sources, name: Arc<str>, at: Span, files: &mut Files<T, I>) -> Option<Vec<(Span, Arc<str>)>> {
+        let text: Arc<str> = match (self.load)(&name) {
+            Ok(text) => text.into(),
+            Err(err) => {
+                files.errors.push(Error::<I>::custom(at, format!("failed to load {name:?}: {err}")));
+                return None;
+            },
+        };
+        let source: Arc<str> = sources.add(name, text.clone());
+        let (node, errors): (Option<T>, Vec<Error<I>>) = T::parse_str_recovery(source.clone(), &text);
+        files.errors.extend(errors);
+
+        // Resolve its imports
+        let imports: Vec<Import> = node.as_ref().map(|node| (self.imports)(node)).unwrap_or_default();
+        let names: Vec<Arc<str>> = imports.iter().map(|import| (self.resolve)(Some(&source), &import.path).into()).collect();
+        files.files.push(File { name: source, node, imports: names.clone() });
+        Some(imports.into_iter().map(|import| import.span).zip(names).collect())
+    }
+
+    /// Parses a root file and all the files it (transitively) imports.
+    ///
+    /// # Arguments
+    /// - `sources`: The [`Sources`] to add the texts of all loaded files to.
+    /// - `root`: The path of the root file (resolved like imports, but without an importer).
+    ///
+    /// # Returns
+    /// The parsed [`Files`], which also collects all errors found. If files failed to load or
+    /// parse, the rest is still parsed as far as possible.
+    pub fn parse(&mut self, sources: &mut Sources, root: &str) -> Files<T, I> {
+        let mut files: Files<T, I> = Files { files: Vec::new(), errors: Vec::new() };
+        let root: Arc<str> = (self.resolve)(None, root).into();
+        // Every file loaded or failed to load, such that it's done only once
+        let mut seen: HashSet<Arc<str>> = HashSet::from([root.clone()]);
+        // The files currently being imported, for detecting cycles, with their imports left to visit
+        let mut stack: Vec<(Arc<str>, Imports)> = Vec::new();
+        if let Some(imports) = self.load_file(sources, root.clone(), Span::empty(root.clone(), 0), &mut files) {
+            stack.push((root, imports.into_iter()));
+        }
+
+        // Visit imports depth-first, but without recursing to keep deep chains off the call stack
+        while let Some((_, imports)) = stack.last_mut() {
+            let Some((at, name)) = imports.next() else {
+                stack.pop();
+                continue;
+            };
+            if let Some(i) = stack.iter().position(|(file, _)| *file == name) {
+                let cycle: Vec<&str> = stack[i..].iter().map(|(file, _)| &**file).chain([&*name]).collect();
+                files.errors.push(Error::<I>::custom(at, format!("import cycle: {}", cycle.join(" -> "))));
+                continue;
+            } else if !seen.insert(name.clone()) {
+                continue;
+            }
+            if let Some(imports) = self.load_file(sources, name.clone(), at, &mut files) {
+                stack.push((name, imports.into_iter()));
+            }
+        }
+        files
+    }
+}
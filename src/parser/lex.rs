@@ -0,0 +1,370 @@
+//  LEX.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:18:33
+//  Last edited:
+//    18 Oct 2026, 23:52:17
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines the [`Lex`]-trait, which turns source text into tokens that
+//!   [`Parse`](trait@super::Parse)rs can parse instead of characters.
+//!
+//!   Usually, you don't use this module directly, but rather
+//!   [derive](derive@super::Lex) [`Lex`] on a token enum.
+//
+
+// Module definitions
+mod pattern;
+
+// Imports
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::sync::Arc;
+
+use chumsky::prelude::{Parser, just};
+use chumsky::stream::Stream;
+pub use pattern::Error;
+use pattern::Node;
+
+use super::{Input, InputStream};
+use crate::span::Span;
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Lexes source text into tokens.
+///
+/// At every position, the rule of the [`Lexer`] with the longest match wins. Characters that no
+/// rule matches are skipped with an error.
+///
+/// # Arguments
+/// - `source`: The name of the source (see [`Sources`](crate::span::Sources)).
+/// - `text`: The source text.
+///
+/// # Returns
+/// The tokens with their [`Span`]s, and an [`Error`](super::Error) for every run of characters
+/// that couldn't be lexed.
+pub fn tokenize<L: Lex>(source: Arc<str>, text: &str) -> (Vec<(L, Span)>, Vec<super::Error<L>>) {
+    let chars: Vec<char> = text.chars().collect();
+    let lexer: &Lexer = L::lexer();
+    let mut tokens: Vec<(L, Span)> = Vec::new();
+    let mut errors: Vec<super::Error<L>> = Vec::new();
+    let mut pos: usize = 0;
+    let mut unknown: Option<usize> = None;
+    while pos < chars.len() {
+        let Some((rule, end)) = lexer.next(&chars, pos) else {
+            unknown.get_or_insert(pos);
+            pos += 1;
+            continue;
+        };
+        if let Some(start) = unknown.take() {
+            let text: String = chars[start..pos].iter().collect();
+            errors.push(super::Error::custom(Span::new(source.clone(), start..pos), format!("Unexpected character(s) {text:?}")));
+        }
+
+        // Turn the match into a token
+        if !lexer.rules[rule].skip {
+            let span: Span = Span::new(source.clone(), pos..end);
+            match L::token(rule, &chars[pos..end].iter().collect::<String>()) {
+                Ok(token) => tokens.push((token, span)),
+                Err(msg) => errors.push(super::Error::custom(span, msg)),
+            }
+        }
+        pos = end;
+    }
+    if let Some(start) = unknown {
+        let text: String = chars[start..].iter().collect();
+        errors.push(super::Error::custom(Span::new(source, start..chars.len()), format!("Unexpected character(s) {text:?}")));
+    }
+    (tokens, errors)
+}
+
+/// Lexes source text into an [`InputStream`].
+///
+/// This implements [`Input::stream()`] for derived tokens.
+///
+/// # Arguments
+/// - `source`: The name of the source (see [`Sources`](crate::span::Sources)).
+/// - `text`: The source text.
+///
+/// # Returns
+/// An [`InputStream`] over the tokens, and any [`Error`](super::Error)s found while lexing.
+#[inline]
+pub fn stream<L: Lex>(source: Arc<str>, text: &str) -> (InputStream<'_, L>, Vec<super::Error<L>>) {
+    let eoi: Span = Span::empty(source.clone(), text.chars().count());
+    let (tokens, errors): (Vec<(L, Span)>, Vec<super::Error<L>>) = tokenize(source, text);
+    (Stream::from_iter(eoi, Box::new(tokens.into_iter())), errors)
+}
+
+/// Checks whether an input can parse the token with the given literal text.
+///
+/// This is used by the [derive macro](derive@super::Parse) to reject keywords and punctuation
+/// that aren't tokens at compile time, instead of panicking in [`literal()`] when parsing.
+///
+/// # Arguments
+/// - `literal`: The text of the token (e.g., `fn`).
+///
+/// # Returns
+/// True if it's one of the [`Input::LITERALS`] of `I` (or `I` can parse any), false otherwise.
+pub const fn accepts<I: Input>(literal: &str) -> bool {
+    let Some(literals) = I::LITERALS else { return true };
+    let mut i: usize = 0;
+    while i < literals.len() {
+        let (lhs, rhs): (&[u8], &[u8]) = (literals[i].as_bytes(), literal.as_bytes());
+        if lhs.len() == rhs.len() {
+            let mut j: usize = 0;
+            while j < lhs.len() && lhs[j] == rhs[j] {
+                j += 1;
+            }
+            if j == lhs.len() {
+                return true;
+            }
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Parses the token with the given literal text.
+///
+/// This implements [`Input::keyword()`] and [`Input::punct()`] for derived tokens.
+///
+/// # Arguments
+/// - `literal`: The text of the token (e.g., `fn`).
+///
+/// # Returns
+/// A [`Parser`] that produces the [`Span`] of the token.
+///
+/// # Panics
+/// This function panics if there is no token with that text. The [derive
+/// macro](derive@super::Parse) already rejects those at compile time (see [`accepts()`]).
+#[inline]
+pub fn literal<L: Lex>(literal: &'static str) -> impl Parser<L, Span, Error = super::Error<L>> + Clone {
+    let token: L = L::from_literal(literal).unwrap_or_else(|| panic!("There is no token {literal:?} in {}", std::any::type_name::<L>()));
+    just(token).map_with_span(|_, span| span)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// A compiled regular expression for matching tokens.
+///
+/// It supports a subset of the usual syntax:
+/// - literal characters, and `\` to escape punctuation (e.g., `\*`);
+/// - `.` for any character except newlines;
+/// - classes (e.g., `[a-z_]` or `[^"]`, but not `[z-a]`), and `\d`, `\w`, `\s` and their negations `\D`, `\W` and
+///   `\S`;
+/// - `\n`, `\r` and `\t`;
+/// - groups with `(...)` and alternatives with `|`; and
+/// - the quantifiers `*`, `+` and `?`.
+///
+/// Patterns always match from the current position, and find the longest match.
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    /// The root node of the pattern.
+    root: Node,
+}
+
+// Constructors
+impl Pattern {
+    /// Constructor for the Pattern.
+    ///
+    /// # Arguments
+    /// - `pattern`: The textual representation of the pattern.
+    ///
+    /// # Returns
+    /// A new Pattern.
+    ///
+    /// # Errors
+    /// This function errors if the pattern was malformed.
+    pub fn new(pattern: &str) -> Result<Self, Error> { Ok(Self { root: pattern::compile(pattern)? }) }
+}
+
+// Matching
+impl Pattern {
+    /// Finds the longest non-empty match at a position.
+    ///
+    /// # Arguments
+    /// - `chars`: The characters to match.
+    /// - `pos`: The position to match at.
+    ///
+    /// # Returns
+    /// The position just after the longest match, or [`None`] if there is no non-empty match.
+    #[inline]
+    pub fn longest(&self, chars: &[char], pos: usize) -> Option<usize> { self.root.ends(chars, BTreeSet::from([pos])).last().copied().filter(|end| *end > pos) }
+}
+
+
+
+/// Defines a single rule of a [`Lexer`].
+#[derive(Clone, Debug)]
+pub struct Rule {
+    /// The literal text to match, if this is a literal token.
+    literal: Option<Vec<char>>,
+    /// The pattern to match, if this is a pattern.
+    pattern: Option<Pattern>,
+    /// Whether matches are skipped instead of becoming tokens.
+    skip:    bool,
+}
+
+// Constructors
+impl Rule {
+    /// Constructor for a Rule that matches literal text.
+    ///
+    /// # Arguments
+    /// - `literal`: The text to match (e.g., `fn`).
+    ///
+    /// # Returns
+    /// A new Rule.
+    #[inline]
+    pub fn literal(literal: &str) -> Self { Self { literal: Some(literal.chars().collect()), pattern: None, skip: false } }
+
+    /// Constructor for a Rule that matches a [`Pattern`].
+    ///
+    /// # Arguments
+    /// - `pattern`: The pattern to match (e.g., `[a-z]+`).
+    ///
+    /// # Returns
+    /// A new Rule.
+    ///
+    /// # Panics
+    /// This function panics if the pattern was malformed. The [derive](derive@super::Lex) already
+    /// rejects those at compile time, so this only happens for rules built by hand.
+    #[inline]
+    pub fn pattern(pattern: &str) -> Self {
+        match Pattern::new(pattern) {
+            Ok(pattern) => Self { literal: None, pattern: Some(pattern), skip: false },
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Constructor for a Rule that skips matches of a [`Pattern`] (e.g., whitespace or comments).
+    ///
+    /// # Arguments
+    /// - `pattern`: The pattern to match (e.g., `\s+`).
+    ///
+    /// # Returns
+    /// A new Rule.
+    ///
+    /// # Panics
+    /// This function panics if the pattern was malformed.
+    #[inline]
+    pub fn skip(pattern: &str) -> Self { Self { skip: true, ..Self::pattern(pattern) } }
+}
+
+// Matching
+impl Rule {
+    /// Finds the longest non-empty match at a position.
+    ///
+    /// # Arguments
+    /// - `chars`: The characters to match.
+    /// - `pos`: The position to match at.
+    ///
+    /// # Returns
+    /// The position just after the longest match, or [`None`] if there is no non-empty match.
+    #[inline]
+    pub fn longest(&self, chars: &[char], pos: usize) -> Option<usize> {
+        match (&self.literal, &self.pattern) {
+            (Some(literal), _) => chars[pos..].starts_with(literal).then_some(pos + literal.len()).filter(|end| *end > pos),
+            (None, Some(pattern)) => pattern.longest(chars, pos),
+            (None, None) => None,
+        }
+    }
+}
+
+
+
+/// Splits text into tokens by a list of [`Rule`]s.
+#[derive(Clone, Debug)]
+pub struct Lexer {
+    /// The rules, in order.
+    rules: Vec<Rule>,
+}
+
+// Constructors
+impl Lexer {
+    /// Constructor for the Lexer.
+    ///
+    /// # Arguments
+    /// - `rules`: The [`Rule`]s to match.
+    ///
+    /// # Returns
+    /// A new Lexer.
+    #[inline]
+    pub const fn new(rules: Vec<Rule>) -> Self { Self { rules } }
+}
+
+// Matching
+impl Lexer {
+    /// Finds the rule that matches at a position.
+    ///
+    /// The longest match wins. If matches are equally long, literal rules win over patterns, and
+    /// otherwise the first rule wins.
+    ///
+    /// # Arguments
+    /// - `chars`: The characters to match.
+    /// - `pos`: The position to match at.
+    ///
+    /// # Returns
+    /// The index of the matching rule and the position just after its match, or [`None`] if no
+    /// rule matched.
+    pub fn next(&self, chars: &[char], pos: usize) -> Option<(usize, usize)> {
+        let mut best: Option<(usize, usize, bool)> = None;
+        for (i, rule) in self.rules.iter().enumerate() {
+            let Some(end) = rule.longest(chars, pos) else { continue };
+            let literal: bool = rule.literal.is_some();
+            if best.is_none_or(|(_, best_end, best_literal)| end > best_end || (end == best_end && literal && !best_literal)) {
+                best = Some((i, end, literal));
+            }
+        }
+        best.map(|(i, end, _)| (i, end))
+    }
+}
+
+
+
+/// Marks a type as the tokens produced by a [`Lexer`].
+///
+/// Usually, you would [derive](derive@super::Lex) this trait instead of implementing it manually.
+/// That also implements [`Input`], such that nodes can be [`Parse`](trait@super::Parse)d from
+/// the tokens.
+pub trait Lex: Input + Display {
+    /// Returns the lexer producing this token.
+    ///
+    /// # Returns
+    /// A [`Lexer`] that is built once.
+    fn lexer() -> &'static Lexer;
+
+    /// Constructs a token from a match.
+    ///
+    /// # Arguments
+    /// - `rule`: The index of the [`Rule`] that matched. Is never a skipped rule.
+    /// - `text`: The matched text.
+    ///
+    /// # Returns
+    /// The token.
+    ///
+    /// # Errors
+    /// This function errors with a message if the text couldn't be turned into a token (e.g., if
+    /// a number was too large).
+    fn token(rule: usize, text: &str) -> Result<Self, String>;
+
+    /// Returns the literal text of a token, if it always has the same text.
+    ///
+    /// # Returns
+    /// The text (e.g., `fn`), or [`None`] if it isn't a literal token (e.g., an identifier).
+    fn literal(&self) -> Option<&'static str>;
+
+    /// Constructs a literal token from its text.
+    ///
+    /// # Arguments
+    /// - `literal`: The text of the token (e.g., `fn`).
+    ///
+    /// # Returns
+    /// The token, or [`None`] if there is no literal token with that text.
+    fn from_literal(literal: &str) -> Option<Self>;
+}
@@ -0,0 +1,274 @@
+//  PATTERN.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 23:47:01
+//  Last edited:
+//    18 Oct 2026, 23:47:01
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements the compiler of lexer [`Pattern`](super::Pattern)s.
+//!
+//!   This file is also included by the `Lex` derive-macro to reject
+//!   malformed patterns at compile time, so it may only depend on `std`.
+//
+
+use std::collections::BTreeSet;
+use std::error;
+use std::fmt::{Display, Formatter, Result as FResult};
+
+
+/***** ERRORS *****/
+/// Defines errors that occur when compiling a [`Pattern`](super::Pattern).
+#[derive(Debug)]
+pub enum Error {
+    /// The pattern ended where something else was expected.
+    UnexpectedEnd { pattern: String, expected: &'static str },
+    /// The pattern had an unexpected character.
+    Unexpected { pattern: String, pos: usize, c: char, expected: &'static str },
+    /// The pattern had a range in a class that ends before it starts (e.g., `[z-a]`).
+    Reversed { pattern: String, pos: usize, lo: char, hi: char },
+}
+impl Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::UnexpectedEnd { pattern, expected } => write!(f, "Unexpected end of pattern {pattern:?} (expected {expected})"),
+            Self::Unexpected { pattern, pos, c, expected } => {
+                write!(f, "Unexpected {c:?} at position {pos} in pattern {pattern:?} (expected {expected})")
+            },
+            Self::Reversed { pattern, pos, lo, hi } => write!(f, "Reversed range {lo:?}-{hi:?} at position {pos} in pattern {pattern:?}"),
+        }
+    }
+}
+impl error::Error for Error {}
+
+
+
+
+
+/***** HELPERS *****/
+/// Defines the nodes of a compiled [`Pattern`](super::Pattern).
+#[derive(Clone, Debug)]
+pub(super) enum Node {
+    /// Matches one character in (or, if negated, not in) any of the given ranges.
+    Class { ranges: Vec<(char, char)>, negated: bool },
+    /// Matches nodes after each other.
+    Seq(Vec<Node>),
+    /// Matches any of the nodes.
+    Alt(Vec<Node>),
+    /// Matches a node at least `min` times, and at most once if not `many`.
+    Repeat { node: Box<Node>, min: usize, many: bool },
+}
+impl Node {
+    /// Finds where this node can end when started at any of the given positions.
+    ///
+    /// # Arguments
+    /// - `chars`: The characters to match.
+    /// - `starts`: The positions to start at.
+    ///
+    /// # Returns
+    /// The positions just after every match.
+    pub(super) fn ends(&self, chars: &[char], starts: BTreeSet<usize>) -> BTreeSet<usize> {
+        match self {
+            Self::Class { ranges, negated } => starts
+                .into_iter()
+                .filter(|pos| chars.get(*pos).is_some_and(|c| ranges.iter().any(|(lo, hi)| lo <= c && c <= hi) != *negated))
+                .map(|pos| pos + 1)
+                .collect(),
+            Self::Seq(nodes) => nodes.iter().fold(starts, |starts, node| node.ends(chars, starts)),
+            Self::Alt(nodes) => nodes.iter().flat_map(|node| node.ends(chars, starts.clone())).collect(),
+            Self::Repeat { node, min, many } => {
+                let mut res: BTreeSet<usize> = if *min == 0 { starts.clone() } else { BTreeSet::new() };
+                let mut cur: BTreeSet<usize> = starts;
+                loop {
+                    // Only continue from positions we haven't been at, such that empty matches end
+                    cur = node.ends(chars, cur).difference(&res).copied().collect();
+                    if cur.is_empty() {
+                        return res;
+                    }
+                    res.extend(cur.iter().copied());
+                    if !*many {
+                        return res;
+                    }
+                }
+            },
+        }
+    }
+}
+
+
+
+/// Compiles [`Pattern`](super::Pattern)s from their textual representation.
+struct Compiler<'p> {
+    /// The pattern, for errors.
+    pattern: &'p str,
+    /// The characters of the pattern.
+    chars:   Vec<char>,
+    /// The position in `chars`.
+    pos:     usize,
+}
+impl<'p> Compiler<'p> {
+    /// Returns an error for the current position.
+    #[inline]
+    fn error(&self, expected: &'static str) -> Error {
+        match self.chars.get(self.pos) {
+            Some(c) => Error::Unexpected { pattern: self.pattern.into(), pos: self.pos, c: *c, expected },
+            None => Error::UnexpectedEnd { pattern: self.pattern.into(), expected },
+        }
+    }
+
+    /// Compiles alternatives separated by `|`.
+    fn alt(&mut self) -> Result<Node, Error> {
+        let mut alts: Vec<Node> = vec![self.seq()?];
+        while self.chars.get(self.pos) == Some(&'|') {
+            self.pos += 1;
+            alts.push(self.seq()?);
+        }
+        Ok(if alts.len() == 1 { alts.pop().unwrap() } else { Node::Alt(alts) })
+    }
+
+    /// Compiles a sequence of (quantified) atoms.
+    fn seq(&mut self) -> Result<Node, Error> {
+        let mut seq: Vec<Node> = Vec::new();
+        while let Some(c) = self.chars.get(self.pos) {
+            if *c == '|' || *c == ')' {
+                break;
+            }
+            let node: Node = self.atom()?;
+            let (min, many): (usize, bool) = match self.chars.get(self.pos) {
+                Some('*') => (0, true),
+                Some('+') => (1, true),
+                Some('?') => (0, false),
+                _ => {
+                    seq.push(node);
+                    continue;
+                },
+            };
+            self.pos += 1;
+            seq.push(Node::Repeat { node: Box::new(node), min, many });
+        }
+        Ok(Node::Seq(seq))
+    }
+
+    /// Compiles a single character, class or group.
+    fn atom(&mut self) -> Result<Node, Error> {
+        let Some(c) = self.chars.get(self.pos).copied() else { return Err(self.error("an atom")) };
+        self.pos += 1;
+        match c {
+            '(' => {
+                let node: Node = self.alt()?;
+                if self.chars.get(self.pos) != Some(&')') {
+                    return Err(self.error("')'"));
+                }
+                self.pos += 1;
+                Ok(node)
+            },
+            '[' => self.class(),
+            '.' => Ok(Node::Class { ranges: vec![('\n', '\n')], negated: true }),
+            '\\' => {
+                let (ranges, negated): (Vec<(char, char)>, bool) = self.escape()?;
+                Ok(Node::Class { ranges, negated })
+            },
+            '*' | '+' | '?' => {
+                self.pos -= 1;
+                Err(self.error("an atom before a quantifier"))
+            },
+            c => Ok(Node::Class { ranges: vec![(c, c)], negated: false }),
+        }
+    }
+
+    /// Compiles a character class, after its `[`.
+    fn class(&mut self) -> Result<Node, Error> {
+        let negated: bool = self.chars.get(self.pos) == Some(&'^');
+        if negated {
+            self.pos += 1;
+        }
+        let mut ranges: Vec<(char, char)> = Vec::new();
+        loop {
+            let Some(c) = self.chars.get(self.pos).copied() else { return Err(self.error("']'")) };
+            let start: usize = self.pos;
+            self.pos += 1;
+            let lo: char = match c {
+                ']' if !ranges.is_empty() => return Ok(Node::Class { ranges, negated }),
+                '\\' => match self.escape()? {
+                    (escaped, false) if escaped.len() == 1 && escaped[0].0 == escaped[0].1 => escaped[0].0,
+                    (escaped, false) => {
+                        ranges.extend(escaped);
+                        continue;
+                    },
+                    (_, true) => {
+                        self.pos -= 1;
+                        return Err(self.error("no negated escapes in a class"));
+                    },
+                },
+                c => c,
+            };
+            if self.chars.get(self.pos) == Some(&'-') && self.chars.get(self.pos + 1).is_some_and(|c| *c != ']') {
+                let hi: char = self.chars[self.pos + 1];
+                if hi < lo {
+                    return Err(Error::Reversed { pattern: self.pattern.into(), pos: start, lo, hi });
+                }
+                self.pos += 2;
+                ranges.push((lo, hi));
+            } else {
+                ranges.push((lo, lo));
+            }
+        }
+    }
+
+    /// Compiles an escape, after its `\`.
+    ///
+    /// Only punctuation is escaped to itself, such that escapes that mean something else in other
+    /// regular expressions (e.g., `\b` or `\x41`) are rejected instead of silently matching a
+    /// letter.
+    fn escape(&mut self) -> Result<(Vec<(char, char)>, bool), Error> {
+        let Some(c) = self.chars.get(self.pos).copied() else { return Err(self.error("an escaped character")) };
+        self.pos += 1;
+        let digits: Vec<(char, char)> = vec![('0', '9')];
+        let word: Vec<(char, char)> = vec![('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')];
+        let space: Vec<(char, char)> = vec![(' ', ' '), ('\t', '\r')];
+        Ok(match c {
+            'd' => (digits, false),
+            'D' => (digits, true),
+            'w' => (word, false),
+            'W' => (word, true),
+            's' => (space, false),
+            'S' => (space, true),
+            'n' => (vec![('\n', '\n')], false),
+            'r' => (vec![('\r', '\r')], false),
+            't' => (vec![('\t', '\t')], false),
+            c if !c.is_alphanumeric() => (vec![(c, c)], false),
+            _ => {
+                self.pos -= 1;
+                return Err(self.error("d, D, w, W, s, S, n, r, t or punctuation after '\\'"));
+            },
+        })
+    }
+}
+
+
+
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Compiles a pattern from its textual representation.
+///
+/// # Arguments
+/// - `pattern`: The textual representation of the pattern.
+///
+/// # Returns
+/// The root [`Node`] of the pattern.
+///
+/// # Errors
+/// This function errors if the pattern was malformed.
+pub(super) fn compile(pattern: &str) -> Result<Node, Error> {
+    let mut compiler = Compiler { pattern, chars: pattern.chars().collect(), pos: 0 };
+    let root: Node = compiler.alt()?;
+    if compiler.pos < compiler.chars.len() {
+        return Err(compiler.error("end of pattern"));
+    }
+    Ok(root)
+}
@@ -0,0 +1,167 @@
+//  MEMO.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:40:36
+//  Last edited:
+//    18 Oct 2026, 21:40:36
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements packrat memoisation of node parsers, which remembers
+//!   what a node parsed at every position such that backtracking doesn't
+//!   parse it again.
+//!
+//!   Nodes are memoised with `#[parse(memo)]` (see the
+//!   [derive macro](derive@super::Parse)), but only within a [`scope()`].
+//
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use chumsky::prelude::{Parser, empty, filter_map};
+use chumsky::{BoxedParser, Error as _};
+
+use super::{Error, Input};
+use crate::span::Span;
+
+
+/***** CONSTANTS *****/
+thread_local! {
+    /// The results of memoised parsers per [`scope()`], innermost last.
+    static SCOPES: RefCell<Vec<Table>> = const { RefCell::new(Vec::new()) };
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Finds what a node parsed at some position in the innermost [`scope()`].
+///
+/// # Arguments
+/// - `start`: The position at which the node was parsed.
+///
+/// # Returns
+/// The node and the position after it, or the error it failed with. [`None`] if it wasn't parsed
+/// there yet, or if there is no scope.
+fn lookup<I: Input, T: 'static + Clone>(start: usize) -> Option<Result<(T, usize), Error<I>>> {
+    SCOPES.with_borrow(|scopes| scopes.last()?.get(&(TypeId::of::<(I, T)>(), start))?.downcast_ref::<Result<(T, usize), Error<I>>>().cloned())
+}
+
+/// Remembers what a node parsed at some position in the innermost [`scope()`], if any.
+///
+/// # Arguments
+/// - `start`: The position at which the node was parsed.
+/// - `res`: The node and the position after it, or the error it failed with.
+fn store<I: Input, T: 'static>(start: usize, res: Result<(T, usize), Error<I>>) {
+    SCOPES.with_borrow_mut(|scopes| {
+        if let Some(scope) = scopes.last_mut() {
+            scope.insert((TypeId::of::<(I, T)>(), start), Box::new(res));
+        }
+    })
+}
+
+/// Returns a parser for the current position.
+///
+/// # Returns
+/// A [`Parser`] that consumes nothing and produces the start of the next input.
+#[inline]
+fn pos<I: Input>() -> impl Parser<I, usize, Error = Error<I>> + Clone { empty().map_with_span(|(), span: Span| span.start) }
+
+/// Returns a parser that skips input up to some position.
+///
+/// # Arguments
+/// - `end`: The position to skip to.
+///
+/// # Returns
+/// A [`Parser`] that consumes all input ending at or before `end`.
+#[inline]
+fn skip<I: Input>(end: usize) -> impl Parser<I, (), Error = Error<I>> + Clone {
+    // NOTE: Unlike `try_map()`, `filter_map()` places its error before the input, where it merges
+    // with whatever error follows the skipped input instead of replacing it
+    filter_map(move |span: Span, input| if span.end <= end { Ok(()) } else { Err(Error::<I>::expected_input_found(span, [], Some(input))) })
+        .repeated()
+        .ignored()
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// The results of memoised parsers in a single [`scope()`], per node type (and input) and
+/// position.
+type Table = HashMap<(TypeId, usize), Box<dyn Any>>;
+
+
+
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Memoises the parsers of nodes while a closure runs.
+///
+/// All memoised results are forgotten afterwards, so the closure should parse a single input.
+/// Outside of any scope, memoised parsers simply parse. [`Parse::parse_str()`](super::Parse) and
+/// friends run in their own scope already.
+///
+/// # Arguments
+/// - `run`: The closure to run, typically parsing something.
+///
+/// # Returns
+/// The result of the closure.
+pub fn scope<R>(run: impl FnOnce() -> R) -> R {
+    /// Forgets the innermost scope when dropped, even when panicking.
+    struct Guard;
+    impl Drop for Guard {
+        #[inline]
+        fn drop(&mut self) { SCOPES.with_borrow_mut(|scopes| scopes.pop()); }
+    }
+
+    SCOPES.with_borrow_mut(|scopes| scopes.push(HashMap::new()));
+    let _guard = Guard;
+    run()
+}
+
+/// Memoises a node parser.
+///
+/// Within a [`scope()`], the first time the parser runs at some position, its result is
+/// remembered. Any later time, the remembered node is cloned and the input it spans skipped, or
+/// the remembered error is returned, without parsing again. This makes backtracking between
+/// variants that share long prefixes linear instead of exponential.
+///
+/// This is used by the [derive macro](derive@super::Parse) for nodes marked `#[parse(memo)]`.
+/// Note that the parser should not recover from errors (see [`recover`](super::recover)), as
+/// those aren't remembered. Similarly, errors after a remembered node may list fewer expected
+/// alternatives.
+///
+/// # Arguments
+/// - `parser`: The parser to memoise.
+///
+/// # Returns
+/// A [`Parser`] that produces the same results.
+pub fn memo<I: Input, T: 'static + Clone, P: 'static + Parser<I, T, Error = Error<I>> + Clone>(parser: P) -> impl Parser<I, T, Error = Error<I>> + Clone {
+    pos().then_with(move |start: usize| -> BoxedParser<'static, I, T, Error<I>> {
+        match lookup::<I, T>(start) {
+            Some(Ok((node, end))) => skip(end).map(move |()| node.clone()).boxed(),
+            Some(Err(err)) => {
+                let at: usize = err.span().start;
+                skip(at).ignore_then(empty().try_map(move |(), _| Err(err.clone()))).boxed()
+            },
+            None => parser
+                .clone()
+                .then(pos())
+                .map(move |(node, end): (T, usize)| {
+                    store::<I, T>(start, Ok((node.clone(), end)));
+                    node
+                })
+                .map_err(move |err: Error<I>| {
+                    store::<I, T>(start, Err(err.clone()));
+                    err
+                })
+                .boxed(),
+        }
+    })
+}
@@ -4,7 +4,7 @@
 //  Created:
 //    05 Mar 2025, 17:46:02
 //  Last edited:
-//    05 Mar 2025, 17:49:35
+//    18 Oct 2026, 23:52:17
 //  Auto updated?
 //    Yes
 //
@@ -13,5 +13,268 @@
 //!
 //!   This library wraps around [chumsky](::chumsky) to implement the parsing, and you are likely
 //!   to interface with it. The only thing this library adds is the [`trait@Parse`]-trait and its
-//!   associated [derive macro](derive@Parse).
+//!   associated [derive macro](derive@Parse), plus the [`trait@Unparse`]-trait to print nodes
+//!   back, the [`trait@Generate`]-trait to generate random ones and the [`trait@Reparse`]-trait
+//!   to update them incrementally. Nodes can be parsed from characters or from tokens produced by
+//!   a [`trait@Lex`]er.
 //
+
+// Module definitions
+pub mod combinators;
+pub mod cst;
+pub mod doc;
+pub mod generate;
+mod impls;
+pub mod import;
+pub mod lex;
+pub mod memo;
+pub mod pratt;
+pub mod recover;
+pub mod reparse;
+pub mod roundtrip;
+#[cfg(feature = "trace")]
+pub mod trace;
+pub mod unparse;
+
+// Imports
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::sync::Arc;
+
+pub use ast_toolkit_2_macros::{Generate, Lex, Parse, Reparse, Unparse};
+pub use chumsky;
+use chumsky::error::Simple;
+use chumsky::prelude::{Parser, Recursive, end, just};
+use chumsky::stream::Stream;
+use chumsky::text;
+
+pub use generate::Generate;
+pub use lex::Lex;
+pub use reparse::Reparse;
+pub use unparse::Unparse;
+
+pub use crate::span::{Span, Spanned};
+
+
+/***** CONSTANTS *****/
+thread_local! {
+    /// The parsers returned by [`cached()`], per type.
+    static PARSERS: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+
+
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Builds the parser of a type only once per thread, allowing it to (indirectly) refer to itself.
+///
+/// The first call declares a [`Recursive`] parser for `T` and then calls `build` to define it.
+/// Any call made while `build` runs (e.g., because `T` contains a `Box<T>`) returns the declared
+/// parser, closing the loop. All later calls return the same parser. Parsers over different
+/// [`Input`]s are cached separately.
+///
+/// This is used by the [derive macro](derive@Parse), but is also useful for manual impls of
+/// recursive nodes.
+///
+/// # Arguments
+/// - `build`: Some closure building the parser of `T`.
+///
+/// # Returns
+/// A [`Recursive`] parser for `T`.
+pub fn cached<I: Input, T: 'static, P: 'static + Parser<I, T, Error = Error<I>>>(build: impl FnOnce() -> P) -> Recursive<'static, I, T, Error<I>> {
+    let id: TypeId = TypeId::of::<Recursive<'static, I, T, Error<I>>>();
+    if let Some(parser) =
+        PARSERS.with_borrow(|parsers| parsers.get(&id).and_then(|parser| parser.downcast_ref::<Recursive<'static, I, T, Error<I>>>()).cloned())
+    {
+        return parser;
+    }
+
+    // Declare it before building, such that recursive calls find it
+    let mut parser: Recursive<'static, I, T, Error<I>> = Recursive::declare();
+    PARSERS.with_borrow_mut(|parsers| parsers.insert(id, Box::new(parser.clone())));
+    parser.define(build());
+    parser
+}
+
+/// Wraps the parser of a node in a trace point if the `trace`-feature is enabled.
+///
+/// This is used by the [derive macro](derive@Parse) for every node and variant. Without the
+/// feature, the parser is returned as-is.
+///
+/// # Arguments
+/// - `name`: The name of the node in traces.
+/// - `parser`: The parser to trace.
+///
+/// # Returns
+/// A [`Parser`] that behaves the same (see `trace::point()`).
+#[inline]
+pub fn traced<I: Input, T, P: Parser<I, T, Error = Error<I>> + Clone>(name: &'static str, parser: P) -> impl Parser<I, T, Error = Error<I>> + Clone {
+    #[cfg(feature = "trace")]
+    return trace::point(name, parser);
+    #[cfg(not(feature = "trace"))]
+    {
+        let _ = name;
+        parser
+    }
+}
+
+/// Turns source text into a [`Stream`] that chumsky parsers can parse.
+///
+/// Every character is spanned by its (character) offset in the text.
+///
+/// # Arguments
+/// - `source`: The name of the source (see [`Sources`](crate::span::Sources)).
+/// - `text`: The source text.
+///
+/// # Returns
+/// A [`Stream`] over the characters in `text`.
+pub fn stream(source: impl Into<Arc<str>>, text: &str) -> InputStream<'_, char> {
+    let source: Arc<str> = source.into();
+    let eoi: Span = Span::empty(source.clone(), text.chars().count());
+    Stream::from_iter(eoi, Box::new(text.chars().enumerate().map(move |(i, c)| (c, Span::new(source.clone(), i..i + 1)))))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// The errors produced by [`Parse`]rs over some [`Input`].
+///
+/// These implement [`Report`](crate::report::Report) if the `report`-feature is enabled and the
+/// input implements [`Display`](std::fmt::Display).
+pub type Error<I = char> = Simple<I, Span>;
+
+/// The stream that [`Parse`]rs over some [`Input`] parse.
+pub type InputStream<'t, I> = Stream<'t, I, Span, Box<dyn 't + Iterator<Item = (I, Span)>>>;
+
+
+
+/// Marks a type as something that [`Parse`]rs can parse, i.e., characters or tokens.
+///
+/// This is implemented for [`char`], and the [`Lex`](derive@Lex) derive macro implements it for
+/// tokens.
+pub trait Input: 'static + Clone + Debug + Eq + Hash {
+    /// The texts of the keywords and punctuation that [`Input::keyword()`] and [`Input::punct()`]
+    /// can parse, or [`None`] if they can parse any (like characters can).
+    ///
+    /// The [`Parse`](derive@Parse) derive macro checks the ones it parses against this at compile
+    /// time.
+    const LITERALS: Option<&'static [&'static str]> = None;
+
+    /// Turns source text into a stream of this input.
+    ///
+    /// # Arguments
+    /// - `source`: The name of the source (see [`Sources`](crate::span::Sources)).
+    /// - `text`: The source text.
+    ///
+    /// # Returns
+    /// An [`InputStream`] over the input in `text`, and any [`Error`]s found while producing it.
+    fn stream(source: Arc<str>, text: &str) -> (InputStream<'_, Self>, Vec<Error<Self>>);
+
+    /// Returns a parser for a keyword, such as `let` or `fn`.
+    ///
+    /// # Arguments
+    /// - `keyword`: The keyword to parse.
+    ///
+    /// # Returns
+    /// A [`Parser`] that produces the [`Span`] of the keyword.
+    fn keyword(keyword: &'static str) -> impl Parser<Self, Span, Error = Error<Self>> + Clone;
+
+    /// Returns a parser for a piece of punctuation, such as `=` or `->`.
+    ///
+    /// # Arguments
+    /// - `punct`: The punctuation to parse.
+    ///
+    /// # Returns
+    /// A [`Parser`] that produces the [`Span`] of the punctuation.
+    fn punct(punct: &'static str) -> impl Parser<Self, Span, Error = Error<Self>> + Clone;
+
+    /// Returns a parser that skips any whitespace.
+    ///
+    /// # Returns
+    /// A [`Parser`] that skips zero or more whitespace.
+    fn whitespace() -> impl Parser<Self, (), Error = Error<Self>> + Clone;
+}
+
+// Std impls
+impl Input for char {
+    #[inline]
+    fn stream(source: Arc<str>, text: &str) -> (InputStream<'_, Self>, Vec<Error<Self>>) { (stream(source, text), Vec::new()) }
+
+    /// Parses a keyword, which doesn't match if it's directly followed by more identifier
+    /// characters (e.g., `letter` is not `let`).
+    #[inline]
+    fn keyword(keyword: &'static str) -> impl Parser<Self, Span, Error = Error<Self>> + Clone { text::keyword(keyword).map_with_span(|_, span| span) }
+
+    #[inline]
+    fn punct(punct: &'static str) -> impl Parser<Self, Span, Error = Error<Self>> + Clone { just(punct).map_with_span(|_, span| span) }
+
+    #[inline]
+    fn whitespace() -> impl Parser<Self, (), Error = Error<Self>> + Clone { text::whitespace().ignored() }
+}
+
+
+
+/// Marks an AST node as parsable from source text.
+///
+/// Nodes are parsed from characters by default, or from tokens with another [`Input`] (see the
+/// [`Lex`](derive@Lex) derive macro).
+///
+/// Usually, you would [derive](derive@Parse) this trait instead of implementing it manually.
+pub trait Parse<I: Input = char>: 'static + Sized {
+    /// Returns a [chumsky](::chumsky) parser for this node.
+    ///
+    /// Parsers of derived nodes are built once per thread (see [`cached()`]), such that nodes may
+    /// contain themselves (e.g., `Box<Self>`).
+    ///
+    /// # Returns
+    /// A [`Parser`] over the input that produces this node.
+    fn parser() -> impl Parser<I, Self, Error = Error<I>> + Clone;
+
+    /// Convenience function for parsing this node from an entire source text.
+    ///
+    /// Whitespace around the node is skipped. When parsing tokens, the text is lexed first.
+    ///
+    /// # Arguments
+    /// - `source`: The name of the source (see [`Sources`](crate::span::Sources)).
+    /// - `text`: The source text.
+    ///
+    /// # Returns
+    /// The parsed node.
+    ///
+    /// # Errors
+    /// This function errors with all [`Error`]s found if the `text` wasn't a valid node.
+    #[inline]
+    fn parse_str(source: impl Into<Arc<str>>, text: &str) -> Result<Self, Vec<Error<I>>> {
+        match Self::parse_str_recovery(source, text) {
+            (Some(node), errors) if errors.is_empty() => Ok(node),
+            (_, errors) => Err(errors),
+        }
+    }
+
+    /// Convenience function for parsing this node from an entire source text, recovering from
+    /// syntax errors where possible.
+    ///
+    /// How nodes recover is defined through the [`recover`]-module (see the
+    /// [derive macro](derive@Parse)).
+    ///
+    /// # Arguments
+    /// - `source`: The name of the source (see [`Sources`](crate::span::Sources)).
+    /// - `text`: The source text.
+    ///
+    /// # Returns
+    /// A tuple of the (partial) node, if any could be made, and all [`Error`]s found.
+    #[inline]
+    fn parse_str_recovery(source: impl Into<Arc<str>>, text: &str) -> (Option<Self>, Vec<Error<I>>) {
+        let (stream, mut errors): (InputStream<I>, Vec<Error<I>>) = I::stream(source.into(), text);
+        let (node, parse_errors): (Option<Self>, Vec<Error<I>>) =
+            memo::scope(|| I::whitespace().ignore_then(Self::parser()).then_ignore(I::whitespace()).then_ignore(end()).parse_recovery(stream));
+        errors.extend(parse_errors);
+        (node, errors)
+    }
+}
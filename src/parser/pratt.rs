@@ -0,0 +1,462 @@
+//  PRATT.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:53:56
+//  Last edited:
+//    18 Oct 2026, 21:18:33
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements parsing expressions with prefix, infix and postfix
+//!   operators by precedence.
+//!
+//!   Usually, you don't use this module directly, but rather derive
+//!   [`Parse`](derive@super::Parse) on an expression enum with operator
+//!   variants. See the derive macro for more information.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::rc::Rc;
+
+use chumsky::BoxedParser;
+use chumsky::prelude::{Parser, choice};
+
+use super::combinators::{keyword, padded_left, punct};
+use super::{Error, Input, Parse};
+use crate::span::Span;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Computes the span of an operator node from the spans of its outermost parts.
+///
+/// # Arguments
+/// - `first`: The span of the leftmost part.
+/// - `last`: The span of the rightmost part.
+///
+/// # Returns
+/// A [`Span`] covering both, or `first` if they are in different sources.
+#[inline]
+fn join(first: &Span, last: &Span) -> Span { first.join(last).unwrap_or_else(|| first.clone()) }
+
+/// Builds a parser for any of the given operators, which produces the operator's builder and span.
+///
+/// # Arguments
+/// - `ops`: The [`Operator`]s to parse.
+///
+/// # Returns
+/// A [`BoxedParser`] trying every operator's token in order.
+fn tokens<I: Input, T: 'static>(ops: &[&Operator<T>]) -> BoxedParser<'static, I, (Build<T>, Span), Error<I>> {
+    choice(
+        ops.iter()
+            .map(|op| {
+                let build: Build<T> = op.build.clone();
+                op.token.parser().map(move |span| (build.clone(), span)).boxed()
+            })
+            .collect::<Vec<_>>(),
+    )
+    .boxed()
+}
+
+/// Gets the name of a type without its path.
+///
+/// # Returns
+/// The last segment of [`std::any::type_name()`], without generic arguments.
+#[cfg(feature = "railroad")]
+fn short_name<T>() -> &'static str {
+    let name: &'static str = std::any::type_name::<T>();
+    let name: &'static str = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// Constructs an operator node from its operands, operator span and total span.
+enum Build<T> {
+    /// Prefix or postfix operators, given the operand, the operator span and the node span.
+    Unary(Rc<dyn Fn(T, Span, Span) -> T>),
+    /// Infix operators, given the left operand, the operator span, the right operand and the node
+    /// span.
+    Binary(Rc<dyn Fn(T, Span, T, Span) -> T>),
+}
+impl<T> Clone for Build<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        match self {
+            Self::Unary(build) => Self::Unary(build.clone()),
+            Self::Binary(build) => Self::Binary(build.clone()),
+        }
+    }
+}
+impl<T> Build<T> {
+    /// Builds a prefix or postfix operator node.
+    #[inline]
+    fn unary(&self, operand: T, op: Span, span: Span) -> T {
+        match self {
+            Self::Unary(build) => build(operand, op, span),
+            Self::Binary(_) => unreachable!(),
+        }
+    }
+
+    /// Builds an infix operator node.
+    #[inline]
+    fn binary(&self, lhs: T, op: Span, rhs: T, span: Span) -> T {
+        match self {
+            Self::Binary(build) => build(lhs, op, rhs, span),
+            Self::Unary(_) => unreachable!(),
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Builds a parser for expressions with operators.
+///
+/// Operators with a higher precedence bind tighter. Within a precedence, postfix operators bind
+/// tighter than prefix operators, which bind tighter than infix operators. Operators of the same
+/// fixity and precedence are tried in the given order, so list e.g. `<=` before `<`.
+///
+/// # Arguments
+/// - `atom`: The [`Parser`] of the operands that aren't operator expressions themselves.
+/// - `ops`: The [`Operator`]s to parse.
+///
+/// # Returns
+/// A [`BoxedParser`] that produces the expression.
+///
+/// # Panics
+/// This function panics if infix operators with the same precedence have a different
+/// [`Assoc`]iativity.
+pub fn pratt<I: Input, T: 'static>(atom: impl 'static + Parser<I, T, Error = Error<I>> + Clone, ops: Vec<Operator<T>>) -> BoxedParser<'static, I, T, Error<I>> {
+    // Build the levels from the tightest to the loosest
+    let mut level: BoxedParser<'static, I, (T, Span), Error<I>> = padded_left(atom.map_with_span(|node, span| (node, span))).boxed();
+    for prec in precedences(&ops) {
+        let at = |fixity: fn(&Fixity) -> bool| -> Vec<&Operator<T>> { ops.iter().filter(|op| op.prec == prec && fixity(&op.fixity)).collect() };
+
+        // Postfix, then prefix...
+        let postfix: Vec<&Operator<T>> = at(|fixity| matches!(fixity, Fixity::Postfix));
+        if !postfix.is_empty() {
+            level = level
+                .then(tokens(&postfix).repeated())
+                .foldl(|(operand, first), (build, op)| {
+                    let span: Span = join(&first, &op);
+                    (build.unary(operand, op, span.clone()), span)
+                })
+                .boxed();
+        }
+        let prefix: Vec<&Operator<T>> = at(|fixity| matches!(fixity, Fixity::Prefix));
+        if !prefix.is_empty() {
+            level = tokens(&prefix)
+                .repeated()
+                .then(level)
+                .foldr(|(build, op), (operand, last)| {
+                    let span: Span = join(&op, &last);
+                    (build.unary(operand, op, span.clone()), span)
+                })
+                .boxed();
+        }
+
+        // ...then infix
+        let infix: Vec<&Operator<T>> = at(|fixity| matches!(fixity, Fixity::Infix(_)));
+        let Some(assoc) = infix.first().map(|op| op.fixity) else { continue };
+        if infix.iter().any(|op| op.fixity != assoc) {
+            panic!("Infix operators with precedence {prec} have different associativities");
+        }
+        let rest = tokens(&infix).then(level.clone());
+        level = match assoc {
+            Fixity::Infix(Assoc::Left) => level
+                .then(rest.repeated())
+                .foldl(|(lhs, first), ((build, op), (rhs, last))| {
+                    let span: Span = join(&first, &last);
+                    (build.binary(lhs, op, rhs, span.clone()), span)
+                })
+                .boxed(),
+            Fixity::Infix(Assoc::Right) => level
+                .then(rest.repeated())
+                .map(|(first, rest)| {
+                    // Fold from the right, pairing every operator with the operand before it
+                    let mut lhss: Vec<(T, Span)> = Vec::with_capacity(rest.len() + 1);
+                    lhss.push(first);
+                    let mut ops: Vec<(Build<T>, Span)> = Vec::with_capacity(rest.len());
+                    for (op, operand) in rest {
+                        ops.push(op);
+                        lhss.push(operand);
+                    }
+                    let mut acc: (T, Span) = lhss.pop().unwrap();
+                    while let (Some((build, op)), Some((lhs, first))) = (ops.pop(), lhss.pop()) {
+                        let span: Span = join(&first, &acc.1);
+                        acc = (build.binary(lhs, op, acc.0, span.clone()), span);
+                    }
+                    acc
+                })
+                .boxed(),
+            Fixity::Infix(Assoc::None) => level
+                .then(rest.or_not())
+                .map(|((lhs, first), rest)| match rest {
+                    Some(((build, op), (rhs, last))) => {
+                        let span: Span = join(&first, &last);
+                        (build.binary(lhs, op, rhs, span.clone()), span)
+                    },
+                    None => (lhs, first),
+                })
+                .boxed(),
+            Fixity::Prefix | Fixity::Postfix => unreachable!(),
+        };
+    }
+    level.map(|(node, _)| node).boxed()
+}
+
+/// Finds the precedences used by operators, from the highest to the lowest.
+///
+/// # Arguments
+/// - `ops`: The [`Operator`]s to find the precedences of.
+///
+/// # Returns
+/// Every precedence once, in descending order.
+#[inline]
+pub fn precedences<T>(ops: &[Operator<T>]) -> Vec<u32> {
+    let mut precs: Vec<u32> = ops.iter().map(|op| op.prec).collect();
+    precs.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+    precs.dedup();
+    precs
+}
+
+/// Serializes the operators of an expression into a [`Diagram`](crate::railroad::Diagram).
+///
+/// The diagram has one track per precedence, referring to the next-tighter one, and ends with
+/// the precedence table. The operands are referred to as `<name> atom`.
+///
+/// # Returns
+/// A [`Diagram`](crate::railroad::Diagram) showing the expression syntax of `T`.
+#[cfg(feature = "railroad")]
+pub fn to_railroad_diagram<T: Pratt>() -> crate::railroad::Diagram<'static> {
+    use railroad::{Choice, Comment, Empty, Node, NonTerminal, Optional, Repeat, Sequence, Stack, Terminal};
+
+    /// Renders zero or more repetitions of a node.
+    fn many(node: Box<dyn Node>) -> Box<dyn Node> { Box::new(Optional::new(Repeat::new(node, Empty))) }
+    /// Renders a choice between the tokens of the given operators.
+    fn tokens<T>(ops: &[&Operator<T>]) -> Box<dyn Node> {
+        Box::new(Choice::new(ops.iter().map(|op| Box::new(Terminal::new(op.token.to_string())) as Box<dyn Node>).collect()))
+    }
+
+    let name: &'static str = short_name::<T>();
+    let ops: Vec<Operator<T>> = T::operators();
+    let mut precs: Vec<u32> = precedences(&ops);
+    precs.reverse();
+    let level_name = |i: usize| -> String { if i < precs.len() { format!("{name} {}", precs[i]) } else { format!("{name} atom") } };
+
+    // Generate the tracks from the loosest to the tightest
+    let mut diag = crate::railroad::Diagram::new(NonTerminal::new(level_name(0)));
+    let mut table: Vec<Box<dyn Node>> = Vec::with_capacity(precs.len() + 1);
+    table.push(Box::new(Comment::new("precedence | fixity | operators".into())));
+    for (i, prec) in precs.iter().enumerate() {
+        let at = |fixity: fn(&Fixity) -> bool| -> Vec<&Operator<T>> { ops.iter().filter(|op| op.prec == *prec && fixity(&op.fixity)).collect() };
+        let postfix: Vec<&Operator<T>> = at(|fixity| matches!(fixity, Fixity::Postfix));
+        let prefix: Vec<&Operator<T>> = at(|fixity| matches!(fixity, Fixity::Prefix));
+        let infix: Vec<&Operator<T>> = at(|fixity| matches!(fixity, Fixity::Infix(_)));
+        let operand = || -> Box<dyn Node> {
+            let mut seq: Vec<Box<dyn Node>> = Vec::with_capacity(3);
+            if !prefix.is_empty() {
+                seq.push(many(tokens(&prefix)));
+            }
+            seq.push(Box::new(NonTerminal::new(level_name(i + 1))));
+            if !postfix.is_empty() {
+                seq.push(many(tokens(&postfix)));
+            }
+            Box::new(Sequence::new(seq))
+        };
+        let track: Box<dyn Node> = match infix.first().map(|op| op.fixity) {
+            Some(Fixity::Infix(Assoc::None)) => {
+                Box::new(Sequence::new(vec![operand(), Box::new(Optional::new(Sequence::new(vec![tokens(&infix), operand()])))]))
+            },
+            Some(_) => Box::new(Sequence::new(vec![operand(), many(Box::new(Sequence::new(vec![tokens(&infix), operand()])))])),
+            None => operand(),
+        };
+        diag.add_nonterm(level_name(i), track);
+
+        // Add the level's rows to the table
+        for (fixity, ops) in [(infix.first().map(|op| op.fixity), &infix), (Some(Fixity::Prefix), &prefix), (Some(Fixity::Postfix), &postfix)] {
+            if let (Some(fixity), false) = (fixity, ops.is_empty()) {
+                let ops: Vec<String> = ops.iter().map(|op| format!("{} ({})", op.token, op.name)).collect();
+                table.push(Box::new(Comment::new(format!("{prec} | {fixity} | {}", ops.join(", ")))));
+            }
+        }
+    }
+    diag.add_nonterm(format!("{name} precedence"), Stack::new(table));
+    diag
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines how infix operators with the same precedence group.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Assoc {
+    /// `a - b - c` is `(a - b) - c`.
+    Left,
+    /// `a ^ b ^ c` is `a ^ (b ^ c)`.
+    Right,
+    /// `a == b == c` is not allowed.
+    None,
+}
+impl Display for Assoc {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Left => write!(f, "left"),
+            Self::Right => write!(f, "right"),
+            Self::None => write!(f, "none"),
+        }
+    }
+}
+
+
+
+/// Defines where an operator goes relative to its operands.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Fixity {
+    /// Before its operand (e.g., `-a`).
+    Prefix,
+    /// In between its operands (e.g., `a + b`).
+    Infix(Assoc),
+    /// After its operand (e.g., `a?`).
+    Postfix,
+}
+impl Display for Fixity {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Prefix => write!(f, "prefix"),
+            Self::Infix(assoc) => write!(f, "infix, {assoc}"),
+            Self::Postfix => write!(f, "postfix"),
+        }
+    }
+}
+
+
+
+/// Defines the token of an operator.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Token {
+    /// A keyword (e.g., `and`), which may not be directly followed by identifier characters.
+    Keyword(&'static str),
+    /// Punctuation (e.g., `+`).
+    Punct(&'static str),
+}
+impl Display for Token {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Keyword(token) | Self::Punct(token) => write!(f, "{token}"),
+        }
+    }
+}
+impl Token {
+    /// Returns a parser for this token.
+    ///
+    /// # Returns
+    /// A [`Parser`] over any [`Input`] that produces the [`Span`] of the token.
+    #[inline]
+    pub fn parser<I: Input>(&self) -> BoxedParser<'static, I, Span, Error<I>> {
+        match self {
+            Self::Keyword(token) => keyword(token).boxed(),
+            Self::Punct(token) => punct(token).boxed(),
+        }
+    }
+}
+
+
+
+/// Defines a single operator of an expression.
+pub struct Operator<T> {
+    /// The name of the operator (e.g., the variant constructing it).
+    pub name:   &'static str,
+    /// The operator's token.
+    pub token:  Token,
+    /// Where the operator goes relative to its operands.
+    pub fixity: Fixity,
+    /// How tightly the operator binds. Higher binds tighter.
+    pub prec:   u32,
+    /// Constructs the node.
+    build:      Build<T>,
+}
+
+// Constructors
+impl<T> Operator<T> {
+    /// Constructor for a prefix operator.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the operator.
+    /// - `prec`: How tightly the operator binds. Higher binds tighter.
+    /// - `token`: The operator's [`Token`].
+    /// - `build`: Constructs the node from the operand, the operator's [`Span`] and the node's
+    ///   [`Span`].
+    ///
+    /// # Returns
+    /// A new Operator.
+    #[inline]
+    pub fn prefix(name: &'static str, prec: u32, token: Token, build: impl 'static + Fn(T, Span, Span) -> T) -> Self {
+        Self { name, token, fixity: Fixity::Prefix, prec, build: Build::Unary(Rc::new(build)) }
+    }
+
+    /// Constructor for an infix operator.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the operator.
+    /// - `prec`: How tightly the operator binds. Higher binds tighter.
+    /// - `assoc`: How the operator groups with operators of the same precedence.
+    /// - `token`: The operator's [`Token`].
+    /// - `build`: Constructs the node from the left operand, the operator's [`Span`], the right
+    ///   operand and the node's [`Span`].
+    ///
+    /// # Returns
+    /// A new Operator.
+    #[inline]
+    pub fn infix(name: &'static str, prec: u32, assoc: Assoc, token: Token, build: impl 'static + Fn(T, Span, T, Span) -> T) -> Self {
+        Self { name, token, fixity: Fixity::Infix(assoc), prec, build: Build::Binary(Rc::new(build)) }
+    }
+
+    /// Constructor for a postfix operator.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the operator.
+    /// - `prec`: How tightly the operator binds. Higher binds tighter.
+    /// - `token`: The operator's [`Token`].
+    /// - `build`: Constructs the node from the operand, the operator's [`Span`] and the node's
+    ///   [`Span`].
+    ///
+    /// # Returns
+    /// A new Operator.
+    #[inline]
+    pub fn postfix(name: &'static str, prec: u32, token: Token, build: impl 'static + Fn(T, Span, Span) -> T) -> Self {
+        Self { name, token, fixity: Fixity::Postfix, prec, build: Build::Unary(Rc::new(build)) }
+    }
+}
+
+
+
+/// Marks an expression node as consisting of operands and operators.
+///
+/// This is implemented by the [derive macro](derive@super::Parse) for enums with operator
+/// variants. Its [`Parse::parser()`] then calls [`pratt()`] with these.
+pub trait Pratt<I: Input = char>: Parse<I> {
+    /// Returns a [chumsky](::chumsky) parser for the operands of operators.
+    ///
+    /// # Returns
+    /// A [`Parser`] over the input that produces this node.
+    fn atom() -> impl Parser<I, Self, Error = Error<I>> + Clone;
+
+    /// Returns the operators of this node.
+    ///
+    /// # Returns
+    /// A list of [`Operator`]s that construct this node.
+    fn operators() -> Vec<Operator<Self>>;
+}
@@ -0,0 +1,116 @@
+//  RECOVER.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:56:55
+//  Last edited:
+//    18 Oct 2026, 20:56:55
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines how nodes recover from syntax errors, such that one parse
+//!   finds all of them.
+//!
+//!   Usually, you don't use this module directly, but rather configure it
+//!   through the [derive macro](derive@super::Parse).
+//
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use chumsky::prelude::{Parser, nested_delimiters, none_of, one_of, skip_parser};
+
+use super::{Error, Parse};
+use crate::span::Span;
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Recovers a node by skipping input until a synchronisation token.
+///
+/// If the node fails to parse, then the input is skipped until one of the synchronisation tokens
+/// or the end of the input. The skipped input becomes the node's [error node](Recover::error()).
+///
+/// Tokens in `consume` end the node (e.g., `;`), and are skipped too. Tokens in `before` end
+/// something around the node (e.g., `}`), and are left alone. If nothing would be skipped, the
+/// node fails as usual, such that lists of nodes still end.
+///
+/// # Arguments
+/// - `consume`: The characters to synchronise on, including them in the error node.
+/// - `before`: The characters to synchronise on, excluding them from the error node.
+/// - `parser`: The [`Parser`] of the node.
+///
+/// # Returns
+/// A [`Parser`] that produces the node.
+#[inline]
+pub fn sync<T: Recover>(
+    consume: &'static str,
+    before: &'static str,
+    parser: impl Parser<char, T, Error = Error> + Clone,
+) -> impl Parser<char, T, Error = Error> + Clone {
+    let skip = none_of(format!("{consume}{before}")).repeated();
+    let recovery = skip.clone().then(one_of(consume)).ignored().or(skip.at_least(1).ignored()).map_with_span(|_, span| T::error(span));
+    parser.recover_with(skip_parser(recovery))
+}
+
+/// Recovers a delimited node by skipping to its closing delimiter.
+///
+/// If the node fails to parse, then the input is skipped until the closing delimiter that matches
+/// the opening one, respecting any nested parentheses, brackets or braces. The skipped input
+/// becomes the node's [error node](Recover::error()).
+///
+/// # Arguments
+/// - `open`: The opening delimiter (e.g., `(`).
+/// - `close`: The closing delimiter (e.g., `)`).
+/// - `parser`: The [`Parser`] of the node, including its delimiters.
+///
+/// # Returns
+/// A [`Parser`] that produces the node.
+#[inline]
+pub fn nested<T: Recover>(open: char, close: char, parser: impl Parser<char, T, Error = Error> + Clone) -> impl Parser<char, T, Error = Error> + Clone {
+    parser.recover_with(nested_delimiters(open, close, [('(', ')'), ('[', ']'), ('{', '}')], T::error))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Marks an AST node as having a fallback for input that failed to parse.
+///
+/// The [derive macro](derive@super::Parse) implements this for enums with a variant marked
+/// `#[parse(error)]`.
+pub trait Recover: Parse {
+    /// Constructs the error node of this type.
+    ///
+    /// # Arguments
+    /// - `span`: The [`Span`] of the input that failed to parse.
+    ///
+    /// # Returns
+    /// A new instance of Self that represents the unparsable input.
+    fn error(span: Span) -> Self;
+}
+
+// Std impls
+impl<T: Recover> Recover for Box<T> {
+    #[inline]
+    fn error(span: Span) -> Self { Box::new(T::error(span)) }
+}
+impl<T: Recover> Recover for Rc<T> {
+    #[inline]
+    fn error(span: Span) -> Self { Rc::new(T::error(span)) }
+}
+impl<T: Recover> Recover for Arc<T> {
+    #[inline]
+    fn error(span: Span) -> Self { Arc::new(T::error(span)) }
+}
+impl<T: Parse> Recover for Option<T> {
+    /// Recovers as [`None`].
+    #[inline]
+    fn error(_span: Span) -> Self { None }
+}
+impl<T: Parse> Recover for Vec<T> {
+    /// Recovers as an empty list.
+    #[inline]
+    fn error(_span: Span) -> Self { Vec::new() }
+}
@@ -0,0 +1,519 @@
+//  REPARSE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:29:40
+//  Last edited:
+//    18 Oct 2026, 21:29:40
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines the [`Reparse`]-trait, which updates parsed AST nodes after
+//!   an edit to the source text by only reparsing the nodes affected by
+//!   it (e.g., for language servers).
+//!
+//!   Usually, you would [derive](derive@super::Reparse) it next to
+//!   [`Parse`], and then keep the tree in an [`Incremental`].
+//
+
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use chumsky::prelude::Parser;
+
+use super::{Error, Input, InputStream, Parse};
+use crate::span::Span;
+
+
+/***** HELPER MACROS *****/
+/// Implements [`Reparse`] for a pointer-like type by reparsing its pointee.
+///
+/// # Syntax
+/// Call it with a type to implement it for (including the generic `T`), a function returning a
+/// mutable reference to the pointee and optionally another bound on `T`.
+macro_rules! wrapper_impl {
+    ($type:ty, $get:expr $(, $bound:path)?) => {
+        impl<I: Input, T: $($bound +)? Reparse<I>> Reparse<I> for $type {
+            #[inline]
+            fn reparse(&mut self, ctx: Context<'_, I>) -> Option<Span> { $get(self).reparse(ctx) }
+
+            #[inline]
+            fn shift(&mut self, from: usize, delta: isize) { $get(self).shift(from, delta) }
+
+            #[inline]
+            fn is_operator(&self) -> bool { <T as Reparse<I>>::is_operator(self) }
+        }
+    };
+}
+
+/// Implements [`Reparse`] for types that never contain spans, which are hence never reparsed nor
+/// shifted.
+///
+/// # Syntax
+/// Call it with a comma-separated list of types to implement it for.
+macro_rules! leaf_impl {
+    ($($type:ty),+) => {
+        $(
+            impl<I: Input> Reparse<I> for $type {
+                #[inline]
+                fn reparse(&mut self, _ctx: Context<'_, I>) -> Option<Span> { None }
+
+                #[inline]
+                fn shift(&mut self, _from: usize, _delta: isize) {}
+            }
+        )+
+    };
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Finds the bytes of a range of characters in some text.
+///
+/// # Arguments
+/// - `text`: The text to find the range in.
+/// - `range`: The range of characters.
+///
+/// # Returns
+/// The equivalent range of bytes.
+///
+/// # Panics
+/// This function panics if `range` is out of bounds of the `text`.
+fn bytes(text: &str, range: Range<usize>) -> Range<usize> {
+    let mut offsets = text.char_indices().map(|(i, _)| i).chain([text.len()]);
+    let start: usize = offsets.nth(range.start).unwrap_or_else(|| panic!("Character {} is out of bounds", range.start));
+    let end: usize = if range.end > range.start {
+        offsets.nth(range.end - range.start - 1).unwrap_or_else(|| panic!("Character {} is out of bounds", range.end))
+    } else {
+        start
+    };
+    start..end
+}
+
+/// Moves a position after an edit.
+///
+/// # Arguments
+/// - `pos`: The position to move.
+/// - `from`: The position from which on positions are moved.
+/// - `delta`: The number of characters to move them by.
+///
+/// # Returns
+/// The moved position.
+#[inline]
+fn shift(pos: usize, from: usize, delta: isize) -> usize { if pos >= from { pos.saturating_add_signed(delta) } else { pos } }
+
+
+
+
+
+/***** LIBRARY *****/
+/// Describes a change to source text: a range of characters replaced by new text.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Edit {
+    /// The range of characters that is replaced, in the text before the edit.
+    pub range: Range<usize>,
+    /// The text it is replaced with.
+    pub text:  String,
+}
+
+// Constructors
+impl Edit {
+    /// Constructor for an Edit that replaces a range of characters.
+    ///
+    /// # Arguments
+    /// - `range`: The range of characters that is replaced, in the text before the edit.
+    /// - `text`: The text it is replaced with.
+    ///
+    /// # Returns
+    /// A new Edit.
+    #[inline]
+    pub fn new(range: Range<usize>, text: impl Into<String>) -> Self { Self { range, text: text.into() } }
+
+    /// Constructor for an Edit that inserts text.
+    ///
+    /// # Arguments
+    /// - `pos`: The character before which to insert the text.
+    /// - `text`: The text to insert.
+    ///
+    /// # Returns
+    /// A new Edit.
+    #[inline]
+    pub fn insert(pos: usize, text: impl Into<String>) -> Self { Self { range: pos..pos, text: text.into() } }
+
+    /// Constructor for an Edit that deletes a range of characters.
+    ///
+    /// # Arguments
+    /// - `range`: The range of characters to delete.
+    ///
+    /// # Returns
+    /// A new Edit.
+    #[inline]
+    pub fn delete(range: Range<usize>) -> Self { Self { range, text: String::new() } }
+}
+
+
+
+/// Describes an [`Edit`] to the nodes that are [`Reparse`]d.
+///
+/// It's passed down the tree, with every node deciding whether the edit falls within it.
+#[derive(Debug)]
+pub struct Context<'t, I = char> {
+    /// The name of the source.
+    source:  &'t Arc<str>,
+    /// The source text after the edit.
+    text:    &'t str,
+    /// The range of characters that was replaced, in the text before the edit.
+    range:   &'t Range<usize>,
+    /// The number of characters it was replaced with.
+    len:     usize,
+    /// Whether the node is the operand of an operator.
+    operand: bool,
+    /// The input that nodes are parsed from.
+    input:   PhantomData<fn() -> I>,
+}
+
+// Constructors
+impl<'t, I> Context<'t, I> {
+    /// Constructor for the Context.
+    ///
+    /// # Arguments
+    /// - `source`: The name of the source (see [`Sources`](crate::span::Sources)).
+    /// - `text`: The source text after the edit.
+    /// - `edit`: The [`Edit`] that was made to the source text.
+    ///
+    /// # Returns
+    /// A new Context for the root of the tree.
+    #[inline]
+    pub fn new(source: &'t Arc<str>, text: &'t str, edit: &'t Edit) -> Self {
+        Self { source, text, range: &edit.range, len: edit.text.chars().count(), operand: false, input: PhantomData }
+    }
+
+    /// Returns a copy of this Context for a child node.
+    ///
+    /// # Arguments
+    /// - `operand`: Whether the child is the operand of an operator. If so, it's only reparsed if
+    ///   it then still isn't an operator itself, as it would otherwise bind differently.
+    ///
+    /// # Returns
+    /// A new Context.
+    #[inline]
+    pub fn with_operand(mut self, operand: bool) -> Self {
+        self.operand = operand;
+        self
+    }
+}
+
+// Copying
+impl<'t, I> Clone for Context<'t, I> {
+    #[inline]
+    fn clone(&self) -> Self { *self }
+}
+impl<'t, I> Copy for Context<'t, I> {}
+
+// Matching
+impl<'t, I: Input> Context<'t, I> {
+    /// Returns the number of characters by which positions after the edit move.
+    ///
+    /// # Returns
+    /// The difference in length between the new and the replaced text.
+    #[inline]
+    pub fn delta(&self) -> isize { self.len as isize - self.range.len() as isize }
+
+    /// Checks whether a node may contain the edit.
+    ///
+    /// # Arguments
+    /// - `span`: The [`Span`] of the node, from before the edit.
+    ///
+    /// # Returns
+    /// True if the node touches the replaced range, or false if its children can be skipped.
+    #[inline]
+    pub fn overlaps(&self, span: &Span) -> bool { span.start <= self.range.end && self.range.start <= span.end }
+
+    /// Checks whether a node can absorb the edit.
+    ///
+    /// This is only the case if the edit falls strictly within it, i.e., the node's first and last
+    /// characters are unchanged. Otherwise, the edit may extend the node or merge it with its
+    /// neighbours.
+    ///
+    /// # Arguments
+    /// - `span`: The [`Span`] of the node, from before the edit.
+    ///
+    /// # Returns
+    /// True if the node contains the edit.
+    #[inline]
+    pub fn contains(&self, span: &Span) -> bool { span.start < self.range.start && self.range.end < span.end }
+
+    /// Moves all spans in a node that come after the edit.
+    ///
+    /// # Arguments
+    /// - `node`: The node to move, which is unaffected by the edit.
+    #[inline]
+    pub fn shift<T: Reparse<I>>(&self, node: &mut T) { node.shift(self.range.end, self.delta()) }
+}
+
+// Reparsing
+impl<'t, I: Input> Context<'t, I> {
+    /// Reparses a node from its text after the edit, replacing it if it absorbs it.
+    ///
+    /// # Arguments
+    /// - `node`: The node to reparse.
+    /// - `span`: The [`Span`] of the node, from before the edit.
+    ///
+    /// # Returns
+    /// The [`Span`] of the new node, or [`None`] if it doesn't [contain](Context::contains()) the
+    /// edit, if its new text isn't a valid `T` or if it's an operand that became an operator. Then,
+    /// the `node` is left untouched.
+    pub fn reparse<T: Parse<I> + Reparse<I>>(&self, node: &mut T, span: &Span) -> Option<Span> {
+        if !self.contains(span) {
+            return None;
+        }
+
+        // Parse only the node's new text
+        let end: usize = span.end.saturating_add_signed(self.delta());
+        let (stream, errors): (InputStream<I>, Vec<Error<I>>) = I::stream(self.source.clone(), &self.text[bytes(self.text, span.start..end)]);
+        if !errors.is_empty() {
+            return None;
+        }
+        let mut new: T = T::parser().then_ignore(chumsky::prelude::end()).parse(stream).ok()?;
+        if self.operand && new.is_operator() {
+            return None;
+        }
+
+        // Move it to where the node is in the source text
+        new.shift(0, span.start as isize);
+        *node = new;
+        Some(Span::new(self.source.clone(), span.start..end))
+    }
+}
+
+
+
+/// Marks an AST node as incrementally reparsable after edits to its source text.
+///
+/// Nodes reparse by first asking their children, such that the smallest node that contains the
+/// edit is reparsed. Nodes that can't absorb it leave that to their parent, up to the root. Only
+/// nodes with a [`Span`] of themselves can be reparsed, while any others are only searched for
+/// children.
+///
+/// Usually, you would [derive](derive@super::Reparse) this trait instead of implementing it
+/// manually.
+pub trait Reparse<I: Input = char> {
+    /// Reparses the smallest node containing an edit.
+    ///
+    /// If a node is reparsed, it's replaced by the new one, and all spans in the rest of the tree
+    /// are [`shift()`](Reparse::shift())ed to after the edit.
+    ///
+    /// # Arguments
+    /// - `ctx`: The [`Context`] describing the edit.
+    ///
+    /// # Returns
+    /// The [`Span`] of the node that was reparsed, or [`None`] if this node didn't absorb the
+    /// edit. Then, this node is left untouched.
+    fn reparse(&mut self, ctx: Context<'_, I>) -> Option<Span>;
+
+    /// Moves all spans in this node that come after a position.
+    ///
+    /// # Arguments
+    /// - `from`: The position from which on spans are moved.
+    /// - `delta`: The number of characters to move them by.
+    fn shift(&mut self, from: usize, delta: isize);
+
+    /// Returns whether this node is an operator, which must not replace an operand when reparsed
+    /// (see [`Context::with_operand()`]).
+    ///
+    /// # Returns
+    /// False by default.
+    #[inline]
+    fn is_operator(&self) -> bool { false }
+}
+
+// Std impls
+wrapper_impl!(Box<T>, Box::as_mut);
+wrapper_impl!(Rc<T>, Rc::make_mut, Clone);
+wrapper_impl!(Arc<T>, Arc::make_mut, Clone);
+impl<I: Input, T: Reparse<I>> Reparse<I> for Option<T> {
+    #[inline]
+    fn reparse(&mut self, ctx: Context<'_, I>) -> Option<Span> { self.as_mut().and_then(|node| node.reparse(ctx)) }
+
+    #[inline]
+    fn shift(&mut self, from: usize, delta: isize) {
+        if let Some(node) = self {
+            node.shift(from, delta);
+        }
+    }
+}
+impl<I: Input, T: Reparse<I>> Reparse<I> for Vec<T> {
+    /// Reparses the first element that absorbs the edit, and moves the ones after it.
+    #[inline]
+    fn reparse(&mut self, ctx: Context<'_, I>) -> Option<Span> {
+        let mut nodes = self.iter_mut();
+        while let Some(node) = nodes.next() {
+            if let Some(span) = node.reparse(ctx) {
+                for node in nodes {
+                    ctx.shift(node);
+                }
+                return Some(span);
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn shift(&mut self, from: usize, delta: isize) {
+        for node in self {
+            node.shift(from, delta);
+        }
+    }
+}
+impl<I: Input> Reparse<I> for Span {
+    #[inline]
+    fn reparse(&mut self, _ctx: Context<'_, I>) -> Option<Span> { None }
+
+    #[inline]
+    fn shift(&mut self, from: usize, delta: isize) {
+        self.start = shift(self.start, from, delta);
+        self.end = shift(self.end, from, delta);
+    }
+}
+leaf_impl!(String, char, bool, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+
+
+/// Keeps a parsed node up-to-date with its source text as it's edited.
+///
+/// Every [`Edit`] only reparses the smallest node containing it (see [`Reparse`]), reusing the
+/// rest of the tree. If no node can absorb it, the whole text is parsed again.
+///
+/// Nodes are reparsed in isolation, which gives the same result as parsing the whole text as long
+/// as where a node ends doesn't depend on the text after it. Operands of operators are only
+/// replaced by non-operators to keep precedence intact.
+///
+/// # Example
+/// ```
+/// # use ast_toolkit_2::parser::{Parse, Reparse, Span};
+/// # use ast_toolkit_2::parser::chumsky::text;
+/// use ast_toolkit_2::parser::reparse::{Edit, Incremental};
+///
+/// #[derive(Parse, Reparse)]
+/// #[parse(keyword = "let")]
+/// struct Stmt(#[parse(with = text::ident)] String, #[parse(punct = "=")] Span, Expr, #[parse(punct = ";")] Span, #[parse(span)] Span);
+///
+/// #[derive(Parse, Reparse)]
+/// enum Expr {
+///     #[parse(infix, punct = "+", prec = 1)]
+///     Add(Box<Expr>, Box<Expr>, #[parse(span)] Span),
+///     Call(#[parse(with = text::ident)] String, #[parse(delim("(", ")"), sep = ",")] Vec<Expr>, #[parse(span)] Span),
+///     Var(#[parse(with = text::ident)] String, #[parse(span)] Span),
+/// }
+///
+/// let mut doc = Incremental::<Vec<Stmt>>::new("main.lang", "let x = a + foo(b); let y = c;").unwrap();
+/// // Replace `b` with `b + c`, which only reparses the call
+/// let reparsed: Span = doc.edit(Edit::new(16..17, "b + c")).unwrap();
+/// assert_eq!(reparsed, Span::new("main.lang", 12..22));
+/// // And moves the next statement
+/// assert_eq!(doc.node()[1].4, Span::new("main.lang", 24..34));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Incremental<T, I = char> {
+    /// The name of the source.
+    source: Arc<str>,
+    /// The current source text.
+    text:   String,
+    /// The most recent node that parsed.
+    node:   T,
+    /// Whether the `node` is outdated, because the text didn't parse after some edit.
+    stale:  bool,
+    /// The input that the node is parsed from.
+    input:  PhantomData<fn() -> I>,
+}
+
+// Constructors
+impl<I: Input, T: Parse<I> + Reparse<I>> Incremental<T, I> {
+    /// Constructor for the Incremental that parses its initial text.
+    ///
+    /// # Arguments
+    /// - `source`: The name of the source (see [`Sources`](crate::span::Sources)).
+    /// - `text`: The initial source text.
+    ///
+    /// # Returns
+    /// A new Incremental with the parsed node.
+    ///
+    /// # Errors
+    /// This function errors with all [`Error`]s found if the `text` wasn't a valid node.
+    #[inline]
+    pub fn new(source: impl Into<Arc<str>>, text: impl Into<String>) -> Result<Self, Vec<Error<I>>> {
+        let source: Arc<str> = source.into();
+        let text: String = text.into();
+        let node: T = T::parse_str(source.clone(), &text)?;
+        Ok(Self { source, text, node, stale: false, input: PhantomData })
+    }
+}
+
+// Editing
+impl<I: Input, T: Parse<I> + Reparse<I>> Incremental<T, I> {
+    /// Applies an edit to the source text and updates the node accordingly.
+    ///
+    /// # Arguments
+    /// - `edit`: The [`Edit`] to apply.
+    ///
+    /// # Returns
+    /// The [`Span`] of the text that was reparsed, in the text after the edit.
+    ///
+    /// # Errors
+    /// This function errors with all [`Error`]s found if the text no longer parses. Then, the
+    /// [`node()`](Incremental::node()) is kept as it was, and the text is parsed entirely on every
+    /// edit until it parses again.
+    ///
+    /// # Panics
+    /// This function panics if the edit's range is out of bounds of the current text.
+    pub fn edit(&mut self, edit: Edit) -> Result<Span, Vec<Error<I>>> {
+        let range: Range<usize> = bytes(&self.text, edit.range.clone());
+        self.text.replace_range(range, &edit.text);
+
+        // Try to only reparse the affected node
+        if !self.stale
+            && let Some(span) = self.node.reparse(Context::new(&self.source, &self.text, &edit))
+        {
+            return Ok(span);
+        }
+
+        // Else, parse everything
+        self.node = T::parse_str(self.source.clone(), &self.text).inspect_err(|_| self.stale = true)?;
+        self.stale = false;
+        Ok(Span::new(self.source.clone(), 0..self.text.chars().count()))
+    }
+}
+
+// Accessors
+impl<T, I> Incremental<T, I> {
+    /// Returns the current source text.
+    ///
+    /// # Returns
+    /// The text with all edits applied.
+    #[inline]
+    pub fn text(&self) -> &str { &self.text }
+
+    /// Returns the most recent node that parsed.
+    ///
+    /// # Returns
+    /// The node, which may be outdated if the current text doesn't parse (see
+    /// [`is_stale()`](Incremental::is_stale())).
+    #[inline]
+    pub fn node(&self) -> &T { &self.node }
+
+    /// Returns whether the node is outdated, because the current text doesn't parse.
+    #[inline]
+    pub fn is_stale(&self) -> bool { self.stale }
+
+    /// Returns the most recent node that parsed.
+    ///
+    /// # Returns
+    /// The node, consuming the Incremental.
+    #[inline]
+    pub fn into_node(self) -> T { self.node }
+}
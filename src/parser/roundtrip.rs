@@ -0,0 +1,712 @@
+//  ROUNDTRIP.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:06:27
+//  Last edited:
+//    18 Oct 2026, 23:42:55
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines a [`Roundtrip`] tester that checks that the [`Parse`]- and
+//!   [`Unparse`]-implementations of a node agree with each other.
+//
+
+use std::fmt::{Debug, Display, Formatter, Result as FResult};
+use std::marker::PhantomData;
+use std::mem::discriminant;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::{error, fs, io};
+
+use super::generate::{Generate, Generator};
+use super::{Error, Input, Parse, Unparse};
+use crate::diff::DiffFormatter;
+
+
+/***** CONSTANTS *****/
+/// The name of the source that printed text is parsed from.
+pub const PRINTED: &str = "<printed>";
+
+
+
+
+
+/***** ERRORS *****/
+/// Defines the ways in which a node can fail to round-trip.
+#[derive(Debug)]
+pub enum Failure<I: Input = char> {
+    /// The input itself could not be parsed.
+    Unparsable { name: Arc<str>, input: String, errors: Vec<Error<I>> },
+    /// The printed node could not be parsed.
+    Reparse { name: Arc<str>, input: String, printed: String, errors: Vec<Error<I>> },
+    /// Printing the parsed printed node printed something else.
+    Unstable { name: Arc<str>, input: String, first: String, second: String },
+    /// Parsing the printed node produced a different node. If it has spans, the first value that
+    /// differs in their [`Debug`]-representations is pointed at in the printed text.
+    Mismatch { name: Arc<str>, printed: String, expected: String, actual: String, difference: Option<(Range<usize>, Arc<str>)> },
+}
+impl<I: Input> Display for Failure<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Unparsable { name, input, errors } => {
+                writeln!(f, "{name}: input does not parse")?;
+                write!(f, "{}", AnnotatedFormatter { text: input, annots: errors.iter().map(annotation).collect() })
+            },
+            Self::Reparse { name, input, printed, errors } => {
+                writeln!(f, "{name}: printed node does not parse")?;
+                writeln!(f, "input:")?;
+                write!(f, "{}", AnnotatedFormatter { text: input, annots: Vec::new() })?;
+                writeln!(f, "printed:")?;
+                write!(f, "{}", AnnotatedFormatter { text: printed, annots: errors.iter().map(annotation).collect() })
+            },
+            Self::Unstable { name, input, first, second } => {
+                writeln!(f, "{name}: printing is not idempotent")?;
+                writeln!(f, "input:")?;
+                write!(f, "{}", AnnotatedFormatter { text: input, annots: Vec::new() })?;
+                writeln!(f, "printed once (-) vs. twice (+):")?;
+                write!(f, "{}", DiffFormatter { expected: first, actual: second })?;
+                let (line, col): (usize, usize) = divergence(first, second);
+                writeln!(f, "(first difference at {line}:{col})")
+            },
+            Self::Mismatch { name, printed, expected, actual, difference } => {
+                writeln!(f, "{name}: parsing the printed node produced another node")?;
+                writeln!(f, "printed:")?;
+                write!(
+                    f,
+                    "{}",
+                    AnnotatedFormatter { text: printed, annots: difference.iter().map(|(range, message)| (range.clone(), message.to_string())).collect() }
+                )?;
+                writeln!(f, "original (-) vs. parsed (+):")?;
+                write!(f, "{}", DiffFormatter { expected, actual })?;
+                if expected == actual {
+                    return writeln!(f, "(no difference in their Debug-representations)");
+                }
+                let (line, col): (usize, usize) = divergence(expected, actual);
+                writeln!(f, "(first difference at {line}:{col})")
+            },
+        }
+    }
+}
+impl<I: Input> error::Error for Failure<I> {}
+
+
+
+
+
+/***** FORMATTERS *****/
+/// Writes a text with line numbers, and carets underneath annotated character ranges.
+struct AnnotatedFormatter<'t> {
+    /// The text to write.
+    text:   &'t str,
+    /// The character ranges to annotate, with a message.
+    annots: Vec<(Range<usize>, String)>,
+}
+impl<'t> Display for AnnotatedFormatter<'t> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        let lines: Vec<&str> = self.text.split('\n').collect();
+        let gutter: usize = lines.len().to_string().len();
+        let mut start: usize = 0;
+        for (i, line) in lines.iter().enumerate() {
+            let len: usize = line.chars().count();
+            writeln!(f, "{:>gutter$} | {line}", i + 1)?;
+
+            // Annotate the ranges starting on this line (or pointing at its end)
+            for (range, message) in &self.annots {
+                if range.start >= start && (range.start < start + len || (range.start == start + len && i == lines.len() - 1)) {
+                    let col: usize = range.start - start;
+                    let width: usize = range.end.min(start + len).saturating_sub(range.start).max(1);
+                    writeln!(f, "{:gutter$} | {}{} {message}", "", " ".repeat(col), "^".repeat(width))?;
+                }
+            }
+            start += len + 1;
+        }
+        Ok(())
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Turns a parse error into an annotation.
+///
+/// # Arguments
+/// - `err`: The [`Error`] to annotate.
+///
+/// # Returns
+/// The character range of the error, and its message.
+#[inline]
+fn annotation<I: Input>(err: &Error<I>) -> (Range<usize>, String) {
+    let expected: Vec<String> = err.expected().map(|tok| tok.as_ref().map(|c| format!("{c:?}")).unwrap_or_else(|| "end of input".into())).collect();
+    let found: String = err.found().map(|c| format!("{c:?}")).unwrap_or_else(|| "end of input".into());
+    let message: String = if expected.is_empty() { format!("unexpected {found}") } else { format!("expected {}, found {found}", expected.join(" or ")) };
+    (err.span().range(), message)
+}
+
+/// Finds the first character at which two texts differ.
+///
+/// # Arguments
+/// - `expected`: The text that was expected.
+/// - `actual`: The text that was produced.
+///
+/// # Returns
+/// The line and column of the first differing character in `expected`, both one-indexed.
+fn divergence(expected: &str, actual: &str) -> (usize, usize) {
+    let pos: usize = expected.chars().zip(actual.chars()).take_while(|(e, a)| e == a).count();
+    let before: String = expected.chars().take(pos).collect();
+    let col: usize = before.rsplit('\n').next().map(|line| line.chars().count()).unwrap_or(0);
+    (before.matches('\n').count() + 1, col + 1)
+}
+
+/// Removes as much of a text as possible while it keeps failing.
+///
+/// Chunks of characters are removed, halving their size until single characters are tried.
+///
+/// # Arguments
+/// - `text`: The text to minimize.
+/// - `fails`: Some closure that returns whether a candidate text still fails.
+///
+/// # Returns
+/// The smallest failing text found.
+fn minimize(text: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    let mut size: usize = chars.len().div_ceil(2);
+    while size > 0 {
+        let mut i: usize = 0;
+        let mut changed: bool = false;
+        while i < chars.len() {
+            let candidate: String = chars[..i].iter().chain(&chars[(i + size).min(chars.len())..]).collect();
+            if fails(&candidate) {
+                chars = candidate.chars().collect();
+                changed = true;
+            } else {
+                i += size;
+            }
+        }
+        if !changed || size > 1 {
+            size /= 2;
+        }
+    }
+    chars.into_iter().collect()
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// A value in the pretty-printed [`Debug`]-representation of a node (i.e., `{:#?}`).
+///
+/// This is used to compare nodes structurally without knowing their type.
+#[derive(Debug)]
+struct DebugTree<'t> {
+    /// The name of the field the value is in, if any.
+    field:    Option<&'t str>,
+    /// The first line of the value, without the field name and trailing comma (e.g., `Add(`).
+    head:     &'t str,
+    /// The values nested in this one.
+    children: Vec<DebugTree<'t>>,
+}
+
+// Constructors
+impl<'t> DebugTree<'t> {
+    /// Parses the pretty-printed [`Debug`]-representation of a value.
+    ///
+    /// # Arguments
+    /// - `text`: The representation to parse.
+    ///
+    /// # Returns
+    /// A new DebugTree, or [`None`] if the text is not laid out like derived representations.
+    fn parse(text: &'t str) -> Option<Self> {
+        let mut lines = text.lines().map(str::trim);
+        let tree: Self = Self::parse_value(lines.next()?, &mut lines)?;
+        if lines.next().is_some() {
+            return None;
+        }
+        Some(tree)
+    }
+
+    /// Parses a single value and the values nested in it.
+    ///
+    /// # Arguments
+    /// - `line`: The first line of the value.
+    /// - `lines`: The lines after it.
+    ///
+    /// # Returns
+    /// A new DebugTree, or [`None`] if the lines ended before the value did.
+    fn parse_value(line: &'t str, lines: &mut impl Iterator<Item = &'t str>) -> Option<Self> {
+        let (field, head): (Option<&str>, &str) = match line.split_once(": ") {
+            Some((field, head)) if !field.is_empty() && field.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '#') => (Some(field), head),
+            _ => (None, line),
+        };
+        let head: &str = head.strip_suffix(',').unwrap_or(head);
+        let mut children: Vec<Self> = Vec::new();
+        if head.ends_with(['(', '{', '[']) {
+            loop {
+                let line: &str = lines.next()?;
+                if line.starts_with([')', '}', ']']) {
+                    break;
+                }
+                children.push(Self::parse_value(line, lines)?);
+            }
+        }
+        Some(Self { field, head, children })
+    }
+}
+
+// Comparison
+impl<'t> DebugTree<'t> {
+    /// Returns the name of the value.
+    ///
+    /// # Returns
+    /// The name of its type or variant (e.g., `Add`), or its text if it has no nested values.
+    #[inline]
+    fn name(&self) -> &'t str { self.head.trim_end_matches(['(', '{', '[']).trim_end() }
+
+    /// Checks whether this value is a [`Span`](crate::span::Span).
+    ///
+    /// # Returns
+    /// True if it is, false otherwise.
+    #[inline]
+    fn is_span(&self) -> bool { self.head == "Span {" }
+
+    /// Returns the character range in the printed text that this value was parsed from.
+    ///
+    /// # Returns
+    /// The range covering all spans in this value that point into the [`PRINTED`] text, or
+    /// [`None`] if it has none.
+    fn range(&self) -> Option<Range<usize>> {
+        if self.is_span() {
+            let field = |name: &str| self.children.iter().find(|child| child.field == Some(name)).map(|child| child.head);
+            if field("source")? != format!("{PRINTED:?}") {
+                return None;
+            }
+            return Some(field("start")?.parse().ok()?..field("end")?.parse().ok()?);
+        }
+        self.children.iter().filter_map(Self::range).reduce(|lhs, rhs| lhs.start.min(rhs.start)..lhs.end.max(rhs.end))
+    }
+
+    /// Returns the single value this one wraps, not counting spans (e.g., the expression in a
+    /// parenthesized expression).
+    ///
+    /// # Returns
+    /// The wrapped DebugTree, or [`None`] if this one has no or multiple values.
+    fn wrapped(&self) -> Option<&Self> {
+        let mut values = self.children.iter().filter(|child| !child.is_span());
+        let value: &Self = values.next()?;
+        if values.next().is_some() {
+            return None;
+        }
+        Some(value)
+    }
+
+    /// Finds the first value that differs between this (expected) value and an actual one.
+    ///
+    /// Spans are not compared. Parenthesized variants in `actual` that wrap a value where this
+    /// one has it are seen through, as parsing wraps operands in them where printing added
+    /// parentheses.
+    ///
+    /// # Arguments
+    /// - `actual`: The DebugTree to compare with.
+    /// - `parens`: The names of the parenthesized variants (see [`Unparse::paren()`]).
+    ///
+    /// # Returns
+    /// The path of values in `actual` from itself to the first one that differs, and the value
+    /// that was expected there. [`None`] if they're the same.
+    fn diff<'s>(&'s self, actual: &'s Self, parens: &[&str]) -> Option<(Vec<&'s Self>, &'s Self)> {
+        if self.is_span() && actual.is_span() {
+            return None;
+        }
+        let (mut path, expected): (Vec<&'s Self>, &'s Self) = if self.head == actual.head && self.children.len() == actual.children.len() {
+            self.children.iter().zip(&actual.children).find_map(|(exp, act)| exp.diff(act, parens))?
+        } else {
+            match actual.wrapped() {
+                Some(inner) if inner.head == self.head && parens.contains(&actual.name()) => self.diff(inner, parens)?,
+                _ => (Vec::new(), self),
+            }
+        };
+        path.insert(0, actual);
+        Some((path, expected))
+    }
+}
+
+
+
+
+
+// Formatting
+impl<'t> DebugTree<'t> {
+    /// Writes the value like its [`Debug`]-representation, but with spans elided.
+    ///
+    /// # Arguments
+    /// - `f`: The [`Formatter`] to write to.
+    /// - `indent`: The number of spaces to indent the value with.
+    ///
+    /// # Errors
+    /// This function errors if we failed to write to `f`.
+    fn write(&self, f: &mut Formatter<'_>, indent: usize) -> FResult {
+        write!(f, "{:indent$}", "")?;
+        if let Some(field) = self.field {
+            write!(f, "{field}: ")?;
+        }
+        if self.is_span() {
+            return write!(f, "Span {{ .. }}");
+        }
+        write!(f, "{}", self.head)?;
+        let close: char = match self.head.chars().last() {
+            Some('(') => ')',
+            Some('{') => '}',
+            Some('[') => ']',
+            _ => return Ok(()),
+        };
+        writeln!(f)?;
+        for child in &self.children {
+            child.write(f, indent + 4)?;
+            writeln!(f, ",")?;
+        }
+        write!(f, "{:indent$}{close}", "")
+    }
+}
+impl<'t> Display for DebugTree<'t> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { self.write(f, 0) }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Checks that printing nodes with [`Unparse`] and parsing them with [`Parse`] round-trips.
+///
+/// Two properties are checked:
+/// - for nodes, that `parse(print(node)) == node` (see [`Roundtrip::check_node()`]); and
+/// - for source texts, that `print(parse(text))` parses and is printed the same again (see
+///   [`Roundtrip::check_source()`]).
+///
+/// Failing source texts and generated nodes are minimized to the smallest one that still fails
+/// in the same way.
+///
+/// Nodes are parsed from characters by default, or from another [`Input`] given as `I`.
+///
+/// In tests, [`Roundtrip::run_dir()`] checks a whole directory of source files at once.
+///
+/// # Example
+/// ```
+/// use ast_toolkit_2::parser::chumsky::text;
+/// use ast_toolkit_2::parser::roundtrip::Roundtrip;
+/// use ast_toolkit_2::parser::{Parse, Unparse};
+///
+/// #[derive(Debug, PartialEq, Parse, Unparse)]
+/// enum Expr {
+///     #[parse(infix, punct = "+", prec = 1)]
+///     Add(Box<Expr>, Box<Expr>),
+///     Paren(#[parse(delim("(", ")"))] Box<Expr>),
+///     Var(#[parse(with = text::ident)] String),
+/// }
+///
+/// let check = Roundtrip::new().with_width(20);
+/// check.check_source::<Expr>("<example>", "a+(b +c)").unwrap();
+///
+/// // Printed as `a + (b + c)`
+/// let var = |name: &str| Box::new(Expr::Var(name.into()));
+/// check.check_node("<example>", &Expr::Add(var("a"), Box::new(Expr::Add(var("b"), var("c"))))).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Roundtrip<I = char> {
+    /// The line width to print with.
+    width:    usize,
+    /// Whether to minimize failing source texts and generated nodes.
+    minimize: bool,
+    /// The parenthesized variants of nested nodes, besides that of the checked node itself.
+    parens:   Vec<&'static str>,
+    /// The input that nodes are parsed from.
+    input:    PhantomData<fn() -> I>,
+}
+
+// Constructors
+impl<I: Input> Default for Roundtrip<I> {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+impl<I: Input> Roundtrip<I> {
+    /// Constructor for the Roundtrip.
+    ///
+    /// # Returns
+    /// A new Roundtrip that prints 80 characters wide and minimizes failures.
+    #[inline]
+    pub const fn new() -> Self { Self { width: 80, minimize: true, parens: Vec::new(), input: PhantomData } }
+
+    /// Sets the line width to print with.
+    ///
+    /// # Arguments
+    /// - `width`: The maximum number of characters per line.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub const fn with_width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets whether to minimize failing source texts and generated nodes.
+    ///
+    /// # Arguments
+    /// - `minimize`: Whether to minimize them.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub const fn with_minimize(mut self, minimize: bool) -> Self {
+        self.minimize = minimize;
+        self
+    }
+
+    /// Also sees through the parenthesized variant of another node when comparing nodes.
+    ///
+    /// Only that of the checked node itself is seen through by default (see
+    /// [`Roundtrip::check_node_by()`]). Use this for expressions nested in it, like those in
+    /// statements.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_parens_of<T: Unparse>(mut self) -> Self {
+        self.parens.extend(T::paren());
+        self
+    }
+}
+
+// Checking
+impl<I: Input> Roundtrip<I> {
+    /// Checks that a node is parsed back from its printed form.
+    ///
+    /// See [`Roundtrip::check_node_by()`] for how the nodes are compared.
+    ///
+    /// # Arguments
+    /// - `name`: Some name for the node, for reporting.
+    /// - `node`: The node to check.
+    ///
+    /// # Errors
+    /// This function errors with a [`Failure`] if the printed node didn't parse or parsed to
+    /// another node.
+    #[inline]
+    pub fn check_node<T: Parse<I> + Unparse + Debug + PartialEq>(&self, name: impl Into<Arc<str>>, node: &T) -> Result<(), Failure<I>> {
+        self.check_node_by(name, node, T::eq)
+    }
+
+    /// Checks that a node is parsed back from its printed form, comparing them with a custom
+    /// function.
+    ///
+    /// If `eq` finds them different, they are compared again by their [`Debug`]-representations.
+    /// Then, [`Span`](crate::span::Span)s are not compared, as the parsed node points into the
+    /// printed text. Neither are the parenthesized variants that operands were parsed into where
+    /// printing added parentheses (see [`operand()`](super::unparse::operand())). For example,
+    /// `Add(a, Add(b, c))` is printed as `a + (b + c)`, which parses to
+    /// `Add(a, Paren(Add(b, c)))`. Only the variant given by [`Unparse::paren()`] of `T` is seen
+    /// through, and those added with [`Roundtrip::with_parens_of()`].
+    ///
+    /// If their representations only differ in spans and parenthesized variants, the nodes are
+    /// considered the same. If they don't differ at all, they aren't.
+    ///
+    /// # Arguments
+    /// - `name`: Some name for the node, for reporting.
+    /// - `node`: The node to check.
+    /// - `eq`: Some closure that decides if the original and the parsed node are the same.
+    ///
+    /// # Errors
+    /// This function errors with a [`Failure`] if the printed node didn't parse or parsed to
+    /// another node.
+    pub fn check_node_by<T: Parse<I> + Unparse + Debug>(&self, name: impl Into<Arc<str>>, node: &T, eq: impl Fn(&T, &T) -> bool) -> Result<(), Failure<I>> {
+        let name: Arc<str> = name.into();
+        let printed: String = node.unparse_to_string(self.width);
+        match T::parse_str(PRINTED, &printed) {
+            Ok(parsed) if eq(node, &parsed) => Ok(()),
+            Ok(parsed) => {
+                let (expected, actual): (String, String) = (format!("{node:#?}"), format!("{parsed:#?}"));
+                let (Some(exp), Some(act)) = (DebugTree::parse(&expected), DebugTree::parse(&actual)) else {
+                    return Err(Failure::Mismatch { name, printed, expected, actual, difference: None });
+                };
+                let parens: Vec<&str> = T::paren().into_iter().chain(self.parens.iter().copied()).collect();
+                let Some((path, first)) = exp.diff(&act, &parens) else {
+                    // Something that isn't printed made `eq` fail
+                    if expected == actual {
+                        return Err(Failure::Mismatch { name, printed, expected: exp.to_string(), actual: act.to_string(), difference: None });
+                    }
+                    return Ok(());
+                };
+
+                // Point at the smallest value around the difference that has a span
+                let message: Arc<str> = format!("expected `{}`, parsed `{}`", first.name(), path[path.len() - 1].name()).into();
+                let difference: Option<(Range<usize>, Arc<str>)> = path.iter().rev().find_map(|value| value.range()).map(|range| (range, message));
+                Err(Failure::Mismatch { name, printed, expected: exp.to_string(), actual: act.to_string(), difference })
+            },
+            Err(errors) => Err(Failure::Reparse { name, input: format!("{node:#?}"), printed, errors }),
+        }
+    }
+
+    /// Checks that randomly generated nodes are parsed back from their printed forms.
+    ///
+    /// A node is generated for every seed. If it fails, it is minimized by generating it again
+    /// from the same seed with a smaller budget, for the smallest budget that still fails in the
+    /// same way (unless disabled with [`Roundtrip::with_minimize()`]).
+    ///
+    /// # Arguments
+    /// - `generator`: The [`Generator`] to generate nodes with, which is reseeded for every seed.
+    /// - `seeds`: The seeds to generate nodes from.
+    ///
+    /// # Returns
+    /// A list of [`Failure`]s named after their seeds, which is empty if all nodes passed.
+    pub fn check_generated<T: Generate + Parse<I> + Unparse + Debug + PartialEq>(&self, generator: &Generator, seeds: Range<u64>) -> Vec<Failure<I>> {
+        let mut failures: Vec<Failure<I>> = Vec::new();
+        for seed in seeds {
+            let name: Arc<str> = format!("seed {seed}").into();
+            let check = |mut generator: Generator| self.check_node(name.clone(), &generator.generate::<T>());
+            let mut generator: Generator = generator.clone().with_seed(seed);
+            let Err(mut failure) = check(generator.clone()) else { continue };
+            if !self.minimize {
+                failures.push(failure);
+                continue;
+            }
+
+            // Lower the depth as far as possible first, then the number of nodes
+            let kind = discriminant(&failure);
+            let fails = |generator: Generator| check(generator).err().filter(|f| discriminant(f) == kind);
+            if let Some((depth, shrunk)) = (0..generator.max_depth()).find_map(|depth| fails(generator.clone().with_max_depth(depth)).map(|f| (depth, f))) {
+                generator = generator.with_max_depth(depth);
+                failure = shrunk;
+            }
+            let mut size: usize = generator.max_size();
+            while size > 0 {
+                size /= 2;
+                let Some(shrunk) = fails(generator.clone().with_max_size(size)) else { break };
+                generator = generator.with_max_size(size);
+                failure = shrunk;
+            }
+            failures.push(failure);
+        }
+        failures
+    }
+
+    /// Checks that a source text is printed such that it parses and prints the same again.
+    ///
+    /// If it fails, the reported input is minimized (unless disabled with
+    /// [`Roundtrip::with_minimize()`]).
+    ///
+    /// # Arguments
+    /// - `name`: The name of the source text.
+    /// - `text`: The source text to check.
+    ///
+    /// # Errors
+    /// This function errors with a [`Failure`] if the text didn't parse, its printed form didn't
+    /// parse or printing that printed something else.
+    pub fn check_source<T: Parse<I> + Unparse>(&self, name: impl Into<Arc<str>>, text: &str) -> Result<(), Failure<I>> {
+        let name: Arc<str> = name.into();
+        let Err(failure) = self.check_source_once::<T>(&name, text) else { return Ok(()) };
+        if !self.minimize || matches!(failure, Failure::Unparsable { .. }) {
+            return Err(failure);
+        }
+
+        // Find the smallest text that fails in the same way
+        let kind = discriminant(&failure);
+        let minimal: String = minimize(text, |candidate| self.check_source_once::<T>(&name, candidate).is_err_and(|f| discriminant(&f) == kind));
+        Err(self.check_source_once::<T>(&name, &minimal).err().unwrap_or(failure))
+    }
+
+    /// Checks a source text once, without minimizing.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the source text.
+    /// - `text`: The source text to check.
+    ///
+    /// # Errors
+    /// This function errors with a [`Failure`] if the text didn't round-trip.
+    fn check_source_once<T: Parse<I> + Unparse>(&self, name: &Arc<str>, text: &str) -> Result<(), Failure<I>> {
+        let node: T = T::parse_str(name.clone(), text).map_err(|errors| Failure::Unparsable { name: name.clone(), input: text.into(), errors })?;
+        let first: String = node.unparse_to_string(self.width);
+        let node: T =
+            T::parse_str(PRINTED, &first).map_err(|errors| Failure::Reparse { name: name.clone(), input: text.into(), printed: first.clone(), errors })?;
+        let second: String = node.unparse_to_string(self.width);
+        if first != second {
+            return Err(Failure::Unstable { name: name.clone(), input: text.into(), first, second });
+        }
+        Ok(())
+    }
+
+    /// Checks a corpus of source texts.
+    ///
+    /// # Arguments
+    /// - `corpus`: The names and texts of the sources to check.
+    ///
+    /// # Returns
+    /// A list of [`Failure`]s, which is empty if all texts passed.
+    pub fn check_corpus<T: Parse<I> + Unparse>(&self, corpus: impl IntoIterator<Item = (impl Into<Arc<str>>, impl AsRef<str>)>) -> Vec<Failure<I>> {
+        corpus.into_iter().filter_map(|(name, text)| self.check_source::<T>(name, text.as_ref()).err()).collect()
+    }
+
+    /// Checks all files with a particular extension in a directory, recursively.
+    ///
+    /// The files are named by their path relative to the directory.
+    ///
+    /// # Arguments
+    /// - `dir`: The directory with the source files.
+    /// - `ext`: The extension of source files (e.g., `lang`).
+    ///
+    /// # Returns
+    /// A list of [`Failure`]s, which is empty if all files passed.
+    ///
+    /// # Errors
+    /// This function errors if we failed to read the directory or any of the files.
+    pub fn check_dir<T: Parse<I> + Unparse>(&self, dir: impl AsRef<Path>, ext: &str) -> io::Result<Vec<Failure<I>>> {
+        fn find_files(dir: &Path, ext: &str, files: &mut Vec<PathBuf>) -> io::Result<()> {
+            for entry in fs::read_dir(dir)? {
+                let path: PathBuf = entry?.path();
+                if path.is_dir() {
+                    find_files(&path, ext, files)?;
+                } else if path.extension().is_some_and(|e| e == ext) {
+                    files.push(path);
+                }
+            }
+            Ok(())
+        }
+
+        let dir: &Path = dir.as_ref();
+        let mut files: Vec<PathBuf> = Vec::new();
+        find_files(dir, ext, &mut files)?;
+        files.sort();
+        let mut corpus: Vec<(String, String)> = Vec::with_capacity(files.len());
+        for path in files {
+            let rel: &Path = path.strip_prefix(dir).unwrap_or(&path);
+            corpus.push((rel.components().map(|comp| comp.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/"), fs::read_to_string(&path)?));
+        }
+        Ok(self.check_corpus::<T>(corpus))
+    }
+
+    /// Checks all files with a particular extension in a directory, and panics if any failed.
+    ///
+    /// This is meant to be called from a `#[test]`.
+    ///
+    /// # Arguments
+    /// - `dir`: The directory with the source files.
+    /// - `ext`: The extension of source files (e.g., `lang`).
+    ///
+    /// # Panics
+    /// This function panics if any file failed, listing all failures, or if we failed to read
+    /// files.
+    pub fn run_dir<T: Parse<I> + Unparse>(&self, dir: impl AsRef<Path>, ext: &str) {
+        let dir: &Path = dir.as_ref();
+        let failures: Vec<Failure<I>> = match self.check_dir::<T>(dir, ext) {
+            Ok(failures) => failures,
+            Err(err) => panic!("Failed to run round-trip tests in {:?}: {err}", dir.display()),
+        };
+        if !failures.is_empty() {
+            let list: Vec<String> = failures.iter().map(Failure::to_string).collect();
+            panic!("{} round-trip failure(s):\n\n{}", failures.len(), list.join("\n"));
+        }
+    }
+}
@@ -0,0 +1,376 @@
+//  TRACE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:32:42
+//  Last edited:
+//    18 Oct 2026, 23:27:53
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements an opt-in trace of which node parsers were attempted
+//!   where, and how they fared, to debug grammars with.
+//!
+//!   Derived parsers are only traced if the `trace`-feature is enabled,
+//!   and then only while [`record()`] runs.
+//
+
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter, Result as FResult};
+#[cfg(feature = "json")]
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use chumsky::error::SimpleReason;
+use chumsky::prelude::{Parser, empty};
+#[cfg(feature = "json")]
+use serde::Serialize;
+
+use super::{Error, Input};
+use crate::span::Span;
+
+
+/***** CONSTANTS *****/
+thread_local! {
+    /// The trace being recorded by [`record()`] on this thread, if any.
+    static RECORDING: RefCell<Option<Recording>> = const { RefCell::new(None) };
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Describes an [`Error`] in a single line.
+///
+/// # Arguments
+/// - `err`: The [`Error`] to describe.
+///
+/// # Returns
+/// A message like `expected 'a', found ';'`, using the [`Debug`]-representation of the input.
+fn describe<I: Input>(err: &Error<I>) -> String {
+    let show = |input: Option<&I>| -> String { input.map(|i| format!("{i:?}")).unwrap_or_else(|| "end of input".into()) };
+    match err.reason() {
+        SimpleReason::Custom(msg) => msg.clone(),
+        SimpleReason::Unclosed { delimiter, .. } => format!("unclosed {delimiter:?}"),
+        SimpleReason::Unexpected => {
+            let mut expected: Vec<String> = err.expected().map(|i| show(i.as_ref())).collect();
+            expected.sort();
+            if expected.is_empty() {
+                format!("unexpected {}", show(err.found()))
+            } else {
+                format!("expected {}, found {}", expected.join(" or "), show(err.found()))
+            }
+        },
+    }
+}
+
+/// Records that a node parser is attempted, if recording.
+///
+/// # Arguments
+/// - `name`: The name of the node.
+/// - `start`: The position at which it's attempted.
+fn enter(name: &'static str, start: usize) {
+    RECORDING.with_borrow_mut(|recording| {
+        if let Some(recording) = recording {
+            let parent: Option<usize> = recording.open.last().copied();
+            let elapsed: Duration = recording.started.elapsed();
+            recording.open.push(recording.events.len());
+            recording.events.push(Event {
+                name,
+                parent,
+                depth: recording.open.len() - 1,
+                start,
+                end: start,
+                outcome: Outcome::Matched,
+                error: None,
+                entered: elapsed,
+                exited: elapsed,
+            });
+        }
+    })
+}
+
+/// Records that the most recently attempted node parser finished, if recording.
+///
+/// # Arguments
+/// - `end`: The position at which it matched up to, or at which it failed.
+/// - `error`: A description of the error if it failed.
+fn exit(end: usize, error: Option<String>) {
+    RECORDING.with_borrow_mut(|recording| {
+        if let Some(recording) = recording
+            && let Some(i) = recording.open.pop()
+        {
+            let event: &mut Event = &mut recording.events[i];
+            event.end = end;
+            event.exited = recording.started.elapsed();
+            if error.is_some() {
+                event.outcome = Outcome::Failed;
+                event.error = error;
+            }
+        }
+    })
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// The state of a trace being recorded.
+struct Recording {
+    /// When recording started.
+    started: Instant,
+    /// The events recorded so far, in the order the parsers were attempted.
+    events:  Vec<Event>,
+    /// The indices of the events of the parsers that haven't finished yet.
+    open:    Vec<usize>,
+}
+
+
+
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Records a trace of all traced parsers that run on this thread while a closure runs.
+///
+/// # Arguments
+/// - `run`: The closure to run, typically parsing something.
+///
+/// # Returns
+/// A tuple of the closure's result and the recorded [`Trace`].
+///
+/// # Example
+/// ```
+/// use ast_toolkit_2::parser::chumsky::prelude::*;
+/// use ast_toolkit_2::parser::chumsky::text;
+/// use ast_toolkit_2::parser::trace;
+/// use ast_toolkit_2::parser::{Error, Parse, Span};
+///
+/// fn ident() -> impl Parser<char, String, Error = Error> + Clone { text::ident() }
+///
+/// #[derive(Debug, Parse)]
+/// enum Stmt {
+///     #[parse(keyword = "let")]
+///     Let(#[parse(with = ident)] String, #[parse(punct = ";")] Span),
+///     Expr(#[parse(with = ident)] String, #[parse(punct = ";")] Span),
+/// }
+///
+/// let (res, trace) = trace::record(|| Stmt::parse_str("main.lang", "x;"));
+/// assert!(res.is_ok());
+/// assert_eq!(trace.to_string(), "Stmt @ 0..2: matched\n  Stmt::Let @ 0..0: backtracked (unexpected end of input)\n  Stmt::Expr @ 0..2: matched\n");
+/// assert_eq!(trace.failures().count(), 0);
+/// ```
+pub fn record<R>(run: impl FnOnce() -> R) -> (R, Trace) {
+    /// Restores the recording that was active before when dropped, even when panicking.
+    struct Guard(Option<Recording>);
+    impl Drop for Guard {
+        #[inline]
+        fn drop(&mut self) { RECORDING.set(self.0.take()); }
+    }
+
+    let guard = Guard(RECORDING.replace(Some(Recording { started: Instant::now(), events: Vec::new(), open: Vec::new() })));
+    let res: R = run();
+    let recording: Recording = RECORDING.take().unwrap_or_else(|| panic!("Recording stopped while recording"));
+    drop(guard);
+
+    // Failures that an ancestor recovered from were backtracked. Parents come before their
+    // children, so this propagates down through failed parsers that were themselves backtracked.
+    let mut events: Vec<Event> = recording.events;
+    for i in 0..events.len() {
+        if events[i].outcome == Outcome::Failed && events[i].parent.is_some_and(|parent| events[parent].outcome != Outcome::Failed) {
+            events[i].outcome = Outcome::Backtracked;
+        }
+    }
+    (res, Trace { events })
+}
+
+/// Wraps a parser in a trace point.
+///
+/// This is used by the [derive macro](derive@super::Parse) (through [`traced()`](super::traced()))
+/// for every node and variant, but is also useful for manual impls.
+///
+/// # Arguments
+/// - `name`: The name of the node in the [`Trace`].
+/// - `parser`: The parser to trace.
+///
+/// # Returns
+/// A [`Parser`] that behaves the same, but records an [`Event`] whenever it runs while
+/// [`record()`]ing.
+pub fn point<I: Input, T, P: Parser<I, T, Error = Error<I>> + Clone>(name: &'static str, parser: P) -> impl Parser<I, T, Error = Error<I>> + Clone {
+    let pos = || empty().map_with_span(|(), span: Span| span.start);
+    pos()
+        .map(move |start| enter(name, start))
+        .ignore_then(parser.map_err(|err: Error<I>| {
+            exit(err.span().start, Some(describe(&err)));
+            err
+        }))
+        .then(pos())
+        .map(|(node, end)| {
+            exit(end, None);
+            node
+        })
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Describes how a traced parser fared.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize), serde(rename_all = "lowercase"))]
+pub enum Outcome {
+    /// It parsed its node.
+    Matched,
+    /// It failed, but a parser it ran in tried something else.
+    Backtracked,
+    /// It failed, and so did the parser it ran in.
+    Failed,
+}
+
+// Formatting
+impl Display for Outcome {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Matched => f.write_str("matched"),
+            Self::Backtracked => f.write_str("backtracked"),
+            Self::Failed => f.write_str("failed"),
+        }
+    }
+}
+
+
+
+/// Describes a single attempt of a traced parser.
+#[derive(Clone, Debug)]
+pub struct Event {
+    /// The name of the node (e.g., `Expr` or `Stmt::Let`).
+    pub name:    &'static str,
+    /// The index of the event of the parser it ran in, if any.
+    pub parent:  Option<usize>,
+    /// How many parsers it ran in.
+    pub depth:   usize,
+    /// The position at which it was attempted.
+    pub start:   usize,
+    /// The position it matched up to, or at which it failed.
+    pub end:     usize,
+    /// How it fared.
+    pub outcome: Outcome,
+    /// Why it failed, if it did.
+    pub error:   Option<String>,
+    /// When it was attempted, since recording started.
+    pub entered: Duration,
+    /// When it finished, since recording started.
+    pub exited:  Duration,
+}
+
+
+
+/// A trace of traced parsers, recorded by [`record()`].
+///
+/// Its [`Display`]-implementation writes it as an indented tree. With the `json`-feature, it can
+/// also be written as a timeline (see [`Trace::write_json()`]).
+#[derive(Clone, Debug, Default)]
+pub struct Trace {
+    /// The recorded events, in the order the parsers were attempted.
+    events: Vec<Event>,
+}
+
+// Accessors
+impl Trace {
+    /// Returns the recorded events.
+    ///
+    /// # Returns
+    /// A slice of [`Event`]s in the order the parsers were attempted, i.e., every event comes
+    /// after its parent.
+    #[inline]
+    pub fn events(&self) -> &[Event] { &self.events }
+
+    /// Returns the events that failed the parse.
+    ///
+    /// # Returns
+    /// An iterator over the [`Outcome::Failed`] [`Event`]s, typically the path to the error.
+    #[inline]
+    pub fn failures(&self) -> impl Iterator<Item = &Event> { self.events.iter().filter(|event| event.outcome == Outcome::Failed) }
+}
+
+// Serialization
+#[cfg(feature = "json")]
+impl Trace {
+    /// Writes the trace as a timeline in the [Trace Event Format], which can be opened in, e.g.,
+    /// Perfetto or `chrome://tracing`.
+    ///
+    /// Every event becomes a complete (`X`) event, with the positions, outcome and error as
+    /// arguments.
+    ///
+    /// [Trace Event Format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+    ///
+    /// # Arguments
+    /// - `writer`: The [`Write`]r to write to.
+    ///
+    /// # Errors
+    /// This function errors if we failed to write to the `writer`.
+    pub fn write_json(&self, writer: impl Write) -> io::Result<()> {
+        /// A single event in the Trace Event Format.
+        #[derive(Serialize)]
+        struct JsonEvent<'e> {
+            name: &'e str,
+            cat:  &'static str,
+            ph:   &'static str,
+            ts:   f64,
+            dur:  f64,
+            pid:  u32,
+            tid:  u32,
+            args: JsonArgs<'e>,
+        }
+        /// The arguments of an event.
+        #[derive(Serialize)]
+        struct JsonArgs<'e> {
+            start:   usize,
+            end:     usize,
+            outcome: Outcome,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            error:   Option<&'e str>,
+        }
+        /// The whole trace.
+        #[derive(Serialize)]
+        struct JsonTrace<'e> {
+            #[serde(rename = "traceEvents")]
+            trace_events: Vec<JsonEvent<'e>>,
+        }
+
+        let trace_events: Vec<JsonEvent> = self
+            .events
+            .iter()
+            .map(|event| JsonEvent {
+                name: event.name,
+                cat:  "parser",
+                ph:   "X",
+                ts:   event.entered.as_secs_f64() * 1e6,
+                dur:  event.exited.saturating_sub(event.entered).as_secs_f64() * 1e6,
+                pid:  1,
+                tid:  1,
+                args: JsonArgs { start: event.start, end: event.end, outcome: event.outcome, error: event.error.as_deref() },
+            })
+            .collect();
+        serde_json::to_writer(writer, &JsonTrace { trace_events })?;
+        Ok(())
+    }
+}
+
+// Formatting
+impl Display for Trace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        for event in &self.events {
+            write!(f, "{:indent$}{} @ {}..{}: {}", "", event.name, event.start, event.end, event.outcome, indent = 2 * event.depth)?;
+            if let Some(error) = &event.error {
+                write!(f, " ({error})")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
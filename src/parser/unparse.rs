@@ -0,0 +1,135 @@
+//  UNPARSE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:00:50
+//  Last edited:
+//    18 Oct 2026, 23:42:55
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines the [`Unparse`]-trait, which prints AST nodes back to source
+//!   text.
+//
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::doc::Doc;
+
+
+/***** HELPER MACROS *****/
+/// Implements [`Unparse`] for a pointer-like type that defers to the pointee.
+///
+/// # Syntax
+/// Call it with a type to implement it for (including the generic `T`).
+macro_rules! deref_impl {
+    ($type:ty) => {
+        impl<T: ?Sized + Unparse> Unparse for $type {
+            #[inline]
+            fn unparse(&self) -> Doc { <T as Unparse>::unparse(self) }
+        }
+    };
+}
+
+/// Implements [`Unparse`] for a type that is printed through its [`Display`](std::fmt::Display)
+/// implementation.
+///
+/// # Syntax
+/// Call it with a comma-separated list of types to implement it for.
+macro_rules! display_impl {
+    ($($type:ty),+) => {
+        $(
+            impl Unparse for $type {
+                #[inline]
+                fn unparse(&self) -> Doc { Doc::text(self.to_string()) }
+            }
+        )+
+    };
+}
+
+
+
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Prints the operand of an operator, wrapped in parentheses if it would otherwise bind
+/// differently.
+///
+/// This is used by the [derive macro](derive@super::Unparse) for expressions, which assumes that
+/// parenthesized expressions can be parsed. The parentheses are laid out like a
+/// [delimited](Doc::delimited()) field, such that the printed expression prints the same after
+/// parsing it again.
+///
+/// # Arguments
+/// - `doc`: The [`Doc`] of the operand.
+/// - `parens`: Whether to wrap it in parentheses.
+///
+/// # Returns
+/// A [`Doc`] printing the operand.
+#[inline]
+pub fn operand(doc: Doc, parens: bool) -> Doc { if parens { Doc::delimited("(", ")", doc) } else { doc } }
+
+
+
+
+
+/***** LIBRARY *****/
+/// Marks an AST node as printable back to source text.
+///
+/// Usually, you would [derive](derive@super::Unparse) this trait instead of implementing it
+/// manually. Then, it prints the same syntax as the derived [`Parse`](trait@super::Parse).
+pub trait Unparse {
+    /// Returns a [`Doc`] that prints this node.
+    ///
+    /// # Returns
+    /// A [`Doc`] that can be laid out within any line width.
+    fn unparse(&self) -> Doc;
+
+    /// Returns the variant of this node that an [`operand()`] is parsed into if it was printed
+    /// with parentheses.
+    ///
+    /// The [derive macro](derive@super::Unparse) returns the variant that only has a field
+    /// delimited by parentheses for expressions, and [`None`] for other nodes.
+    ///
+    /// # Returns
+    /// The name of the variant (e.g., `Paren`), or [`None`] if printing never adds parentheses.
+    #[inline]
+    fn paren() -> Option<&'static str>
+    where Self: Sized {
+        None
+    }
+
+    /// Convenience function for printing this node as source text.
+    ///
+    /// # Arguments
+    /// - `width`: The maximum number of characters per line.
+    ///
+    /// # Returns
+    /// The printed node.
+    #[inline]
+    fn unparse_to_string(&self, width: usize) -> String { self.unparse().pretty(width) }
+}
+
+// Std impls
+deref_impl!(&T);
+deref_impl!(&mut T);
+deref_impl!(Box<T>);
+deref_impl!(Rc<T>);
+deref_impl!(Arc<T>);
+impl<T: Unparse> Unparse for Option<T> {
+    /// Prints the node if it's there.
+    #[inline]
+    fn unparse(&self) -> Doc { self.as_ref().map(T::unparse).unwrap_or_default() }
+}
+impl<T: Unparse> Unparse for Vec<T> {
+    /// Prints the nodes separated by spaces, or one per line if they don't fit.
+    #[inline]
+    fn unparse(&self) -> Doc { Doc::join(self.iter().map(T::unparse), Doc::line()) }
+}
+impl Unparse for str {
+    #[inline]
+    fn unparse(&self) -> Doc { Doc::text(self.to_string()) }
+}
+display_impl!(String, char, bool, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
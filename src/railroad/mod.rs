@@ -4,7 +4,7 @@
 //  Created:
 //    05 Mar 2025, 17:31:22
 //  Last edited:
-//    06 Mar 2025, 13:03:05
+//    18 Oct 2026, 22:01:47
 //  Auto updated?
 //    Yes
 //
@@ -42,7 +42,6 @@ pub trait Railroad {
     ///
     /// # Returns
     /// A [`NonTerminal`](railroad::NonTerminal) that can be rendered as a railroad track.
-    #[inline]
     fn to_railroad_node() -> Self::Output;
 
     /// Serializes this node into a [`railroad`](railroad) track.
@@ -54,12 +53,13 @@ pub trait Railroad {
     /// and then later [adding it as a separate track](Diagram::add_nonterm()) in the final
     /// diagram.
     ///
-    /// By default, it simply refers to the [`Railroad::to_railroad_node()`]-implementation.
+    /// There is no default, as [`Railroad::InlineOutput`] may differ from [`Railroad::Output`].
+    /// Implementations that don't have a separate inline form can simply return
+    /// [`Railroad::to_railroad_node()`].
     ///
     /// # Returns
     /// A [`Node`](railroad::Node) that can be rendered within a railroad track.
-    #[inline]
-    fn to_railroad_node_inline() -> Self::InlineOutput { Self::to_railroad_node() }
+    fn to_railroad_node_inline() -> Self::InlineOutput;
 
     /// Convenience function for generation a diagram that only shows this node.
     ///
@@ -0,0 +1,498 @@
+//  DIAGNOSTIC.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 19:57:10
+//  Last edited:
+//    18 Oct 2026, 20:39:38
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines the [`Diagnostic`], an owned snapshot of everything a
+//!   [`Report`] says about an error.
+//
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::sync::Arc;
+
+use ariadne::{Color, ReportKind};
+
+use super::Report;
+use super::lint::Lint;
+use super::locale::Locales;
+use super::render::{AriadneRenderer, RenderConfig, Renderer};
+use super::suggestion::Suggestion;
+use crate::span::{Sources, Span};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Builds an [`ariadne::Cache`] from [`Sources`].
+///
+/// # Arguments
+/// - `sources`: The [`Sources`] to build the cache from.
+///
+/// # Returns
+/// Something implementing [`ariadne::Cache`] that can be used to render reports.
+#[inline]
+pub(crate) fn ariadne_cache(sources: &Sources) -> impl ariadne::Cache<Arc<str>> {
+    ariadne::sources(sources.iter().map(|(name, text)| (name.clone(), text.clone())))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines how bad a [`Diagnostic`] is.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// Something that the user might like to know, but which isn't a problem.
+    Note,
+    /// Something that is likely a problem, but which doesn't prevent compilation.
+    Warning,
+    /// Something that prevents compilation from succeeding.
+    Error,
+}
+impl Severity {
+    /// Returns the [`Color`] with which this severity is typically rendered.
+    ///
+    /// # Returns
+    /// A [`Color`] that can be given to [ariadne](::ariadne).
+    #[inline]
+    pub const fn color(&self) -> Color {
+        match self {
+            Self::Note => Color::Fixed(147),
+            Self::Warning => Color::Yellow,
+            Self::Error => Color::Red,
+        }
+    }
+}
+impl Display for Severity {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Note => write!(f, "note"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+impl From<Severity> for ReportKind<'static> {
+    #[inline]
+    fn from(value: Severity) -> Self {
+        match value {
+            Severity::Note => Self::Advice,
+            Severity::Warning => Self::Warning,
+            Severity::Error => Self::Error,
+        }
+    }
+}
+
+
+
+/// Defines a single annotated [`Span`] in a [`Diagnostic`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Label {
+    /// The span that is annotated.
+    pub span:    Span,
+    /// Some message to show at the annotated span, if any.
+    pub message: Option<String>,
+}
+impl Label {
+    /// Constructor for a Label that does not have any message.
+    ///
+    /// # Arguments
+    /// - `span`: The [`Span`] to annotate.
+    ///
+    /// # Returns
+    /// A new Label that only highlights the given `span`.
+    #[inline]
+    pub fn new(span: Span) -> Self { Self { span, message: None } }
+
+    /// Constructor for a Label that annotates a span with a message.
+    ///
+    /// # Arguments
+    /// - `span`: The [`Span`] to annotate.
+    /// - `message`: Some message to show at the span.
+    ///
+    /// # Returns
+    /// A new Label that highlights the given `span` with the given `message`.
+    #[inline]
+    pub fn with_message(span: Span, message: impl Display) -> Self { Self { span, message: Some(message.to_string()) } }
+}
+
+
+
+/// Defines an owned snapshot of all the information a [`Report`] provides.
+///
+/// This is useful to collect reports of different types together, or to build simple reports
+/// without defining a new type for them. It also implements [`Report`] itself.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Diagnostic {
+    /// How bad the diagnostic is.
+    pub severity:    Severity,
+    /// Some code that identifies the kind of diagnostic, if any.
+    pub code:        Option<String>,
+    /// The lint that decides the severity of the diagnostic, if any.
+    pub lint:        Option<&'static Lint>,
+    /// The main message of the diagnostic.
+    pub message:     String,
+    /// The primary location where the diagnostic occurred.
+    pub primary:     Label,
+    /// Any additional locations that are relevant for the diagnostic.
+    pub labels:      Vec<Label>,
+    /// Any notes that give additional context.
+    pub notes:       Vec<String>,
+    /// Any help messages that tell the user how to fix it.
+    pub helps:       Vec<String>,
+    /// Any suggested edits that fix it.
+    pub suggestions: Vec<Suggestion>,
+    /// The arguments to the messages if they are message IDs, or [`None`] if they are text.
+    ///
+    /// See [`Diagnostic::localize()`] for how the messages are resolved.
+    pub args:        Option<BTreeMap<String, String>>,
+}
+
+// Constructors
+impl Diagnostic {
+    /// Constructor for a Diagnostic without any labels, notes or help.
+    ///
+    /// # Arguments
+    /// - `severity`: The [`Severity`] of the diagnostic.
+    /// - `message`: The main message of the diagnostic.
+    /// - `span`: The primary location where the diagnostic occurred.
+    ///
+    /// # Returns
+    /// A new Diagnostic that only consists of the given `message` at the given `span`.
+    #[inline]
+    pub fn new(severity: Severity, message: impl Display, span: Span) -> Self {
+        Self {
+            severity,
+            code: None,
+            lint: None,
+            message: message.to_string(),
+            primary: Label::new(span),
+            labels: Vec::new(),
+            notes: Vec::new(),
+            helps: Vec::new(),
+            suggestions: Vec::new(),
+            args: None,
+        }
+    }
+
+    /// Convenience constructor for a Diagnostic of [`Severity::Error`].
+    ///
+    /// # Arguments
+    /// - `message`: The main message of the diagnostic.
+    /// - `span`: The primary location where the diagnostic occurred.
+    ///
+    /// # Returns
+    /// A new error Diagnostic.
+    #[inline]
+    pub fn error(message: impl Display, span: Span) -> Self { Self::new(Severity::Error, message, span) }
+
+    /// Convenience constructor for a Diagnostic of [`Severity::Warning`].
+    ///
+    /// # Arguments
+    /// - `message`: The main message of the diagnostic.
+    /// - `span`: The primary location where the diagnostic occurred.
+    ///
+    /// # Returns
+    /// A new warning Diagnostic.
+    #[inline]
+    pub fn warning(message: impl Display, span: Span) -> Self { Self::new(Severity::Warning, message, span) }
+
+    /// Convenience constructor for a Diagnostic of [`Severity::Note`].
+    ///
+    /// # Arguments
+    /// - `message`: The main message of the diagnostic.
+    /// - `span`: The primary location where the diagnostic occurred.
+    ///
+    /// # Returns
+    /// A new note Diagnostic.
+    #[inline]
+    pub fn note(message: impl Display, span: Span) -> Self { Self::new(Severity::Note, message, span) }
+}
+
+// Builder-like
+impl Diagnostic {
+    /// Sets the code of this Diagnostic.
+    ///
+    /// # Arguments
+    /// - `code`: Some code that identifies the kind of diagnostic.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_code(mut self, code: impl Display) -> Self {
+        self.code = Some(code.to_string());
+        self
+    }
+
+    /// Sets the lint of this Diagnostic.
+    ///
+    /// # Arguments
+    /// - `lint`: The [`Lint`] that decides the severity of the diagnostic.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_lint(mut self, lint: &'static Lint) -> Self {
+        self.lint = Some(lint);
+        self
+    }
+
+    /// Sets the message of the primary label of this Diagnostic.
+    ///
+    /// # Arguments
+    /// - `message`: Some message to show at the primary location.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_primary_message(mut self, message: impl Display) -> Self {
+        self.primary.message = Some(message.to_string());
+        self
+    }
+
+    /// Adds an additional label to this Diagnostic.
+    ///
+    /// # Arguments
+    /// - `label`: The [`Label`] to add.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    /// Adds a note to this Diagnostic.
+    ///
+    /// # Arguments
+    /// - `note`: The note to add.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_note(mut self, note: impl Display) -> Self {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    /// Adds a help message to this Diagnostic.
+    ///
+    /// # Arguments
+    /// - `help`: The help message to add.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_help(mut self, help: impl Display) -> Self {
+        self.helps.push(help.to_string());
+        self
+    }
+
+    /// Adds a suggested edit to this Diagnostic.
+    ///
+    /// # Arguments
+    /// - `suggestion`: The [`Suggestion`] to add.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+
+    /// Adds an argument to the messages of this Diagnostic.
+    ///
+    /// This marks the messages as message IDs, to be resolved by [`Diagnostic::localize()`].
+    ///
+    /// # Arguments
+    /// - `name`: The name of the argument (e.g., `name` for `{ $name }`).
+    /// - `value`: The value of the argument.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl Display) -> Self {
+        self.args.get_or_insert_with(BTreeMap::new).insert(name.into(), value.to_string());
+        self
+    }
+}
+
+// Layout
+impl Diagnostic {
+    /// Orders the secondary labels of this Diagnostic per file and position.
+    ///
+    /// Labels in the file of the primary label come first, followed by those in other files in
+    /// order of first appearance. Within a file, labels are ordered by position, keeping labels at
+    /// the same position in their original order.
+    pub fn sort_labels(&mut self) {
+        let mut files: Vec<Arc<str>> = vec![self.primary.span.source.clone()];
+        for label in &self.labels {
+            if !files.contains(&label.span.source) {
+                files.push(label.span.source.clone());
+            }
+        }
+        self.labels.sort_by_key(|label| (files.iter().position(|file| *file == label.span.source), label.span.start, label.span.end));
+    }
+
+    /// Moves secondary labels in files unknown to the given [`Sources`] into notes.
+    ///
+    /// Such labels can't be shown as a snippet, so instead they become a note with their message
+    /// and location (e.g., `first defined here (std.lib:12..16)`).
+    ///
+    /// # Arguments
+    /// - `sources`: The [`Sources`] in which to find the source texts of the spans.
+    pub fn detach_unknown_labels(&mut self, sources: &Sources) {
+        let mut notes: Vec<String> = Vec::new();
+        self.labels.retain(|label| {
+            if sources.get(&label.span.source).is_some() {
+                return true;
+            }
+            notes.push(match &label.message {
+                Some(message) => format!("{message} ({})", label.span),
+                None => format!("related location at {}", label.span),
+            });
+            false
+        });
+        self.notes.splice(0..0, notes);
+    }
+}
+
+// Report
+impl Report for Diagnostic {
+    #[inline]
+    fn severity(&self) -> Severity { self.severity }
+
+    #[inline]
+    fn code(&self) -> Option<String> { self.code.clone() }
+
+    #[inline]
+    fn lint(&self) -> Option<&'static Lint> { self.lint }
+
+    #[inline]
+    fn message(&self) -> String { self.message.clone() }
+
+    #[inline]
+    fn primary(&self) -> Label { self.primary.clone() }
+
+    #[inline]
+    fn labels(&self) -> Vec<Label> { self.labels.clone() }
+
+    #[inline]
+    fn notes(&self) -> Vec<String> { self.notes.clone() }
+
+    #[inline]
+    fn helps(&self) -> Vec<String> { self.helps.clone() }
+
+    #[inline]
+    fn suggestions(&self) -> Vec<Suggestion> { self.suggestions.clone() }
+
+    #[inline]
+    fn args(&self) -> Option<BTreeMap<String, String>> { self.args.clone() }
+
+    #[inline]
+    fn to_diagnostic(&self) -> Diagnostic { self.clone() }
+}
+
+// Localization
+impl Diagnostic {
+    /// Resolves the messages of this Diagnostic if they are message IDs.
+    ///
+    /// If [`Diagnostic::args`] is [`Some`], every message, label, note, help and suggestion
+    /// message is looked up in the `locales` and formatted with the arguments. Texts that aren't
+    /// defined in any locale (e.g., notes added by the lint configuration) are kept as-is.
+    ///
+    /// # Arguments
+    /// - `locales`: The [`Locales`] to resolve the message IDs in.
+    ///
+    /// # Returns
+    /// A new Diagnostic with all messages as text.
+    pub fn localize(&self, locales: &Locales) -> Self {
+        let mut res: Self = self.clone();
+        let Some(args) = res.args.take() else { return res };
+        let resolve = |text: &mut String| {
+            if let Some(resolved) = locales.format(text, &args) {
+                *text = resolved;
+            }
+        };
+        resolve(&mut res.message);
+        for label in std::iter::once(&mut res.primary).chain(res.labels.iter_mut()) {
+            if let Some(message) = &mut label.message {
+                resolve(message);
+            }
+        }
+        res.notes.iter_mut().chain(res.helps.iter_mut()).chain(res.suggestions.iter_mut().map(|sugg| &mut sugg.message)).for_each(resolve);
+        res
+    }
+}
+
+// ariadne
+impl Diagnostic {
+    /// Turns this Diagnostic into an [`ariadne::Report`] with a particular configuration.
+    ///
+    /// # Arguments
+    /// - `config`: The [`ariadne::Config`] that determines how the report is rendered.
+    ///
+    /// # Returns
+    /// An [`ariadne::Report`] that can be written to the terminal.
+    pub fn into_ariadne_report(self, config: ariadne::Config) -> ariadne::Report<'static, Span> {
+        let Diagnostic { severity, code, lint: _, message, primary, labels, notes, helps, suggestions, args: _ } = self;
+
+        // Build the primary label first, then add the secondary ones
+        let mut builder = ariadne::Report::build(severity.into(), primary.span.clone()).with_message(message).with_config(config);
+        if let Some(code) = code {
+            builder = builder.with_code(code);
+        }
+        let mut label = ariadne::Label::new(primary.span).with_color(severity.color()).with_order(-1);
+        if let Some(message) = primary.message {
+            label = label.with_message(message);
+        }
+        builder.add_label(label);
+        for Label { span, message } in labels {
+            let mut label = ariadne::Label::new(span).with_color(Color::Blue);
+            if let Some(message) = message {
+                label = label.with_message(message);
+            }
+            builder.add_label(label);
+        }
+        // Suggestions are shown inline, like `rustc` does for short ones
+        for Suggestion { message, span, replacement, .. } in suggestions {
+            builder.add_label(ariadne::Label::new(span).with_color(Color::Green).with_message(format!("help: {message}: `{replacement}`")));
+        }
+
+        // Add the notes & helps
+        for note in notes {
+            builder.add_note(note);
+        }
+        for help in helps {
+            builder.add_help(help);
+        }
+        builder.finish()
+    }
+}
+// Rendering
+impl Diagnostic {
+    /// Renders this Diagnostic with [ariadne](::ariadne) to a string.
+    ///
+    /// # Arguments
+    /// - `sources`: The [`Sources`] in which to find the source texts of the spans.
+    /// - `color`: Whether to render with colours.
+    ///
+    /// # Returns
+    /// A [`String`] with the rendered diagnostic.
+    pub fn render_to_string(&self, sources: &Sources, color: bool) -> String {
+        AriadneRenderer.render_to_string(self, sources, &RenderConfig { color, width: None })
+    }
+}
+impl From<Diagnostic> for ariadne::Report<'static, Span> {
+    #[inline]
+    fn from(value: Diagnostic) -> Self { value.into_ariadne_report(ariadne::Config::default()) }
+}
@@ -0,0 +1,256 @@
+//  JSON.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 19:59:59
+//  Last edited:
+//    18 Oct 2026, 20:39:38
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines a machine-readable JSON representation of [`Report`]s,
+//!   modelled after the output of `rustc --error-format=json`.
+//
+
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Diagnostic, Diagnostics, Label, Report, Suggestion};
+use crate::span::{Sources, Span};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Resolves a [`Label`] to a [`JsonSpan`].
+///
+/// # Arguments
+/// - `label`: The [`Label`] to resolve.
+/// - `is_primary`: Whether this is the primary label.
+/// - `sources`: The [`Sources`] in which to find the source text of the span.
+///
+/// # Returns
+/// A [`JsonSpan`] with byte offsets, lines and columns. If the source text of the span is unknown,
+/// character offsets are used as byte offsets, lines are `0` and columns are the 1-indexed
+/// character offsets.
+fn resolve_label(label: &Label, is_primary: bool, sources: &Sources) -> JsonSpan {
+    let Span { source, start, end } = &label.span;
+    let (line_start, column_start): (usize, usize) = sources.line_col(source, *start).unwrap_or((0, *start + 1));
+    let (line_end, column_end): (usize, usize) = sources.line_col(source, *end).unwrap_or((0, *end + 1));
+
+    // Collect the text of the lines covered
+    let mut text: Vec<JsonSpanLine> = Vec::with_capacity(1 + line_end.saturating_sub(line_start));
+    for line in line_start..=line_end {
+        let Some(contents) = sources.line(source, line) else { continue };
+        let highlight_start: usize = if line == line_start { column_start } else { 1 };
+        let highlight_end: usize = if line == line_end { column_end } else { contents.chars().count() + 1 };
+        text.push(JsonSpanLine { text: contents.into(), highlight_start, highlight_end });
+    }
+
+    JsonSpan {
+        file_name: source.to_string(),
+        byte_start: sources.byte_offset(source, *start).unwrap_or(*start),
+        byte_end: sources.byte_offset(source, *end).unwrap_or(*end),
+        line_start,
+        line_end,
+        column_start,
+        column_end,
+        is_primary,
+        text,
+        label: label.message.clone(),
+        suggested_replacement: None,
+        suggestion_applicability: None,
+        expansion: None,
+    }
+}
+
+/// Creates a child [`JsonDiagnostic`] for a [`Suggestion`].
+///
+/// # Arguments
+/// - `sugg`: The [`Suggestion`] to represent.
+/// - `sources`: The [`Sources`] in which to find the source text of the span.
+///
+/// # Returns
+/// A new `help`-level [`JsonDiagnostic`] with a single span that carries the replacement.
+fn suggestion_child(sugg: &Suggestion, sources: &Sources) -> JsonDiagnostic {
+    let mut span: JsonSpan = resolve_label(&Label::new(sugg.span.clone()), true, sources);
+    span.suggested_replacement = Some(sugg.replacement.clone());
+    span.suggestion_applicability = Some(sugg.applicability.to_string());
+    let mut res: JsonDiagnostic = child("help", &sugg.message);
+    res.spans.push(span);
+    res
+}
+
+/// Creates a child [`JsonDiagnostic`] for a note or help message.
+///
+/// # Arguments
+/// - `level`: The level of the child (i.e., `note` or `help`).
+/// - `message`: The message of the child.
+///
+/// # Returns
+/// A new [`JsonDiagnostic`] without any spans, children or rendered text.
+#[inline]
+fn child(level: &str, message: &str) -> JsonDiagnostic {
+    JsonDiagnostic {
+        message_type: None,
+        message:      message.into(),
+        code:         None,
+        level:        level.into(),
+        spans:        Vec::new(),
+        children:     Vec::new(),
+        rendered:     None,
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the JSON representation of a single diagnostic.
+///
+/// This mirrors the format emitted by `rustc --error-format=json`, such that tooling written for
+/// that can consume it.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JsonDiagnostic {
+    /// The type of message. Always `diagnostic` for toplevel diagnostics, omitted for children.
+    #[serde(rename = "$message_type", default, skip_serializing_if = "Option::is_none")]
+    pub message_type: Option<String>,
+    /// The main message of the diagnostic.
+    pub message:      String,
+    /// The code of the diagnostic, if any.
+    pub code:         Option<JsonCode>,
+    /// The severity of the diagnostic (e.g., `error`, `warning`, `note` or `help`).
+    pub level:        String,
+    /// The locations the diagnostic annotates.
+    pub spans:        Vec<JsonSpan>,
+    /// Any notes, help messages or suggestions attached to the diagnostic.
+    pub children:     Vec<JsonDiagnostic>,
+    /// The diagnostic as it would be rendered to a terminal. Omitted (`null`) for children.
+    pub rendered:     Option<String>,
+}
+impl JsonDiagnostic {
+    /// Constructor for the JsonDiagnostic that creates it from a [`Report`].
+    ///
+    /// # Arguments
+    /// - `report`: The [`Report`] to convert.
+    /// - `sources`: The [`Sources`] in which to find the source texts of the spans.
+    /// - `color`: Whether the rendered text should contain ANSI colour codes.
+    ///
+    /// # Returns
+    /// A new JsonDiagnostic representing the report.
+    #[inline]
+    pub fn new(report: impl Report, sources: &Sources, color: bool) -> Self { Self::from_diagnostic(&report.to_diagnostic(), sources, color) }
+
+    /// Constructor for the JsonDiagnostic that creates it from a [`Diagnostic`].
+    ///
+    /// # Arguments
+    /// - `diag`: The [`Diagnostic`] to convert.
+    /// - `sources`: The [`Sources`] in which to find the source texts of the spans.
+    /// - `color`: Whether the rendered text should contain ANSI colour codes.
+    ///
+    /// # Returns
+    /// A new JsonDiagnostic representing the diagnostic.
+    pub fn from_diagnostic(diag: &Diagnostic, sources: &Sources, color: bool) -> Self {
+        let mut spans: Vec<JsonSpan> = Vec::with_capacity(1 + diag.labels.len());
+        spans.push(resolve_label(&diag.primary, true, sources));
+        spans.extend(diag.labels.iter().map(|label| resolve_label(label, false, sources)));
+
+        let mut children: Vec<JsonDiagnostic> = Vec::with_capacity(diag.notes.len() + diag.helps.len() + diag.suggestions.len());
+        children.extend(diag.notes.iter().map(|note| child("note", note)));
+        children.extend(diag.helps.iter().map(|help| child("help", help)));
+        children.extend(diag.suggestions.iter().map(|sugg| suggestion_child(sugg, sources)));
+
+        Self {
+            message_type: Some("diagnostic".into()),
+            message: diag.message.clone(),
+            code: diag.code.as_ref().map(|code| JsonCode { code: code.clone(), explanation: None }),
+            level: diag.severity.to_string(),
+            spans,
+            children,
+            rendered: Some(diag.render_to_string(sources, color)),
+        }
+    }
+}
+
+/// Defines the JSON representation of a diagnostic's code.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JsonCode {
+    /// The code itself (e.g., `E0042`).
+    pub code:        String,
+    /// A long-form explanation of the code, if any.
+    pub explanation: Option<String>,
+}
+
+/// Defines the JSON representation of an annotated location.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JsonSpan {
+    /// The name of the source the span points into.
+    pub file_name:                String,
+    /// The start of the span (inclusive) as a byte offset.
+    pub byte_start:               usize,
+    /// The end of the span (exclusive) as a byte offset.
+    pub byte_end:                 usize,
+    /// The 1-indexed line the span starts on.
+    pub line_start:               usize,
+    /// The 1-indexed line the span ends on.
+    pub line_end:                 usize,
+    /// The 1-indexed column (in characters) the span starts at.
+    pub column_start:             usize,
+    /// The 1-indexed column (in characters) the span ends at (exclusive).
+    pub column_end:               usize,
+    /// Whether this is the primary location of the diagnostic.
+    pub is_primary:               bool,
+    /// The source lines covered by the span.
+    pub text:                     Vec<JsonSpanLine>,
+    /// The message shown at the span, if any.
+    pub label:                    Option<String>,
+    /// Any text that is suggested to replace the span with.
+    pub suggested_replacement:    Option<String>,
+    /// How confident we are that the suggested replacement is correct (e.g., `MachineApplicable`).
+    pub suggestion_applicability: Option<String>,
+    /// Macro expansion information. Always `null`, but present for compatibility with `rustc`.
+    pub expansion:                Option<serde_json::Value>,
+}
+
+/// Defines the JSON representation of a source line covered by a span.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JsonSpanLine {
+    /// The text of the line, without newline.
+    pub text:            String,
+    /// The 1-indexed column (in characters) where the highlight starts.
+    pub highlight_start: usize,
+    /// The 1-indexed column (in characters) where the highlight ends (exclusive).
+    pub highlight_end:   usize,
+}
+
+
+
+// Rendering
+impl Diagnostics {
+    /// Renders all collected diagnostics as JSON to the given writer.
+    ///
+    /// Like `rustc`, every diagnostic is written as a single JSON object on its own line. The
+    /// diagnostics are written in the same order as [`Diagnostics::render()`] would, but no
+    /// summary is written. If a [`Registry`](super::Registry) is set, the explanations of codes
+    /// are included.
+    ///
+    /// # Arguments
+    /// - `sources`: The [`Sources`] in which to find the source texts of the spans.
+    /// - `writer`: The [`Write`]r to render to.
+    ///
+    /// # Errors
+    /// This function errors if we failed to write to the given `writer`.
+    pub fn render_json(&self, sources: &Sources, mut writer: impl Write) -> io::Result<()> {
+        for diag in self.localized() {
+            let mut json: JsonDiagnostic = JsonDiagnostic::from_diagnostic(&diag, sources, self.config.color);
+            json.rendered = Some(self.renderer.render_to_string(&diag, sources, &self.config));
+            if let (Some(registry), Some(code)) = (self.registry, &mut json.code) {
+                code.explanation = registry.get(&code.code).map(|code| code.explain().to_string());
+            }
+            serde_json::to_writer(&mut writer, &json)?;
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
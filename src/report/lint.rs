@@ -0,0 +1,330 @@
+//  LINT.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:08:09
+//  Last edited:
+//    18 Oct 2026, 21:57:04
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines [`Lint`]s, diagnostics that users can silence, warn about or
+//!   deny, and a [`LintConfig`] that decides which of those they do.
+//
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::str::FromStr;
+
+
+/***** CONSTANTS *****/
+/// The name of the special lint group that matches all warnings.
+pub const WARNINGS: &str = "warnings";
+
+
+
+
+
+/***** ERRORS *****/
+/// Defines errors that occur when parsing a [`LintConfig`].
+#[derive(Debug)]
+pub enum Error {
+    /// A flag was given without the name of a lint.
+    MissingLint { flag: String },
+    /// A flag was not a level flag.
+    UnknownFlag { flag: String },
+    /// A level was not a known level.
+    UnknownLevel { raw: String },
+    /// A line in a configuration file was malformed.
+    IllegalLine { line: usize, raw: String },
+}
+impl Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::MissingLint { flag } => write!(f, "Missing lint name after {flag:?}"),
+            Self::UnknownFlag { flag } => write!(f, "Unknown lint flag {flag:?} (expected '-A', '-W', '-D' or their long forms)"),
+            Self::UnknownLevel { raw } => write!(f, "Unknown lint level {raw:?} (expected 'allow', 'warn' or 'deny')"),
+            Self::IllegalLine { line, raw } => write!(f, "Illegal line {line} {raw:?} (expected '<lint> = \"<level>\"')"),
+        }
+    }
+}
+impl error::Error for Error {}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines what to do with diagnostics of a [`Lint`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    /// The diagnostics are silenced.
+    Allow,
+    /// The diagnostics are shown as warnings.
+    Warn,
+    /// The diagnostics are shown as errors.
+    Deny,
+}
+impl Level {
+    /// Returns the flag that sets a lint to this level on the command line.
+    ///
+    /// # Returns
+    /// `-A`, `-W` or `-D`.
+    #[inline]
+    pub fn flag(&self) -> &'static str {
+        match self {
+            Self::Allow => "-A",
+            Self::Warn => "-W",
+            Self::Deny => "-D",
+        }
+    }
+}
+impl Display for Level {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Allow => write!(f, "allow"),
+            Self::Warn => write!(f, "warn"),
+            Self::Deny => write!(f, "deny"),
+        }
+    }
+}
+impl FromStr for Level {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Self::Allow),
+            "warn" => Ok(Self::Warn),
+            "deny" => Ok(Self::Deny),
+            raw => Err(Error::UnknownLevel { raw: raw.into() }),
+        }
+    }
+}
+
+
+
+/// Declares a kind of diagnostic that users can configure the [`Level`] of.
+///
+/// # Example
+/// ```
+/// use ast_toolkit_2::report::{Level, Lint};
+///
+/// pub const UNUSED_VARIABLES: Lint = Lint::new("unused_variables", Level::Warn, "Detects variables that are never read.");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Lint {
+    /// The name by which users refer to the lint (e.g., `unused_variables`).
+    pub name:        &'static str,
+    /// The level of the lint if the user doesn't configure it.
+    pub default:     Level,
+    /// A short description of what the lint detects.
+    pub description: &'static str,
+}
+impl Lint {
+    /// Constructor for the Lint.
+    ///
+    /// # Arguments
+    /// - `name`: The name by which users refer to the lint.
+    /// - `default`: The [`Level`] of the lint if the user doesn't configure it.
+    /// - `description`: A short description of what the lint detects.
+    ///
+    /// # Returns
+    /// A new Lint.
+    #[inline]
+    pub const fn new(name: &'static str, default: Level, description: &'static str) -> Self { Self { name, default, description } }
+}
+impl Display for Lint {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "{}", self.name) }
+}
+
+
+
+/// Decides the [`Level`] of every [`Lint`].
+///
+/// It is built from command-line flags (e.g., `-D unused_variables`) and/or configuration files.
+/// Later settings override earlier ones. The special name [`warnings`](WARNINGS) refers to all
+/// warnings, not just lints; so `-D warnings` makes all warnings errors and `-A warnings` silences
+/// them.
+#[derive(Clone, Debug, Default)]
+pub struct LintConfig {
+    /// The levels set for individual lints.
+    levels:   HashMap<String, Level>,
+    /// The level set for all warnings, if any.
+    warnings: Option<Level>,
+}
+
+// Constructors
+impl LintConfig {
+    /// Constructor for the LintConfig that leaves all lints at their default level.
+    ///
+    /// # Returns
+    /// A new LintConfig.
+    #[inline]
+    pub fn new() -> Self { Self { levels: HashMap::new(), warnings: None } }
+
+    /// Constructor for the LintConfig that parses it from command-line arguments.
+    ///
+    /// The following flags are recognized, where `<lint>` is either given in the same argument
+    /// or in the next:
+    /// - `-A <lint>` or `--allow <lint>` (or `--allow=<lint>`);
+    /// - `-W <lint>` or `--warn <lint>` (or `--warn=<lint>`); and
+    /// - `-D <lint>` or `--deny <lint>` (or `--deny=<lint>`).
+    ///
+    /// # Arguments
+    /// - `args`: The arguments to parse. Should only contain lint flags.
+    ///
+    /// # Returns
+    /// A new LintConfig with the given levels.
+    ///
+    /// # Errors
+    /// This function errors if an argument was not a lint flag, or if a flag misses its lint.
+    pub fn from_args<S: AsRef<str>>(args: impl IntoIterator<Item = S>) -> Result<Self, Error> {
+        let mut res: Self = Self::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let arg: &str = arg.as_ref();
+            let (flag, lint): (&str, Option<&str>) = match arg.split_once('=') {
+                Some((flag, lint)) if flag.starts_with("--") => (flag, Some(lint)),
+                _ if arg.len() > 2 && !arg.starts_with("--") => match arg.split_at_checked(2) {
+                    Some((flag, lint)) => (flag, Some(lint)),
+                    None => return Err(Error::UnknownFlag { flag: arg.into() }),
+                },
+                _ => (arg, None),
+            };
+            let level: Level = match flag {
+                "-A" | "--allow" => Level::Allow,
+                "-W" | "--warn" => Level::Warn,
+                "-D" | "--deny" => Level::Deny,
+                flag => return Err(Error::UnknownFlag { flag: flag.into() }),
+            };
+            match lint {
+                Some(lint) => res.set(lint, level),
+                None => match args.next() {
+                    Some(lint) => res.set(lint.as_ref(), level),
+                    None => return Err(Error::MissingLint { flag: flag.into() }),
+                },
+            }
+        }
+        Ok(res)
+    }
+
+    /// Constructor for the LintConfig that parses it from a configuration file.
+    ///
+    /// The file is a subset of TOML: every line is either empty, a comment starting with `#`, a
+    /// section header (e.g., `[lints]`) or a `<lint> = "<level>"` pair. Section headers are
+    /// ignored.
+    ///
+    /// # Arguments
+    /// - `text`: The contents of the configuration file.
+    ///
+    /// # Returns
+    /// A new LintConfig with the given levels.
+    ///
+    /// # Errors
+    /// This function errors if a line was malformed or named an unknown level.
+    pub fn from_config(text: &str) -> Result<Self, Error> {
+        let mut res: Self = Self::new();
+        for (i, raw) in text.lines().enumerate() {
+            let line: &str = raw.split_once('#').map(|(line, _)| line).unwrap_or(raw).trim();
+            if line.is_empty() || (line.starts_with('[') && line.ends_with(']')) {
+                continue;
+            }
+            let Some((lint, level)) = line.split_once('=') else { return Err(Error::IllegalLine { line: i + 1, raw: raw.into() }) };
+            let level: &str = level.trim().trim_matches('"');
+            res.set(lint.trim(), level.parse()?);
+        }
+        Ok(res)
+    }
+
+    /// Makes all warnings errors.
+    ///
+    /// This is equivalent to `-D warnings`.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_warnings_as_errors(mut self) -> Self {
+        self.warnings = Some(Level::Deny);
+        self
+    }
+
+    /// Extends this LintConfig with the settings of another.
+    ///
+    /// Settings in `other` override those in `self`. Useful to let command-line flags override a
+    /// configuration file.
+    ///
+    /// # Arguments
+    /// - `other`: The LintConfig to apply on top of this one.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_overrides(mut self, other: Self) -> Self {
+        self.levels.extend(other.levels);
+        self.warnings = other.warnings.or(self.warnings);
+        self
+    }
+}
+
+// Configuration
+impl LintConfig {
+    /// Sets the level of a lint.
+    ///
+    /// # Arguments
+    /// - `lint`: The name of the lint, or [`warnings`](WARNINGS) for all warnings.
+    /// - `level`: The [`Level`] to set it to.
+    #[inline]
+    pub fn set(&mut self, lint: impl Into<String>, level: Level) {
+        let lint: String = lint.into();
+        if lint == WARNINGS {
+            self.warnings = Some(level);
+        } else {
+            self.levels.insert(lint, level);
+        }
+    }
+
+    /// Returns the level of a lint.
+    ///
+    /// Note that this does not take [`warnings`](WARNINGS) into account; see
+    /// [`LintConfig::warnings()`] for that.
+    ///
+    /// # Arguments
+    /// - `lint`: The [`Lint`] to get the level of.
+    ///
+    /// # Returns
+    /// The configured [`Level`], or the lint's default if it isn't configured.
+    #[inline]
+    pub fn level(&self, lint: &Lint) -> Level { self.levels.get(lint.name).copied().unwrap_or(lint.default) }
+
+    /// Returns whether the level of a lint was explicitly configured.
+    ///
+    /// # Arguments
+    /// - `lint`: The [`Lint`] to check.
+    ///
+    /// # Returns
+    /// True if it was, or false if it has its default level.
+    #[inline]
+    pub fn is_configured(&self, lint: &Lint) -> bool { self.levels.contains_key(lint.name) }
+
+    /// Returns the level set for all warnings, if any.
+    #[inline]
+    pub fn warnings(&self) -> Option<Level> { self.warnings }
+
+    /// Returns the names of configured lints that aren't known.
+    ///
+    /// # Arguments
+    /// - `lints`: The [`Lint`]s known to the tool.
+    ///
+    /// # Returns
+    /// An [`Iterator`] over the names that don't match any of the `lints`.
+    #[inline]
+    pub fn unknown<'s>(&'s self, lints: &'s [&'s Lint]) -> impl 's + Iterator<Item = &'s str> {
+        self.levels.keys().map(String::as_str).filter(|name| !lints.iter().any(|lint| lint.name == *name))
+    }
+}
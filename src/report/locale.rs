@@ -0,0 +1,474 @@
+//  LOCALE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:39:38
+//  Last edited:
+//    18 Oct 2026, 20:39:38
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines [`Bundle`]s of translated messages in (a subset of) the
+//!   [Fluent](https://projectfluent.org) syntax, and [`Locales`] that
+//!   resolve message IDs against them with a fallback.
+//
+
+use std::collections::{BTreeMap, HashMap};
+use std::error;
+use std::fmt::{Display, Formatter, Result as FResult};
+
+
+/***** CONSTANTS *****/
+/// The maximum depth of message references that we resolve, to catch cycles.
+const MAX_DEPTH: usize = 16;
+
+
+
+
+
+/***** ERRORS *****/
+/// Defines errors that occur when parsing or checking a [`Bundle`].
+#[derive(Debug)]
+pub enum Error {
+    /// A line was neither an entry, attribute, continuation nor comment.
+    IllegalLine { line: usize, raw: String },
+    /// An entry or attribute had an illegal identifier.
+    IllegalIdentifier { line: usize, id: String },
+    /// An entry was defined twice.
+    DuplicateEntry { line: usize, id: String },
+    /// A placeable (`{ ... }`) was opened but not closed.
+    UnterminatedPlaceable { line: usize },
+    /// A placeable had contents we don't support.
+    IllegalPlaceable { line: usize, raw: String },
+    /// Some message IDs were missing from a bundle.
+    MissingIds { locale: String, ids: Vec<String> },
+}
+impl Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::IllegalLine { line, raw } => write!(f, "Illegal line {line} {raw:?} (expected '<id> = <pattern>', '.<attribute> = <pattern>' or a comment)"),
+            Self::IllegalIdentifier { line, id } => write!(f, "Illegal identifier {id:?} on line {line}"),
+            Self::DuplicateEntry { line, id } => write!(f, "Duplicate definition of {id:?} on line {line}"),
+            Self::UnterminatedPlaceable { line } => write!(f, "Unterminated placeable in the pattern on line {line}"),
+            Self::IllegalPlaceable { line, raw } => {
+                write!(f, "Illegal placeable {{{raw}}} in the pattern on line {line} (expected a '$variable', '\"literal\"', message or '-term')")
+            },
+            Self::MissingIds { locale, ids } => {
+                write!(f, "Locale {locale:?} is missing messages {}", ids.iter().map(|id| format!("{id:?}")).collect::<Vec<_>>().join(", "))
+            },
+        }
+    }
+}
+impl error::Error for Error {}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Checks whether something is a valid Fluent identifier.
+///
+/// # Arguments
+/// - `id`: The text to check.
+///
+/// # Returns
+/// True if it starts with an ASCII letter followed by only ASCII letters, digits, `_` or `-`.
+#[inline]
+fn is_identifier(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic()) && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Splits a reference to a message into its identifier and attribute.
+///
+/// # Arguments
+/// - `id`: The reference, either `id` or `id.attribute`.
+///
+/// # Returns
+/// A tuple of the identifier and the attribute, if any, or [`None`] if either isn't a valid
+/// identifier.
+#[inline]
+fn split_reference(id: &str) -> Option<(&str, Option<&str>)> {
+    let (id, attr): (&str, Option<&str>) = match id.split_once('.') {
+        Some((id, attr)) => (id, Some(attr)),
+        None => (id, None),
+    };
+    if is_identifier(id) && attr.is_none_or(is_identifier) { Some((id, attr)) } else { None }
+}
+
+/// Parses the text of a pattern into its elements.
+///
+/// # Arguments
+/// - `text`: The text to parse.
+/// - `line`: The line on which the pattern starts, for errors.
+///
+/// # Returns
+/// A new [`Pattern`].
+///
+/// # Errors
+/// This function errors if a placeable was unterminated or illegal.
+fn parse_pattern(text: &str, line: usize) -> Result<Pattern, Error> {
+    let mut elems: Vec<Element> = Vec::new();
+    let mut text_buf: String = String::new();
+    let mut rem: &str = text;
+    while let Some(open) = rem.find('{') {
+        text_buf.push_str(&rem[..open]);
+        // NOTE: String literals may contain braces themselves, so skip over those first
+        let after: &str = &rem[open + 1..];
+        let skip: usize = match after.trim_start().strip_prefix('"') {
+            Some(lit) => match lit.find('"') {
+                Some(end) => after.len() - lit.len() + end + 1,
+                None => return Err(Error::UnterminatedPlaceable { line }),
+            },
+            None => 0,
+        };
+        let Some(close) = after[skip..].find('}').map(|close| open + 1 + skip + close) else { return Err(Error::UnterminatedPlaceable { line }) };
+        let raw: &str = &rem[open + 1..close];
+        let inner: &str = raw.trim();
+        if inner.len() >= 2 && inner.starts_with('"') && inner.ends_with('"') {
+            text_buf.push_str(&inner[1..inner.len() - 1]);
+        } else {
+            let elem: Element = if let Some(var) = inner.strip_prefix('$').filter(|var| is_identifier(var)) {
+                Element::Variable(var.into())
+            } else if let Some(term) = inner.strip_prefix('-').filter(|term| split_reference(term).is_some()) {
+                Element::Term(term.into())
+            } else if split_reference(inner).is_some() {
+                Element::Message(inner.into())
+            } else {
+                return Err(Error::IllegalPlaceable { line, raw: raw.into() });
+            };
+            if !text_buf.is_empty() {
+                elems.push(Element::Text(std::mem::take(&mut text_buf)));
+            }
+            elems.push(elem);
+        }
+        rem = &rem[close + 1..];
+    }
+    text_buf.push_str(rem);
+    if !text_buf.is_empty() {
+        elems.push(Element::Text(text_buf));
+    }
+    Ok(Pattern(elems))
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// A single piece of a [`Pattern`].
+#[derive(Clone, Debug)]
+enum Element {
+    /// Literal text.
+    Text(String),
+    /// A reference to an argument (`{ $name }`).
+    Variable(String),
+    /// A reference to another message (`{ id }` or `{ id.attribute }`).
+    Message(String),
+    /// A reference to a term (`{ -id }` or `{ -id.attribute }`).
+    Term(String),
+}
+
+/// The value of a message or attribute, with placeables to fill in.
+#[derive(Clone, Debug)]
+struct Pattern(Vec<Element>);
+
+/// A message or term with its attributes.
+#[derive(Clone, Debug, Default)]
+struct Entry {
+    /// The value of the entry itself, if any.
+    value: Option<Pattern>,
+    /// The values of the entry's attributes.
+    attrs: HashMap<String, Pattern>,
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Implemented by [`Report`](super::Report)s whose messages are message IDs, to list those IDs.
+///
+/// This is implemented by the [derive](derive@super::Report) for reports with a `message_id`.
+/// Use [`Bundle::verify()`] in a test to check that a bundle provides all of them.
+pub trait Localized {
+    /// All message IDs that the report may use (e.g., `dup-def` or `dup-def.first`).
+    const MESSAGE_IDS: &'static [&'static str];
+}
+
+
+
+/// A collection of translated messages for a single locale.
+///
+/// Bundles are parsed from a subset of the [Fluent](https://projectfluent.org) syntax:
+/// ```ftl
+/// # Comments start with a hash.
+/// -lang = Rust
+///
+/// dup-def = the name `{ $name }` is defined multiple times
+///     .redefined = `{ $name }` redefined here
+///     .first = previous definition of `{ $name }` here
+/// dup-def-help = { -lang } does not allow shadowing items
+/// ```
+/// That is, messages, terms (prefixed with `-`), attributes, multi-line patterns (indented
+/// continuation lines) and the placeables `{ $variable }`, `{ "literal" }`, `{ message }` and
+/// `{ -term }` are supported. Selectors and functions are not.
+#[derive(Clone, Debug)]
+pub struct Bundle {
+    /// The name of the locale (e.g., `en-US`).
+    locale:   String,
+    /// The messages in the bundle.
+    messages: HashMap<String, Entry>,
+    /// The terms in the bundle.
+    terms:    HashMap<String, Entry>,
+}
+
+// Constructors
+impl Bundle {
+    /// Constructor for the Bundle that parses it from Fluent source text.
+    ///
+    /// # Arguments
+    /// - `locale`: The name of the locale (e.g., `en-US`).
+    /// - `text`: The Fluent source text to parse.
+    ///
+    /// # Returns
+    /// A new Bundle with the messages in `text`.
+    ///
+    /// # Errors
+    /// This function errors if the `text` was malformed.
+    pub fn parse(locale: impl Into<String>, text: &str) -> Result<Self, Error> {
+        // First, collect the raw patterns per entry
+        // NOTE: Every pattern is (line, id, attribute, text)
+        let mut raws: Vec<(usize, String, Option<String>, String)> = Vec::new();
+        for (i, raw) in text.lines().enumerate() {
+            let line: usize = i + 1;
+            let trimmed: &str = raw.trim();
+            if trimmed.is_empty() || raw.starts_with('#') {
+                continue;
+            }
+            if raw.starts_with(char::is_whitespace) {
+                // Either an attribute or a continuation of the previous pattern
+                let Some(last) = raws.last_mut() else { return Err(Error::IllegalLine { line, raw: raw.into() }) };
+                if let Some(attr) = trimmed.strip_prefix('.') {
+                    let Some((attr, value)) = attr.split_once('=') else { return Err(Error::IllegalLine { line, raw: raw.into() }) };
+                    let attr: &str = attr.trim();
+                    if !is_identifier(attr) {
+                        return Err(Error::IllegalIdentifier { line, id: attr.into() });
+                    }
+                    let id: String = last.1.clone();
+                    raws.push((line, id, Some(attr.into()), value.trim().into()));
+                } else {
+                    if !last.3.is_empty() {
+                        last.3.push('\n');
+                    }
+                    last.3.push_str(trimmed);
+                }
+            } else {
+                let Some((id, value)) = raw.split_once('=') else { return Err(Error::IllegalLine { line, raw: raw.into() }) };
+                let id: &str = id.trim();
+                if !is_identifier(id.strip_prefix('-').unwrap_or(id)) {
+                    return Err(Error::IllegalIdentifier { line, id: id.into() });
+                }
+                raws.push((line, id.into(), None, value.trim().into()));
+            }
+        }
+
+        // Then parse the patterns into entries
+        let mut res: Self = Self { locale: locale.into(), messages: HashMap::new(), terms: HashMap::new() };
+        for (line, id, attr, text) in raws {
+            let pattern: Pattern = parse_pattern(&text, line)?;
+            let (entries, name): (&mut HashMap<String, Entry>, &str) = match id.strip_prefix('-') {
+                Some(term) => (&mut res.terms, term),
+                None => (&mut res.messages, &id),
+            };
+            match attr {
+                Some(attr) => {
+                    if entries.entry(name.into()).or_default().attrs.insert(attr.clone(), pattern).is_some() {
+                        return Err(Error::DuplicateEntry { line, id: format!("{id}.{attr}") });
+                    }
+                },
+                None => {
+                    if entries.contains_key(name) {
+                        return Err(Error::DuplicateEntry { line, id });
+                    }
+                    entries.insert(name.into(), Entry { value: (!pattern.0.is_empty()).then_some(pattern), attrs: HashMap::new() });
+                },
+            }
+        }
+        Ok(res)
+    }
+}
+
+// Lookup
+impl Bundle {
+    /// Returns the name of the locale of this Bundle.
+    #[inline]
+    pub fn locale(&self) -> &str { &self.locale }
+
+    /// Checks whether this Bundle has a message.
+    ///
+    /// # Arguments
+    /// - `id`: The ID of the message, optionally with an attribute (e.g., `dup-def.first`).
+    ///
+    /// # Returns
+    /// True if the message (or its attribute) is defined in this bundle.
+    #[inline]
+    pub fn has(&self, id: &str) -> bool { self.pattern(&self.messages, id).is_some() }
+
+    /// Checks that this Bundle has all messages used by some [`Localized`] report.
+    ///
+    /// This is intended to be called from a test, such that missing translations are caught
+    /// early:
+    /// ```
+    /// use ast_toolkit_2::report::{Bundle, Report, Span};
+    ///
+    /// #[derive(Report)]
+    /// #[report(message_id = "dup-def")]
+    /// struct DuplicateDef {
+    ///     #[arg]
+    ///     name: String,
+    ///     #[label(primary, "dup-def.redefined")]
+    ///     span: Span,
+    /// }
+    ///
+    /// // In a test, typically with `include_str!("../locales/en.ftl")`
+    /// let bundle = Bundle::parse("en", "dup-def = `{ $name }` is defined twice\n    .redefined = redefined here\n").unwrap();
+    /// bundle.verify::<DuplicateDef>().unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// This function errors with [`Error::MissingIds`] if any of the IDs is missing.
+    pub fn verify<T: ?Sized + Localized>(&self) -> Result<(), Error> {
+        let ids: Vec<String> = T::MESSAGE_IDS.iter().filter(|id| !self.has(id)).map(|id| id.to_string()).collect();
+        if ids.is_empty() { Ok(()) } else { Err(Error::MissingIds { locale: self.locale.clone(), ids }) }
+    }
+
+    /// Formats a message with arguments.
+    ///
+    /// Arguments referenced by the message but not given are written as `{$name}`.
+    ///
+    /// # Arguments
+    /// - `id`: The ID of the message, optionally with an attribute (e.g., `dup-def.first`).
+    /// - `args`: The arguments to fill in.
+    ///
+    /// # Returns
+    /// The formatted message, or [`None`] if this bundle doesn't define it.
+    #[inline]
+    pub fn format(&self, id: &str, args: &BTreeMap<String, String>) -> Option<String> {
+        let pattern: &Pattern = self.pattern(&self.messages, id)?;
+        let mut res: String = String::new();
+        self.format_pattern(pattern, args, 0, &mut res);
+        Some(res)
+    }
+
+    /// Finds the pattern of a message or term.
+    ///
+    /// # Arguments
+    /// - `entries`: Either the messages or terms to search.
+    /// - `id`: The ID of the entry, optionally with an attribute.
+    ///
+    /// # Returns
+    /// The [`Pattern`], or [`None`] if it isn't defined.
+    fn pattern<'s>(&'s self, entries: &'s HashMap<String, Entry>, id: &str) -> Option<&'s Pattern> {
+        let (id, attr): (&str, Option<&str>) = split_reference(id)?;
+        let entry: &Entry = entries.get(id)?;
+        match attr {
+            Some(attr) => entry.attrs.get(attr),
+            None => entry.value.as_ref(),
+        }
+    }
+
+    /// Formats a pattern to a string.
+    ///
+    /// # Arguments
+    /// - `pattern`: The [`Pattern`] to format.
+    /// - `args`: The arguments to fill in.
+    /// - `depth`: How many references deep we are.
+    /// - `res`: The string to write to.
+    fn format_pattern(&self, pattern: &Pattern, args: &BTreeMap<String, String>, depth: usize, res: &mut String) {
+        for elem in &pattern.0 {
+            let (entries, id, sigil): (&HashMap<String, Entry>, &str, &str) = match elem {
+                Element::Text(text) => {
+                    res.push_str(text);
+                    continue;
+                },
+                Element::Variable(var) => {
+                    match args.get(var) {
+                        Some(value) => res.push_str(value),
+                        None => res.push_str(&format!("{{${var}}}")),
+                    }
+                    continue;
+                },
+                Element::Message(id) => (&self.messages, id, ""),
+                Element::Term(id) => (&self.terms, id, "-"),
+            };
+            match self.pattern(entries, id) {
+                Some(pattern) if depth < MAX_DEPTH => self.format_pattern(pattern, args, depth + 1, res),
+                _ => res.push_str(&format!("{{{sigil}{id}}}")),
+            }
+        }
+    }
+}
+
+
+
+/// A chain of [`Bundle`]s in which message IDs are resolved, ending in a fallback.
+///
+/// Typically, the fallback is English, such that untranslated messages are still shown in a
+/// language most users read.
+#[derive(Clone, Debug)]
+pub struct Locales {
+    /// The preferred bundles, in order of preference.
+    preferred: Vec<Bundle>,
+    /// The bundle to use if none of the preferred ones defines a message.
+    fallback:  Bundle,
+}
+
+// Constructors
+impl Locales {
+    /// Constructor for the Locales.
+    ///
+    /// # Arguments
+    /// - `fallback`: The [`Bundle`] used for messages that aren't translated (typically English).
+    ///
+    /// # Returns
+    /// A new Locales that only uses the `fallback`.
+    #[inline]
+    pub fn new(fallback: Bundle) -> Self { Self { preferred: Vec::new(), fallback } }
+
+    /// Adds a preferred locale.
+    ///
+    /// Locales added first are preferred over those added later (e.g., add `nl-BE` before `nl`).
+    ///
+    /// # Arguments
+    /// - `bundle`: The [`Bundle`] of the locale.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_locale(mut self, bundle: Bundle) -> Self {
+        self.preferred.push(bundle);
+        self
+    }
+}
+
+// Lookup
+impl Locales {
+    /// Returns the bundle used for messages that aren't translated.
+    #[inline]
+    pub fn fallback(&self) -> &Bundle { &self.fallback }
+
+    /// Formats a message in the most preferred locale that defines it.
+    ///
+    /// # Arguments
+    /// - `id`: The ID of the message, optionally with an attribute (e.g., `dup-def.first`).
+    /// - `args`: The arguments to fill in.
+    ///
+    /// # Returns
+    /// The formatted message, or [`None`] if no bundle defines it.
+    pub fn format(&self, id: &str, args: &BTreeMap<String, String>) -> Option<String> {
+        self.preferred.iter().chain(std::iter::once(&self.fallback)).find_map(|bundle| bundle.format(id, args))
+    }
+}
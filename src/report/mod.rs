@@ -4,15 +4,242 @@
 //  Created:
 //    05 Mar 2025, 17:50:20
 //  Last edited:
-//    05 Mar 2025, 17:51:20
+//    18 Oct 2026, 20:39:38
 //  Auto updated?
 //    Yes
 //
 //  Description:
 //!   Library for generating very human-readable compilation errors.
-//!   
+//!
 //!   This library mostly wraps around [ariadne](::ariadne), except that it aims to be more
 //!   general. Specifically, it provides the [`Report`](trait@Report)-trait that can be
 //!   [automatically derived](derive@Report) on errors to convenient generate them from normal
 //!   Rust errors.
 //
+
+// Module definitions
+pub mod diagnostic;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod lint;
+pub mod locale;
+#[cfg(feature = "parser")]
+pub mod parser;
+pub mod registry;
+pub mod render;
+#[cfg(feature = "json")]
+pub mod sarif;
+pub mod sink;
+pub mod snapshot;
+pub mod suggestion;
+
+// Imports
+use std::collections::BTreeMap;
+
+// Re-exports
+pub use ariadne;
+pub use ast_toolkit_2_macros::Report;
+pub use diagnostic::{Diagnostic, Label, Severity};
+#[cfg(feature = "json")]
+pub use json::JsonDiagnostic;
+pub use lint::{Level, Lint, LintConfig};
+pub use locale::{Bundle, Localized, Locales};
+pub use registry::{Code, Registry};
+pub use render::{AriadneRenderer, HtmlRenderer, RenderConfig, Renderer, RustcRenderer};
+#[cfg(feature = "json")]
+pub use sarif::{SarifDriver, SarifLog};
+pub use sink::Diagnostics;
+pub use snapshot::Harness;
+pub use suggestion::{Applicability, Suggestion, apply_suggestions};
+
+#[doc(inline)]
+pub use crate::codes;
+pub use crate::span::{Sources, Span, Spanned};
+
+
+/***** LIBRARY *****/
+/// Marks an error as something that can be reported to the user in a human-readable way.
+///
+/// Only [`Report::message()`] and [`Report::primary()`] have to be implemented; the others
+/// default to an error without any code, additional labels, notes or help. Usually, you would
+/// [derive](derive@Report) this trait instead of implementing it manually.
+pub trait Report {
+    /// Returns how bad this error is.
+    ///
+    /// By default, every report is an [error](Severity::Error).
+    ///
+    /// # Returns
+    /// The [`Severity`] of this report.
+    #[inline]
+    fn severity(&self) -> Severity { Severity::Error }
+
+    /// Returns a code that uniquely identifies the kind of error, if any.
+    ///
+    /// # Returns
+    /// Some code (e.g., `E0042`), or [`None`] if this report has none.
+    #[inline]
+    fn code(&self) -> Option<String> { None }
+
+    /// Returns the lint that users can configure the severity of this report with, if any.
+    ///
+    /// If given, the [`Diagnostics`]-sink overrides [`Report::severity()`] with the level
+    /// configured for the lint, or silences the report altogether.
+    ///
+    /// # Returns
+    /// Some [`Lint`], or [`None`] if this report isn't configurable.
+    #[inline]
+    fn lint(&self) -> Option<&'static Lint> { None }
+
+    /// Returns the main message of the error.
+    ///
+    /// # Returns
+    /// A [`String`] describing what went wrong.
+    fn message(&self) -> String;
+
+    /// Returns the primary location of the error.
+    ///
+    /// # Returns
+    /// A [`Label`] that annotates where the error occurred.
+    fn primary(&self) -> Label;
+
+    /// Returns any additional locations relevant to the error.
+    ///
+    /// # Returns
+    /// A list of [`Label`]s that annotate secondary locations.
+    #[inline]
+    fn labels(&self) -> Vec<Label> { Vec::new() }
+
+    /// Returns any notes giving additional context to the error.
+    ///
+    /// # Returns
+    /// A list of notes to show below the error.
+    #[inline]
+    fn notes(&self) -> Vec<String> { Vec::new() }
+
+    /// Returns any help messages that tell the user how to fix the error.
+    ///
+    /// # Returns
+    /// A list of help messages to show below the error.
+    #[inline]
+    fn helps(&self) -> Vec<String> { Vec::new() }
+
+    /// Returns any suggested edits to the source text that fix the error.
+    ///
+    /// # Returns
+    /// A list of [`Suggestion`]s that are rendered inline and can be applied automatically.
+    #[inline]
+    fn suggestions(&self) -> Vec<Suggestion> { Vec::new() }
+
+    /// Returns the arguments to the messages, if they are message IDs.
+    ///
+    /// If this returns [`Some`], [`Report::message()`] and the messages of the labels, notes,
+    /// helps and suggestions are interpreted as message IDs, resolved against the [`Locales`]
+    /// given to the [`Diagnostics`]-sink at render time.
+    ///
+    /// # Returns
+    /// A map of argument names to their values, or [`None`] if the messages are text.
+    #[inline]
+    fn args(&self) -> Option<BTreeMap<String, String>> { None }
+
+
+    /// Collects everything this report says into an owned [`Diagnostic`].
+    ///
+    /// Usually, you don't need to override this.
+    ///
+    /// # Returns
+    /// A [`Diagnostic`] with the information from all the other methods.
+    #[inline]
+    fn to_diagnostic(&self) -> Diagnostic {
+        Diagnostic {
+            severity:    self.severity(),
+            code:        self.code(),
+            lint:        self.lint(),
+            message:     self.message(),
+            primary:     self.primary(),
+            labels:      self.labels(),
+            notes:       self.notes(),
+            helps:       self.helps(),
+            suggestions: self.suggestions(),
+            args:        self.args(),
+        }
+    }
+
+    /// Turns this report into an [`ariadne::Report`] that can be written to the terminal.
+    ///
+    /// Usually, you don't need to override this.
+    ///
+    /// # Returns
+    /// An [`ariadne::Report`] build from this report's [`Diagnostic`].
+    #[inline]
+    fn to_ariadne_report(&self) -> ariadne::Report<'static, Span> { self.to_diagnostic().into() }
+}
+
+// Default impls
+impl<T: ?Sized + Report> Report for &T {
+    #[inline]
+    fn severity(&self) -> Severity { <T as Report>::severity(self) }
+
+    #[inline]
+    fn code(&self) -> Option<String> { <T as Report>::code(self) }
+
+    #[inline]
+    fn lint(&self) -> Option<&'static Lint> { <T as Report>::lint(self) }
+
+    #[inline]
+    fn message(&self) -> String { <T as Report>::message(self) }
+
+    #[inline]
+    fn primary(&self) -> Label { <T as Report>::primary(self) }
+
+    #[inline]
+    fn labels(&self) -> Vec<Label> { <T as Report>::labels(self) }
+
+    #[inline]
+    fn notes(&self) -> Vec<String> { <T as Report>::notes(self) }
+
+    #[inline]
+    fn helps(&self) -> Vec<String> { <T as Report>::helps(self) }
+
+    #[inline]
+    fn suggestions(&self) -> Vec<Suggestion> { <T as Report>::suggestions(self) }
+
+    #[inline]
+    fn args(&self) -> Option<BTreeMap<String, String>> { <T as Report>::args(self) }
+
+    #[inline]
+    fn to_diagnostic(&self) -> Diagnostic { <T as Report>::to_diagnostic(self) }
+}
+impl<T: ?Sized + Report> Report for Box<T> {
+    #[inline]
+    fn severity(&self) -> Severity { <T as Report>::severity(self) }
+
+    #[inline]
+    fn code(&self) -> Option<String> { <T as Report>::code(self) }
+
+    #[inline]
+    fn lint(&self) -> Option<&'static Lint> { <T as Report>::lint(self) }
+
+    #[inline]
+    fn message(&self) -> String { <T as Report>::message(self) }
+
+    #[inline]
+    fn primary(&self) -> Label { <T as Report>::primary(self) }
+
+    #[inline]
+    fn labels(&self) -> Vec<Label> { <T as Report>::labels(self) }
+
+    #[inline]
+    fn notes(&self) -> Vec<String> { <T as Report>::notes(self) }
+
+    #[inline]
+    fn helps(&self) -> Vec<String> { <T as Report>::helps(self) }
+
+    #[inline]
+    fn suggestions(&self) -> Vec<Suggestion> { <T as Report>::suggestions(self) }
+
+    #[inline]
+    fn args(&self) -> Option<BTreeMap<String, String>> { <T as Report>::args(self) }
+
+    #[inline]
+    fn to_diagnostic(&self) -> Diagnostic { <T as Report>::to_diagnostic(self) }
+}
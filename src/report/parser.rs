@@ -0,0 +1,137 @@
+//  PARSER.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:09:33
+//  Last edited:
+//    18 Oct 2026, 20:09:33
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements [`Report`] for the errors produced by [chumsky](::chumsky)
+//!   parsers, such that they don't have to be converted by hand.
+//
+
+use std::fmt::Display;
+use std::hash::Hash;
+
+use chumsky::error::{Simple, SimpleReason};
+
+use super::{Label, Report};
+use crate::span::Span;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Describes a token that was expected or found.
+///
+/// # Arguments
+/// - `token`: The token, or [`None`] if it's the end of the input.
+///
+/// # Returns
+/// The token in backticks, or `end of input`.
+#[inline]
+fn describe<I: Display>(token: Option<&I>) -> String {
+    match token {
+        Some(token) => format!("`{token}`"),
+        None => "end of input".into(),
+    }
+}
+
+/// Describes the tokens that a parser expected.
+///
+/// The tokens are deduplicated and sorted, with the end of the input last, so that the same error
+/// is always described the same way.
+///
+/// # Arguments
+/// - `err`: The [`Simple`] error to describe the expected tokens of.
+///
+/// # Returns
+/// A phrase like "`a`", "`a` or `b`" or "one of `a`, `b` or `c`", or [`None`] if nothing was
+/// expected.
+fn describe_expected<I: Display + Hash + Eq>(err: &Simple<I, Span>) -> Option<String> {
+    let mut tokens: Vec<String> = err.expected().filter_map(|token| token.as_ref().map(|token| describe(Some(token)))).collect();
+    tokens.sort();
+    tokens.dedup();
+    if err.expected().any(Option::is_none) {
+        tokens.push(describe::<I>(None));
+    }
+
+    match tokens.len() {
+        0 => None,
+        1 => tokens.pop(),
+        n => {
+            let last: String = tokens.pop().unwrap_or_else(|| unreachable!());
+            Some(format!("{}{} or {last}", if n > 2 { "one of " } else { "" }, tokens.join(", ")))
+        },
+    }
+}
+
+/// Describes what was expected and found.
+///
+/// # Arguments
+/// - `err`: The [`Simple`] error to describe.
+/// - `what`: What was expected instead of the expected tokens (e.g., the parser's label), if any.
+///
+/// # Returns
+/// A phrase like "expected `a` or `b`, found `c`" or "found unexpected `c`".
+fn describe_unexpected<I: Display + Hash + Eq>(err: &Simple<I, Span>, what: Option<String>) -> String {
+    let found: String = describe(err.found());
+    match what.or_else(|| describe_expected(err)) {
+        Some(expected) => format!("expected {expected}, found {found}"),
+        None => format!("found unexpected {found}"),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Reports a [`Simple`] parse error.
+///
+/// Depending on its [`SimpleReason`], it reads as:
+/// - "expected one of `a`, `b` or `c`, found `d`", where the expected tokens are sorted and
+///   deduplicated, or summarized by the parser's label if it has one;
+/// - "unclosed delimiter `(`", with labels at the delimiter and where it should have been closed;
+///   or
+/// - the custom message.
+impl<I: Display + Hash + Eq> Report for Simple<I, Span> {
+    #[inline]
+    fn message(&self) -> String {
+        match self.reason() {
+            SimpleReason::Unexpected => describe_unexpected(self, self.label().map(String::from)),
+            SimpleReason::Unclosed { delimiter, .. } => format!("unclosed delimiter {}", describe(Some(delimiter))),
+            SimpleReason::Custom(msg) => msg.clone(),
+        }
+    }
+
+    #[inline]
+    fn primary(&self) -> Label {
+        match self.reason() {
+            SimpleReason::Unexpected => match (self.label(), describe_expected(self)) {
+                // The tokens are summarized by the label in the message, so list them here
+                (Some(_), Some(expected)) => Label::with_message(self.span(), format!("expected {expected}")),
+                _ => Label::with_message(self.span(), format!("unexpected {}", describe(self.found()))),
+            },
+            SimpleReason::Unclosed { span, .. } => Label::with_message(span.clone(), "unclosed delimiter"),
+            SimpleReason::Custom(_) => Label::new(self.span()),
+        }
+    }
+
+    #[inline]
+    fn labels(&self) -> Vec<Label> {
+        match self.reason() {
+            SimpleReason::Unclosed { .. } => vec![Label::with_message(self.span(), describe_unexpected(self, None))],
+            SimpleReason::Unexpected | SimpleReason::Custom(_) => Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn notes(&self) -> Vec<String> {
+        match (self.reason(), self.label()) {
+            (SimpleReason::Unclosed { .. } | SimpleReason::Custom(_), Some(label)) => vec![format!("while parsing {label}")],
+            _ => Vec::new(),
+        }
+    }
+}
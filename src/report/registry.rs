@@ -0,0 +1,190 @@
+//  REGISTRY.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:06:41
+//  Last edited:
+//    18 Oct 2026, 20:06:41
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines a registry of error [`Code`]s with long-form explanations,
+//!   which is populated with the [`codes!()`](crate::codes)-macro.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+
+
+/***** MACROS *****/
+/// Declares error [`Code`]s and a [`Registry`] containing all of them.
+///
+/// Every code becomes a constant named after it, which can be given to the
+/// [`Report`](derive@super::Report)-derive as `#[report(code = E0042)]`. Because it's a path, using
+/// an unknown code is a compile error.
+///
+/// # Syntax
+/// First, give the (visibility and) name of the registry as a `static`. Then, list the codes as
+/// their names followed by a body with a `title`, an `explanation` (typically in markdown) and,
+/// optionally, a list of `examples` (typically source text exhibiting the error).
+///
+/// # Example
+/// ```
+/// ast_toolkit_2::codes! {
+///     pub static CODES;
+///
+///     /// Something was defined twice.
+///     pub E0042 {
+///         title: "Duplicate definition",
+///         explanation: "A name was defined twice in the same scope.\n\nRename one of them.",
+///         examples: ["let x = 1;\nlet x = 2;"],
+///     }
+/// }
+///
+/// assert_eq!(CODES.get("e0042"), Some(&E0042));
+/// assert!(E0042.explain().to_string().starts_with("# E0042: Duplicate definition\n"));
+/// ```
+///
+/// Referring to a code that was never declared doesn't compile:
+/// ```compile_fail
+/// use ast_toolkit_2::report::{Report, Span};
+///
+/// #[derive(Report)]
+/// #[report(message = "Duplicate definition", code = E0042)]
+/// struct Duplicate(#[label(primary)] Span);
+/// ```
+#[macro_export]
+macro_rules! codes {
+    (
+        $(#[$attr:meta])*
+        $vis:vis static $registry:ident;
+
+        $(
+            $(#[$cattr:meta])*
+            $cvis:vis $name:ident {
+                title: $title:expr,
+                explanation: $explanation:expr
+                $(, examples: [$($example:expr),* $(,)?])?
+                $(,)?
+            }
+        )*
+    ) => {
+        $(
+            $(#[$cattr])*
+            $cvis const $name: $crate::report::Code = $crate::report::Code {
+                name: ::std::stringify!($name),
+                title: $title,
+                explanation: $explanation,
+                examples: &[$($($example),*)?],
+            };
+        )*
+
+        $(#[$attr])*
+        $vis static $registry: $crate::report::Registry = $crate::report::Registry::new(&[$(&$name),*]);
+    };
+}
+
+
+
+
+
+/***** FORMATTERS *****/
+/// Writes the long-form explanation of a [`Code`], like `rustc --explain` does.
+///
+/// The output is markdown.
+pub struct CodeExplainFormatter<'c> {
+    /// The code to explain.
+    code: &'c Code,
+}
+impl<'c> Display for CodeExplainFormatter<'c> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        let Code { name, title, explanation, examples } = self.code;
+        writeln!(f, "# {name}: {title}")?;
+        writeln!(f)?;
+        writeln!(f, "{}", explanation.trim_end())?;
+        if !examples.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "## {}", if examples.len() == 1 { "Example" } else { "Examples" })?;
+            for example in *examples {
+                writeln!(f)?;
+                writeln!(f, "```")?;
+                writeln!(f, "{}", example.trim_end())?;
+                writeln!(f, "```")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines a code identifying a kind of error, with a long-form explanation.
+///
+/// Typically, you declare these with the [`codes!()`](crate::codes)-macro.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Code {
+    /// The name of the code (e.g., `E0042`).
+    pub name:        &'static str,
+    /// A short, single-line title of the code.
+    pub title:       &'static str,
+    /// A long-form explanation of the code, in markdown.
+    pub explanation: &'static str,
+    /// Any examples of source text exhibiting the error.
+    pub examples:    &'static [&'static str],
+}
+impl Code {
+    /// Returns a formatter that writes the long-form explanation of this Code.
+    ///
+    /// # Returns
+    /// A [`CodeExplainFormatter`] that implements [`Display`].
+    #[inline]
+    pub fn explain(&self) -> CodeExplainFormatter<'_> { CodeExplainFormatter { code: self } }
+}
+impl Display for Code {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "{}", self.name) }
+}
+
+
+
+/// Defines a collection of all [`Code`]s known to a tool.
+///
+/// Typically, you declare this with the [`codes!()`](crate::codes)-macro.
+#[derive(Clone, Copy, Debug)]
+pub struct Registry {
+    /// The codes in this registry.
+    codes: &'static [&'static Code],
+}
+impl Registry {
+    /// Constructor for the Registry.
+    ///
+    /// # Arguments
+    /// - `codes`: The [`Code`]s in the registry.
+    ///
+    /// # Returns
+    /// A new Registry with the given codes.
+    #[inline]
+    pub const fn new(codes: &'static [&'static Code]) -> Self { Self { codes } }
+
+    /// Finds a code by name.
+    ///
+    /// The name is matched case-insensitively, so that `e0042` finds `E0042`.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the code to find.
+    ///
+    /// # Returns
+    /// The [`Code`], or [`None`] if it isn't in this registry.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&'static Code> { self.codes.iter().copied().find(|code| code.name.eq_ignore_ascii_case(name)) }
+
+    /// Returns an iterator over the codes in this registry.
+    ///
+    /// # Returns
+    /// An [`Iterator`] over the [`Code`]s in order of declaration.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &'static Code> { self.codes.iter().copied() }
+}
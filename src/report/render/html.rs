@@ -0,0 +1,190 @@
+//  HTML.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:14:43
+//  Last edited:
+//    18 Oct 2026, 20:35:39
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements a [`Renderer`] that emits semantic HTML, such that
+//!   diagnostics can be styled with CSS in a browser.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::io::{self, Write};
+
+use super::layout::{FileLabels, Segment, group_labels, prepare};
+use super::{RenderConfig, Renderer};
+use crate::report::{Diagnostic, Severity, Suggestion};
+use crate::span::Sources;
+
+
+/***** FORMATTERS *****/
+/// Writes text with HTML special characters escaped.
+struct EscapeFormatter<'t>(&'t str);
+impl<'t> Display for EscapeFormatter<'t> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        for c in self.0.chars() {
+            match c {
+                '&' => write!(f, "&amp;")?,
+                '<' => write!(f, "&lt;")?,
+                '>' => write!(f, "&gt;")?,
+                '"' => write!(f, "&quot;")?,
+                '\'' => write!(f, "&#39;")?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns the class of a label covering a character.
+///
+/// # Arguments
+/// - `segs`: The [`Segment`]s on the line.
+/// - `i`: The 0-indexed character on the line.
+///
+/// # Returns
+/// `primary` if a primary label covers it, `secondary` if only other labels do, or [`None`] if
+/// no label covers it.
+#[inline]
+fn label_class(segs: &[Segment], i: usize) -> Option<&'static str> {
+    let mut covering = segs.iter().filter(|seg| seg.start <= i && i < seg.end).peekable();
+    covering.peek()?;
+    if covering.any(|seg| seg.primary) { Some("primary") } else { Some("secondary") }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Renders [`Diagnostic`]s as semantic HTML.
+///
+/// Every diagnostic becomes a `<div class="diagnostic error">` (or `warning`/`note`) with:
+/// - a `header` with the `severity`, `code` and `message`;
+/// - a `snippet` per file, with its `location`, the `source-line`s (each a `line-number` and a
+///   `<code class="source">` in which labelled text is a `label primary` or `label secondary`),
+///   and the `label-message`s of the labels on that line;
+/// - a `note` or `help` per note and help message, each with a `kind` and `text`; and
+/// - a `suggestion` per suggestion, with a `kind`, `text` and `<code class="replacement">`.
+///
+/// The summary written by the [`Diagnostics`](crate::report::Diagnostics)-sink becomes a
+/// `<div class="summary error">` with a `severity` and `message`.
+///
+/// All text is escaped. Colours and the width limit of the [`RenderConfig`] are ignored; style the
+/// classes with CSS instead.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HtmlRenderer;
+
+// Helpers
+impl HtmlRenderer {
+    /// Renders the source lines of a file with their labels.
+    ///
+    /// # Arguments
+    /// - `file`: The [`FileLabels`] to render.
+    /// - `sources`: The [`Sources`] to find the lines in.
+    /// - `writer`: The [`Write`]r to render to.
+    ///
+    /// # Errors
+    /// This function errors if we failed to write to the given `writer`.
+    fn render_snippet(&self, file: &FileLabels, sources: &Sources, writer: &mut dyn Write) -> io::Result<()> {
+        let name = EscapeFormatter(&file.name);
+        writeln!(writer, "<div class=\"snippet\" data-file=\"{name}\">")?;
+        match file.position {
+            Some((line, col)) => {
+                writeln!(writer, "<div class=\"location\"><span class=\"file\">{name}</span>:<span class=\"position\">{line}:{col}</span></div>")?
+            },
+            None => writeln!(writer, "<div class=\"location\"><span class=\"file\">{name}</span>:<span class=\"position\">{}</span></div>", file.offset)?,
+        }
+
+        let mut prev: Option<usize> = None;
+        for (line, segs) in &file.lines {
+            if prev.is_some_and(|prev| *line > prev + 1) {
+                writeln!(writer, "<div class=\"skipped\">...</div>")?;
+            }
+            prev = Some(*line);
+
+            // Write the line with the labelled parts marked
+            let text: Vec<char> = sources.line(&file.name, *line).unwrap_or("").chars().collect();
+            write!(writer, "<div class=\"source-line\" data-line=\"{line}\"><span class=\"line-number\">{line}</span><code class=\"source\">")?;
+            let mut i: usize = 0;
+            while i < text.len() {
+                let class: Option<&str> = label_class(segs, i);
+                let run: String = text[i..].iter().enumerate().take_while(|(j, _)| label_class(segs, i + j) == class).map(|(_, c)| *c).collect();
+                i += run.chars().count();
+                match class {
+                    Some(class) => write!(writer, "<span class=\"label {class}\">{}</span>", EscapeFormatter(&run))?,
+                    None => write!(writer, "{}", EscapeFormatter(&run))?,
+                }
+            }
+            writeln!(writer, "</code></div>")?;
+
+            // Write the messages of the labels ending on this line
+            let mut segs: Vec<&Segment> = segs.iter().collect();
+            segs.sort_by_key(|seg| (seg.start, seg.end));
+            for seg in segs {
+                if let Some(message) = &seg.message {
+                    let class: &str = if seg.primary { "primary" } else { "secondary" };
+                    writeln!(writer, "<div class=\"label-message {class}\" data-line=\"{line}\">{}</div>", EscapeFormatter(message))?;
+                }
+            }
+        }
+        writeln!(writer, "</div>")
+    }
+}
+
+// Renderer
+impl Renderer for HtmlRenderer {
+    fn render(&self, diag: &Diagnostic, sources: &Sources, _config: &RenderConfig, writer: &mut dyn Write) -> io::Result<()> {
+        let diag: &Diagnostic = &prepare(diag, sources);
+
+        // Write the header
+        writeln!(writer, "<div class=\"diagnostic {}\">", diag.severity)?;
+        write!(writer, "<div class=\"header\"><span class=\"severity\">{}</span>", diag.severity)?;
+        if let Some(code) = &diag.code {
+            write!(writer, "<span class=\"code\">{}</span>", EscapeFormatter(code))?;
+        }
+        writeln!(writer, "<span class=\"message\">{}</span></div>", EscapeFormatter(&diag.message))?;
+
+        // Write the snippets per file
+        for file in group_labels(diag, sources) {
+            self.render_snippet(&file, sources, writer)?;
+        }
+
+        // Write the notes, helps and suggestions
+        for (kind, text) in diag.notes.iter().map(|note| ("note", note)).chain(diag.helps.iter().map(|help| ("help", help))) {
+            writeln!(writer, "<div class=\"{kind}\"><span class=\"kind\">{kind}</span><span class=\"text\">{}</span></div>", EscapeFormatter(text))?;
+        }
+        for Suggestion { message, span, replacement, applicability } in &diag.suggestions {
+            writeln!(
+                writer,
+                "<div class=\"suggestion\" data-file=\"{}\" data-start=\"{}\" data-end=\"{}\" data-applicability=\"{applicability}\"><span \
+                 class=\"kind\">help</span><span class=\"text\">{}</span><code class=\"replacement\">{}</code></div>",
+                EscapeFormatter(&span.source),
+                span.start,
+                span.end,
+                EscapeFormatter(message),
+                EscapeFormatter(replacement)
+            )?;
+        }
+        writeln!(writer, "</div>")
+    }
+
+    #[inline]
+    fn render_summary(&self, severity: Severity, message: &str, _config: &RenderConfig, writer: &mut dyn Write) -> io::Result<()> {
+        writeln!(
+            writer,
+            "<div class=\"summary {severity}\"><span class=\"severity\">{severity}</span><span class=\"message\">{}</span></div>",
+            EscapeFormatter(message)
+        )
+    }
+}
@@ -0,0 +1,135 @@
+//  LAYOUT.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:14:43
+//  Last edited:
+//    18 Oct 2026, 20:35:39
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines how the labels of a [`Diagnostic`] are laid out over files
+//!   and lines, shared by the built-in renderers.
+//
+
+use std::sync::Arc;
+
+use crate::report::{Diagnostic, Label};
+use crate::span::Sources;
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Prepares a diagnostic for rendering.
+///
+/// This orders its labels per file (see [`Diagnostic::sort_labels()`]) and moves labels in unknown
+/// files to its notes (see [`Diagnostic::detach_unknown_labels()`]).
+///
+/// # Arguments
+/// - `diag`: The [`Diagnostic`] to prepare.
+/// - `sources`: The [`Sources`] to resolve the labels' spans in.
+///
+/// # Returns
+/// A prepared copy of the `diag`.
+pub(crate) fn prepare(diag: &Diagnostic, sources: &Sources) -> Diagnostic {
+    let mut diag: Diagnostic = diag.clone();
+    diag.sort_labels();
+    diag.detach_unknown_labels(sources);
+    diag
+}
+
+/// Groups the labels of a diagnostic per file and line.
+///
+/// # Arguments
+/// - `diag`: The [`Diagnostic`] to group the labels of.
+/// - `sources`: The [`Sources`] to resolve the labels' spans in.
+///
+/// # Returns
+/// A list of [`FileLabels`], with the file of the primary label first and the others in order of
+/// appearance. Every file has its lines in order.
+pub(crate) fn group_labels(diag: &Diagnostic, sources: &Sources) -> Vec<FileLabels> {
+    let mut files: Vec<FileLabels> = Vec::new();
+    for (label, primary) in [(&diag.primary, true)].into_iter().chain(diag.labels.iter().map(|label| (label, false))) {
+        let i: usize = match files.iter().position(|file| file.name == label.span.source) {
+            Some(i) => i,
+            None => {
+                files.push(FileLabels {
+                    name:     label.span.source.clone(),
+                    position: None,
+                    pinned:   false,
+                    offset:   label.span.start,
+                    lines:    Vec::new(),
+                });
+                files.len() - 1
+            },
+        };
+        files[i].add(label, primary, sources);
+    }
+    for file in &mut files {
+        file.lines.sort_by_key(|(line, _)| *line);
+    }
+    files
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// The part of a label that falls on a single line.
+#[derive(Clone, Debug)]
+pub(crate) struct Segment {
+    /// The 0-indexed character where the segment starts.
+    pub(crate) start:   usize,
+    /// The 0-indexed character where the segment ends (exclusive). Always after `start`.
+    pub(crate) end:     usize,
+    /// Whether this is part of the primary label.
+    pub(crate) primary: bool,
+    /// The message to show, if any. Only the last segment of a label has one.
+    pub(crate) message: Option<String>,
+}
+
+/// All labels of a diagnostic in a single file.
+#[derive(Debug)]
+pub(crate) struct FileLabels {
+    /// The name of the file.
+    pub(crate) name:     Arc<str>,
+    /// The 1-indexed line and column of the first label in the file, if the file is known.
+    pub(crate) position: Option<(usize, usize)>,
+    /// Whether `position` is that of the primary label, and should thus not be changed.
+    pub(crate) pinned:   bool,
+    /// The offset of the first label in the file, for unknown files.
+    pub(crate) offset:   usize,
+    /// The segments of the labels, per 1-indexed line.
+    pub(crate) lines:    Vec<(usize, Vec<Segment>)>,
+}
+impl FileLabels {
+    /// Adds a label to the file.
+    ///
+    /// # Arguments
+    /// - `label`: The [`Label`] to add.
+    /// - `primary`: Whether it's the primary label.
+    /// - `sources`: The [`Sources`] to resolve its span in.
+    pub(crate) fn add(&mut self, label: &Label, primary: bool, sources: &Sources) {
+        let (Some((sline, scol)), Some((eline, ecol))) = (sources.line_col(&self.name, label.span.start), sources.line_col(&self.name, label.span.end)) else {
+            return;
+        };
+        if !self.pinned && self.position.is_none_or(|pos| primary || (sline, scol) < pos) {
+            self.position = Some((sline, scol));
+            self.pinned = primary;
+        }
+
+        // Split it into segments per line
+        // NOTE: Only the first and last lines of multi-line labels are shown
+        for line in [sline, eline].into_iter().take(if sline == eline { 1 } else { 2 }) {
+            let len: usize = sources.line(&self.name, line).map(|text| text.chars().count()).unwrap_or(0);
+            let start: usize = if line == sline { scol - 1 } else { 0 };
+            let end: usize = if line == eline { ecol - 1 } else { len };
+            let seg = Segment { start, end: end.max(start + 1), primary, message: if line == eline { label.message.clone() } else { None } };
+            match self.lines.iter_mut().find(|(l, _)| *l == line) {
+                Some((_, segs)) => segs.push(seg),
+                None => self.lines.push((line, vec![seg])),
+            }
+        }
+    }
+}
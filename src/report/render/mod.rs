@@ -0,0 +1,119 @@
+//  MOD.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:12:06
+//  Last edited:
+//    18 Oct 2026, 20:35:39
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines the [`Renderer`]-trait that abstracts over how [`Diagnostic`]s
+//!   are written to the terminal, and the backends implementing it.
+//
+
+// Declare the submodules
+pub mod html;
+mod layout;
+pub mod rustc;
+
+// Imports
+use std::fmt::Debug;
+use std::io::{self, Write};
+
+pub use html::HtmlRenderer;
+pub use rustc::RustcRenderer;
+
+use super::diagnostic::ariadne_cache;
+use super::{Diagnostic, Severity};
+use crate::span::Sources;
+
+
+/***** LIBRARY *****/
+/// Defines settings shared by all [`Renderer`]s.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RenderConfig {
+    /// Whether to render with ANSI colours.
+    pub color: bool,
+    /// The maximum width (in characters) of rendered lines, if any. Renderers may ignore this.
+    pub width: Option<usize>,
+}
+impl Default for RenderConfig {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+impl RenderConfig {
+    /// Constructor for the RenderConfig.
+    ///
+    /// # Returns
+    /// A new RenderConfig that renders with colours and without width limit.
+    #[inline]
+    pub const fn new() -> Self { Self { color: true, width: None } }
+}
+
+
+
+/// Abstracts over backends that render [`Diagnostic`]s in a human-readable way.
+pub trait Renderer: Debug + Send + Sync {
+    /// Renders a single diagnostic.
+    ///
+    /// # Arguments
+    /// - `diag`: The [`Diagnostic`] to render.
+    /// - `sources`: The [`Sources`] in which to find the source texts of the spans.
+    /// - `config`: The [`RenderConfig`] with settings for rendering.
+    /// - `writer`: The [`Write`]r to render to.
+    ///
+    /// # Errors
+    /// This function errors if we failed to write to the given `writer`.
+    fn render(&self, diag: &Diagnostic, sources: &Sources, config: &RenderConfig, writer: &mut dyn Write) -> io::Result<()>;
+
+    /// Renders a line of the summary written after all diagnostics (e.g., "aborting due to 2
+    /// previous errors").
+    ///
+    /// By default, this writes it as plain text prefixed with the severity.
+    ///
+    /// # Arguments
+    /// - `severity`: The [`Severity`] of the line.
+    /// - `message`: The text of the line.
+    /// - `config`: The [`RenderConfig`] with settings for rendering.
+    /// - `writer`: The [`Write`]r to render to.
+    ///
+    /// # Errors
+    /// This function errors if we failed to write to the given `writer`.
+    #[inline]
+    fn render_summary(&self, severity: Severity, message: &str, config: &RenderConfig, writer: &mut dyn Write) -> io::Result<()> {
+        let _ = config;
+        writeln!(writer, "{severity}: {message}")
+    }
+
+    /// Renders a single diagnostic to a string.
+    ///
+    /// # Arguments
+    /// - `diag`: The [`Diagnostic`] to render.
+    /// - `sources`: The [`Sources`] in which to find the source texts of the spans.
+    /// - `config`: The [`RenderConfig`] with settings for rendering.
+    ///
+    /// # Returns
+    /// A [`String`] with the rendered diagnostic.
+    fn render_to_string(&self, diag: &Diagnostic, sources: &Sources, config: &RenderConfig) -> String {
+        let mut buf: Vec<u8> = Vec::new();
+        // NOTE: Writing to a `Vec` never fails
+        let _ = self.render(diag, sources, config, &mut buf);
+        String::from_utf8_lossy(&buf).into_owned()
+    }
+}
+
+
+
+/// Renders [`Diagnostic`]s with [ariadne](::ariadne).
+///
+/// This is the default backend. It ignores [`RenderConfig::width`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AriadneRenderer;
+impl Renderer for AriadneRenderer {
+    #[inline]
+    fn render(&self, diag: &Diagnostic, sources: &Sources, config: &RenderConfig, writer: &mut dyn Write) -> io::Result<()> {
+        layout::prepare(diag, sources).into_ariadne_report(ariadne::Config::default().with_color(config.color)).write(ariadne_cache(sources), writer)
+    }
+}
@@ -0,0 +1,403 @@
+//  RUSTC.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:12:06
+//  Last edited:
+//    18 Oct 2026, 23:33:03
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Implements a [`Renderer`] that mimics the layout of `rustc`'s
+//!   diagnostics.
+//
+
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use super::layout::{FileLabels, Segment, group_labels, prepare};
+use super::{RenderConfig, Renderer};
+use crate::report::{Diagnostic, Severity, Suggestion};
+use crate::span::Sources;
+
+
+/***** CONSTANTS *****/
+/// The ANSI code that resets the style.
+const RESET: &str = "\x1b[0m";
+/// The ANSI code for bold text.
+const BOLD: &str = "\x1b[1m";
+/// The ANSI code for bold, red text. Used for errors.
+const RED: &str = "\x1b[1;31m";
+/// The ANSI code for bold, yellow text. Used for warnings.
+const YELLOW: &str = "\x1b[1;33m";
+/// The ANSI code for bold, green text. Used for notes.
+const GREEN: &str = "\x1b[1;32m";
+/// The ANSI code for bold, cyan text. Used for help messages.
+const CYAN: &str = "\x1b[1;36m";
+/// The ANSI code for bold, blue text. Used for the gutter and secondary labels.
+const BLUE: &str = "\x1b[1;34m";
+
+/// The text that replaces parts of a line cut off because of the width limit.
+const ELLIPSIS: &str = "...";
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns the style with which a severity is rendered.
+///
+/// # Arguments
+/// - `severity`: The [`Severity`] to find the style of.
+///
+/// # Returns
+/// An ANSI code.
+#[inline]
+fn severity_style(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Note => GREEN,
+        Severity::Warning => YELLOW,
+        Severity::Error => RED,
+    }
+}
+
+/// Returns the number of digits in a number.
+///
+/// # Arguments
+/// - `n`: The number to count the digits of.
+///
+/// # Returns
+/// The number of digits when written in decimal.
+#[inline]
+fn digits(n: usize) -> usize { n.checked_ilog10().unwrap_or(0) as usize + 1 }
+
+/// Finds the line on which a suggestion can be shown as a snippet.
+///
+/// # Arguments
+/// - `sugg`: The [`Suggestion`] to find the line of.
+/// - `sources`: The [`Sources`] to resolve its span in.
+///
+/// # Returns
+/// The 1-indexed line and column where the suggestion starts, or [`None`] if its source is
+/// unknown or if it spans multiple lines.
+fn suggestion_pos(sugg: &Suggestion, sources: &Sources) -> Option<(usize, usize)> {
+    let (sline, scol): (usize, usize) = sources.line_col(&sugg.span.source, sugg.span.start)?;
+    let (eline, _): (usize, usize) = sources.line_col(&sugg.span.source, sugg.span.end)?;
+    if sline != eline || sugg.replacement.contains('\n') {
+        return None;
+    }
+    Some((sline, scol))
+}
+
+/// Decides which part of a line to show if it doesn't fit the width limit.
+///
+/// # Arguments
+/// - `len`: The length of the line, in characters.
+/// - `lo`: The 0-indexed character where the first highlight on the line starts.
+/// - `hi`: The 0-indexed character where the last highlight on the line ends (exclusive).
+/// - `avail`: The number of characters available for the line, if limited.
+///
+/// # Returns
+/// The range of characters to show.
+fn window(len: usize, lo: usize, hi: usize, avail: Option<usize>) -> (usize, usize) {
+    let Some(avail) = avail else { return (0, len) };
+    if len <= avail {
+        return (0, len);
+    }
+    let avail: usize = avail.saturating_sub(2 * ELLIPSIS.len()).max(1);
+    let left: usize = if hi <= avail { 0 } else { lo.saturating_sub(4).min(len.saturating_sub(avail)) };
+    (left, len.min(left + avail))
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// Writes text with or without ANSI styles.
+#[derive(Clone, Copy, Debug)]
+struct Painter {
+    /// Whether to write styles at all.
+    color: bool,
+}
+impl Painter {
+    /// Styles some text.
+    ///
+    /// # Arguments
+    /// - `style`: The ANSI code to style with.
+    /// - `text`: The text to style.
+    ///
+    /// # Returns
+    /// The styled text, or the text as-is if we're not colouring.
+    #[inline]
+    fn paint(&self, style: &str, text: impl Display) -> String { if self.color { format!("{style}{text}{RESET}") } else { text.to_string() } }
+}
+
+
+
+/// A line of annotations underneath a source line, built character by character.
+#[derive(Debug, Default)]
+struct Row {
+    /// The characters in the row and their styles.
+    chars: Vec<(char, &'static str)>,
+}
+impl Row {
+    /// Puts a string in the row.
+    ///
+    /// # Arguments
+    /// - `col`: The 0-indexed column to start at.
+    /// - `text`: The text to put.
+    /// - `style`: The ANSI code to style the text with.
+    fn put(&mut self, col: usize, text: &str, style: &'static str) {
+        for (i, c) in text.chars().enumerate() {
+            if self.chars.len() <= col + i {
+                self.chars.resize(col + i + 1, (' ', ""));
+            }
+            self.chars[col + i] = (c, style);
+        }
+    }
+
+    /// Renders the row.
+    ///
+    /// # Arguments
+    /// - `painter`: The [`Painter`] to style with.
+    ///
+    /// # Returns
+    /// The row as a string.
+    fn render(&self, painter: Painter) -> String {
+        let mut res: String = String::new();
+        let mut i: usize = 0;
+        while i < self.chars.len() {
+            let style: &str = self.chars[i].1;
+            let chunk: String = self.chars[i..].iter().take_while(|(_, s)| *s == style).map(|(c, _)| *c).collect();
+            i += chunk.chars().count();
+            res.push_str(&if style.is_empty() { chunk } else { painter.paint(style, chunk) });
+        }
+        res
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Renders [`Diagnostic`]s in the same layout as `rustc`.
+///
+/// For example:
+/// ```text
+/// error[E0425]: cannot find value `x` in this scope
+///  --> main.rs:2:11
+///   |
+/// 2 |     print(x);
+///   |           ^ not found in this scope
+///   |
+///   = help: did you mean `xy`?
+/// ```
+///
+/// Lines longer than [`RenderConfig::width`] are cut off around the labels.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RustcRenderer;
+
+// Helpers
+impl RustcRenderer {
+    /// Renders the source lines of a file with their labels.
+    ///
+    /// # Arguments
+    /// - `file`: The [`FileLabels`] to render.
+    /// - `sources`: The [`Sources`] to find the lines in.
+    /// - `severity`: The [`Severity`] of the diagnostic.
+    /// - `gutter`: The width of the gutter.
+    /// - `config`: The [`RenderConfig`] with settings for rendering.
+    /// - `writer`: The [`Write`]r to render to.
+    ///
+    /// # Errors
+    /// This function errors if we failed to write to the given `writer`.
+    fn render_snippet(
+        &self,
+        file: &mut FileLabels,
+        sources: &Sources,
+        severity: Severity,
+        gutter: usize,
+        config: &RenderConfig,
+        writer: &mut dyn Write,
+    ) -> io::Result<()> {
+        let painter = Painter { color: config.color };
+        let pad: String = " ".repeat(gutter);
+        let bar: String = painter.paint(BLUE, "|");
+        let avail: Option<usize> = config.width.map(|width| width.saturating_sub(gutter + 3));
+
+        writeln!(writer, "{pad} {bar}")?;
+        let mut prev: Option<usize> = None;
+        for (line, segs) in &mut file.lines {
+            // Mark skipped lines
+            if let Some(prev) = prev {
+                if *line == prev + 2 {
+                    let text: &str = sources.line(&file.name, prev + 1).unwrap_or("");
+                    writeln!(writer, "{} {bar} {text}", painter.paint(BLUE, format!("{:>gutter$}", prev + 1)))?;
+                } else if *line > prev + 2 {
+                    writeln!(writer, "{}", painter.paint(BLUE, ELLIPSIS))?;
+                }
+            }
+            prev = Some(*line);
+
+            // Write the line itself, possibly cut off
+            segs.sort_by_key(|seg| (seg.start, seg.end));
+            let text: Vec<char> = sources.line(&file.name, *line).unwrap_or("").chars().collect();
+            let lo: usize = segs.iter().map(|seg| seg.start).min().unwrap_or(0);
+            let hi: usize = segs.iter().map(|seg| seg.end).max().unwrap_or(0);
+            let (left, right): (usize, usize) = window(text.len(), lo, hi, avail);
+            let shown: String = text[left..right].iter().collect();
+            let prefix: &str = if left > 0 { ELLIPSIS } else { "" };
+            let suffix: &str = if right < text.len() { ELLIPSIS } else { "" };
+            writeln!(writer, "{} {bar} {prefix}{shown}{suffix}", painter.paint(BLUE, format!("{line:>gutter$}")))?;
+            let col = |i: usize| -> usize { i.saturating_sub(left) + prefix.len() };
+
+            // Write the underlines, with the message of the last label inline
+            let style = |seg: &Segment| -> &'static str { if seg.primary { severity_style(severity) } else { BLUE } };
+            let mut row: Row = Row::default();
+            for seg in segs.iter() {
+                let c: &str = if seg.primary { "^" } else { "-" };
+                row.put(col(seg.start), &c.repeat(seg.end - seg.start), style(seg));
+            }
+            let mut rest: Vec<&Segment> = segs.iter().filter(|seg| seg.message.is_some()).collect();
+            if segs.last().is_some_and(|seg| seg.message.is_some())
+                && let Some(last) = rest.pop()
+            {
+                row.put(col(hi) + 1, last.message.as_deref().unwrap_or(""), style(last));
+            }
+            writeln!(writer, "{pad} {bar} {}", row.render(painter))?;
+
+            // Write the other messages below it, connected with lines
+            if !rest.is_empty() {
+                let mut row: Row = Row::default();
+                for seg in &rest {
+                    row.put(col(seg.start), "|", style(seg));
+                }
+                writeln!(writer, "{pad} {bar} {}", row.render(painter))?;
+            }
+            while let Some(seg) = rest.pop() {
+                let mut row: Row = Row::default();
+                for other in &rest {
+                    row.put(col(other.start), "|", style(other));
+                }
+                row.put(col(seg.start), seg.message.as_deref().unwrap_or(""), style(seg));
+                writeln!(writer, "{pad} {bar} {}", row.render(painter))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Renders a suggestion as a separate snippet with the replacement applied.
+    ///
+    /// # Arguments
+    /// - `sugg`: The [`Suggestion`] to render.
+    /// - `(sline, scol)`: The line and column where it starts, as found by [`suggestion_pos()`].
+    /// - `sources`: The [`Sources`] to find the lines in.
+    /// - `gutter`: The width of the gutter.
+    /// - `config`: The [`RenderConfig`] with settings for rendering.
+    /// - `writer`: The [`Write`]r to render to.
+    ///
+    /// # Errors
+    /// This function errors if we failed to write to the given `writer`.
+    fn render_suggestion(
+        &self,
+        sugg: &Suggestion,
+        (sline, scol): (usize, usize),
+        sources: &Sources,
+        gutter: usize,
+        config: &RenderConfig,
+        writer: &mut dyn Write,
+    ) -> io::Result<()> {
+        let Suggestion { message, span, replacement, .. } = sugg;
+        let text: Vec<char> = sources.line(&span.source, sline).unwrap_or("").chars().collect();
+        // NOTE: Lines are given without their `\n` or `\r\n`, which the span may reach into
+        let start: usize = (scol - 1).min(text.len());
+        let end: usize = (scol - 1 + span.len()).min(text.len());
+        let repl_len: usize = replacement.chars().count();
+
+        // Decide what the line looks like and how to mark it
+        let (line, mark, len): (String, &str, usize) = if replacement.is_empty() {
+            (text.iter().collect(), "-", end - start)
+        } else {
+            let line: String = text[..start].iter().chain(replacement.chars().collect::<Vec<char>>().iter()).chain(text[end..].iter()).collect();
+            (line, if start == end { "+" } else { "~" }, repl_len)
+        };
+
+        let painter = Painter { color: config.color };
+        let pad: String = " ".repeat(gutter);
+        let bar: String = painter.paint(BLUE, "|");
+        writeln!(writer, "{}: {}", painter.paint(CYAN, "help"), painter.paint(BOLD, message))?;
+        writeln!(writer, "{pad} {bar}")?;
+        writeln!(writer, "{} {bar} {line}", painter.paint(BLUE, format!("{sline:>gutter$}")))?;
+        let mut row: Row = Row::default();
+        row.put(start, &mark.repeat(len.max(1)), CYAN);
+        writeln!(writer, "{pad} {bar} {}", row.render(painter))
+    }
+}
+
+// Renderer
+impl Renderer for RustcRenderer {
+    fn render(&self, diag: &Diagnostic, sources: &Sources, config: &RenderConfig, writer: &mut dyn Write) -> io::Result<()> {
+        let diag: &Diagnostic = &prepare(diag, sources);
+        let painter = Painter { color: config.color };
+
+        let mut files: Vec<FileLabels> = group_labels(diag, sources);
+        let max_line: usize = files.iter().flat_map(|file| file.lines.iter().map(|(line, _)| *line)).max().unwrap_or(1);
+        let max_line: usize = diag.suggestions.iter().filter_map(|sugg| suggestion_pos(sugg, sources)).map(|(line, _)| line).fold(max_line, usize::max);
+        let gutter: usize = digits(max_line);
+        let pad: String = " ".repeat(gutter);
+
+        // Write the header
+        let level: String = match &diag.code {
+            Some(code) => format!("{}[{code}]", diag.severity),
+            None => diag.severity.to_string(),
+        };
+        writeln!(writer, "{}{}", painter.paint(severity_style(diag.severity), level), painter.paint(BOLD, format!(": {}", diag.message)))?;
+
+        // Write the snippets per file
+        for (i, file) in files.iter_mut().enumerate() {
+            let arrow: &str = if i == 0 { "-->" } else { ":::" };
+            if i > 0 {
+                writeln!(writer, "{pad} {}", painter.paint(BLUE, "|"))?;
+            }
+            match file.position {
+                Some((line, col)) => writeln!(writer, "{pad}{} {}:{line}:{col}", painter.paint(BLUE, arrow), file.name)?,
+                None => {
+                    writeln!(writer, "{pad}{} {}:{}", painter.paint(BLUE, arrow), file.name, file.offset)?;
+                    continue;
+                },
+            }
+            self.render_snippet(file, sources, diag.severity, gutter, config, writer)?;
+        }
+
+        // Write the notes, helps and suggestions that can't be shown as snippets
+        let mut inline: Vec<(&str, String)> = Vec::with_capacity(diag.notes.len() + diag.helps.len());
+        inline.extend(diag.notes.iter().map(|note| ("note", note.clone())));
+        inline.extend(diag.helps.iter().map(|help| ("help", help.clone())));
+        let mut snippets: Vec<(&Suggestion, (usize, usize))> = Vec::with_capacity(diag.suggestions.len());
+        for sugg in &diag.suggestions {
+            match suggestion_pos(sugg, sources) {
+                Some(pos) => snippets.push((sugg, pos)),
+                None => inline.push(("help", format!("{}: `{}`", sugg.message, sugg.replacement))),
+            }
+        }
+        if !inline.is_empty() {
+            writeln!(writer, "{pad} {}", painter.paint(BLUE, "|"))?;
+        }
+        for (kind, text) in inline {
+            writeln!(writer, "{pad} {} {}: {text}", painter.paint(BLUE, "="), painter.paint(BOLD, kind))?;
+        }
+        for (sugg, pos) in snippets {
+            self.render_suggestion(sugg, pos, sources, gutter, config, writer)?;
+        }
+        writeln!(writer)
+    }
+
+    #[inline]
+    fn render_summary(&self, severity: Severity, message: &str, config: &RenderConfig, writer: &mut dyn Write) -> io::Result<()> {
+        let painter = Painter { color: config.color };
+        writeln!(writer, "{}{}", painter.paint(severity_style(severity), severity), painter.paint(BOLD, format!(": {message}")))
+    }
+}
@@ -0,0 +1,529 @@
+//  SARIF.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:00:57
+//  Last edited:
+//    18 Oct 2026, 23:34:01
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines an export of [`Report`](super::Report)s to
+//!   [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html),
+//!   the format ingested by most code-scanning dashboards.
+//
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Code, Diagnostic, Diagnostics, Label, Registry, Severity, Suggestion};
+use crate::span::Sources;
+
+
+/***** CONSTANTS *****/
+/// The URI of the SARIF 2.1.0 schema.
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// The SARIF version we emit.
+pub const SARIF_VERSION: &str = "2.1.0";
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Converts the name of a source to the URI of its artifact.
+///
+/// Backslashes are taken to be path separators, and anything else not allowed in the path of a
+/// URI (e.g., spaces, `#` or non-ASCII characters) is percent-encoded.
+///
+/// # Arguments
+/// - `name`: The name of the source.
+///
+/// # Returns
+/// The URI (reference) of the source.
+fn uri(name: &str) -> String {
+    let mut res: String = String::with_capacity(name.len());
+    for b in name.bytes() {
+        match b {
+            b'\\' => res.push('/'),
+            b'a'..=b'z'
+            | b'A'..=b'Z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~'
+            | b'!'
+            | b'$'
+            | b'&'
+            | b'\''
+            | b'('
+            | b')'
+            | b'*'
+            | b'+'
+            | b','
+            | b';'
+            | b'='
+            | b':'
+            | b'@'
+            | b'/' => res.push(b as char),
+            b => res.push_str(&format!("%{b:02X}")),
+        }
+    }
+    res
+}
+
+/// Resolves a [`Label`] to a [`SarifLocation`].
+///
+/// # Arguments
+/// - `label`: The [`Label`] to resolve.
+/// - `id`: The identifier of the location, if it's a related location.
+/// - `artifacts`: A map of source names to their index in the run's artifacts.
+/// - `sources`: The [`Sources`] in which to find the source text of the span.
+///
+/// # Returns
+/// A new [`SarifLocation`].
+fn resolve_label(label: &Label, id: Option<usize>, artifacts: &HashMap<&str, usize>, sources: &Sources) -> SarifLocation {
+    let span = &label.span;
+    let start: Option<(usize, usize)> = sources.line_col(&span.source, span.start);
+    let end: Option<(usize, usize)> = sources.line_col(&span.source, span.end);
+    SarifLocation {
+        id,
+        physical_location: SarifPhysicalLocation {
+            artifact_location: SarifArtifactLocation { uri: uri(&span.source), index: artifacts.get(&*span.source).copied() },
+            region:            SarifRegion {
+                start_line:   start.map(|(line, _)| line),
+                start_column: start.map(|(_, col)| col),
+                end_line:     end.map(|(line, _)| line),
+                end_column:   end.map(|(_, col)| col),
+                char_offset:  span.start,
+                char_length:  span.len(),
+            },
+        },
+        message: label.message.as_ref().map(SarifMessage::new),
+    }
+}
+
+
+
+
+/// Converts a [`Suggestion`] to a [`SarifFix`].
+///
+/// # Arguments
+/// - `sugg`: The [`Suggestion`] to convert.
+///
+/// # Returns
+/// A new [`SarifFix`] that replaces a single region.
+fn suggestion_fix(sugg: &Suggestion) -> SarifFix {
+    SarifFix {
+        description:      SarifMessage::new(&sugg.message),
+        artifact_changes: vec![SarifArtifactChange {
+            artifact_location: SarifArtifactLocation { uri: uri(&sugg.span.source), index: None },
+            replacements:      vec![SarifReplacement {
+                deleted_region:   SarifRegion {
+                    start_line:   None,
+                    start_column: None,
+                    end_line:     None,
+                    end_column:   None,
+                    char_offset:  sugg.span.start,
+                    char_length:  sugg.span.len(),
+                },
+                inserted_content: SarifMessage::new(&sugg.replacement),
+            }],
+        }],
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// The toplevel SARIF log.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SarifLog {
+    /// The schema of the log. Always [`SARIF_SCHEMA`].
+    #[serde(rename = "$schema")]
+    pub schema:  String,
+    /// The version of SARIF. Always [`SARIF_VERSION`].
+    pub version: String,
+    /// The runs of tools in this log. We always emit exactly one.
+    pub runs:    Vec<SarifRun>,
+}
+impl SarifLog {
+    /// Constructor for the SarifLog that converts a batch of [`Diagnostic`]s.
+    ///
+    /// Every diagnostic with a code is attributed to a rule with that code as ID. Secondary labels
+    /// become related locations, and notes and help messages are stored in the result's property
+    /// bag.
+    ///
+    /// # Arguments
+    /// - `driver`: A [`SarifDriver`] describing the tool that produced the diagnostics. Any rules
+    ///   already in it are kept, and rules for unknown codes are added.
+    /// - `diags`: The [`Diagnostic`]s to convert.
+    /// - `sources`: The [`Sources`] in which to find the source texts of the spans.
+    ///
+    /// # Returns
+    /// A new SarifLog with a single run.
+    pub fn new<'d>(mut driver: SarifDriver, diags: impl IntoIterator<Item = &'d Diagnostic>, sources: &Sources) -> Self {
+        let diags: Vec<&Diagnostic> = diags.into_iter().collect();
+
+        // Collect the artifacts (i.e., source files) mentioned
+        let mut artifacts: Vec<SarifArtifact> = Vec::new();
+        let mut artifact_idxs: HashMap<&str, usize> = HashMap::new();
+        for label in diags.iter().flat_map(|diag| std::iter::once(&diag.primary).chain(diag.labels.iter())) {
+            if !artifact_idxs.contains_key(&*label.span.source) {
+                artifact_idxs.insert(&label.span.source, artifacts.len());
+                artifacts.push(SarifArtifact {
+                    location: SarifArtifactLocation { uri: uri(&label.span.source), index: None },
+                    length:   sources.get(&label.span.source).map(|text| text.len()),
+                });
+            }
+        }
+
+        // Collect the rules
+        let mut rule_idxs: HashMap<String, usize> = driver.rules.iter().enumerate().map(|(i, rule)| (rule.id.clone(), i)).collect();
+        for diag in &diags {
+            if let Some(code) = &diag.code
+                && !rule_idxs.contains_key(code)
+            {
+                rule_idxs.insert(code.clone(), driver.rules.len());
+                driver.rules.push(SarifRule { id: code.clone(), short_description: None, full_description: None, help: None });
+            }
+        }
+
+        // Then generate the results
+        let results: Vec<SarifResult> = diags
+            .iter()
+            .map(|diag| SarifResult {
+                rule_id:           diag.code.clone(),
+                rule_index:        diag.code.as_ref().and_then(|code| rule_idxs.get(code).copied()),
+                level:             match diag.severity {
+                    Severity::Error => "error".into(),
+                    Severity::Warning => "warning".into(),
+                    Severity::Note => "note".into(),
+                },
+                message:           SarifMessage::new(&diag.message),
+                locations:         vec![resolve_label(&diag.primary, None, &artifact_idxs, sources)],
+                related_locations: diag.labels.iter().enumerate().map(|(i, label)| resolve_label(label, Some(i), &artifact_idxs, sources)).collect(),
+                properties:        if diag.notes.is_empty() && diag.helps.is_empty() {
+                    None
+                } else {
+                    Some(SarifProperties { notes: diag.notes.clone(), helps: diag.helps.clone() })
+                },
+                fixes:             diag.suggestions.iter().map(suggestion_fix).collect(),
+            })
+            .collect();
+
+        Self {
+            schema:  SARIF_SCHEMA.into(),
+            version: SARIF_VERSION.into(),
+            runs:    vec![SarifRun { tool: SarifTool { driver }, column_kind: "unicodeCodePoints".into(), artifacts, results }],
+        }
+    }
+}
+
+/// A single run of a tool.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRun {
+    /// The tool that produced the results.
+    pub tool:        SarifTool,
+    /// How columns are counted. We always count in characters, i.e., `unicodeCodePoints`.
+    pub column_kind: String,
+    /// The files analysed.
+    pub artifacts:   Vec<SarifArtifact>,
+    /// The diagnostics produced.
+    pub results:     Vec<SarifResult>,
+}
+
+/// Describes the tool that produced the results.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SarifTool {
+    /// The main component of the tool.
+    pub driver: SarifDriver,
+}
+
+/// Describes the main component of the tool that produced the results.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    /// The name of the tool.
+    pub name:            String,
+    /// The version of the tool, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version:         Option<String>,
+    /// A URI where more information about the tool can be found, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub information_uri: Option<String>,
+    /// The rules (i.e., diagnostic codes) the tool knows of.
+    #[serde(default)]
+    pub rules:           Vec<SarifRule>,
+}
+impl SarifDriver {
+    /// Constructor for the SarifDriver.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the tool.
+    ///
+    /// # Returns
+    /// A new SarifDriver without version, information URI or rules.
+    #[inline]
+    pub fn new(name: impl Into<String>) -> Self { Self { name: name.into(), version: None, information_uri: None, rules: Vec::new() } }
+
+    /// Sets the version of the tool.
+    ///
+    /// # Arguments
+    /// - `version`: The version of the tool.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Sets the URI where more information about the tool can be found.
+    ///
+    /// # Arguments
+    /// - `uri`: The URI.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_information_uri(mut self, uri: impl Into<String>) -> Self {
+        self.information_uri = Some(uri.into());
+        self
+    }
+
+    /// Adds a rule for every code in a [`Registry`].
+    ///
+    /// Codes for which a rule is already present are skipped.
+    ///
+    /// # Arguments
+    /// - `registry`: The [`Registry`] with the codes to describe.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    pub fn with_registry(mut self, registry: &Registry) -> Self {
+        for code in registry.iter() {
+            if !self.rules.iter().any(|rule| rule.id == code.name) {
+                self.rules.push(code.into());
+            }
+        }
+        self
+    }
+}
+
+/// Describes a rule, i.e., a kind of diagnostic identified by a code.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    /// The code of the rule.
+    pub id:                String,
+    /// A short, single-line description of the rule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_description: Option<SarifMessage>,
+    /// A longer description of the rule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_description:  Option<SarifMessage>,
+    /// Guidance on how to fix diagnostics of this rule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help:              Option<SarifMessage>,
+}
+impl From<&Code> for SarifRule {
+    #[inline]
+    fn from(value: &Code) -> Self {
+        Self {
+            id:                value.name.into(),
+            short_description: Some(SarifMessage::new(value.title)),
+            full_description:  Some(SarifMessage { text: value.explanation.into(), markdown: Some(value.explanation.into()) }),
+            help:              None,
+        }
+    }
+}
+
+/// Describes a file that was analysed.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SarifArtifact {
+    /// Where the file is found.
+    pub location: SarifArtifactLocation,
+    /// The length of the file in bytes, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length:   Option<usize>,
+}
+
+/// Describes a single diagnostic.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    /// The code of the diagnostic, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_id:           Option<String>,
+    /// The index of the rule in the driver's rules, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_index:        Option<usize>,
+    /// The severity of the diagnostic (`error`, `warning` or `note`).
+    pub level:             String,
+    /// The main message of the diagnostic.
+    pub message:           SarifMessage,
+    /// The primary location of the diagnostic.
+    pub locations:         Vec<SarifLocation>,
+    /// Any secondary locations of the diagnostic.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related_locations: Vec<SarifLocation>,
+    /// Any notes and help messages of the diagnostic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties:        Option<SarifProperties>,
+    /// Any suggested edits that fix the diagnostic.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixes:             Vec<SarifFix>,
+}
+
+/// A suggested edit that fixes a diagnostic.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifFix {
+    /// A message explaining the fix.
+    pub description:      SarifMessage,
+    /// The changes to make per file.
+    pub artifact_changes: Vec<SarifArtifactChange>,
+}
+
+/// The changes to make to a single file.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifArtifactChange {
+    /// The file to change.
+    pub artifact_location: SarifArtifactLocation,
+    /// The regions in that file to replace.
+    pub replacements:      Vec<SarifReplacement>,
+}
+
+/// A single replacement of a region of text.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifReplacement {
+    /// The region to remove.
+    pub deleted_region:   SarifRegion,
+    /// The text to insert in its place. Only the `text` is used.
+    pub inserted_content: SarifMessage,
+}
+
+/// A property bag storing the notes and help messages of a diagnostic.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SarifProperties {
+    /// The notes of the diagnostic.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    /// The help messages of the diagnostic.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub helps: Vec<String>,
+}
+
+/// A message in SARIF.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SarifMessage {
+    /// The plain text of the message.
+    pub text:     String,
+    /// A markdown version of the message, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markdown: Option<String>,
+}
+impl SarifMessage {
+    /// Constructor for a SarifMessage with only plain text.
+    ///
+    /// # Arguments
+    /// - `text`: The text of the message.
+    ///
+    /// # Returns
+    /// A new SarifMessage.
+    #[inline]
+    pub fn new(text: impl Into<String>) -> Self { Self { text: text.into(), markdown: None } }
+}
+
+/// A location annotated by a diagnostic.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    /// An identifier of the location, used for related locations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id:                Option<usize>,
+    /// Where the location is physically found.
+    pub physical_location: SarifPhysicalLocation,
+    /// The message shown at the location, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message:           Option<SarifMessage>,
+}
+
+/// A location in a particular file.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    /// The file to which the location refers.
+    pub artifact_location: SarifArtifactLocation,
+    /// The region in that file.
+    pub region:            SarifRegion,
+}
+
+/// A reference to a file.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SarifArtifactLocation {
+    /// The (relative) URI of the file.
+    pub uri:   String,
+    /// The index of the file in the run's artifacts, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+}
+
+/// A region of text in a file.
+///
+/// Lines and columns are only given if the source text of the file is known.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    /// The 1-indexed line the region starts on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_line:   Option<usize>,
+    /// The 1-indexed column the region starts at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_column: Option<usize>,
+    /// The 1-indexed line the region ends on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line:     Option<usize>,
+    /// The 1-indexed column the region ends at (exclusive).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_column:   Option<usize>,
+    /// The 0-indexed character offset the region starts at.
+    pub char_offset:  usize,
+    /// The number of characters in the region.
+    pub char_length:  usize,
+}
+
+
+
+// Rendering
+impl Diagnostics {
+    /// Renders all collected diagnostics as a SARIF log to the given writer.
+    ///
+    /// # Arguments
+    /// - `driver`: A [`SarifDriver`] describing the tool that produced the diagnostics.
+    /// - `sources`: The [`Sources`] in which to find the source texts of the spans.
+    /// - `writer`: The [`Write`]r to render to.
+    ///
+    /// # Errors
+    /// This function errors if we failed to write to the given `writer`.
+    pub fn render_sarif(&self, driver: SarifDriver, sources: &Sources, writer: impl Write) -> io::Result<()> {
+        let driver: SarifDriver = match self.registry {
+            Some(registry) => driver.with_registry(registry),
+            None => driver,
+        };
+        serde_json::to_writer_pretty(writer, &SarifLog::new(driver, self.localized().iter().map(|diag| &**diag), sources))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,388 @@
+//  SINK.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 19:58:17
+//  Last edited:
+//    18 Oct 2026, 21:52:37
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines [`Diagnostics`], a sink that collects all reports emitted
+//!   during a compiler run before rendering them at once.
+//
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use super::render::{AriadneRenderer, RenderConfig, Renderer};
+use super::{Diagnostic, Level, LintConfig, Locales, Registry, Report, Severity};
+use crate::span::Sources;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Pluralizes a noun if necessary.
+///
+/// # Arguments
+/// - `n`: The number of things.
+/// - `noun`: The noun to pluralize.
+///
+/// # Returns
+/// The noun with an 's' appended if `n` is not 1.
+#[inline]
+fn plural(n: usize, noun: &str) -> String { if n == 1 { noun.into() } else { format!("{noun}s") } }
+
+
+
+
+
+/***** LIBRARY *****/
+/// Collects [`Report`]s emitted during a compiler run, and renders them at once.
+///
+/// In particular, the collector:
+/// - keeps track of how many diagnostics of every [`Severity`] have been emitted;
+/// - applies the [`LintConfig`], silencing or changing the severity of diagnostics;
+/// - ignores diagnostics that are identical to one already emitted;
+/// - renders diagnostics in order of the file and position they occur at, with their labels
+///   grouped per file;
+/// - resolves the messages of localized diagnostics in the [`Locales`], if any; and
+/// - optionally stops collecting errors after a maximum, summarizing the rest.
+#[derive(Clone, Debug)]
+pub struct Diagnostics {
+    /// The diagnostics collected so far, in order of emission.
+    diags:               Vec<Diagnostic>,
+    /// The diagnostics we've already seen, for deduplication.
+    seen:                HashSet<Diagnostic>,
+    /// The number of notes, warnings and errors emitted (in that order).
+    counts:              [usize; 3],
+    /// The configuration deciding the levels of lints.
+    lints:               LintConfig,
+    /// The maximum number of errors to collect, if any.
+    max_errors:          Option<usize>,
+    /// The number of errors emitted beyond `max_errors`.
+    suppressed:          usize,
+    /// The backend used to render the diagnostics.
+    pub(crate) renderer: Arc<dyn Renderer>,
+    /// The settings for rendering.
+    pub(crate) config:   RenderConfig,
+    /// The registry of codes to explain, if any.
+    pub(crate) registry: Option<&'static Registry>,
+    /// The locales to resolve message IDs in, if any.
+    pub(crate) locales:  Option<Arc<Locales>>,
+}
+
+// Constructors
+impl Default for Diagnostics {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+impl Diagnostics {
+    /// Constructor for the Diagnostics that initializes it without any diagnostics.
+    ///
+    /// # Returns
+    /// A new Diagnostics that collects an unlimited amount of errors and renders with colours.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            diags:      Vec::new(),
+            seen:       HashSet::new(),
+            counts:     [0; 3],
+            lints:      LintConfig::new(),
+            max_errors: None,
+            suppressed: 0,
+            renderer:   Arc::new(AriadneRenderer),
+            config:     RenderConfig::new(),
+            registry:   None,
+            locales:    None,
+        }
+    }
+
+    /// Sets the maximum number of errors collected.
+    ///
+    /// Any errors emitted beyond this are still counted, but not rendered. Instead, a "too many
+    /// errors" summary is shown.
+    ///
+    /// # Arguments
+    /// - `max`: The maximum number of errors to collect.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_max_errors(mut self, max: usize) -> Self {
+        self.max_errors = Some(max);
+        self
+    }
+
+    /// Sets the configuration deciding the levels of lints.
+    ///
+    /// # Arguments
+    /// - `lints`: The [`LintConfig`] to apply to emitted diagnostics.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_lints(mut self, lints: LintConfig) -> Self {
+        self.lints = lints;
+        self
+    }
+
+    /// Sets whether the diagnostics are rendered with colours.
+    ///
+    /// # Arguments
+    /// - `color`: Whether to render with colours.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.config.color = color;
+        self
+    }
+
+    /// Sets the maximum width of rendered lines.
+    ///
+    /// Note that not every [`Renderer`] supports this.
+    ///
+    /// # Arguments
+    /// - `width`: The maximum width, in characters.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.config.width = Some(width);
+        self
+    }
+
+    /// Sets the backend used to render the diagnostics.
+    ///
+    /// By default, diagnostics are rendered with the [`AriadneRenderer`].
+    ///
+    /// # Arguments
+    /// - `renderer`: The [`Renderer`] to use.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_renderer(mut self, renderer: impl 'static + Renderer) -> Self {
+        self.renderer = Arc::new(renderer);
+        self
+    }
+
+    /// Sets the registry of codes used by the diagnostics.
+    ///
+    /// This is used by machine-readable outputs to include the explanations of codes.
+    ///
+    /// # Arguments
+    /// - `registry`: The [`Registry`] of codes.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_registry(mut self, registry: &'static Registry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Sets the locales in which the messages of localized diagnostics are resolved.
+    ///
+    /// Without locales, the message IDs of such diagnostics are rendered as-is.
+    ///
+    /// # Arguments
+    /// - `locales`: The [`Locales`] to resolve message IDs in.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_locales(mut self, locales: impl Into<Arc<Locales>>) -> Self {
+        self.locales = Some(locales.into());
+        self
+    }
+
+    /// Returns the registry of codes used by the diagnostics, if any.
+    #[inline]
+    pub fn registry(&self) -> Option<&'static Registry> { self.registry }
+}
+
+// Collection
+impl Diagnostics {
+    /// Adds a new report to the Diagnostics.
+    ///
+    /// # Arguments
+    /// - `report`: Some [`Report`] to collect.
+    ///
+    /// # Returns
+    /// True if the report was collected, or false if it was a duplicate, if it was allowed by the
+    /// lint configuration or if it was an error emitted beyond the maximum.
+    pub fn emit(&mut self, report: impl Report) -> bool {
+        let Some(mut diag) = self.apply_lints(report.to_diagnostic()) else { return false };
+        diag.sort_labels();
+        if !self.seen.insert(diag.clone()) {
+            return false;
+        }
+
+        // Count it, and see if it fits
+        let severity: Severity = diag.severity;
+        self.counts[severity as usize] += 1;
+        if severity == Severity::Error && self.max_errors.is_some_and(|max| self.counts[severity as usize] > max) {
+            self.suppressed += 1;
+            return false;
+        }
+        self.diags.push(diag);
+        true
+    }
+
+    /// Applies the lint configuration to a diagnostic.
+    ///
+    /// # Arguments
+    /// - `diag`: The [`Diagnostic`] to apply the configuration to.
+    ///
+    /// # Returns
+    /// The diagnostic with its severity updated and a note explaining it, or [`None`] if it's
+    /// silenced.
+    fn apply_lints(&self, mut diag: Diagnostic) -> Option<Diagnostic> {
+        // Find the level of the lint
+        if let Some(lint) = diag.lint {
+            let level: Level = self.lints.level(lint);
+            diag.severity = match level {
+                Level::Allow => return None,
+                Level::Warn => Severity::Warning,
+                Level::Deny => Severity::Error,
+            };
+            diag.notes.push(if self.lints.is_configured(lint) {
+                format!("`{} {}` set by the lint configuration", level.flag(), lint.name)
+            } else {
+                format!("`{} {}` on by default", level.flag(), lint.name)
+            });
+        }
+
+        // Then apply the level of all warnings
+        if diag.severity == Severity::Warning {
+            match self.lints.warnings() {
+                Some(Level::Allow) => return None,
+                Some(Level::Deny) => {
+                    diag.severity = Severity::Error;
+                    if let Some(lint) = diag.lint {
+                        // NOTE: Replaces the note that we pushed above
+                        diag.notes.pop();
+                        diag.notes.push(format!("`-D {}` implied by `-D warnings`", lint.name));
+                    }
+                },
+                Some(Level::Warn) | None => {},
+            }
+        }
+        Some(diag)
+    }
+
+    /// Returns the number of diagnostics emitted with the given severity.
+    ///
+    /// Note that this includes errors emitted beyond the maximum, but not duplicates.
+    ///
+    /// # Arguments
+    /// - `severity`: The [`Severity`] to count.
+    ///
+    /// # Returns
+    /// The number of diagnostics of that severity.
+    #[inline]
+    pub fn count(&self, severity: Severity) -> usize { self.counts[severity as usize] }
+
+    /// Returns the number of errors emitted beyond the maximum.
+    #[inline]
+    pub fn suppressed(&self) -> usize { self.suppressed }
+
+    /// Returns whether any errors have been emitted.
+    #[inline]
+    pub fn has_errors(&self) -> bool { self.count(Severity::Error) > 0 }
+
+    /// Returns the collected diagnostics, sorted by the file and position they occur at.
+    ///
+    /// Diagnostics at the same position are kept in order of emission.
+    ///
+    /// # Returns
+    /// A list of references to the collected [`Diagnostic`]s.
+    pub fn sorted(&self) -> Vec<&Diagnostic> {
+        let mut diags: Vec<&Diagnostic> = self.diags.iter().collect();
+        diags.sort_by(|lhs, rhs| lhs.primary.span.cmp(&rhs.primary.span));
+        diags
+    }
+
+    /// Returns the collected diagnostics ready for rendering.
+    ///
+    /// This is like [`Diagnostics::sorted()`], except that the messages of localized diagnostics
+    /// are resolved in the [`Locales`] (see [`Diagnostics::with_locales()`]).
+    ///
+    /// # Returns
+    /// A list of the collected [`Diagnostic`]s, borrowed if they didn't need resolving.
+    pub fn localized(&self) -> Vec<Cow<'_, Diagnostic>> {
+        self.sorted()
+            .into_iter()
+            .map(|diag| match (&self.locales, &diag.args) {
+                (Some(locales), Some(_)) => Cow::Owned(diag.localize(locales)),
+                _ => Cow::Borrowed(diag),
+            })
+            .collect()
+    }
+
+    /// Returns the number of diagnostics collected.
+    #[inline]
+    pub fn len(&self) -> usize { self.diags.len() }
+
+    /// Returns whether no diagnostics have been collected.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.diags.is_empty() }
+}
+impl<R: Report> Extend<R> for Diagnostics {
+    #[inline]
+    fn extend<T: IntoIterator<Item = R>>(&mut self, iter: T) {
+        for report in iter {
+            self.emit(report);
+        }
+    }
+}
+
+// Rendering
+impl Diagnostics {
+    /// Renders all collected diagnostics to the given writer, followed by a summary.
+    ///
+    /// The diagnostics are rendered with the backend set by [`Diagnostics::with_renderer()`].
+    ///
+    /// # Arguments
+    /// - `sources`: The [`Sources`] in which to find the source texts of the spans.
+    /// - `writer`: The [`Write`]r to render to.
+    ///
+    /// # Errors
+    /// This function errors if we failed to write to the given `writer`.
+    pub fn render(&self, sources: &Sources, mut writer: impl Write) -> io::Result<()> {
+        for diag in self.localized() {
+            self.renderer.render(&diag, sources, &self.config, &mut writer)?;
+        }
+        self.render_summary(writer)
+    }
+
+    /// Renders a summary of the emitted diagnostics.
+    ///
+    /// This is automatically done by [`Diagnostics::render()`].
+    ///
+    /// # Arguments
+    /// - `writer`: The [`Write`]r to render to.
+    ///
+    /// # Errors
+    /// This function errors if we failed to write to the given `writer`.
+    pub fn render_summary(&self, mut writer: impl Write) -> io::Result<()> {
+        let (errors, warnings): (usize, usize) = (self.count(Severity::Error), self.count(Severity::Warning));
+        if self.suppressed > 0 {
+            let message: String = format!("too many errors emitted ({} more {} not shown)", self.suppressed, if self.suppressed == 1 { "was" } else { "were" });
+            self.renderer.render_summary(Severity::Error, &message, &self.config, &mut writer)?;
+        }
+        let (severity, message): (Severity, String) = match (errors, warnings) {
+            (0, 0) => return Ok(()),
+            (0, w) => (Severity::Warning, format!("{w} {} emitted", plural(w, "warning"))),
+            (e, 0) => (Severity::Error, format!("aborting due to {e} previous {}", plural(e, "error"))),
+            (e, w) => (Severity::Error, format!("aborting due to {e} previous {}; {w} {} emitted", plural(e, "error"), plural(w, "warning"))),
+        };
+        self.renderer.render_summary(severity, &message, &self.config, &mut writer)
+    }
+}
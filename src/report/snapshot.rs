@@ -0,0 +1,423 @@
+//  SNAPSHOT.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:13:21
+//  Last edited:
+//    18 Oct 2026, 22:10:41
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines a [`Harness`] for snapshot-testing the diagnostics produced
+//!   for a directory of input files, similar to `compiletest`.
+//
+
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::{env, error, fs, io};
+
+use super::render::{AriadneRenderer, Renderer};
+use super::{Diagnostic, Diagnostics, Locales, Severity};
+use crate::diff::DiffFormatter;
+use crate::span::Sources;
+
+
+/***** CONSTANTS *****/
+/// The marker that starts an inline annotation.
+pub const ANNOTATION: &str = "//~";
+
+/// The environment variable that enables bless mode by default.
+pub const BLESS_VAR: &str = "BLESS";
+
+
+
+
+
+/***** ERRORS *****/
+/// Defines the ways in which a single input file can fail its test.
+#[derive(Debug)]
+pub enum Failure {
+    /// The rendered diagnostics were not the same as the golden file.
+    Mismatch { path: PathBuf, expected: String, actual: String },
+    /// A diagnostic was emitted without a matching annotation.
+    Unexpected { path: PathBuf, line: usize, severity: Severity, message: String },
+    /// An annotation did not match any diagnostic.
+    Missing { path: PathBuf, line: usize, kind: Kind, message: String },
+}
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Mismatch { path, expected, actual } => {
+                writeln!(f, "{}: rendered diagnostics differ from golden file (run with {BLESS_VAR}=1 to update):", path.display())?;
+                write!(f, "{}", DiffFormatter { expected, actual })
+            },
+            Self::Unexpected { path, line, severity, message } => write!(f, "{}:{line}: unexpected {severity}: {message:?}", path.display()),
+            Self::Missing { path, line, kind, message } => write!(f, "{}:{line}: expected {kind} not found: {message:?}", path.display()),
+        }
+    }
+}
+impl error::Error for Failure {}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Finds all files with a particular extension in a directory, recursively.
+///
+/// # Arguments
+/// - `dir`: The directory to search.
+/// - `ext`: The extension of the files to find.
+/// - `files`: The list to add the found files to.
+///
+/// # Errors
+/// This function errors if we failed to read a directory.
+fn find_files(dir: &Path, ext: &str, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path: PathBuf = entry?.path();
+        if path.is_dir() {
+            find_files(&path, ext, files)?;
+        } else if path.extension().is_some_and(|e| e == ext) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Parses the inline annotations in a source text.
+///
+/// # Arguments
+/// - `text`: The source text to parse.
+///
+/// # Returns
+/// A list of [`Annotation`]s found.
+fn parse_annotations(text: &str) -> Vec<Annotation> {
+    let mut res: Vec<Annotation> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let Some((_, rest)) = line.split_once(ANNOTATION) else { continue };
+
+        // Find the line it refers to
+        let (target, rest): (usize, &str) = if let Some(rest) = rest.strip_prefix('|') {
+            (res.last().map(|annot| annot.line).unwrap_or(i + 1), rest)
+        } else {
+            let carets: usize = rest.chars().take_while(|c| *c == '^').count();
+            ((i + 1).saturating_sub(carets).max(1), &rest[carets..])
+        };
+
+        // Parse the kind and message
+        let rest: &str = rest.trim();
+        let (kind, message): (&str, &str) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let kind: Kind = match kind {
+            "ERROR" => Kind::Error,
+            "WARN" | "WARNING" => Kind::Warning,
+            "NOTE" => Kind::Note,
+            "HELP" => Kind::Help,
+            _ => continue,
+        };
+        res.push(Annotation { line: target, kind, message: message.trim().into() });
+    }
+    res
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// Defines the kinds of inline annotations.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kind {
+    /// `//~ ERROR`, matching an error.
+    Error,
+    /// `//~ WARN`, matching a warning.
+    Warning,
+    /// `//~ NOTE`, matching a note or a note attached to another diagnostic.
+    Note,
+    /// `//~ HELP`, matching a help message attached to a diagnostic.
+    Help,
+}
+impl Kind {
+    /// Returns whether a diagnostic satisfies an annotation of this kind.
+    ///
+    /// # Arguments
+    /// - `diag`: The [`Diagnostic`] to match.
+    /// - `message`: Some text that must occur in the message.
+    ///
+    /// # Returns
+    /// True if it does, or false otherwise.
+    fn matches(&self, diag: &Diagnostic, message: &str) -> bool {
+        match self {
+            Self::Error => diag.severity == Severity::Error && diag.message.contains(message),
+            Self::Warning => diag.severity == Severity::Warning && diag.message.contains(message),
+            Self::Note => (diag.severity == Severity::Note && diag.message.contains(message)) || diag.notes.iter().any(|note| note.contains(message)),
+            Self::Help => diag.helps.iter().any(|help| help.contains(message)) || diag.suggestions.iter().any(|sugg| sugg.message.contains(message)),
+        }
+    }
+}
+impl Display for Kind {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Error => write!(f, "ERROR"),
+            Self::Warning => write!(f, "WARN"),
+            Self::Note => write!(f, "NOTE"),
+            Self::Help => write!(f, "HELP"),
+        }
+    }
+}
+
+/// An inline annotation in a source text.
+#[derive(Clone, Debug)]
+struct Annotation {
+    /// The 1-indexed line it refers to.
+    line:    usize,
+    /// What kind of diagnostic it expects.
+    kind:    Kind,
+    /// Some text that must occur in the diagnostic's message.
+    message: String,
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Runs a compiler over a directory of input files and checks the diagnostics it emits.
+///
+/// For every input file, the harness:
+/// 1. renders all diagnostics without colours, using the path relative to the directory as the
+///    name of the source (so output doesn't depend on where the tests are run);
+/// 2. compares the result to a golden `.stderr` file next to the input, or writes it in bless
+///    mode (an absent golden file means no diagnostics are expected); and
+/// 3. checks that every error and warning has an inline `//~ ERROR <message>` annotation on its
+///    primary line, and vice versa. Use `//~^` to refer to the line above (one `^` per line) and
+///    `//~|` to refer to the same line as the previous annotation.
+///
+/// # Example
+/// ```no_run
+/// use ast_toolkit_2::report::{Diagnostic, Harness, Span};
+///
+/// Harness::new("tests/ui", "lang").run(|sources, name, diags| {
+///     if sources.get(name).is_some_and(|text| text.trim().is_empty()) {
+///         diags.emit(Diagnostic::error("Empty program", Span::empty(name.clone(), 0)));
+///     }
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub struct Harness {
+    /// The directory with the input files.
+    dir:         PathBuf,
+    /// The extension of input files.
+    ext:         String,
+    /// Whether to update the golden files instead of comparing against them.
+    bless:       bool,
+    /// Whether to check the inline annotations.
+    annotations: bool,
+    /// The backend used to render the diagnostics.
+    renderer:    Arc<dyn Renderer>,
+    /// The locales to resolve message IDs in, if any.
+    locales:     Option<Arc<Locales>>,
+}
+
+// Constructors
+impl Harness {
+    /// Constructor for the Harness.
+    ///
+    /// Bless mode is enabled if the [`BLESS`](BLESS_VAR) environment variable is set to anything
+    /// but `0`.
+    ///
+    /// # Arguments
+    /// - `dir`: The directory with the input files. Searched recursively.
+    /// - `ext`: The extension of input files (e.g., `lang`).
+    ///
+    /// # Returns
+    /// A new Harness that renders with the [`AriadneRenderer`] and checks annotations.
+    #[inline]
+    pub fn new(dir: impl Into<PathBuf>, ext: impl Into<String>) -> Self {
+        Self {
+            dir:         dir.into(),
+            ext:         ext.into(),
+            bless:       env::var(BLESS_VAR).is_ok_and(|val| !val.is_empty() && val != "0"),
+            annotations: true,
+            renderer:    Arc::new(AriadneRenderer),
+            locales:     None,
+        }
+    }
+
+    /// Sets whether to update the golden files instead of comparing against them.
+    ///
+    /// # Arguments
+    /// - `bless`: Whether to bless.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_bless(mut self, bless: bool) -> Self {
+        self.bless = bless;
+        self
+    }
+
+    /// Sets whether to check the inline annotations.
+    ///
+    /// # Arguments
+    /// - `annotations`: Whether to check them.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_annotations(mut self, annotations: bool) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    /// Sets the backend used to render the diagnostics.
+    ///
+    /// # Arguments
+    /// - `renderer`: The [`Renderer`] to use.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_renderer(mut self, renderer: impl 'static + Renderer) -> Self {
+        self.renderer = Arc::new(renderer);
+        self
+    }
+
+    /// Sets the locales in which the messages of localized diagnostics are resolved.
+    ///
+    /// Annotations are matched against the resolved messages.
+    ///
+    /// # Arguments
+    /// - `locales`: The [`Locales`] to resolve message IDs in.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_locales(mut self, locales: impl Into<Arc<Locales>>) -> Self {
+        self.locales = Some(locales.into());
+        self
+    }
+}
+
+// Running
+impl Harness {
+    /// Runs the compiler over all input files and collects what went wrong.
+    ///
+    /// # Arguments
+    /// - `compile`: Some closure that is given the [`Sources`] with the input file, the name of
+    ///   the file in there and a [`Diagnostics`] to emit to.
+    ///
+    /// # Returns
+    /// A list of [`Failure`]s, which is empty if all files passed.
+    ///
+    /// # Errors
+    /// This function errors if we failed to read input or golden files, or to write the latter.
+    pub fn check(&self, mut compile: impl FnMut(&Sources, &Arc<str>, &mut Diagnostics)) -> io::Result<Vec<Failure>> {
+        let mut files: Vec<PathBuf> = Vec::new();
+        find_files(&self.dir, &self.ext, &mut files)?;
+        files.sort();
+
+        let mut failures: Vec<Failure> = Vec::new();
+        for path in files {
+            // Run the compiler over the file
+            let text: String = fs::read_to_string(&path)?;
+            let rel: &Path = path.strip_prefix(&self.dir).unwrap_or(&path);
+            let name: String = rel.components().map(|comp| comp.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+            let mut sources: Sources = Sources::new();
+            let name: Arc<str> = sources.add(name, text.as_str());
+            let mut diags: Diagnostics = Diagnostics::new().with_color(false);
+            diags.renderer = self.renderer.clone();
+            diags.locales = self.locales.clone();
+            compile(&sources, &name, &mut diags);
+
+            // Compare the rendered diagnostics
+            let mut actual: Vec<u8> = Vec::new();
+            if !diags.is_empty() {
+                diags.render(&sources, &mut actual)?;
+            }
+            let actual: String = String::from_utf8_lossy(&actual).into_owned();
+            let golden: PathBuf = path.with_extension("stderr");
+            if self.bless {
+                if actual.is_empty() {
+                    if golden.exists() {
+                        fs::remove_file(&golden)?;
+                    }
+                } else {
+                    fs::write(&golden, &actual)?;
+                }
+            } else {
+                let expected: String = if golden.exists() { fs::read_to_string(&golden)? } else { String::new() };
+                if expected != actual {
+                    failures.push(Failure::Mismatch { path: path.clone(), expected, actual });
+                }
+            }
+
+            // Check the annotations
+            if self.annotations {
+                self.check_annotations(&path, &text, &sources, &diags, &mut failures);
+            }
+        }
+        Ok(failures)
+    }
+
+    /// Checks the inline annotations of a file against the emitted diagnostics.
+    ///
+    /// # Arguments
+    /// - `path`: The path of the file, for reporting.
+    /// - `text`: The source text of the file.
+    /// - `sources`: The [`Sources`] to resolve spans in.
+    /// - `diags`: The [`Diagnostics`] emitted for the file.
+    /// - `failures`: The list to add any [`Failure`]s to.
+    fn check_annotations(&self, path: &Path, text: &str, sources: &Sources, diags: &Diagnostics, failures: &mut Vec<Failure>) {
+        let line_of = |diag: &Diagnostic| -> usize { sources.line_col(&diag.primary.span.source, diag.primary.span.start).map(|(line, _)| line).unwrap_or(0) };
+        let annots: Vec<Annotation> = parse_annotations(text);
+        let diags: Vec<Cow<Diagnostic>> = diags.localized();
+
+        // Every annotation must match a diagnostic...
+        let mut matched: Vec<bool> = vec![false; diags.len()];
+        for annot in &annots {
+            let found: Option<usize> = diags
+                .iter()
+                .enumerate()
+                .filter(|(_, diag)| line_of(diag) == annot.line && annot.kind.matches(diag, &annot.message))
+                .min_by_key(|(i, _)| matched[*i])
+                .map(|(i, _)| i);
+            match found {
+                Some(i) => matched[i] |= matches!(annot.kind, Kind::Error | Kind::Warning),
+                None => failures.push(Failure::Missing { path: path.into(), line: annot.line, kind: annot.kind, message: annot.message.clone() }),
+            }
+        }
+
+        // ...and every error and warning must be matched
+        for (diag, matched) in diags.iter().zip(matched) {
+            if !matched && diag.severity > Severity::Note {
+                failures.push(Failure::Unexpected { path: path.into(), line: line_of(diag), severity: diag.severity, message: diag.message.clone() });
+            }
+        }
+    }
+
+    /// Runs the compiler over all input files and panics if any failed.
+    ///
+    /// This is meant to be called from a `#[test]`.
+    ///
+    /// # Arguments
+    /// - `compile`: Some closure that is given the [`Sources`] with the input file, the name of
+    ///   the file in there and a [`Diagnostics`] to emit to.
+    ///
+    /// # Panics
+    /// This function panics if any file failed, listing all failures, or if we failed to read or
+    /// write files.
+    pub fn run(&self, compile: impl FnMut(&Sources, &Arc<str>, &mut Diagnostics)) {
+        let failures: Vec<Failure> = match self.check(compile) {
+            Ok(failures) => failures,
+            Err(err) => panic!("Failed to run snapshot tests in {:?}: {err}", self.dir.display()),
+        };
+        if !failures.is_empty() {
+            let list: Vec<String> = failures.iter().map(Failure::to_string).collect();
+            panic!("{} snapshot test failure(s):\n\n{}", failures.len(), list.join("\n"));
+        }
+    }
+}
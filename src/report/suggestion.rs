@@ -0,0 +1,165 @@
+//  SUGGESTION.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 20:02:36
+//  Last edited:
+//    18 Oct 2026, 20:02:36
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines [`Suggestion`]s, fix-its that can be attached to reports
+//!   and applied to the source text automatically.
+//
+
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::sync::Arc;
+
+use super::Diagnostic;
+use crate::span::sources::char_to_byte;
+use crate::span::{Sources, Span};
+
+
+/***** ERRORS *****/
+/// Defines errors returned by [`apply_suggestions()`].
+#[derive(Debug)]
+pub enum Error {
+    /// Two suggestions wanted to edit the same part of the source text.
+    Overlap { first: Span, second: Span },
+    /// A suggestion pointed into a source we don't know.
+    UnknownSource { span: Span },
+    /// A suggestion pointed beyond the end of its source.
+    OutOfBounds { span: Span },
+}
+impl Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Overlap { first, second } => write!(f, "Suggestion at {second} overlaps with suggestion at {first}"),
+            Self::UnknownSource { span } => write!(f, "Suggestion at {span} points into unknown source {:?}", span.source),
+            Self::OutOfBounds { span } => write!(f, "Suggestion at {span} points beyond the end of its source"),
+        }
+    }
+}
+impl error::Error for Error {}
+
+
+
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Applies the suggestions of a set of diagnostics to the source texts.
+///
+/// Only suggestions that are at least as confident as `min` are applied. Suggestions that are
+/// exactly the same (e.g., because two diagnostics suggest the same fix) are applied once.
+///
+/// # Arguments
+/// - `sources`: The [`Sources`] to apply the suggestions to.
+/// - `diags`: The [`Diagnostic`]s whose suggestions to apply.
+/// - `min`: The minimum [`Applicability`] of suggestions to apply.
+///
+/// # Returns
+/// A new [`Sources`] with the same texts, except that the suggestions are applied.
+///
+/// # Errors
+/// This function errors if two suggestions want to edit overlapping parts of the same text, or if
+/// any suggestion refers to an unknown source or runs beyond it. In that case, nothing is applied.
+pub fn apply_suggestions<'d>(sources: &Sources, diags: impl IntoIterator<Item = &'d Diagnostic>, min: Applicability) -> Result<Sources, Error> {
+    // Collect the suggestions per source, in order
+    let mut edits: BTreeMap<Arc<str>, Vec<&Suggestion>> = BTreeMap::new();
+    for diag in diags {
+        for sugg in diag.suggestions.iter().filter(|sugg| sugg.applicability >= min) {
+            edits.entry(sugg.span.source.clone()).or_default().push(sugg);
+        }
+    }
+
+    // Then apply them per source
+    let mut res: Sources = sources.clone();
+    for (name, mut suggs) in edits {
+        suggs.sort_by(|lhs, rhs| lhs.span.cmp(&rhs.span).then_with(|| lhs.replacement.cmp(&rhs.replacement)));
+        suggs.dedup_by(|lhs, rhs| lhs.span == rhs.span && lhs.replacement == rhs.replacement);
+        for pair in suggs.windows(2) {
+            let (first, second): (&Span, &Span) = (&pair[0].span, &pair[1].span);
+            // NOTE: Two insertions at the same position also overlap, as their order is ambiguous
+            if second.start < first.end || (first.start == second.start && first.end == second.end) {
+                return Err(Error::Overlap { first: first.clone(), second: second.clone() });
+            }
+        }
+
+        // Apply them back-to-front to keep the offsets valid
+        let Some(text) = sources.get(&name) else { return Err(Error::UnknownSource { span: suggs[0].span.clone() }) };
+        let mut text: String = text.to_string();
+        for sugg in suggs.iter().rev() {
+            let start: usize = char_to_byte(&text, sugg.span.start).ok_or_else(|| Error::OutOfBounds { span: sugg.span.clone() })?;
+            let end: usize = char_to_byte(&text, sugg.span.end).ok_or_else(|| Error::OutOfBounds { span: sugg.span.clone() })?;
+            text.replace_range(start..end, &sugg.replacement);
+        }
+        res.add(name, text);
+    }
+    Ok(res)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines how confident we are that a [`Suggestion`] is what the user wants.
+///
+/// Ordered from least to most confident.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Applicability {
+    /// We don't know how confident we are.
+    Unspecified,
+    /// The suggestion contains placeholders (e.g., `<type>`) that the user must fill in.
+    HasPlaceholders,
+    /// The suggestion may be what the user wants, but might not compile.
+    MaybeIncorrect,
+    /// The suggestion is definitely what the user wants, and can be applied automatically.
+    MachineApplicable,
+}
+impl Display for Applicability {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Unspecified => write!(f, "Unspecified"),
+            Self::HasPlaceholders => write!(f, "HasPlaceholders"),
+            Self::MaybeIncorrect => write!(f, "MaybeIncorrect"),
+            Self::MachineApplicable => write!(f, "MachineApplicable"),
+        }
+    }
+}
+
+
+
+/// Defines a suggestion to replace part of the source text to fix a diagnostic.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Suggestion {
+    /// A message explaining the suggestion (e.g., "a variable with a similar name exists").
+    pub message:       String,
+    /// The part of the source text to replace. Empty to insert text.
+    pub span:          Span,
+    /// The text to replace the span with. Empty to remove text.
+    pub replacement:   String,
+    /// How confident we are that this suggestion is correct.
+    pub applicability: Applicability,
+}
+impl Suggestion {
+    /// Constructor for the Suggestion.
+    ///
+    /// # Arguments
+    /// - `message`: A message explaining the suggestion.
+    /// - `span`: The part of the source text to replace.
+    /// - `replacement`: The text to replace the span with.
+    /// - `applicability`: How confident we are that this suggestion is correct.
+    ///
+    /// # Returns
+    /// A new Suggestion.
+    #[inline]
+    pub fn new(message: impl Display, span: Span, replacement: impl Display, applicability: Applicability) -> Self {
+        Self { message: message.to_string(), span, replacement: replacement.to_string(), applicability }
+    }
+}
@@ -4,7 +4,7 @@
 //  Created:
 //    05 Mar 2025, 17:51:58
 //  Last edited:
-//    05 Mar 2025, 17:51:59
+//    18 Oct 2026, 20:09:33
 //  Auto updated?
 //    Yes
 //
@@ -12,3 +12,174 @@
 //!   Library that contributes an [ariadne](::ariadne) and
 //!   [chumsky](::chumsky)-compatibel [`Span`]-implementation.
 //
+
+// Module definitions
+pub mod sources;
+
+// Re-exports
+pub use sources::Sources;
+
+// Imports
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::Arc;
+
+
+/***** LIBRARY *****/
+/// Defines a range of characters in some source text.
+///
+/// Sources are identified by some name (typically their path), which is shared between all spans
+/// pointing into the same source. Offsets are given in _characters_, not bytes, to be consistent
+/// with both [ariadne](::ariadne) and [chumsky](::chumsky).
+///
+/// Spans are ordered by source first and then by position, which makes it easy to sort things
+/// by where they occur.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Span {
+    /// The identifier of the source text this span points into.
+    pub source: Arc<str>,
+    /// The start of the range (inclusive), as a character offset.
+    pub start:  usize,
+    /// The end of the range (exclusive), as a character offset.
+    pub end:    usize,
+}
+
+// Constructors
+impl Span {
+    /// Constructor for the Span.
+    ///
+    /// # Arguments
+    /// - `source`: Some identifier of the source text (e.g., its path).
+    /// - `range`: The range of characters in the source text that this span covers.
+    ///
+    /// # Returns
+    /// A new Span that covers the given `range` in the given `source`.
+    #[inline]
+    pub fn new(source: impl Into<Arc<str>>, range: Range<usize>) -> Self { Self { source: source.into(), start: range.start, end: range.end } }
+
+    /// Constructor for a Span that doesn't cover any characters, but only points to a position.
+    ///
+    /// # Arguments
+    /// - `source`: Some identifier of the source text (e.g., its path).
+    /// - `pos`: The character offset to point at.
+    ///
+    /// # Returns
+    /// A new Span that is empty at the given `pos` in the given `source`.
+    #[inline]
+    pub fn empty(source: impl Into<Arc<str>>, pos: usize) -> Self { Self { source: source.into(), start: pos, end: pos } }
+}
+
+// Ops
+impl Span {
+    /// Joins this Span with another one, producing a Span that covers both.
+    ///
+    /// # Arguments
+    /// - `other`: The other Span to join with.
+    ///
+    /// # Returns
+    /// A new Span that runs from the earliest start to the latest end of both spans, or [`None`]
+    /// if they do not point into the same source.
+    #[inline]
+    pub fn join(&self, other: &Self) -> Option<Self> {
+        if self.source != other.source {
+            return None;
+        }
+        Some(Self { source: self.source.clone(), start: self.start.min(other.start), end: self.end.max(other.end) })
+    }
+}
+
+// Accessors
+impl Span {
+    /// Returns the range of characters covered by this Span.
+    ///
+    /// # Returns
+    /// A [`Range`] of character offsets.
+    #[inline]
+    pub fn range(&self) -> Range<usize> { self.start..self.end }
+
+    /// Returns the number of characters covered by this Span.
+    #[inline]
+    pub fn len(&self) -> usize { self.end.saturating_sub(self.start) }
+
+    /// Returns whether this Span covers no characters at all.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+// Formatting
+impl Display for Span {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "{}:{}..{}", self.source, self.start, self.end) }
+}
+
+// ariadne
+#[cfg(feature = "report")]
+impl ariadne::Span for Span {
+    type SourceId = Arc<str>;
+
+    #[inline]
+    fn source(&self) -> &Self::SourceId { &self.source }
+
+    #[inline]
+    fn start(&self) -> usize { self.start }
+
+    #[inline]
+    fn end(&self) -> usize { self.end }
+}
+
+
+// chumsky
+#[cfg(feature = "parser")]
+impl chumsky::Span for Span {
+    type Context = Arc<str>;
+    type Offset = usize;
+
+    #[inline]
+    fn new(context: Self::Context, range: Range<Self::Offset>) -> Self { Self { source: context, start: range.start, end: range.end } }
+
+    #[inline]
+    fn context(&self) -> Self::Context { self.source.clone() }
+
+    #[inline]
+    fn start(&self) -> Self::Offset { self.start }
+
+    #[inline]
+    fn end(&self) -> Self::Offset { self.end }
+}
+
+
+/// Abstracts over nodes that know where they are in the source text.
+pub trait Spanned {
+    /// Returns the [`Span`] of this node.
+    ///
+    /// # Returns
+    /// A [`Span`] describing where in the source text this node is found.
+    fn span(&self) -> Span;
+}
+
+// Default impls
+impl Spanned for Span {
+    #[inline]
+    fn span(&self) -> Span { self.clone() }
+}
+impl<T: ?Sized + Spanned> Spanned for &T {
+    #[inline]
+    fn span(&self) -> Span { <T as Spanned>::span(self) }
+}
+impl<T: ?Sized + Spanned> Spanned for &mut T {
+    #[inline]
+    fn span(&self) -> Span { <T as Spanned>::span(self) }
+}
+impl<T: ?Sized + Spanned> Spanned for Box<T> {
+    #[inline]
+    fn span(&self) -> Span { <T as Spanned>::span(self) }
+}
+impl<T: ?Sized + Spanned> Spanned for Rc<T> {
+    #[inline]
+    fn span(&self) -> Span { <T as Spanned>::span(self) }
+}
+impl<T: ?Sized + Spanned> Spanned for Arc<T> {
+    #[inline]
+    fn span(&self) -> Span { <T as Spanned>::span(self) }
+}
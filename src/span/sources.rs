@@ -0,0 +1,160 @@
+//  SOURCES.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 19:58:17
+//  Last edited:
+//    18 Oct 2026, 19:59:59
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Defines [`Sources`], which stores the source texts that [`Span`]s
+//!   point into.
+//
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use super::Span;
+
+
+/***** LIBRARY *****/
+/// Stores the source texts that [`Span`]s point into, by name.
+///
+/// This is what renderers use to show the annotated source text of spans. Sources are iterated in
+/// order of their names.
+#[derive(Clone, Debug, Default)]
+pub struct Sources {
+    /// The texts, mapped by their names.
+    texts: BTreeMap<Arc<str>, Arc<str>>,
+}
+
+// Constructors
+impl Sources {
+    /// Constructor for Sources that initializes it without any texts.
+    ///
+    /// # Returns
+    /// A new, empty Sources.
+    #[inline]
+    pub fn new() -> Self { Self { texts: BTreeMap::new() } }
+}
+
+// Collection
+impl Sources {
+    /// Adds a new source text.
+    ///
+    /// If a text with the same name already existed, it is replaced.
+    ///
+    /// # Arguments
+    /// - `name`: Some name (e.g., the path of the file) that identifies the text.
+    /// - `text`: The text itself.
+    ///
+    /// # Returns
+    /// The shared name of the text, which can be given to [`Span::new()`] to create spans
+    /// pointing into it without re-allocating the name.
+    #[inline]
+    pub fn add(&mut self, name: impl Into<Arc<str>>, text: impl Into<Arc<str>>) -> Arc<str> {
+        let name: Arc<str> = name.into();
+        self.texts.insert(name.clone(), text.into());
+        name
+    }
+
+    /// Returns the text with the given name.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the text to find.
+    ///
+    /// # Returns
+    /// The text, or [`None`] if no text with that name was added.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&Arc<str>> { self.texts.get(name) }
+
+    /// Returns the part of a text covered by a [`Span`].
+    ///
+    /// # Arguments
+    /// - `span`: The [`Span`] to get the text of.
+    ///
+    /// # Returns
+    /// The covered text, or [`None`] if the span's source is unknown or if it runs beyond it.
+    #[inline]
+    pub fn slice(&self, span: &Span) -> Option<&str> {
+        let text: &str = self.get(&span.source)?;
+        let start: usize = char_to_byte(text, span.start)?;
+        let end: usize = char_to_byte(text, span.end)?;
+        text.get(start..end)
+    }
+
+    /// Converts a character offset into a text to a byte offset.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the text to index.
+    /// - `offset`: The character offset to convert.
+    ///
+    /// # Returns
+    /// The byte offset, or [`None`] if the source is unknown or the offset runs beyond it.
+    #[inline]
+    pub fn byte_offset(&self, name: &str, offset: usize) -> Option<usize> { char_to_byte(self.get(name)?, offset) }
+
+    /// Finds the line and column of a character offset into a text.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the text to index.
+    /// - `offset`: The character offset to find the position of.
+    ///
+    /// # Returns
+    /// A pair of the line and the column, both 1-indexed and the column in characters, or
+    /// [`None`] if the source is unknown or the offset runs beyond it.
+    pub fn line_col(&self, name: &str, offset: usize) -> Option<(usize, usize)> {
+        let text: &str = self.get(name)?;
+        let (mut line, mut col): (usize, usize) = (1, 1);
+        let mut chars = text.chars();
+        for _ in 0..offset {
+            if chars.next()? == '\n' {
+                line += 1;
+                col = 1;
+            } else {
+                col += 1;
+            }
+        }
+        Some((line, col))
+    }
+
+    /// Returns a particular line in a text.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the text to index.
+    /// - `line`: The 1-indexed number of the line to return.
+    ///
+    /// # Returns
+    /// The line without its newline, or [`None`] if the source is unknown or has fewer lines.
+    #[inline]
+    pub fn line(&self, name: &str, line: usize) -> Option<&str> { self.get(name)?.lines().nth(line.checked_sub(1)?) }
+
+    /// Returns an iterator over the names and texts stored.
+    ///
+    /// # Returns
+    /// An [`Iterator`] yielding pairs of names and texts, in order of name.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&Arc<str>, &Arc<str>)> { self.texts.iter() }
+
+    /// Returns the number of texts stored.
+    #[inline]
+    pub fn len(&self) -> usize { self.texts.len() }
+
+    /// Returns whether no texts have been stored.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.texts.is_empty() }
+}
+
+
+
+/// Converts a character offset into a text to a byte offset.
+///
+/// # Arguments
+/// - `text`: The text to index.
+/// - `offset`: The character offset to convert. May point just beyond the last character.
+///
+/// # Returns
+/// The byte offset of the character, or [`None`] if the offset runs beyond the text.
+pub fn char_to_byte(text: &str, offset: usize) -> Option<usize> { text.char_indices().map(|(i, _)| i).chain(std::iter::once(text.len())).nth(offset) }
@@ -0,0 +1,203 @@
+//  CST.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 22:33:54
+//  Last edited:
+//    18 Oct 2026, 23:45:40
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests reprinting and editing parsed texts with a lossless [`Cst`].
+//
+
+#![cfg(feature = "parser")]
+
+use ast_toolkit_2::parser::chumsky::prelude::*;
+use ast_toolkit_2::parser::chumsky::text;
+use ast_toolkit_2::parser::cst::{Comments, Cst, Error as CstError, TokenKind, Trivia, TriviaKind};
+use ast_toolkit_2::parser::doc::Doc;
+use ast_toolkit_2::parser::{Error, Parse, Span, Unparse};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses an identifier.
+///
+/// # Returns
+/// A [`Parser`] producing the identifier's text.
+fn ident() -> impl Parser<char, String, Error = Error> + Clone { text::ident() }
+
+/// Parses a decimal integer, which may have leading zeros.
+///
+/// # Returns
+/// A [`Parser`] producing the integer's value.
+fn int() -> impl Parser<char, u64, Error = Error> + Clone { text::digits(10).from_str().unwrapped() }
+
+/// Finds the span of a statement.
+///
+/// # Arguments
+/// - `stmt`: The [`Stmt`] to find the span of.
+///
+/// # Returns
+/// The [`Span`] of the whole statement.
+fn span(stmt: &Stmt) -> &Span {
+    match stmt {
+        Stmt::Let(.., span) | Stmt::Print(.., span) => span,
+    }
+}
+
+/// Parses a text and its CST with C-like comments.
+///
+/// # Arguments
+/// - `text`: The source text to parse.
+///
+/// # Returns
+/// The parsed [`Program`] and its [`Cst`].
+fn parse(text: &str) -> (Program, Cst) {
+    Cst::parse::<Program>("a", text, &Comments::default()).unwrap_or_else(|err| panic!("Failed to parse {text:?}: {err}"))
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// A list of statements.
+#[derive(Debug, Parse, Unparse)]
+struct Program(Vec<Stmt>);
+
+/// A single statement.
+#[derive(Debug, Parse, Unparse)]
+enum Stmt {
+    #[parse(keyword = "let")]
+    Let(#[parse(with = ident)] String, #[parse(punct = "=")] Span, Expr, #[parse(punct = ";")] Span, #[parse(span)] Span),
+    #[parse(keyword = "print")]
+    Print(Str, #[parse(punct = ";")] Span, #[parse(span)] Span),
+}
+
+/// An expression.
+#[derive(Debug, Parse, Unparse)]
+enum Expr {
+    #[parse(infix, punct = "+", prec = 1)]
+    Add(Box<Expr>, Box<Expr>),
+    Var(#[parse(with = ident)] String),
+    Int(#[parse(with = int)] u64),
+}
+
+/// A string literal, which may contain comment tokens.
+#[derive(Debug)]
+struct Str(String);
+impl Parse for Str {
+    #[inline]
+    fn parser() -> impl Parser<char, Self, Error = Error> + Clone {
+        just('"').ignore_then(filter(|c: &char| *c != '"').repeated().collect()).then_ignore(just('"')).map(Str)
+    }
+}
+impl Unparse for Str {
+    #[inline]
+    fn unparse(&self) -> Doc { Doc::text(format!("{:?}", self.0)) }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn reprint() {
+    let texts: [&str; 7] = [
+        "",
+        "let x = 1;",
+        "  let x=1 ;let   y = x+ 2;\n\n\n",
+        "// A leading comment\nlet x = 1; // A trailing one\n/* A block\n   comment */ let y = /* inline */ x;\n// At the end",
+        "let x = 1;\r\nlet y = 2;\r\n",
+        "\tlet x = 1;   \n\t\tprint \"a // b /* c */\"; // not in the string\n",
+        "let x = 1; /* unclosed",
+    ];
+    for text in texts {
+        let (_, cst) = parse(text);
+        assert_eq!(cst.to_string(), text);
+    }
+}
+
+#[test]
+fn trivia() {
+    let (_, cst) = parse("// Before\nlet x = 1; // After\n\nlet y = 2;");
+    let texts: Vec<&str> = cst.tokens.iter().map(|token| token.text.as_str()).collect();
+    assert_eq!(texts, ["let", "x", "=", "1", ";", "let", "y", "=", "2", ";"]);
+
+    // Comments on their own line lead the next token, those at the end of a line trail the previous
+    let kinds = |trivia: &[Trivia]| trivia.iter().map(|t| t.kind).collect::<Vec<TriviaKind>>();
+    assert_eq!(kinds(&cst.tokens[0].leading), [TriviaKind::LineComment, TriviaKind::Newline]);
+    assert_eq!(kinds(&cst.tokens[4].trailing), [TriviaKind::Whitespace, TriviaKind::LineComment, TriviaKind::Newline]);
+    assert_eq!(kinds(&cst.tokens[5].leading), [TriviaKind::Newline]);
+    assert_eq!(cst.tokens[3].span, Span::new("a", 18..19));
+    assert!(cst.end.is_empty());
+}
+
+#[test]
+fn edits() {
+    let text: &str = "// First\nlet x = 1; // one\n// Second\nlet y = 2; // two\nprint \"z\";\n";
+    let (program, mut cst) = parse(text);
+
+    // Replacing a statement keeps the comments around it
+    let range = cst.find(span(&program.0[1]));
+    assert_eq!(range, 5..10);
+    let Program(mut new) = Program::parse_str("b", "let y = x + 3;").unwrap();
+    cst.splice(range, &new.remove(0)).unwrap();
+    assert_eq!(cst.to_string(), "// First\nlet x = 1; // one\n// Second\nlet y = x + 3; // two\nprint \"z\";\n");
+
+    // Removing one moves its leading comments to the next
+    let range = cst.find(span(&program.0[0]));
+    cst.remove(range);
+    assert_eq!(cst.to_string(), "// First\n// Second\nlet y = x + 3; // two\nprint \"z\";\n");
+
+    // Inserting one at the end puts it after the trailing newline of the last token
+    let end: usize = cst.tokens.len();
+    cst.splice(end..end, &Stmt::Print(Str("w".into()), Span::empty("b", 0), Span::empty("b", 0))).unwrap();
+    assert_eq!(cst.to_string(), "// First\n// Second\nlet y = x + 3; // two\nprint \"z\";\nprint \"w\";");
+}
+
+#[test]
+fn inserts() {
+    let new = |text: &str| Program::parse_str("b", text).unwrap().0.remove(0);
+
+    // Inserted nodes are spaced from the tokens they would touch
+    let (_, mut cst) = parse("let x = 1;");
+    cst.splice(5..5, &new("let y = 2;")).unwrap();
+    assert_eq!(cst.to_string(), "let x = 1; let y = 2;");
+    cst.splice(0..0, &new("print \"z\";")).unwrap();
+    assert_eq!(cst.to_string(), "print \"z\"; let x = 1; let y = 2;");
+
+    // Unless they glue together, like punctuation does
+    cst.splice(7..7, &Expr::Var("w".into())).unwrap();
+    assert_eq!(cst.to_string(), "print \"z\"; let x = 1 w; let y = 2;");
+    assert_eq!(cst.tokens[7].kind, TokenKind::Text);
+    assert_eq!(cst.tokens[8].kind, TokenKind::Punct);
+
+    // And trivia that is already there is kept
+    let (_, mut cst) = parse("let x = 1;\n");
+    cst.splice(5..5, &new("let y = 2;")).unwrap();
+    assert_eq!(cst.to_string(), "let x = 1;\nlet y = 2;");
+}
+
+#[test]
+fn errors() {
+    assert!(matches!(Cst::parse::<Program>("a", "let x = ;", &Comments::default()), Err(CstError::Parse { .. })));
+    // Comments are only blanked with the given syntax
+    assert!(matches!(Cst::parse::<Program>("a", "let x = 1; # no", &Comments::default()), Err(CstError::Parse { .. })));
+    let comments = Comments { line: vec!["#"], block: vec![], quotes: vec![] };
+    let (_, cst) = Cst::parse::<Program>("a", "let x = 1; # yes", &comments).unwrap();
+    assert_eq!(cst.to_string(), "let x = 1; # yes");
+
+    // Nodes that print differently than they were written can't be aligned
+    let Err(CstError::Misaligned { expected, span }) = Cst::parse::<Program>("a", "print \"a\\tb\";", &Comments::default()) else {
+        panic!("Expected a misaligned string")
+    };
+    assert_eq!((expected.as_str(), span), ("\"a\\\\tb\"", Span::new("a", 6..7)));
+    let Err(CstError::Misaligned { expected, span }) = Cst::parse::<Program>("a", "let x = 007;", &Comments::default()) else {
+        panic!("Expected a misaligned integer")
+    };
+    assert_eq!((expected.as_str(), span), ("7", Span::new("a", 8..9)));
+}
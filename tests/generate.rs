@@ -0,0 +1,146 @@
+//  GENERATE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 22:32:39
+//  Last edited:
+//    18 Oct 2026, 23:31:54
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests generating random nodes with the [`Generate`] derive.
+//
+
+#![cfg(feature = "parser")]
+
+use std::fmt::Debug;
+
+use ast_toolkit_2::parser::chumsky::prelude::*;
+use ast_toolkit_2::parser::chumsky::text;
+use ast_toolkit_2::parser::generate::Generator;
+use ast_toolkit_2::parser::roundtrip::Roundtrip;
+use ast_toolkit_2::parser::{Error, Generate, Parse, Unparse};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses an identifier.
+///
+/// # Returns
+/// A [`Parser`] producing the identifier's text.
+fn ident() -> impl Parser<char, String, Error = Error> + Clone { text::ident() }
+
+/// Checks that generated nodes print to text that parses back to exactly themselves.
+///
+/// # Arguments
+/// - `seeds`: The number of seeds to generate a node for.
+fn check<T: Debug + Generate + Parse + PartialEq + Unparse>(seeds: u64) {
+    for seed in 0..seeds {
+        let node: T = Generator::new(seed).with_max_depth(6).generate();
+        let text: String = node.unparse_to_string(80);
+        let parsed: T = T::parse_str("a", &text).unwrap_or_else(|errs| panic!("Seed {seed}: failed to parse {text:?}: {errs:?}"));
+        assert_eq!(parsed, node, "Seed {seed}: {text:?} parsed to another node");
+        assert_eq!(parsed.unparse_to_string(80), text, "Seed {seed}: printing is unstable");
+    }
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// An expression with every kind of operator.
+#[derive(Debug, Generate, Parse, PartialEq, Unparse)]
+enum Expr {
+    #[parse(infix, punct = "==", prec = 0, assoc = none)]
+    Eq(Box<Expr>, Box<Expr>),
+    #[parse(infix, punct = "+", prec = 1)]
+    Add(Box<Expr>, Box<Expr>),
+    #[parse(infix, punct = "*", prec = 2)]
+    Mul(Box<Expr>, Box<Expr>),
+    #[parse(infix, punct = "^", prec = 3, assoc = right)]
+    Pow(Box<Expr>, Box<Expr>),
+    #[parse(prefix, punct = "-", prec = 3)]
+    Neg(Box<Expr>),
+    #[parse(postfix, punct = "!", prec = 3)]
+    Fact(Box<Expr>),
+    Paren(#[parse(delim("(", ")"))] Box<Expr>),
+    #[parse(weight = 4)]
+    Var(#[parse(with = ident)] String),
+}
+
+/// An expression that is parenthesized by the variant instead of its field.
+#[derive(Debug, Generate, Parse, PartialEq, Unparse)]
+enum Group {
+    #[parse(infix, punct = "+", prec = 1)]
+    Add(Box<Group>, Box<Group>),
+    #[parse(prefix, punct = "-", prec = 2)]
+    Neg(Box<Group>),
+    #[parse(delim("(", ")"))]
+    Paren(Box<Group>),
+    #[parse(weight = 2)]
+    Var(#[parse(with = ident)] String),
+}
+
+/// An expression that cannot be parenthesized at all.
+#[derive(Debug, Generate, Parse, PartialEq, Unparse)]
+enum Bare {
+    #[parse(infix, punct = "+", prec = 1)]
+    Add(Box<Bare>, Box<Bare>),
+    #[parse(infix, punct = "*", prec = 2)]
+    Mul(Box<Bare>, Box<Bare>),
+    #[parse(weight = 2)]
+    Var(#[parse(with = ident)] String),
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn operators() {
+    check::<Expr>(1000);
+    // Both exactly and modulo parentheses
+    assert!(Roundtrip::new().check_generated::<Expr>(&Generator::new(0), 0..1000).is_empty());
+
+    // Operands that bind looser than their operator are parenthesized
+    let texts: Vec<String> = (0..200).map(|seed| Generator::new(seed).source::<Expr>(80)).collect();
+    assert!(texts.iter().any(|text| text.contains(" + (")), "{texts:#?}");
+}
+
+#[test]
+fn delimited_variant() {
+    check::<Group>(500);
+    let texts: Vec<String> = (0..200).map(|seed| Generator::new(seed).source::<Group>(80)).collect();
+    assert!(texts.iter().any(|text| text.contains("-(")), "{texts:#?}");
+}
+
+#[test]
+fn unparenthesized() {
+    // Operands that would need parentheses are generated again instead
+    check::<Bare>(500);
+    for seed in 0..200 {
+        if let Bare::Mul(lhs, rhs) = Generator::new(seed).generate::<Bare>() {
+            assert!(!matches!(*lhs, Bare::Add(..)) && !matches!(*rhs, Bare::Add(..) | Bare::Mul(..)), "Seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn deterministic() {
+    for seed in 0..50 {
+        assert_eq!(Generator::new(seed).generate::<Expr>(), Generator::new(seed).generate::<Expr>());
+    }
+    // Exhausted budgets only generate leaves
+    assert!(matches!(Generator::new(0).with_max_depth(0).generate::<Expr>(), Expr::Var(_)));
+
+    // Reseeding starts over completely, including the budget
+    let mut generator = Generator::new(0).with_max_size(8);
+    let first: Vec<Expr> = (0..4).map(|_| generator.generate()).collect();
+    assert!(matches!(first[3], Expr::Var(_)));
+    for seed in 0..50 {
+        let expected: Expr = Generator::new(seed).with_max_size(8).generate();
+        assert_eq!(generator.clone().with_seed(seed).generate::<Expr>(), expected, "Seed {seed}");
+    }
+}
@@ -0,0 +1,85 @@
+//  HTML.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:59:02
+//  Last edited:
+//    18 Oct 2026, 21:59:02
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests rendering diagnostics as HTML.
+//
+
+#![cfg(feature = "report")]
+
+use ast_toolkit_2::report::{Applicability, Diagnostic, Diagnostics, HtmlRenderer, Label, Sources, Span, Suggestion};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Renders a diagnostic to HTML, including the summary.
+///
+/// # Arguments
+/// - `diag`: The [`Diagnostic`] to render.
+///
+/// # Returns
+/// The rendered HTML.
+fn render(diag: Diagnostic) -> String {
+    let mut sources = Sources::new();
+    sources.add("<a>.rs", "if a < b && c {\n    x\n}\n");
+    let mut diags = Diagnostics::new().with_renderer(HtmlRenderer);
+    diags.emit(diag);
+    let mut buf: Vec<u8> = Vec::new();
+    diags.render(&sources, &mut buf).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn markup() {
+    let diag: Diagnostic = Diagnostic::error("expected `>`, found `&&`", Span::new("<a>.rs", 9..11))
+        .with_code("E<1>")
+        .with_primary_message("a \"quote\"")
+        .with_label(Label::with_message(Span::new("<a>.rs", 3..4), "from <here>"))
+        .with_note("a & b")
+        .with_help("c")
+        .with_suggestion(Suggestion::new("use <", Span::new("<a>.rs", 5..6), "<=", Applicability::MaybeIncorrect));
+    assert_eq!(
+        render(diag),
+        r#"<div class="diagnostic error">
+<div class="header"><span class="severity">error</span><span class="code">E&lt;1&gt;</span><span class="message">expected `&gt;`, found `&amp;&amp;`</span></div>
+<div class="snippet" data-file="&lt;a&gt;.rs">
+<div class="location"><span class="file">&lt;a&gt;.rs</span>:<span class="position">1:10</span></div>
+<div class="source-line" data-line="1"><span class="line-number">1</span><code class="source">if <span class="label secondary">a</span> &lt; b <span class="label primary">&amp;&amp;</span> c {</code></div>
+<div class="label-message secondary" data-line="1">from &lt;here&gt;</div>
+<div class="label-message primary" data-line="1">a &quot;quote&quot;</div>
+</div>
+<div class="note"><span class="kind">note</span><span class="text">a &amp; b</span></div>
+<div class="help"><span class="kind">help</span><span class="text">c</span></div>
+<div class="suggestion" data-file="&lt;a&gt;.rs" data-start="5" data-end="6" data-applicability="MaybeIncorrect"><span class="kind">help</span><span class="text">use &lt;</span><code class="replacement">&lt;=</code></div>
+</div>
+<div class="summary error"><span class="severity">error</span><span class="message">aborting due to 1 previous error</span></div>
+"#
+    );
+}
+
+#[test]
+fn skipped() {
+    let html: String = render(Diagnostic::warning("Unbalanced", Span::new("<a>.rs", 22..23)).with_label(Label::new(Span::new("<a>.rs", 14..15))));
+    assert!(html.starts_with("<div class=\"diagnostic warning\">\n"), "{html}");
+    assert!(
+        html.contains(
+            "<code class=\"source\">if a &lt; b &amp;&amp; c <span class=\"label secondary\">{</span></code></div>\n<div class=\"skipped\">...</div>\n<div \
+             class=\"source-line\" data-line=\"3\"><span class=\"line-number\">3</span><code class=\"source\"><span class=\"label \
+             primary\">}</span></code></div>\n"
+        ),
+        "{html}"
+    );
+    assert!(!html.contains("label-message"), "{html}");
+    assert!(!html.contains('\x1b'), "{html}");
+}
@@ -0,0 +1,242 @@
+//  IMPORT.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 22:53:15
+//  Last edited:
+//    18 Oct 2026, 23:31:27
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests parsing files and everything they import with the import
+//!   [`Driver`].
+//
+
+#![cfg(feature = "parser")]
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+
+use ast_toolkit_2::parser::chumsky::error::SimpleReason;
+use ast_toolkit_2::parser::chumsky::prelude::*;
+use ast_toolkit_2::parser::chumsky::text;
+use ast_toolkit_2::parser::import::{Driver, Files, Import, resolve};
+use ast_toolkit_2::parser::{Error, Parse, Span};
+use ast_toolkit_2::span::Sources;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses an identifier.
+///
+/// # Returns
+/// A [`Parser`] producing the identifier's text.
+fn ident() -> impl Parser<char, String, Error = Error> + Clone { text::ident() }
+
+/// Parses a string literal without escapes.
+///
+/// # Returns
+/// A [`Parser`] producing the string's contents.
+fn string() -> impl Parser<char, String, Error = Error> + Clone {
+    just('"').ignore_then(filter(|c: &char| *c != '"').repeated().collect()).then_ignore(just('"'))
+}
+
+/// Finds the imports in a module.
+///
+/// # Arguments
+/// - `module`: The [`Module`] to search.
+///
+/// # Returns
+/// The [`Import`]s in it, in order.
+fn imports(module: &Module) -> Vec<Import> {
+    module
+        .0
+        .iter()
+        .filter_map(|item| match item {
+            Item::Import(path, span) => Some(Import::new(path, span.clone())),
+            Item::Let(..) => None,
+        })
+        .collect()
+}
+
+/// Parses a root file and everything it imports from memory.
+///
+/// # Arguments
+/// - `texts`: The name and text of every file that exists.
+/// - `root`: The name of the root file.
+///
+/// # Returns
+/// The parsed [`Files`], the [`Sources`] they were added to, and the names of the files loaded.
+fn parse(texts: &[(&str, &str)], root: &str) -> (Files<Module>, Sources, Vec<String>) {
+    let texts: HashMap<&str, &str> = texts.iter().copied().collect();
+    let loaded: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    let mut sources = Sources::new();
+    let files: Files<Module> = Driver::new(imports)
+        .with_loader(|name: &str| {
+            loaded.borrow_mut().push(name.into());
+            texts.get(name).map(|text| text.to_string()).ok_or_else(|| io::ErrorKind::NotFound.into())
+        })
+        .parse(&mut sources, root);
+    (files, sources, loaded.into_inner())
+}
+
+/// Summarizes the errors found by the driver.
+///
+/// # Arguments
+/// - `files`: The [`Files`] with the errors.
+///
+/// # Returns
+/// The span and message of every error, where syntax errors have no message.
+fn errors(files: &Files<Module>) -> Vec<(Span, Option<String>)> {
+    files
+        .errors()
+        .iter()
+        .map(|err| match err.reason() {
+            SimpleReason::Custom(msg) => (err.span(), Some(msg.clone())),
+            _ => (err.span(), None),
+        })
+        .collect()
+}
+
+/// Returns the names of parsed files.
+///
+/// # Arguments
+/// - `files`: The [`Files`] to list.
+///
+/// # Returns
+/// The name of every file, in order.
+fn names(files: &Files<Module>) -> Vec<&str> { files.files().iter().map(|file| &*file.name).collect() }
+
+
+
+
+
+/***** HELPERS *****/
+/// A file of items.
+#[derive(Debug, Parse, PartialEq)]
+struct Module(Vec<Item>);
+
+/// A single item.
+#[derive(Debug, Parse, PartialEq)]
+enum Item {
+    #[parse(keyword = "import")]
+    Import(#[parse(with = string)] String, #[parse(span)] Span),
+    #[parse(keyword = "let")]
+    Let(#[parse(with = ident)] String, #[parse(punct = ";")] Span),
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn imports_once() {
+    // Files imported twice (here `b.lang`) are loaded and parsed once
+    let (files, sources, loaded) = parse(
+        &[
+            ("main.lang", "import \"lib/a.lang\" import \"b.lang\" let x;"),
+            ("lib/a.lang", "import \"../b.lang\"\nimport \"./c.lang\""),
+            ("b.lang", "let y;"),
+            ("lib/c.lang", "import \"../b.lang\""),
+        ],
+        "./main.lang",
+    );
+    assert!(files.is_ok(), "{:?}", files.errors());
+    assert_eq!(names(&files), ["main.lang", "lib/a.lang", "b.lang", "lib/c.lang"]);
+    assert_eq!(loaded, ["main.lang", "lib/a.lang", "b.lang", "lib/c.lang"]);
+
+    let a = files.get("lib/a.lang").unwrap();
+    assert_eq!(a.imports, [Arc::from("b.lang"), Arc::from("lib/c.lang")]);
+    let items: Vec<Item> =
+        vec![Item::Import("../b.lang".into(), Span::new("lib/a.lang", 0..18)), Item::Import("./c.lang".into(), Span::new("lib/a.lang", 19..36))];
+    assert_eq!(a.node, Some(Module(items)));
+    assert_eq!(files.root().map(|file| &*file.name), Some("main.lang"));
+    assert_eq!(sources.get("lib/c.lang").map(|text| &**text), Some("import \"../b.lang\""));
+}
+
+#[test]
+fn cycles() {
+    let (files, _, loaded) =
+        parse(&[("a.lang", "import \"b.lang\""), ("b.lang", "import \"c.lang\" import \"a.lang\""), ("c.lang", "import \"c.lang\"")], "a.lang");
+    assert_eq!(names(&files), ["a.lang", "b.lang", "c.lang"]);
+    assert_eq!(loaded, ["a.lang", "b.lang", "c.lang"]);
+    // Reported at the import closing the cycle
+    assert_eq!(
+        errors(&files),
+        [
+            (Span::new("c.lang", 0..15), Some("import cycle: c.lang -> c.lang".into())),
+            (Span::new("b.lang", 16..31), Some("import cycle: a.lang -> b.lang -> a.lang".into())),
+        ]
+    );
+}
+
+#[test]
+fn failures() {
+    // Files that fail to load are reported at their import, but the rest is still parsed
+    let (files, sources, _) =
+        parse(&[("a.lang", "import \"b.lang\" import \"c.lang\" import \"d.lang\""), ("c.lang", "let ;"), ("d.lang", "let z;")], "a.lang");
+    assert_eq!(names(&files), ["a.lang", "c.lang", "d.lang"]);
+    let errs = errors(&files);
+    assert_eq!(errs.len(), 2, "{errs:?}");
+    assert_eq!(errs[0].0, Span::new("a.lang", 0..15));
+    assert!(errs[0].1.as_ref().is_some_and(|msg| msg.starts_with("failed to load \"b.lang\": ")), "{errs:?}");
+    // Syntax errors are reported in the file itself
+    assert_eq!((errs[1].0.source.as_ref(), errs[1].1.as_ref()), ("c.lang", None));
+    assert_eq!(files.get("c.lang").unwrap().node, None);
+    assert!(sources.get("c.lang").is_some() && sources.get("b.lang").is_none());
+
+    // Files that failed to load are only tried and reported once
+    let (files, _, loaded) = parse(&[("a.lang", "import \"b.lang\" import \"c.lang\""), ("c.lang", "import \"b.lang\"")], "a.lang");
+    assert_eq!(loaded, ["a.lang", "b.lang", "c.lang"]);
+    assert_eq!(errors(&files).iter().map(|(span, _)| span.clone()).collect::<Vec<Span>>(), [Span::new("a.lang", 0..15)]);
+
+    // So is the root
+    let (files, _, _) = parse(&[], "main.lang");
+    assert!(files.root().is_none());
+    assert!(matches!(errors(&files).as_slice(), [(span, Some(_))] if *span == Span::empty("main.lang", 0)));
+}
+
+#[test]
+fn deep() {
+    // Long chains of imports don't overflow the stack
+    const DEPTH: usize = 10_000;
+    let mut sources = Sources::new();
+    let files: Files<Module> = Driver::new(imports)
+        .with_loader(|name: &str| {
+            let i: usize = name.trim_end_matches(".lang").parse().unwrap();
+            Ok(if i < DEPTH { format!("import \"{}.lang\"", i + 1) } else { "import \"0.lang\"".into() })
+        })
+        .parse(&mut sources, "0.lang");
+    assert_eq!(files.files().len(), DEPTH + 1);
+    assert_eq!(files.files()[DEPTH].name.as_ref(), format!("{DEPTH}.lang"));
+    let errs = errors(&files);
+    assert!(matches!(errs.as_slice(), [(_, Some(msg))] if msg.starts_with("import cycle: 0.lang -> 1.lang -> ") && msg.ends_with(" -> 0.lang")));
+}
+
+#[test]
+fn filesystem() {
+    let dir = std::env::temp_dir().join(format!("ast-toolkit-2-import-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("lib")).unwrap();
+    std::fs::write(dir.join("main.lang"), "import \"lib/a.lang\"").unwrap();
+    std::fs::write(dir.join("lib/a.lang"), "let a;").unwrap();
+
+    let root: String = dir.join("main.lang").to_string_lossy().into_owned();
+    let mut sources = Sources::new();
+    let files: Files<Module> = Driver::new(imports).parse(&mut sources, &root);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(files.is_ok(), "{:?}", files.errors());
+    assert_eq!(files.files()[1].name.as_ref(), dir.join("lib/a.lang").to_string_lossy());
+    assert_eq!(files.files()[1].node, Some(Module(vec![Item::Let("a".into(), Span::new(files.files()[1].name.clone(), 5..6))])));
+}
+
+#[test]
+fn resolving() {
+    assert_eq!(resolve(None, "./main.lang"), "main.lang");
+    assert_eq!(resolve(Some("src/main.lang"), "lib/./a.lang"), "src/lib/a.lang");
+    assert_eq!(resolve(Some("src/main.lang"), "../a.lang"), "a.lang");
+    assert_eq!(resolve(Some("main.lang"), "../a.lang"), "../a.lang");
+    assert_eq!(resolve(Some("src/main.lang"), "/abs/../a.lang"), "/a.lang");
+}
@@ -0,0 +1,122 @@
+//  JSON.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:53:01
+//  Last edited:
+//    18 Oct 2026, 23:33:38
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests the rustc-compatible JSON output of diagnostics.
+//
+
+#![cfg(feature = "json")]
+
+use ast_toolkit_2::report::{Applicability, Diagnostic, Diagnostics, JsonDiagnostic, Label, Sources, Span, Suggestion};
+use serde_json::Value;
+
+
+/***** CONSTANTS *****/
+ast_toolkit_2::codes! {
+    static CODES;
+
+    E0001 {
+        title: "Test error",
+        explanation: "Raised by the tests.",
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns some sources to point into.
+///
+/// # Returns
+/// [`Sources`] with a file `a` with a multi-byte character on its first line.
+fn sources() -> Sources {
+    let mut sources = Sources::new();
+    sources.add("a", "let é = 1;\nlet y = é;\n");
+    sources
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn spans() {
+    let diag: Diagnostic = Diagnostic::error("Oops", Span::new("a", 19..20))
+        .with_code("E0001")
+        .with_primary_message("here")
+        .with_label(Label::with_message(Span::new("a", 4..5), "defined here"))
+        .with_note("A note")
+        .with_suggestion(Suggestion::new("Use one", Span::new("a", 19..20), "1", Applicability::MachineApplicable));
+    let json: JsonDiagnostic = JsonDiagnostic::from_diagnostic(&diag, &sources(), false);
+    assert_eq!(json.message_type.as_deref(), Some("diagnostic"));
+    assert_eq!(json.message, "Oops");
+    assert_eq!(json.level, "error");
+    assert_eq!(json.code.as_ref().map(|code| code.code.as_str()), Some("E0001"));
+
+    // The primary span comes first, with byte offsets past the multi-byte character
+    assert_eq!(json.spans.len(), 2);
+    let primary = &json.spans[0];
+    assert!(primary.is_primary);
+    assert_eq!((primary.byte_start, primary.byte_end), (20, 22));
+    assert_eq!((primary.line_start, primary.column_start, primary.line_end, primary.column_end), (2, 9, 2, 10));
+    assert_eq!(primary.text[0].text, "let y = é;");
+    assert_eq!(primary.label.as_deref(), Some("here"));
+    let label = &json.spans[1];
+    assert!(!label.is_primary);
+    assert_eq!((label.byte_start, label.byte_end), (4, 6));
+    assert_eq!((label.line_start, label.column_start), (1, 5));
+
+    // Notes and suggestions become children
+    assert_eq!(json.children.len(), 2);
+    assert_eq!((json.children[0].level.as_str(), json.children[0].message.as_str()), ("note", "A note"));
+    assert_eq!(json.children[1].level, "help");
+    assert_eq!(json.children[1].spans[0].suggested_replacement.as_deref(), Some("1"));
+    assert_eq!(json.children[1].spans[0].suggestion_applicability.as_deref(), Some("MachineApplicable"));
+}
+
+#[test]
+fn render_json() {
+    let mut diags = Diagnostics::new().with_color(false).with_registry(&CODES);
+    diags.emit(Diagnostic::warning("Second", Span::new("a", 11..14)));
+    diags.emit(Diagnostic::error("First", Span::new("a", 0..3)).with_code("E0001").with_note("A note").with_suggestion(Suggestion::new(
+        "Drop it",
+        Span::new("a", 0..4),
+        "",
+        Applicability::MaybeIncorrect,
+    )));
+    let mut buf: Vec<u8> = Vec::new();
+    diags.render_json(&sources(), &mut buf).unwrap();
+    let text: String = String::from_utf8(buf).unwrap();
+
+    // Only toplevel diagnostics are rendered, which includes their children
+    assert_eq!(text.matches("\"rendered\":\"").count(), 2, "{text}");
+    assert_eq!(text.matches("\"rendered\":null").count(), 2, "{text}");
+    assert_eq!(text.matches("A note").count(), 2, "{text}");
+
+    // One object per line, in order of position
+    let lines: Vec<Value> = text.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["message"], "First");
+    assert_eq!(lines[0]["code"]["code"], "E0001");
+    assert!(lines[0]["code"]["explanation"].as_str().unwrap().contains("Raised by the tests."));
+    assert!(lines[0]["rendered"].as_str().unwrap().contains("First"));
+    assert!(lines[0]["rendered"].as_str().unwrap().contains("A note"));
+    assert_eq!((&lines[0]["children"][0]["rendered"], &lines[0]["children"][1]["rendered"]), (&Value::Null, &Value::Null));
+    assert_eq!(lines[1]["message"], "Second");
+    assert_eq!(lines[1]["level"], "warning");
+    assert_eq!(lines[1]["code"], Value::Null);
+    assert_eq!(lines[1]["spans"][0]["expansion"], Value::Null);
+
+    // And they can be read back
+    let json: JsonDiagnostic = serde_json::from_value(lines[1].clone()).unwrap();
+    assert_eq!(json.spans[0].file_name, "a");
+}
@@ -0,0 +1,209 @@
+//  LEX.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 22:36:35
+//  Last edited:
+//    18 Oct 2026, 23:52:17
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests lexing text into tokens with the [`Lex`] derive, and parsing
+//!   nodes from them.
+//
+
+#![cfg(feature = "parser")]
+
+use std::ops::Range;
+
+use ast_toolkit_2::parser::chumsky::error::SimpleReason;
+use ast_toolkit_2::parser::chumsky::prelude::*;
+use ast_toolkit_2::parser::lex::{Pattern, accepts, tokenize};
+use ast_toolkit_2::parser::{Error, Lex, Parse, Span};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Lexes a text.
+///
+/// # Arguments
+/// - `text`: The source text to lex.
+///
+/// # Returns
+/// The tokens found, with their ranges.
+fn lex(text: &str) -> Vec<(Token, Range<usize>)> { tokenize::<Token>("a".into(), text).0.into_iter().map(|(token, span)| (token, span.range())).collect() }
+
+/// Lexes a text, and only keeps the errors.
+///
+/// # Arguments
+/// - `text`: The source text to lex.
+///
+/// # Returns
+/// The ranges and messages of the errors found.
+fn errors(text: &str) -> Vec<(Range<usize>, String)> {
+    tokenize::<Token>("a".into(), text)
+        .1
+        .iter()
+        .map(|err| match err.reason() {
+            SimpleReason::Custom(msg) => (err.span().range(), msg.clone()),
+            reason => panic!("Expected a custom error, got {reason:?}"),
+        })
+        .collect()
+}
+
+/// Matches a pattern at the start of a text.
+///
+/// # Arguments
+/// - `pattern`: The pattern to match.
+/// - `text`: The text to match it against.
+///
+/// # Returns
+/// The length of the longest match, if any.
+fn longest(pattern: &str, text: &str) -> Option<usize> {
+    let chars: Vec<char> = text.chars().collect();
+    Pattern::new(pattern).unwrap_or_else(|err| panic!("{err}")).longest(&chars, 0)
+}
+
+/// Parses an identifier token.
+///
+/// # Returns
+/// A [`Parser`] producing the identifier's text.
+#[allow(clippy::result_large_err)]
+fn ident() -> impl Parser<Token, String, Error = Error<Token>> + Clone {
+    select! { Token::Ident(name) => name }
+}
+
+/// Parses an integer token.
+///
+/// # Returns
+/// A [`Parser`] producing the integer's value.
+#[allow(clippy::result_large_err)]
+fn int() -> impl Parser<Token, u8, Error = Error<Token>> + Clone {
+    select! { Token::Int(value) => value }
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// The tokens of a small language.
+#[derive(Clone, Debug, Eq, Hash, Lex, PartialEq)]
+#[lex(skip = r"\s+", skip = "//[^\n]*")]
+enum Token {
+    #[token("let")]
+    Let,
+    #[token("=")]
+    #[token(":=")]
+    Eq,
+    #[token("==")]
+    EqEq,
+    #[token(";")]
+    Semi,
+    #[regex("[a-z_][a-z0-9_]*")]
+    Ident(String),
+    #[regex(r"\d+")]
+    Int(u8),
+    #[regex(r#""([^"\\]|\\.)*""#)]
+    Str,
+}
+
+/// A statement, parsed from tokens.
+#[derive(Debug, Parse, PartialEq)]
+#[parse(input = Token)]
+enum Stmt {
+    #[parse(keyword = "let")]
+    Let(#[parse(with = ident)] String, #[parse(punct = "=")] Span, #[parse(with = int)] u8, #[parse(punct = ";")] Span),
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn tokens() {
+    // The longest match wins, then literals, then earlier rules
+    assert_eq!(
+        lex("let lets == = x:=1"),
+        [
+            (Token::Let, 0..3),
+            (Token::Ident("lets".into()), 4..8),
+            (Token::EqEq, 9..11),
+            (Token::Eq, 12..13),
+            (Token::Ident("x".into()), 14..15),
+            (Token::Eq, 15..17),
+            (Token::Int(1), 17..18),
+        ]
+    );
+    assert!(errors("let lets == = x:=1").is_empty());
+
+    // Skipped text is dropped
+    assert_eq!(lex("  x // y\n\t\"a \\\" b\" "), [(Token::Ident("x".into()), 2..3), (Token::Str, 10..18)]);
+}
+
+#[test]
+fn unknown() {
+    // Runs of unknown characters are one error, and fields that don't parse are another
+    assert_eq!(lex("x #$ y 300;"), [(Token::Ident("x".into()), 0..1), (Token::Ident("y".into()), 5..6), (Token::Semi, 10..11)]);
+    let errs: Vec<(Range<usize>, String)> = errors("x #$ y 300;");
+    assert_eq!(errs.iter().map(|(range, _)| range.clone()).collect::<Vec<_>>(), [2..4, 7..10]);
+    assert_eq!(errs[0].1, "Unexpected character(s) \"#$\"");
+    assert!(errs[1].1.starts_with("Invalid Int \"300\": "), "{}", errs[1].1);
+    // Also at the end
+    assert_eq!(errors("x @@"), [(2..4, "Unexpected character(s) \"@@\"".into())]);
+}
+
+#[test]
+fn literals() {
+    assert_eq!(Token::Eq.literal(), Some("="));
+    assert_eq!(Token::Ident("x".into()).literal(), None);
+    assert_eq!(Token::from_literal(":="), Some(Token::Eq));
+    assert_eq!(Token::from_literal("x"), None);
+    // Only literals are tokens, while characters are anything
+    assert!(accepts::<Token>(":="));
+    assert!(!accepts::<Token>(":"));
+    assert!(!accepts::<Token>("x"));
+    assert!(accepts::<char>(":"));
+    // Tokens are displayed as they're written, or by name if they can't be
+    let texts: Vec<String> = [Token::Let, Token::Eq, Token::Ident("x".into()), Token::Int(4), Token::Str].iter().map(Token::to_string).collect();
+    assert_eq!(texts, ["let", "=", "x", "4", "Str"]);
+}
+
+#[test]
+fn patterns() {
+    assert_eq!(longest("ab|a", "abc"), Some(2));
+    assert_eq!(longest("(ab)*", "ababa"), Some(4));
+    assert_eq!(longest("a?b+", "bbbc"), Some(3));
+    assert_eq!(longest("[^a-c]+", "xyza"), Some(3));
+    assert_eq!(longest(r"[\d_]+", "1_2a"), Some(3));
+    assert_eq!(longest(r"\W\S", "- "), None);
+    assert_eq!(longest(".*", "ab\ncd"), Some(2));
+    assert_eq!(longest(r"\*\n", "*\n"), Some(2));
+    // Empty matches don't count
+    assert_eq!(longest("a*", "b"), None);
+
+    // Malformed patterns are reported with the same messages as the derive does at compile time
+    let error = |pattern: &str| Pattern::new(pattern).unwrap_err().to_string();
+    assert_eq!(error("[a-z"), "Unexpected end of pattern \"[a-z\" (expected ']')");
+    assert_eq!(error("(ab"), "Unexpected end of pattern \"(ab\" (expected ')')");
+    assert_eq!(error("ab)"), "Unexpected ')' at position 2 in pattern \"ab)\" (expected end of pattern)");
+    assert_eq!(error("a**"), "Unexpected '*' at position 2 in pattern \"a**\" (expected an atom before a quantifier)");
+    assert_eq!(error(r"[\S]"), "Unexpected 'S' at position 2 in pattern \"[\\\\S]\" (expected no negated escapes in a class)");
+    assert_eq!(error("x\\"), "Unexpected end of pattern \"x\\\\\" (expected an escaped character)");
+    // Escapes of letters other than the known ones, and reversed ranges
+    assert_eq!(error(r"\bx"), "Unexpected 'b' at position 1 in pattern \"\\\\bx\" (expected d, D, w, W, s, S, n, r, t or punctuation after '\\')");
+    assert_eq!(error(r"[\x41]"), "Unexpected 'x' at position 2 in pattern \"[\\\\x41]\" (expected d, D, w, W, s, S, n, r, t or punctuation after '\\')");
+    assert_eq!(error("[a-cz-a]"), "Reversed range 'z'-'a' at position 4 in pattern \"[a-cz-a]\"");
+}
+
+#[test]
+fn parse() {
+    let stmt: Stmt = Stmt::parse_str("a", "let x\n  = 4 ;").unwrap();
+    assert_eq!(stmt, Stmt::Let("x".into(), Span::new("a", 8..9), 4, Span::new("a", 12..13)));
+    // Keywords are tokens, so they aren't identifiers
+    assert!(Stmt::parse_str("a", "let let = 4;").is_err());
+    // Lexing errors are reported before parsing errors
+    let errors: Vec<Error<Token>> = Stmt::parse_str("a", "let x = 4 #;").unwrap_err();
+    assert_eq!(errors.first().map(Error::span), Some(Span::new("a", 10..11)));
+}
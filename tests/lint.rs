@@ -0,0 +1,136 @@
+//  LINT.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:57:04
+//  Last edited:
+//    18 Oct 2026, 21:57:04
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests [`Lint`]s, parsing a [`LintConfig`] and how the diagnostics sink
+//!   applies it.
+//
+
+#![cfg(feature = "report")]
+
+use ast_toolkit_2::report::lint::Error;
+use ast_toolkit_2::report::{Diagnostic, Diagnostics, Level, Lint, LintConfig, Severity, Span};
+
+
+/***** CONSTANTS *****/
+/// A lint that warns by default.
+static UNUSED: Lint = Lint::new("unused", Level::Warn, "Detects names that are never used.");
+
+/// A lint that is silent by default.
+static PEDANTIC: Lint = Lint::new("pedantic", Level::Allow, "Detects things nobody cares about.");
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Emits one diagnostic for each lint, and one plain warning, with the given configuration.
+///
+/// # Arguments
+/// - `lints`: The [`LintConfig`] to apply.
+///
+/// # Returns
+/// The [`Diagnostics`] they were emitted to.
+fn emit(lints: LintConfig) -> Diagnostics {
+    let mut diags = Diagnostics::new().with_lints(lints);
+    diags.emit(Diagnostic::warning("Unused 'x'", Span::new("a", 0..1)).with_lint(&UNUSED));
+    diags.emit(Diagnostic::warning("Pedantic", Span::new("a", 1..2)).with_lint(&PEDANTIC));
+    diags.emit(Diagnostic::warning("Plain", Span::new("a", 2..3)));
+    diags
+}
+
+/// Finds the severity and notes of an emitted diagnostic by message.
+///
+/// # Arguments
+/// - `diags`: The [`Diagnostics`] to search.
+/// - `message`: The message of the diagnostic.
+///
+/// # Returns
+/// Its [`Severity`] and notes, or [`None`] if it was silenced.
+fn find(diags: &Diagnostics, message: &str) -> Option<(Severity, Vec<String>)> {
+    diags.sorted().into_iter().find(|diag| diag.message == message).map(|diag| (diag.severity, diag.notes.clone()))
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn from_args() {
+    let config: LintConfig = LintConfig::from_args(["-Dunused", "--allow", "pedantic", "-W", "other", "--deny=more"]).unwrap();
+    assert_eq!(config.level(&UNUSED), Level::Deny);
+    assert_eq!(config.level(&PEDANTIC), Level::Allow);
+    assert!(config.is_configured(&PEDANTIC));
+    let lints: [&Lint; 2] = [&UNUSED, &PEDANTIC];
+    let mut unknown: Vec<&str> = config.unknown(&lints).collect();
+    unknown.sort();
+    assert_eq!(unknown, ["more", "other"]);
+
+    // Later flags override earlier ones
+    let config: LintConfig = LintConfig::from_args(["-D", "unused", "-A", "unused"]).unwrap();
+    assert_eq!(config.level(&UNUSED), Level::Allow);
+    assert_eq!(LintConfig::new().level(&PEDANTIC), Level::Allow);
+}
+
+#[test]
+fn from_args_errors() {
+    assert!(matches!(LintConfig::from_args(["-X", "unused"]), Err(Error::UnknownFlag { flag }) if flag == "-X"));
+    assert!(matches!(LintConfig::from_args(["--forbid=unused"]), Err(Error::UnknownFlag { flag }) if flag == "--forbid"));
+    assert!(matches!(LintConfig::from_args(["-D"]), Err(Error::MissingLint { flag }) if flag == "-D"));
+    // Flags aren't split inside a multi-byte character
+    assert!(matches!(LintConfig::from_args(["€x"]), Err(Error::UnknownFlag { flag }) if flag == "€x"));
+    assert!(matches!(LintConfig::from_args(["-€"]), Err(Error::UnknownFlag { flag }) if flag == "-€"));
+}
+
+#[test]
+fn from_config() {
+    let config: LintConfig = LintConfig::from_config("# Lints\n[lints]\nunused = \"deny\" # no more\n\nwarnings = \"allow\"\n").unwrap();
+    assert_eq!(config.level(&UNUSED), Level::Deny);
+    assert_eq!(config.warnings(), Some(Level::Allow));
+    assert!(matches!(LintConfig::from_config("unused = \"forbid\""), Err(Error::UnknownLevel { raw }) if raw == "forbid"));
+    assert!(matches!(LintConfig::from_config("\nunused"), Err(Error::IllegalLine { line: 2, .. })));
+
+    // Flags override the file
+    let config: LintConfig = config.with_overrides(LintConfig::from_args(["-W", "unused"]).unwrap());
+    assert_eq!(config.level(&UNUSED), Level::Warn);
+    assert_eq!(config.warnings(), Some(Level::Allow));
+}
+
+#[test]
+fn apply() {
+    let diags: Diagnostics = emit(LintConfig::new());
+    assert_eq!(find(&diags, "Unused 'x'"), Some((Severity::Warning, vec!["`-W unused` on by default".into()])));
+    assert_eq!(find(&diags, "Pedantic"), None);
+    assert_eq!(find(&diags, "Plain"), Some((Severity::Warning, vec![])));
+
+    let diags: Diagnostics = emit(LintConfig::from_args(["-D", "unused", "-W", "pedantic"]).unwrap());
+    assert_eq!(find(&diags, "Unused 'x'"), Some((Severity::Error, vec!["`-D unused` set by the lint configuration".into()])));
+    assert_eq!(find(&diags, "Pedantic"), Some((Severity::Warning, vec!["`-W pedantic` set by the lint configuration".into()])));
+    assert_eq!(diags.count(Severity::Error), 1);
+}
+
+#[test]
+fn deny_warnings() {
+    let diags: Diagnostics = emit(LintConfig::from_args(["-D", "warnings"]).unwrap());
+    assert_eq!(find(&diags, "Unused 'x'"), Some((Severity::Error, vec!["`-D unused` implied by `-D warnings`".into()])));
+    assert_eq!(find(&diags, "Pedantic"), None);
+    assert_eq!(find(&diags, "Plain"), Some((Severity::Error, vec![])));
+    assert_eq!(diags.count(Severity::Warning), 0);
+    assert_eq!(diags.count(Severity::Error), 2);
+
+    // Same as the builder
+    let diags: Diagnostics = emit(LintConfig::new().with_warnings_as_errors());
+    assert_eq!(diags.count(Severity::Error), 2);
+
+    // And `-A warnings` silences them
+    let diags: Diagnostics = emit(LintConfig::from_args(["-A", "warnings"]).unwrap());
+    assert!(diags.is_empty());
+}
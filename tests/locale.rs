@@ -0,0 +1,147 @@
+//  LOCALE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:59:56
+//  Last edited:
+//    18 Oct 2026, 21:59:56
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests localized reports, resolving them in [`Locales`] and verifying
+//!   [`Bundle`]s against them.
+//
+
+#![cfg(feature = "report")]
+
+use ast_toolkit_2::report::locale::Error;
+use ast_toolkit_2::report::{Bundle, Diagnostic, Diagnostics, Locales, Localized, Report, Span};
+
+
+/***** CONSTANTS *****/
+/// The English bundle, which defines everything.
+const EN: &str = r#"
+# Terms
+-lang = Lang
+
+dup-def = the name `{ $name }` is defined multiple times
+    .redefined = `{ $name }` redefined here
+    .first = previous definition of `{ $name }` here
+dup-def-note = { -lang } does not allow shadowing
+dup-def-help = rename one of the `{ $name }`s
+unknown = unknown name `{ $name }`
+"#;
+
+/// The Dutch bundle, which only translates the main message of duplicates.
+const NL: &str = r#"
+dup-def = de naam `{ $name }` is meerdere keren gedefinieerd
+"#;
+
+
+
+
+
+/***** HELPERS *****/
+/// A localized struct with nested IDs in its labels, notes and helps.
+#[derive(Report)]
+#[report(message_id = "dup-def")]
+#[note("dup-def-note")]
+#[help("dup-def-help")]
+struct DuplicateDef {
+    #[arg]
+    name:   String,
+    #[label(primary, "dup-def.redefined")]
+    second: Span,
+    #[label("dup-def.first")]
+    first:  Span,
+}
+
+/// A localized enum, with IDs per variant.
+#[derive(Report)]
+#[report(message_id = "dup-def")]
+enum Problem {
+    Duplicate {
+        #[arg]
+        name: String,
+        #[label(primary, "dup-def.first")]
+        span: Span,
+    },
+    #[report(message_id = "unknown")]
+    Unknown(#[arg("name")] String, #[label(primary)] Span),
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn message_ids() {
+    assert_eq!(DuplicateDef::MESSAGE_IDS, ["dup-def", "dup-def.redefined", "dup-def.first", "dup-def-note", "dup-def-help"]);
+    let mut ids: Vec<&str> = Problem::MESSAGE_IDS.to_vec();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids, ["dup-def", "dup-def.first", "unknown"]);
+}
+
+#[test]
+fn verify() {
+    let en: Bundle = Bundle::parse("en", EN).unwrap();
+    en.verify::<DuplicateDef>().unwrap();
+    en.verify::<Problem>().unwrap();
+
+    // Missing IDs are all listed, including attributes of messages that do exist
+    let partial: Bundle = Bundle::parse("en", "dup-def = duplicate\n    .first = first\n").unwrap();
+    match partial.verify::<DuplicateDef>() {
+        Err(Error::MissingIds { locale, ids }) => {
+            assert_eq!(locale, "en");
+            assert_eq!(ids, ["dup-def.redefined", "dup-def-note", "dup-def-help"]);
+        },
+        res => panic!("Expected missing IDs, got {res:?}"),
+    }
+    assert!(matches!(Bundle::parse("nl", NL).unwrap().verify::<Problem>(), Err(Error::MissingIds { ids, .. }) if ids == ["dup-def.first", "unknown"]));
+}
+
+#[test]
+fn localize() {
+    let err = DuplicateDef { name: "x".into(), second: Span::new("a", 10..11), first: Span::new("a", 4..5) };
+    let diag: Diagnostic = err.to_diagnostic();
+    assert_eq!(diag.message, "dup-def");
+    assert_eq!(diag.args.as_ref().and_then(|args| args.get("name")).map(String::as_str), Some("x"));
+
+    // Messages are resolved in the preferred locale, falling back to English
+    let locales: Locales = Locales::new(Bundle::parse("en", EN).unwrap()).with_locale(Bundle::parse("nl", NL).unwrap());
+    let diag: Diagnostic = diag.localize(&locales);
+    assert_eq!(diag.message, "de naam `x` is meerdere keren gedefinieerd");
+    assert_eq!(diag.primary.message.as_deref(), Some("`x` redefined here"));
+    assert_eq!(diag.labels[0].message.as_deref(), Some("previous definition of `x` here"));
+    assert_eq!(diag.notes, ["Lang does not allow shadowing"]);
+    assert_eq!(diag.helps, ["rename one of the `x`s"]);
+    assert_eq!(diag.args, None);
+}
+
+#[test]
+fn sink() {
+    let mut diags = Diagnostics::new().with_locales(Locales::new(Bundle::parse("en", EN).unwrap()));
+    diags.emit(Problem::Unknown("y".into(), Span::new("a", 0..1)));
+    diags.emit(Problem::Duplicate { name: "x".into(), span: Span::new("a", 2..3) });
+    let messages: Vec<String> = diags.localized().into_iter().map(|diag| diag.message.clone()).collect();
+    assert_eq!(messages, ["unknown name `y`", "the name `x` is defined multiple times"]);
+
+    // Without locales, the IDs are kept
+    let mut diags = Diagnostics::new();
+    diags.emit(Problem::Unknown("y".into(), Span::new("a", 0..1)));
+    assert_eq!(diags.localized()[0].message, "unknown");
+}
+
+#[test]
+fn parse_errors() {
+    assert!(matches!(Bundle::parse("en", "a = 1\na = 2\n"), Err(Error::DuplicateEntry { line: 2, .. })));
+    assert!(matches!(Bundle::parse("en", "a = { $x\n"), Err(Error::UnterminatedPlaceable { line: 1 })));
+    assert!(matches!(Bundle::parse("en", "a = { 1 + 1 }\n"), Err(Error::IllegalPlaceable { line: 1, .. })));
+    assert!(matches!(Bundle::parse("en", "1a = b\n"), Err(Error::IllegalIdentifier { line: 1, .. })));
+    assert!(matches!(Bundle::parse("en", "    .a = b\n"), Err(Error::IllegalLine { line: 1, .. })));
+    // Literals may contain braces
+    assert_eq!(Bundle::parse("en", "a = { \"{\" }b\n").unwrap().format("a", &Default::default()).as_deref(), Some("{b"));
+}
@@ -0,0 +1,182 @@
+//  MEMO.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 22:46:20
+//  Last edited:
+//    18 Oct 2026, 22:46:20
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests packrat memoisation of derived parsers with `#[parse(memo)]`.
+//
+
+#![cfg(feature = "parser")]
+
+use std::cell::Cell;
+use std::fmt::Debug;
+
+use ast_toolkit_2::parser::chumsky::prelude::*;
+use ast_toolkit_2::parser::chumsky::text;
+use ast_toolkit_2::parser::memo::scope;
+use ast_toolkit_2::parser::{Error, Parse, Span, stream};
+
+
+/***** CONSTANTS *****/
+thread_local! {
+    /// How many identifiers were parsed on this thread.
+    static IDENTS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Texts that parse, and some that don't.
+const TEXTS: [&str; 10] = ["x", "x!", "(x)", "((x)!)", "(((x!)!)!)!", "( (x) ! )", "", "(x", "x!!", "((x)!)y"];
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses an identifier, and counts that it did.
+///
+/// # Returns
+/// A [`Parser`] producing the identifier's text.
+fn ident() -> impl Parser<char, String, Error = Error> + Clone {
+    text::ident().map(|name: String| {
+        IDENTS.set(IDENTS.get() + 1);
+        name
+    })
+}
+
+/// Parses a text, and counts how many identifiers were parsed while doing so.
+///
+/// # Arguments
+/// - `text`: The source text to parse.
+///
+/// # Returns
+/// The parsed node or errors, and the number of identifiers parsed.
+fn count<T: Parse>(text: &str) -> (Result<T, Vec<Error>>, usize) {
+    IDENTS.set(0);
+    let res = T::parse_str("a", text);
+    (res, IDENTS.get())
+}
+
+/// Parses a text without a scope of its own.
+///
+/// # Arguments
+/// - `text`: The source text to parse.
+///
+/// # Returns
+/// Whether the text was a [`memo::Term`].
+fn parse(text: &str) -> bool { memo::Term::parser().then_ignore(end()).parse(stream("a", text)).is_ok() }
+
+/// Summarizes the result of a parse.
+///
+/// # Arguments
+/// - `res`: The result to summarize.
+///
+/// # Returns
+/// The [`Debug`]-representation of the node, or the spans of the errors.
+fn summary<T: Debug>(res: &Result<T, Vec<Error>>) -> Result<String, Vec<Span>> {
+    match res {
+        Ok(node) => Ok(format!("{node:?}")),
+        Err(errs) => Err(errs.iter().map(Error::span).collect()),
+    }
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// The grammar without memoisation.
+mod plain {
+    use super::*;
+
+    /// An atom, which `Term` parses twice.
+    #[derive(Clone, Debug, Parse, PartialEq)]
+    pub enum Atom {
+        Paren(#[parse(delim("(", ")"))] Box<Term>),
+        Var(#[parse(with = ident)] String),
+    }
+
+    /// A term, whose variants share the whole atom as prefix.
+    #[derive(Clone, Debug, Parse, PartialEq)]
+    pub enum Term {
+        Call(Atom, #[parse(punct = "!")] Span),
+        Plain(Atom),
+    }
+}
+
+/// The same grammar, but with memoisation.
+mod memo {
+    use super::*;
+
+    /// An atom, which `Term` parses twice.
+    #[derive(Clone, Debug, Parse, PartialEq)]
+    #[parse(memo)]
+    pub enum Atom {
+        Paren(#[parse(delim("(", ")"))] Box<Term>),
+        Var(#[parse(with = ident)] String),
+    }
+
+    /// A term, whose variants share the whole atom as prefix.
+    #[derive(Clone, Debug, Parse, PartialEq)]
+    pub enum Term {
+        Call(Atom, #[parse(punct = "!")] Span),
+        Plain(Atom),
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn equivalent() {
+    for text in TEXTS {
+        let (plain, _) = count::<plain::Term>(text);
+        let (memo, _) = count::<memo::Term>(text);
+        assert_eq!(summary(&memo), summary(&plain), "{text:?}");
+    }
+}
+
+#[test]
+fn linear() {
+    // Without memoisation, every level doubles the work
+    for depth in 0..8 {
+        let text: String = format!("{}x{}", "(".repeat(depth), ")".repeat(depth));
+        let (plain, plain_idents) = count::<plain::Term>(&text);
+        let (memo, memo_idents) = count::<memo::Term>(&text);
+        assert_eq!(summary(&memo), summary(&plain));
+        assert_eq!((plain_idents, memo_idents), (1 << (depth + 1), 1), "Depth {depth}");
+    }
+}
+
+#[test]
+fn scopes() {
+    // Outside of a scope, memoised parsers simply parse
+    IDENTS.set(0);
+    assert!(parse("(x)"));
+    assert_eq!(IDENTS.get(), 4);
+
+    // Inside one, they remember across parses, so every scope should parse a single input
+    IDENTS.set(0);
+    assert!(scope(|| parse("(x)") && parse("(x)")));
+    assert_eq!(IDENTS.get(), 1);
+    // But nested scopes start over, and are forgotten afterwards
+    IDENTS.set(0);
+    scope(|| {
+        assert!(parse("x"));
+        scope(|| assert!(parse("x")));
+        assert!(parse("x"));
+    });
+    assert_eq!(IDENTS.get(), 2);
+
+    // Even when panicking
+    assert!(std::panic::catch_unwind(|| scope(|| panic!("Oops"))).is_err());
+    IDENTS.set(0);
+    assert!(parse("x") && parse("x"));
+    assert_eq!(IDENTS.get(), 4);
+}
@@ -0,0 +1,122 @@
+//  MULTIFILE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:59:20
+//  Last edited:
+//    18 Oct 2026, 21:59:20
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests diagnostics with labels in multiple source files.
+//
+
+#![cfg(feature = "report")]
+
+use ast_toolkit_2::report::{Diagnostic, Label, RenderConfig, Renderer, Report, RustcRenderer, Sources, Span};
+
+
+/***** HELPERS *****/
+/// An error with labels in any file.
+#[derive(Report)]
+#[report(message = "Duplicate definition of '{name}'")]
+struct Duplicate {
+    name:   String,
+    #[label(primary, "defined again here")]
+    second: Span,
+    #[label("first defined here")]
+    first:  Span,
+    #[label(each, "also used here")]
+    uses:   Vec<Span>,
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns some sources to point into.
+///
+/// # Returns
+/// [`Sources`] with a file `main.lang` and a file `lib.lang`.
+fn sources() -> Sources {
+    let mut sources = Sources::new();
+    sources.add("main.lang", "import lib;\nlet x = 1;\nprint(x);\n");
+    sources.add("lib.lang", "let x = 2;\nprint(x);\n");
+    sources
+}
+
+/// Creates a duplicate-definition error spanning both files (and one unknown file).
+///
+/// # Returns
+/// A new [`Duplicate`].
+fn duplicate() -> Duplicate {
+    Duplicate {
+        name:   "x".into(),
+        second: Span::new("main.lang", 16..17),
+        first:  Span::new("lib.lang", 4..5),
+        uses:   vec![Span::new("lib.lang", 17..18), Span::new("std.lang", 10..11), Span::new("main.lang", 29..30)],
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn derive() {
+    let err: Duplicate = duplicate();
+    assert_eq!(err.primary(), Label::with_message(Span::new("main.lang", 16..17), "defined again here"));
+    assert_eq!(
+        err.labels(),
+        vec![
+            Label::with_message(Span::new("lib.lang", 4..5), "first defined here"),
+            Label::with_message(Span::new("lib.lang", 17..18), "also used here"),
+            Label::with_message(Span::new("std.lang", 10..11), "also used here"),
+            Label::with_message(Span::new("main.lang", 29..30), "also used here"),
+        ]
+    );
+}
+
+#[test]
+fn sort_labels() {
+    // The primary file comes first, then the others in order of appearance
+    let mut diag: Diagnostic = duplicate().to_diagnostic();
+    diag.sort_labels();
+    let spans: Vec<Span> = diag.labels.iter().map(|label| label.span.clone()).collect();
+    assert_eq!(spans, [Span::new("main.lang", 29..30), Span::new("lib.lang", 4..5), Span::new("lib.lang", 17..18), Span::new("std.lang", 10..11)]);
+
+    // Labels in unknown files become notes
+    diag.detach_unknown_labels(&sources());
+    assert_eq!(diag.labels.len(), 3);
+    assert_eq!(diag.notes, ["also used here (std.lang:10..11)"]);
+}
+
+#[test]
+fn render() {
+    let mut buf: Vec<u8> = Vec::new();
+    RustcRenderer.render(&duplicate().to_diagnostic(), &sources(), &RenderConfig { color: false, width: None }, &mut buf).unwrap();
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        "error: Duplicate definition of 'x'
+ --> main.lang:2:5
+  |
+2 | let x = 1;
+  |     ^ defined again here
+3 | print(x);
+  |       - also used here
+  |
+ ::: lib.lang:1:5
+  |
+1 | let x = 2;
+  |     - first defined here
+2 | print(x);
+  |       - also used here
+  |
+  = note: also used here (std.lang:10..11)
+
+"
+    );
+}
@@ -0,0 +1,114 @@
+//  PARSE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 22:00:35
+//  Last edited:
+//    18 Oct 2026, 22:00:35
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests the [`Parse`]-trait and its derive macro.
+//
+
+#![cfg(feature = "parser")]
+
+use ast_toolkit_2::parser::chumsky::prelude::*;
+use ast_toolkit_2::parser::chumsky::text;
+use ast_toolkit_2::parser::{Error, Parse, Span};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses an identifier.
+///
+/// # Returns
+/// A [`Parser`] producing the identifier's text.
+fn ident() -> impl Parser<char, String, Error = Error> + Clone { text::ident() }
+
+/// Parses a decimal integer.
+///
+/// # Returns
+/// A [`Parser`] producing the integer's value.
+fn int() -> impl Parser<char, u64, Error = Error> + Clone { text::int(10).from_str().unwrapped() }
+
+
+
+
+
+/***** HELPERS *****/
+/// A struct parsed as a sequence, with keywords, punctuation and custom fields.
+#[derive(Debug, PartialEq, Parse)]
+#[parse(keyword = "let")]
+struct Let {
+    #[parse(with = ident)]
+    name:  String,
+    #[parse(punct = "=")]
+    eq:    Span,
+    value: Value,
+    #[parse(punct = ";")]
+    semi:  Span,
+    #[parse(span)]
+    span:  Span,
+}
+
+/// An enum parsed as a choice, with delimited and separated fields.
+#[derive(Debug, PartialEq, Parse)]
+enum Value {
+    #[parse(delim("[", "]"))]
+    List(#[parse(sep = ",", trailing)] Vec<Value>),
+    Tuple(#[parse(delim("(", ")"), sep = ",")] Vec<Value>),
+    #[parse(keyword = "none")]
+    None,
+    Int(#[parse(with = int)] u64),
+    Var(#[parse(with = ident)] String),
+}
+
+/// A struct with optional and repeated fields.
+#[derive(Debug, PartialEq, Parse)]
+struct Block(#[parse(delim("{", "}"))] Vec<Let>, Option<Value>);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn sequence() {
+    assert_eq!(
+        Let::parse_str("a", "  let x = 42 ;\n").unwrap(),
+        Let { name: "x".into(), eq: Span::new("a", 8..9), value: Value::Int(42), semi: Span::new("a", 13..14), span: Span::new("a", 2..14) }
+    );
+}
+
+#[test]
+fn choice() {
+    assert_eq!(Value::parse_str("a", "none").unwrap(), Value::None);
+    // Keywords don't match a prefix of an identifier
+    assert_eq!(Value::parse_str("a", "nonesuch").unwrap(), Value::Var("nonesuch".into()));
+    assert_eq!(
+        Value::parse_str("a", "[1, (x, [ ]), none,]").unwrap(),
+        Value::List(vec![Value::Int(1), Value::Tuple(vec![Value::Var("x".into()), Value::List(vec![])]), Value::None])
+    );
+    // Trailing separators are only allowed where marked
+    assert!(Value::parse_str("a", "(1, 2,)").is_err());
+}
+
+#[test]
+fn repeated() {
+    let block: Block = Block::parse_str("a", "{ let x = 1; let y = x; } none").unwrap();
+    assert_eq!(block.0.iter().map(|stmt| stmt.name.as_str()).collect::<Vec<_>>(), ["x", "y"]);
+    assert_eq!(block.1, Some(Value::None));
+    assert_eq!(Block::parse_str("a", "{}").unwrap(), Block(vec![], None));
+}
+
+#[test]
+fn errors() {
+    let errs: Vec<Error> = Let::parse_str("a", "let x = 1").unwrap_err();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].span(), Span::empty("a", 9));
+    assert!(errs[0].expected().any(|token| *token == Some(';')));
+
+    let errs: Vec<Error> = Let::parse_str("a", "let x = 1; x").unwrap_err();
+    assert_eq!(errs[0].span(), Span::new("a", 11..12));
+}
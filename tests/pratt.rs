@@ -0,0 +1,173 @@
+//  PRATT.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 22:04:49
+//  Last edited:
+//    18 Oct 2026, 23:39:13
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests parsing expressions by precedence with the [`Parse`] derive.
+//
+
+#![cfg(feature = "parser")]
+
+use ast_toolkit_2::parser::chumsky::prelude::*;
+use ast_toolkit_2::parser::chumsky::text;
+#[cfg(feature = "railroad")]
+use ast_toolkit_2::parser::pratt::to_railroad_diagram;
+use ast_toolkit_2::parser::pratt::{Assoc, Fixity, Pratt, precedences};
+use ast_toolkit_2::parser::{Error, Parse, Span};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses a decimal integer.
+///
+/// # Returns
+/// A [`Parser`] producing the integer's value.
+fn int() -> impl Parser<char, u64, Error = Error> + Clone { text::int(10).from_str().unwrapped() }
+
+/// Writes an expression as an S-expression, to see how it grouped.
+///
+/// # Arguments
+/// - `expr`: The [`Expr`] to write.
+///
+/// # Returns
+/// The S-expression, e.g., `(+ 1 (* 2 3))`.
+fn sexp(expr: &Expr) -> String {
+    match expr {
+        Expr::Eq(lhs, rhs) => format!("(== {} {})", sexp(lhs), sexp(rhs)),
+        Expr::Add(lhs, _, rhs) => format!("(+ {} {})", sexp(lhs), sexp(rhs)),
+        Expr::Sub(lhs, rhs) => format!("(- {} {})", sexp(lhs), sexp(rhs)),
+        Expr::Mul(lhs, rhs) => format!("(* {} {})", sexp(lhs), sexp(rhs)),
+        Expr::Pow(lhs, rhs) => format!("(^ {} {})", sexp(lhs), sexp(rhs)),
+        Expr::Neg(operand) => format!("(neg {})", sexp(operand)),
+        Expr::Fact(operand) => format!("(! {})", sexp(operand)),
+        Expr::Paren(expr) => sexp(expr),
+        Expr::Int(value) => value.to_string(),
+    }
+}
+
+/// Parses an expression and writes it as an S-expression.
+///
+/// # Arguments
+/// - `text`: The source text to parse.
+///
+/// # Returns
+/// The S-expression, or [`None`] if it failed to parse.
+fn parse(text: &str) -> Option<String> { Expr::parse_str("a", text).ok().map(|expr| sexp(&expr)) }
+
+
+
+
+
+/***** HELPERS *****/
+/// An expression with every kind of operator.
+#[derive(Debug, Parse)]
+enum Expr {
+    #[parse(infix, punct = "==", prec = 0, assoc = none)]
+    Eq(Box<Expr>, Box<Expr>),
+    #[parse(infix, punct = "+", prec = 1)]
+    Add(Box<Expr>, #[parse(op)] Span, Box<Expr>),
+    #[parse(infix, punct = "-", prec = 1)]
+    Sub(Box<Expr>, Box<Expr>),
+    #[parse(infix, punct = "*", prec = 2)]
+    Mul(Box<Expr>, Box<Expr>),
+    #[parse(infix, punct = "^", prec = 3, assoc = right)]
+    Pow(Box<Expr>, Box<Expr>),
+    #[parse(prefix, punct = "-", prec = 4)]
+    Neg(Box<Expr>),
+    #[parse(postfix, punct = "!", prec = 4)]
+    Fact(Box<Expr>),
+    Paren(#[parse(delim("(", ")"))] Box<Expr>),
+    Int(#[parse(with = int)] u64),
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn precedence() {
+    assert_eq!(parse("1 + 2 * 3").as_deref(), Some("(+ 1 (* 2 3))"));
+    assert_eq!(parse("1 * 2 + 3").as_deref(), Some("(+ (* 1 2) 3)"));
+    assert_eq!(parse("2 * 3 ^ 2").as_deref(), Some("(* 2 (^ 3 2))"));
+    assert_eq!(parse("(1 + 2) * 3").as_deref(), Some("(* (+ 1 2) 3)"));
+    assert_eq!(parse("1 + 2 == 3").as_deref(), Some("(== (+ 1 2) 3)"));
+}
+
+#[test]
+fn associativity() {
+    assert_eq!(parse("1 - 2 - 3").as_deref(), Some("(- (- 1 2) 3)"));
+    assert_eq!(parse("1 + 2 - 3 + 4").as_deref(), Some("(+ (- (+ 1 2) 3) 4)"));
+    assert_eq!(parse("2 ^ 3 ^ 4").as_deref(), Some("(^ 2 (^ 3 4))"));
+    // Non-associative operators don't chain
+    assert_eq!(parse("1 == 2"), Some("(== 1 2)".into()));
+    assert_eq!(parse("1 == 2 == 3"), None);
+}
+
+#[test]
+fn unary() {
+    assert_eq!(parse("-1 + 2").as_deref(), Some("(+ (neg 1) 2)"));
+    assert_eq!(parse("--1").as_deref(), Some("(neg (neg 1))"));
+    assert_eq!(parse("3!!").as_deref(), Some("(! (! 3))"));
+    // Postfix binds tighter than prefix at the same precedence, and both than infix
+    assert_eq!(parse("-3!").as_deref(), Some("(neg (! 3))"));
+    assert_eq!(parse("-2 ^ 2").as_deref(), Some("(^ (neg 2) 2)"));
+    assert_eq!(parse("1 - -2").as_deref(), Some("(- 1 (neg 2))"));
+}
+
+#[test]
+fn operator_spans() {
+    let Expr::Add(_, op, _) = Expr::parse_str("a", "1 +  (2)").unwrap() else { panic!("Expected an addition") };
+    assert_eq!(op, Span::new("a", 2..3));
+}
+
+#[test]
+fn operators() {
+    let ops = <Expr as Pratt>::operators();
+    assert_eq!(ops.len(), 7);
+    assert_eq!(precedences(&ops), [4, 3, 2, 1, 0]);
+    let fixities: Vec<(&str, Fixity)> = ops.iter().map(|op| (op.name, op.fixity)).collect();
+    assert!(fixities.contains(&("Pow", Fixity::Infix(Assoc::Right))));
+    assert!(fixities.contains(&("Eq", Fixity::Infix(Assoc::None))));
+    assert!(fixities.contains(&("Fact", Fixity::Postfix)));
+}
+
+#[cfg(feature = "railroad")]
+#[test]
+fn railroad() {
+    let svg: String = to_railroad_diagram::<Expr>().into_svg().to_string();
+    let texts = |class: &str| -> Vec<&str> {
+        svg.split("<text ")
+            .skip(1)
+            .filter(|text| text.starts_with(class))
+            .filter_map(|text| Some(text.split_once('>')?.1.split_once("</text>")?.0.trim()))
+            .collect()
+    };
+
+    // One track per precedence, from the loosest to the tightest, and one for the table
+    let comments: Vec<&str> = texts("class=\"comment\"");
+    assert_eq!(&comments[..6], ["Expr 0", "Expr 1", "Expr 2", "Expr 3", "Expr 4", "Expr precedence"]);
+    // The table has a row per level and fixity, including the non-associative one
+    assert_eq!(
+        &comments[6..],
+        [
+            "precedence | fixity | operators",
+            "0 | infix, none | == (Eq)",
+            "1 | infix, left | + (Add), - (Sub)",
+            "2 | infix, left | * (Mul)",
+            "3 | infix, right | ^ (Pow)",
+            "4 | prefix | - (Neg)",
+            "4 | postfix | ! (Fact)",
+        ]
+    );
+
+    // Every track refers to the next-tighter one, and the tightest to the operands
+    let labels: Vec<&str> = texts("x=");
+    assert_eq!(&labels[..9], ["Expr 0", "Expr 1", "==", "Expr 1", "Expr 2", "+", "-", "Expr 2", "Expr 3"]);
+    assert!(labels.contains(&"Expr atom"));
+}
@@ -0,0 +1,130 @@
+//  RECOVER.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 22:05:45
+//  Last edited:
+//    18 Oct 2026, 22:05:45
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests recovering from syntax errors with the [`Parse`] derive.
+//
+
+#![cfg(feature = "parser")]
+
+use ast_toolkit_2::parser::chumsky::prelude::*;
+use ast_toolkit_2::parser::chumsky::text;
+use ast_toolkit_2::parser::recover::Recover;
+use ast_toolkit_2::parser::{Error, Parse, Span};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses an identifier.
+///
+/// # Returns
+/// A [`Parser`] producing the identifier's text.
+fn ident() -> impl Parser<char, String, Error = Error> + Clone { text::ident() }
+
+/// Parses a decimal integer.
+///
+/// # Returns
+/// A [`Parser`] producing the integer's value.
+fn int() -> impl Parser<char, u64, Error = Error> + Clone { text::int(10).from_str().unwrapped() }
+
+/// Parses a block, and summarises which statements it has.
+///
+/// # Arguments
+/// - `text`: The source text to parse.
+///
+/// # Returns
+/// The statements of the block, as the names they define or the spans of their errors, and the
+/// spans of the errors found.
+fn parse(text: &str) -> (Option<Vec<String>>, Vec<Span>) {
+    let (block, errs): (Option<Block>, Vec<Error>) = Block::parse_str_recovery("a", text);
+    let stmts: Option<Vec<String>> = block.map(|block| {
+        block
+            .0
+            .iter()
+            .map(|stmt| match stmt {
+                Stmt::Let(name, ..) => name.clone(),
+                Stmt::Block(block) => format!("{{{}}}", block.0.len()),
+                Stmt::Error(span) => format!("error {:?}", span.range()),
+            })
+            .collect()
+    });
+    (stmts, errs.iter().map(Error::span).collect())
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// A statement that skips to the end of itself on errors.
+#[derive(Debug, Parse)]
+#[parse(sync = ";", sync_before = "}")]
+enum Stmt {
+    #[parse(keyword = "let")]
+    Let(#[parse(with = ident)] String, #[parse(punct = "=")] Span, #[parse(with = int)] u64, #[parse(punct = ";")] Span),
+    Block(Block),
+    #[parse(error)]
+    Error(#[parse(span)] Span),
+}
+
+/// A block that skips to its closing brace on errors.
+#[derive(Debug, Parse)]
+struct Block(#[parse(delim("{", "}"), recover)] Vec<Stmt>);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn sync() {
+    assert_eq!(parse("{ let x = 1; let y = 2; }"), (Some(vec!["x".into(), "y".into()]), vec![]));
+    let Block(stmts) = Block::parse_str("a", "{ let x = 1; }").unwrap();
+    assert!(matches!(stmts.as_slice(), [Stmt::Let(_, eq, 1, semi)] if *eq == Span::new("a", 8..9) && *semi == Span::new("a", 11..12)));
+
+    // The broken statement is skipped up to and including its semicolon
+    let (stmts, errs) = parse("{ let x = ; let y = 2; }");
+    assert_eq!(stmts, Some(vec!["error 2..11".into(), "y".into()]));
+    assert_eq!(errs, [Span::new("a", 10..11)]);
+
+    // But not including the end of the block
+    let (stmts, errs) = parse("{ let x = 1; let y = }");
+    assert_eq!(stmts, Some(vec!["x".into(), "error 13..21".into()]));
+    assert_eq!(errs, [Span::new("a", 21..22)]);
+}
+
+#[test]
+fn multiple() {
+    let (stmts, errs) = parse("{ let = 1; let y = 2; let z 3; }");
+    assert_eq!(stmts, Some(vec!["error 2..10".into(), "y".into(), "error 22..30".into()]));
+    assert_eq!(errs, [Span::new("a", 6..7), Span::new("a", 28..29)]);
+}
+
+#[test]
+fn nested() {
+    // Errors in inner blocks are recovered there, and parsing continues in the outer block
+    let (stmts, errs) = parse("{ { let x = 1; ] } let y = 2; }");
+    assert_eq!(stmts, Some(vec!["{2}".into(), "y".into()]));
+    assert_eq!(errs, [Span::new("a", 15..16)]);
+
+    // Unclosed blocks can't be recovered
+    let (stmts, errs) = parse("{ let x = 1; ");
+    assert_eq!(stmts, None);
+    assert!(!errs.is_empty() && errs.iter().all(|span| *span == Span::empty("a", 13)));
+
+    // Without recovery, the same errors are reported
+    let full: Vec<Error> = Block::parse_str("a", "{ let x = ; let y = 2; }").unwrap_err();
+    assert_eq!(full.iter().map(Error::span).collect::<Vec<_>>(), [Span::new("a", 10..11)]);
+}
+
+#[test]
+fn error_node() {
+    assert!(matches!(Stmt::error(Span::new("a", 1..3)), Stmt::Error(span) if span == Span::new("a", 1..3)));
+    assert!(<Vec<Stmt>>::error(Span::new("a", 1..3)).is_empty());
+}
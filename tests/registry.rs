@@ -0,0 +1,76 @@
+//  REGISTRY.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:56:32
+//  Last edited:
+//    18 Oct 2026, 21:56:32
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests the registry of error codes and how the [`Report`]-derive refers
+//!   to them.
+//
+
+#![cfg(feature = "report")]
+
+use ast_toolkit_2::report::{Code, Report, Span};
+
+
+/***** CONSTANTS *****/
+ast_toolkit_2::codes! {
+    static CODES;
+
+    /// Something was defined twice.
+    E0042 {
+        title: "Duplicate definition",
+        explanation: "A name was defined twice in the same scope.\n\nRename one of them.\n",
+        examples: ["let x = 1;\nlet x = 2;", "fn f() {}\nfn f() {}\n"],
+    }
+    E0043 {
+        title: "Unknown name",
+        explanation: "A name was used but never defined.",
+    }
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// An error that refers to a registered code.
+#[derive(Report)]
+#[report(message = "Duplicate definition", code = E0042)]
+struct Duplicate(#[label(primary)] Span);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn registry() {
+    let names: Vec<&str> = CODES.iter().map(|code| code.name).collect();
+    assert_eq!(names, ["E0042", "E0043"]);
+    assert_eq!(CODES.get("E0043"), Some(&E0043));
+    assert_eq!(CODES.get("e0042"), Some(&E0042));
+    assert_eq!(CODES.get("E0044"), None);
+    assert_eq!(E0043.examples, &[] as &[&str]);
+}
+
+#[test]
+fn explain() {
+    assert_eq!(
+        E0042.explain().to_string(),
+        "# E0042: Duplicate definition\n\nA name was defined twice in the same scope.\n\nRename one of them.\n\n## Examples\n\n```\nlet x = 1;\nlet \
+         x = 2;\n```\n\n```\nfn f() {}\nfn f() {}\n```\n"
+    );
+    assert_eq!(E0043.explain().to_string(), "# E0043: Unknown name\n\nA name was used but never defined.\n");
+}
+
+#[test]
+fn derive() {
+    let code: &Code = &E0042;
+    assert_eq!(Duplicate(Span::new("a", 0..1)).code().as_deref(), Some(code.name));
+}
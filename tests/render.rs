@@ -0,0 +1,149 @@
+//  RENDER.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:58:07
+//  Last edited:
+//    18 Oct 2026, 23:33:03
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests the rustc-style renderer and selecting renderers per
+//!   [`Diagnostics`]-sink.
+//
+
+#![cfg(feature = "report")]
+
+use ast_toolkit_2::report::{Applicability, Diagnostic, Diagnostics, Label, RenderConfig, Renderer, RustcRenderer, Sources, Span, Suggestion};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns some sources to point into.
+///
+/// # Returns
+/// [`Sources`] with a short file `main.rs` and a file `long.rs` with one long line.
+fn sources() -> Sources {
+    let mut sources = Sources::new();
+    sources.add("main.rs", "fn main() {\n    print(x);\n}\n");
+    sources.add("long.rs", format!("let x = {}y + 1;\n", "a + ".repeat(30)));
+    sources
+}
+
+/// Renders a diagnostic with the [`RustcRenderer`].
+///
+/// # Arguments
+/// - `diag`: The [`Diagnostic`] to render.
+/// - `config`: The [`RenderConfig`] to render with.
+///
+/// # Returns
+/// The rendered text.
+fn render(diag: &Diagnostic, config: RenderConfig) -> String {
+    let mut buf: Vec<u8> = Vec::new();
+    RustcRenderer.render(diag, &sources(), &config, &mut buf).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn layout() {
+    let diag: Diagnostic = Diagnostic::error("cannot find value `x` in this scope", Span::new("main.rs", 22..23))
+        .with_code("E0425")
+        .with_primary_message("not found in this scope")
+        .with_label(Label::with_message(Span::new("main.rs", 3..7), "in this function"))
+        .with_note("a note")
+        .with_help("did you mean `xy`?");
+    assert_eq!(
+        render(&diag, RenderConfig { color: false, width: None }),
+        "error[E0425]: cannot find value `x` in this scope
+ --> main.rs:2:11
+  |
+1 | fn main() {
+  |    ---- in this function
+2 |     print(x);
+  |           ^ not found in this scope
+  |
+  = note: a note
+  = help: did you mean `xy`?
+
+"
+    );
+}
+
+#[test]
+fn color() {
+    let diag: Diagnostic = Diagnostic::warning("unknown `x`", Span::new("main.rs", 22..23));
+    let text: String = render(&diag, RenderConfig { color: true, width: None });
+    assert!(text.starts_with("\x1b[1;33mwarning\x1b[0m\x1b[1m: unknown `x`\x1b[0m\n"), "{text:?}");
+    assert!(text.contains("\x1b[1;33m^\x1b[0m"), "{text:?}");
+    assert!(!render(&diag, RenderConfig { color: false, width: None }).contains('\x1b'));
+}
+
+#[test]
+fn width() {
+    let diag: Diagnostic = Diagnostic::warning("unknown `y`", Span::new("long.rs", 128..129)).with_primary_message("here");
+    assert_eq!(
+        render(&diag, RenderConfig { color: false, width: Some(40) }),
+        "warning: unknown `y`
+ --> long.rs:1:129
+  |
+1 | ...a + a + a + a + a + a + y + 1;
+  |                            ^ here
+
+"
+    );
+
+    // Without a limit, the line is shown in full
+    let text: String = render(&diag, RenderConfig { color: false, width: None });
+    assert!(text.contains(&format!("1 | let x = {}y + 1;\n", "a + ".repeat(30))), "{text}");
+}
+
+#[test]
+fn select() {
+    let mut diags = Diagnostics::new().with_color(false).with_renderer(RustcRenderer);
+    diags.emit(Diagnostic::error("cannot find value `x` in this scope", Span::new("main.rs", 22..23)));
+    let mut buf: Vec<u8> = Vec::new();
+    diags.render(&sources(), &mut buf).unwrap();
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        "error: cannot find value `x` in this scope
+ --> main.rs:2:11
+  |
+2 |     print(x);
+  |           ^
+
+error: aborting due to 1 previous error
+"
+    );
+
+    // By default, ariadne is used
+    let mut diags = Diagnostics::new().with_color(false);
+    diags.emit(Diagnostic::error("cannot find value `x` in this scope", Span::new("main.rs", 22..23)));
+    let mut buf: Vec<u8> = Vec::new();
+    diags.render(&sources(), &mut buf).unwrap();
+    assert!(String::from_utf8(buf).unwrap().contains("╭─[ main.rs:2:11 ]"));
+}
+
+#[test]
+fn crlf() {
+    let mut sources = Sources::new();
+    sources.add("main.rs", "fn main() {\r\n    print(x)\r\n}\r\n");
+    // Suggestions right before, on and after the carriage return
+    for (span, replacement, line) in [(25..25, ";", "print(x);"), (24..26, ");", "print(x);"), (26..26, ";", "print(x);"), (25..26, "", "print(x)")] {
+        let diag: Diagnostic = Diagnostic::error("expected `;`", Span::new("main.rs", 24..25)).with_suggestion(Suggestion::new(
+            "add it",
+            Span::new("main.rs", span.clone()),
+            replacement,
+            Applicability::MachineApplicable,
+        ));
+        let mut buf: Vec<u8> = Vec::new();
+        RustcRenderer.render(&diag, &sources, &RenderConfig { color: false, width: None }, &mut buf).unwrap();
+        let text: String = String::from_utf8(buf).unwrap();
+        assert!(!text.contains('\r'), "{span:?}: {text}");
+        assert!(text.contains(&format!("2 |     {line}\n")), "{span:?}: {text}");
+    }
+}
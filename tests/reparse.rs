@@ -0,0 +1,184 @@
+//  REPARSE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 22:38:54
+//  Last edited:
+//    18 Oct 2026, 22:38:54
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests incrementally reparsing edited texts with the [`Reparse`]
+//!   derive.
+//
+
+#![cfg(feature = "parser")]
+
+use ast_toolkit_2::parser::chumsky::prelude::*;
+use ast_toolkit_2::parser::chumsky::text;
+use ast_toolkit_2::parser::reparse::{Edit, Incremental};
+use ast_toolkit_2::parser::{Error, Parse, Reparse, Span};
+
+
+/***** CONSTANTS *****/
+/// The texts that edits start from.
+const TEXTS: [&str; 4] = [
+    "let x = 1 + 2;",
+    "let abc = foo(bar, 12) * (x + yy); { let y = abc + 3 * z; }",
+    "{ { let a = b; } let cc = (d); }\nlet e = f + g * h;",
+    "let x = a * b * c + d; let y = ((e)) + f(g, h + i, j * k);",
+];
+
+/// The text that edits insert.
+const SNIPPETS: [&str; 16] = ["", "x", "yz", "1", "+", " * ", " ", "(", ")", ";", "{", "}", ",", "f(", "let q = 4;", "a + b"];
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses an identifier.
+///
+/// # Returns
+/// A [`Parser`] producing the identifier's text.
+fn ident() -> impl Parser<char, String, Error = Error> + Clone { text::ident() }
+
+/// Parses a decimal integer.
+///
+/// # Returns
+/// A [`Parser`] producing the integer's value.
+fn int() -> impl Parser<char, u64, Error = Error> + Clone { text::int(10).from_str().unwrapped() }
+
+/// Shorthand for a span in the test source.
+///
+/// # Arguments
+/// - `start`: The first character in the span.
+/// - `end`: The character after the span.
+///
+/// # Returns
+/// A new [`Span`].
+fn span(start: usize, end: usize) -> Span { Span::new("a", start..end) }
+
+
+
+
+
+/***** HELPERS *****/
+/// A list of statements.
+#[derive(Debug, Parse, PartialEq, Reparse)]
+struct Program(Vec<Stmt>);
+
+/// A statement, which may nest.
+#[derive(Debug, Parse, PartialEq, Reparse)]
+enum Stmt {
+    #[parse(keyword = "let")]
+    Let(#[parse(with = ident)] String, #[parse(punct = "=")] Span, Expr, #[parse(punct = ";")] Span, #[parse(span)] Span),
+    Block(#[parse(delim("{", "}"))] Vec<Stmt>, #[parse(span)] Span),
+}
+
+/// An expression with operators.
+#[derive(Debug, Parse, PartialEq, Reparse)]
+enum Expr {
+    #[parse(infix, punct = "+", prec = 1)]
+    Add(Box<Expr>, Box<Expr>, #[parse(span)] Span),
+    #[parse(infix, punct = "*", prec = 2)]
+    Mul(Box<Expr>, Box<Expr>, #[parse(span)] Span),
+    Paren(#[parse(delim("(", ")"))] Box<Expr>, #[parse(span)] Span),
+    Call(#[parse(with = ident)] String, #[parse(delim("(", ")"), sep = ",")] Vec<Expr>, #[parse(span)] Span),
+    Var(#[parse(with = ident)] String, #[parse(span)] Span),
+    Int(#[parse(with = int)] u64, #[parse(span)] Span),
+}
+
+/// A small, deterministic random number generator for the edits.
+struct Rng(u64);
+impl Rng {
+    /// Returns a random number below a bound.
+    ///
+    /// # Arguments
+    /// - `bound`: The exclusive upper bound.
+    ///
+    /// # Returns
+    /// A number in `0..bound`.
+    fn below(&mut self, bound: usize) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 33) as usize) % bound
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn smallest() {
+    let mut doc = Incremental::<Program>::new("a", "let x = 1 + foo(2);").unwrap();
+    // Only the call is reparsed
+    assert_eq!(doc.edit(Edit::new(16..17, "bar")), Ok(span(12, 20)));
+    assert_eq!(doc.text(), "let x = 1 + foo(bar);");
+    let Stmt::Let(_, _, Expr::Add(_, rhs, _), semi, stmt) = &doc.node().0[0] else { panic!("Expected an addition in {:?}", doc.node()) };
+    assert!(matches!(&**rhs, Expr::Call(name, args, _) if name == "foo" && matches!(args.as_slice(), [Expr::Var(arg, _)] if arg == "bar")));
+    // And everything after it moved
+    assert_eq!((semi, stmt), (&span(20, 21), &span(0, 21)));
+}
+
+#[test]
+fn operands() {
+    // Operands that become operators are reparsed with their operator
+    let mut doc = Incremental::<Program>::new("a", "let x = a * bcd;").unwrap();
+    assert_eq!(doc.edit(Edit::new(13..14, " + ")), Ok(span(8, 17)));
+    let Stmt::Let(_, _, expr, ..) = &doc.node().0[0] else { panic!("Expected a let") };
+    assert!(matches!(expr, Expr::Add(lhs, _, _) if matches!(**lhs, Expr::Mul(..))), "{expr:?}");
+
+    // But not if they're parenthesized
+    let mut doc = Incremental::<Program>::new("a", "let x = a * (bcd);").unwrap();
+    assert_eq!(doc.edit(Edit::new(14..15, " + ")), Ok(span(13, 18)));
+}
+
+#[test]
+fn errors() {
+    let mut doc = Incremental::<Program>::new("a", "let x = 1;").unwrap();
+    assert!(doc.edit(Edit::delete(8..9)).is_err());
+    assert!(doc.is_stale());
+    assert_eq!(doc.node(), &Program::parse_str("a", "let x = 1;").unwrap());
+    // Once it parses again, the whole text is parsed
+    assert_eq!(doc.edit(Edit::insert(8, "22")), Ok(span(0, 11)));
+    assert!(!doc.is_stale());
+    assert_eq!(doc.into_node(), Program::parse_str("a", "let x = 22;").unwrap());
+}
+
+#[test]
+fn equivalent() {
+    // Random edits give the same nodes as parsing the edited text from scratch
+    let mut rng = Rng(42);
+    let mut incremental: usize = 0;
+    for text in TEXTS {
+        let mut doc = Incremental::<Program>::new("a", text).unwrap();
+        for _ in 0..500 {
+            let chars: Vec<char> = doc.text().chars().collect();
+            let start: usize = rng.below(chars.len() + 1);
+            let end: usize = (start + rng.below(4)).min(chars.len());
+            let edit = Edit::new(start..end, SNIPPETS[rng.below(SNIPPETS.len())]);
+
+            let before: String = doc.text().into();
+            let res = doc.edit(edit.clone());
+            match (&res, Program::parse_str("a", doc.text())) {
+                (Ok(reparsed), Ok(full)) => {
+                    assert_eq!(doc.node(), &full, "Applying {edit:?} to {before:?} gave another node than parsing {:?}", doc.text());
+                    incremental += usize::from(reparsed.range() != (0..doc.text().chars().count()));
+                },
+                (Err(_), Err(_)) => {
+                    // Undo edits that break the text, which parses it again
+                    assert!(doc.is_stale());
+                    let undo = Edit::new(start..start + edit.text.chars().count(), chars[start..end].iter().collect::<String>());
+                    assert_eq!(doc.edit(undo), Ok(span(0, chars.len())));
+                    assert_eq!((doc.text(), doc.node()), (before.as_str(), &Program::parse_str("a", &before).unwrap()));
+                },
+                (res, full) => panic!("Applying {edit:?} to {before:?} gave {res:?}, but parsing {:?} gave {full:?}", doc.text()),
+            }
+        }
+    }
+    // Make sure that enough edits were actually reparsed incrementally
+    assert!(incremental > 200, "Only {incremental} edits were reparsed incrementally");
+}
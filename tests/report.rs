@@ -0,0 +1,115 @@
+//  REPORT.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:52:24
+//  Last edited:
+//    18 Oct 2026, 23:28:39
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests the [`Report`]-trait and its derive macro.
+//
+
+#![cfg(feature = "report")]
+
+use ast_toolkit_2::report::{Diagnostic, Label, Report, Severity, Span};
+
+
+/***** HELPERS *****/
+/// A struct-shaped error.
+#[derive(Report)]
+#[report(message = "Unknown variable '{name}'", code = "E0001")]
+#[help("Define it first")]
+struct UnknownVar {
+    name:  String,
+    #[label(primary, "not found")]
+    usage: Span,
+    #[label("did you mean this?")]
+    close: Option<Span>,
+    #[note]
+    why:   Option<String>,
+}
+
+/// An enum-shaped error, with a default message.
+#[derive(Report)]
+#[report(message = "Something went wrong", severity = warning)]
+#[note("Reported by the tests")]
+enum Problem {
+    Default(#[label(primary)] Span),
+    #[report(message = "Duplicate '{0}'", severity = error, code = "E0002")]
+    Duplicate(String, #[label(primary, "second")] Span, #[label("first")] Span),
+}
+
+/// An error whose fields have the same names as the derive's locals.
+#[derive(Report)]
+#[report(message = "Cannot use '{res}'")]
+struct Shadowing {
+    #[note]
+    res:  String,
+    #[label(primary)]
+    span: Span,
+    #[label("'{res}' is defined here")]
+    def:  Option<Span>,
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn derive_struct() {
+    let err = UnknownVar { name: "foo".into(), usage: Span::new("a", 4..7), close: Some(Span::new("a", 0..3)), why: None };
+    assert_eq!(err.severity(), Severity::Error);
+    assert_eq!(err.code().as_deref(), Some("E0001"));
+    assert_eq!(err.message(), "Unknown variable 'foo'");
+    assert_eq!(err.primary(), Label::with_message(Span::new("a", 4..7), "not found"));
+    assert_eq!(err.labels(), vec![Label::with_message(Span::new("a", 0..3), "did you mean this?")]);
+    assert!(err.notes().is_empty());
+    assert_eq!(err.helps(), vec!["Define it first".to_string()]);
+
+    // Optional fields are only added if given
+    let err = UnknownVar { name: "foo".into(), usage: Span::new("a", 4..7), close: None, why: Some("It's a test".into()) };
+    assert!(err.labels().is_empty());
+    assert_eq!(err.notes(), vec!["It's a test".to_string()]);
+}
+
+#[test]
+fn derive_enum() {
+    let err = Problem::Default(Span::new("a", 1..2));
+    assert_eq!(err.severity(), Severity::Warning);
+    assert_eq!(err.code(), None);
+    assert_eq!(err.message(), "Something went wrong");
+    assert_eq!(err.primary(), Label::new(Span::new("a", 1..2)));
+    assert_eq!(err.notes(), vec!["Reported by the tests".to_string()]);
+
+    // Variants override the toplevel
+    let err = Problem::Duplicate("x".into(), Span::new("a", 10..11), Span::new("a", 4..5));
+    assert_eq!(err.severity(), Severity::Error);
+    assert_eq!(err.code().as_deref(), Some("E0002"));
+    assert_eq!(err.message(), "Duplicate 'x'");
+    assert_eq!(err.labels(), vec![Label::with_message(Span::new("a", 4..5), "first")]);
+}
+
+#[test]
+fn derive_hygiene() {
+    let err = Shadowing { res: "x".into(), span: Span::new("a", 4..5), def: Some(Span::new("a", 0..1)) };
+    assert_eq!(err.message(), "Cannot use 'x'");
+    assert_eq!(err.labels(), vec![Label::with_message(Span::new("a", 0..1), "'x' is defined here")]);
+    assert_eq!(err.notes(), vec!["x".to_string()]);
+}
+
+#[test]
+fn to_diagnostic() {
+    let diag: Diagnostic = Problem::Duplicate("x".into(), Span::new("a", 10..11), Span::new("a", 4..5)).to_diagnostic();
+    assert_eq!(
+        diag,
+        Diagnostic::error("Duplicate 'x'", Span::new("a", 10..11))
+            .with_code("E0002")
+            .with_primary_message("second")
+            .with_label(Label::with_message(Span::new("a", 4..5), "first"))
+            .with_note("Reported by the tests")
+    );
+}
@@ -0,0 +1,253 @@
+//  ROUNDTRIP.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 22:12:46
+//  Last edited:
+//    18 Oct 2026, 23:42:55
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests the [`Roundtrip`] tester for [`Parse`]- and
+//!   [`Unparse`]-implementations.
+//
+
+#![cfg(feature = "parser")]
+
+use ast_toolkit_2::parser::chumsky::prelude::*;
+use ast_toolkit_2::parser::chumsky::text;
+use ast_toolkit_2::parser::doc::Doc;
+use ast_toolkit_2::parser::generate::Generator;
+use ast_toolkit_2::parser::roundtrip::{Failure, Roundtrip};
+use ast_toolkit_2::parser::{Error, Generate, Lex, Parse, Span, Unparse};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses an identifier.
+///
+/// # Returns
+/// A [`Parser`] producing the identifier's text.
+fn ident() -> impl Parser<char, String, Error = Error> + Clone { text::ident() }
+
+/// Parses an identifier token.
+///
+/// # Returns
+/// A [`Parser`] producing the identifier's text.
+#[allow(clippy::result_large_err)]
+fn ident_token() -> impl Parser<Token, String, Error = Error<Token>> + Clone {
+    select! { Token::Ident(name) => name }
+}
+
+/// Shorthand for a variable.
+///
+/// # Arguments
+/// - `name`: The name of the variable.
+///
+/// # Returns
+/// A boxed [`Expr::Var`].
+fn var(name: &str) -> Box<Expr> { Box::new(Expr::Var(name.into())) }
+
+
+
+
+
+/***** HELPERS *****/
+/// An expression with operators that need parentheses.
+#[derive(Debug, Generate, PartialEq, Parse, Unparse)]
+enum Expr {
+    #[parse(infix, punct = "+", prec = 1)]
+    Add(Box<Expr>, Box<Expr>),
+    #[parse(infix, punct = "^", prec = 2, assoc = right)]
+    Pow(Box<Expr>, Box<Expr>),
+    #[parse(prefix, punct = "-", prec = 3)]
+    Neg(Box<Expr>),
+    Paren(#[parse(delim("(", ")"))] Box<Expr>),
+    #[parse(weight = 4)]
+    Var(#[parse(with = ident)] String),
+}
+
+/// An expression that fails to round-trip wherever it has a swapped pair.
+#[derive(Debug, Generate, PartialEq, Parse, Unparse)]
+enum Deep {
+    #[parse(infix, punct = "+", prec = 1)]
+    Add(Box<Deep>, Box<Deep>),
+    Paren(#[parse(delim("(", ")"))] Box<Deep>),
+    Swap(Swap),
+    #[parse(weight = 4)]
+    Var(#[parse(with = ident)] String),
+}
+
+/// A variable that knows where it was parsed.
+#[derive(Debug, Generate, PartialEq, Parse, Unparse)]
+struct Name(#[parse(with = ident)] String, #[parse(span)] Span);
+
+/// A pair of names that is printed the wrong way around.
+#[derive(Debug, Generate, PartialEq, Parse)]
+struct Swap(Name, Name);
+impl Unparse for Swap {
+    #[inline]
+    fn unparse(&self) -> Doc { Doc::join([self.1.unparse(), self.0.unparse()], Doc::Space) }
+}
+
+/// An expression statement.
+#[derive(Debug, PartialEq, Parse, Unparse)]
+struct Stmt(Expr, #[parse(punct = ";")] Span);
+
+/// An expression that is printed negated.
+#[derive(Debug, PartialEq, Parse)]
+struct Negated(Expr);
+impl Unparse for Negated {
+    #[inline]
+    fn unparse(&self) -> Doc { Doc::concat([Doc::text("-"), self.0.unparse()]) }
+}
+
+/// The tokens of assignments.
+#[derive(Clone, Debug, Eq, Hash, Lex, PartialEq)]
+#[lex(skip = r"\s+")]
+enum Token {
+    #[token("=")]
+    Eq,
+    #[regex("[a-z]+")]
+    Ident(String),
+}
+
+/// An assignment, parsed from tokens.
+#[derive(Debug, PartialEq, Parse, Unparse)]
+#[parse(input = Token)]
+struct Assign(#[parse(with = ident_token)] String, #[parse(punct = "=")] Span, #[parse(with = ident_token)] String);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn operators() {
+    let check = Roundtrip::new();
+    // Parsing wraps these in `Paren`, which the original doesn't have
+    check.check_node("right", &Expr::Add(var("a"), Box::new(Expr::Add(var("b"), var("c"))))).unwrap();
+    check.check_node("left", &Expr::Pow(Box::new(Expr::Pow(var("a"), var("b"))), var("c"))).unwrap();
+    check.check_node("prefix", &Expr::Neg(Box::new(Expr::Add(var("a"), Box::new(Expr::Neg(var("b"))))))).unwrap();
+    // But parentheses that are there are kept
+    check.check_node("paren", &Expr::Add(var("a"), Box::new(Expr::Paren(var("b"))))).unwrap();
+    check.check_node("nested", &Expr::Paren(Box::new(Expr::Paren(Box::new(Expr::Add(var("a"), var("b"))))))).unwrap();
+}
+
+#[test]
+fn parens() {
+    assert_eq!(Expr::paren(), Some("Paren"));
+    assert_eq!(Name::paren(), None);
+
+    // Only parenthesized variants are seen through, not other wrappers
+    let check = Roundtrip::new();
+    let failure: Failure = check.check_node("neg", &Negated(Expr::Var("a".into()))).unwrap_err();
+    let Failure::Mismatch { expected, actual, .. } = &failure else { panic!("Expected a mismatch, got {failure:?}") };
+    assert!(!expected.contains("Neg(") && actual.contains("Neg("), "{expected}\n{actual}");
+
+    // Those of nested expressions only when asked
+    let stmt = Stmt(Expr::Add(var("a"), Box::new(Expr::Add(var("b"), var("c")))), Span::empty("a", 0));
+    assert!(matches!(check.check_node("stmt", &stmt), Err(Failure::Mismatch { .. })));
+    check.clone().with_parens_of::<Expr>().check_node("stmt", &stmt).unwrap();
+
+    // Nodes that only differ in what isn't printed do not pass
+    let failure: Failure = check.check_node_by("eq", &Expr::Var("a".into()), |_, _| false).unwrap_err();
+    assert!(matches!(&failure, Failure::Mismatch { difference: None, expected, actual, .. } if expected == actual), "{failure:?}");
+    assert!(failure.to_string().ends_with("(no difference in their Debug-representations)\n"), "{failure}");
+}
+
+#[test]
+fn spans() {
+    // Spans point into the printed text, so they're not compared
+    Roundtrip::new().check_node("name", &Name("x".into(), Span::empty("a", 0))).unwrap();
+    Roundtrip::new().check_node("swap", &Swap(Name("x".into(), Span::empty("a", 0)), Name("x".into(), Span::empty("a", 0)))).unwrap();
+}
+
+#[test]
+fn mismatch() {
+    let swap = Swap(Name("x".into(), Span::empty("a", 0)), Name("yy".into(), Span::empty("a", 0)));
+    let Err(failure) = Roundtrip::new().check_node("swap", &swap) else { panic!("Expected swapped names to fail") };
+    let Failure::Mismatch { printed, expected, actual, difference, .. } = &failure else { panic!("Expected a mismatch, got {failure:?}") };
+    assert_eq!(printed, "yy x");
+    assert_eq!(difference.as_ref().map(|(range, message)| (range.clone(), &**message)), Some((0..2, "expected `\"x\"`, parsed `\"yy\"`")));
+    // Spans are elided from the compared nodes
+    assert!(!expected.contains("start") && !actual.contains("start"), "{expected}\n{actual}");
+
+    let text: String = failure.to_string();
+    assert!(text.contains("\n1 | yy x\n  | ^^ expected `\"x\"`, parsed `\"yy\"`\n"), "{text}");
+    assert!(text.contains("\n-         \"x\",\n") && text.contains("\n+         \"yy\",\n"), "{text}");
+    assert!(text.find("\n-         \"x\",\n") < text.find("\n+         \"yy\",\n"), "{text}");
+}
+
+#[test]
+fn source() {
+    let check = Roundtrip::new().with_width(10);
+    check.check_source::<Expr>("ok", "a+-(b)^c^(d + e)").unwrap();
+    assert!(matches!(check.check_source::<Expr>("bad", "a +"), Err(Failure::Unparsable { .. })));
+
+    // Failing texts are minimized to the smallest text that fails in the same way
+    let failure: Failure = check.check_source::<Swap>("swap", "  xxx   yyy  ").unwrap_err();
+    let Failure::Unstable { input, first, second, .. } = &failure else { panic!("Expected an unstable print, got {failure:?}") };
+    assert_eq!((input.as_str(), first.as_str(), second.as_str()), ("x y", "y x", "x y"));
+    assert!(failure.to_string().ends_with("- y x\n+ x y\n(first difference at 1:1)\n"), "{failure}");
+    assert!(
+        matches!(check.with_minimize(false).check_source::<Swap>("swap", "  xxx   yyy  "), Err(Failure::Unstable { input, .. }) if input == "  xxx   yyy  ")
+    );
+}
+
+#[test]
+fn corpus() {
+    let failures: Vec<Failure> = Roundtrip::new().check_corpus::<Expr>([("a", "a + b"), ("b", "(a"), ("c", "-a ^ -b")]);
+    assert_eq!(failures.len(), 1);
+    assert!(matches!(&failures[0], Failure::Unparsable { name, .. } if &**name == "b"));
+}
+
+#[test]
+fn generated() {
+    let generator = Generator::new(0).with_max_depth(6);
+    assert!(Roundtrip::new().check_generated::<Expr>(&generator, 0..100).is_empty());
+
+    // Failures are named after their seed
+    let failures: Vec<Failure> = Roundtrip::new().with_minimize(false).check_generated::<Swap>(&generator, 0..10);
+    assert!(!failures.is_empty());
+    assert!(failures.iter().all(|failure| matches!(failure, Failure::Mismatch { name, .. } if name.starts_with("seed "))));
+}
+
+#[test]
+fn minimize() {
+    // Failing nodes are generated again with smaller budgets, which are smaller nodes
+    let printed = |failures: &[Failure]| -> Vec<(String, String)> {
+        failures
+            .iter()
+            .map(|failure| match failure {
+                Failure::Mismatch { name, printed, .. } => (name.to_string(), printed.clone()),
+                failure => panic!("Expected a mismatch, got {failure:?}"),
+            })
+            .collect()
+    };
+    let generator = Generator::new(0).with_max_depth(6);
+    let full: Vec<(String, String)> = printed(&Roundtrip::new().with_minimize(false).check_generated::<Deep>(&generator, 0..50));
+    let small: Vec<(String, String)> = printed(&Roundtrip::new().check_generated::<Deep>(&generator, 0..50));
+    assert!(!full.is_empty());
+    assert_eq!(full.iter().map(|(name, _)| name).collect::<Vec<_>>(), small.iter().map(|(name, _)| name).collect::<Vec<_>>());
+    assert!(full.iter().zip(&small).all(|((_, full), (_, small))| small.len() <= full.len()), "{full:?}\n{small:?}");
+    assert!(full.iter().map(|(_, text)| text.len()).sum::<usize>() > small.iter().map(|(_, text)| text.len()).sum::<usize>(), "{full:?}\n{small:?}");
+    // The depth is lowered as far as it still fails, which leaves only the swapped pair
+    assert!(small.iter().all(|(_, text)| !text.contains('+') && text.split_whitespace().count() == 2), "{small:?}");
+    assert!(full.iter().any(|(_, text)| text.contains('+')), "{full:?}");
+
+    // Texts that don't parse at all aren't minimized, as any smaller text would fail the same
+    let check = Roundtrip::new();
+    assert!(matches!(check.check_source::<Expr>("bad", "a + (b ^ c"), Err(Failure::Unparsable { input, .. }) if input == "a + (b ^ c"));
+}
+
+#[test]
+fn tokens() {
+    let check = Roundtrip::new();
+    check.check_source::<Assign>("ok", "a   =\n b").unwrap();
+    check.check_node("node", &Assign("a".into(), Span::empty("a", 0), "b".into())).unwrap();
+
+    // Errors from lexing are reported before those from parsing
+    let Err(Failure::Unparsable { errors, .. }) = check.check_source::<Assign>("bad", "a = 1") else { panic!("Expected an unparsable input") };
+    assert_eq!(errors.iter().map(Error::span).collect::<Vec<_>>(), [Span::new("bad", 4..5), Span::empty("bad", 5)]);
+}
@@ -0,0 +1,185 @@
+//  SARIF.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:53:33
+//  Last edited:
+//    18 Oct 2026, 23:34:01
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests the SARIF export of diagnostics against the shape of the SARIF
+//!   2.1.0 schema.
+//
+
+#![cfg(feature = "json")]
+
+use ast_toolkit_2::report::sarif::{SARIF_SCHEMA, SARIF_VERSION};
+use ast_toolkit_2::report::{Applicability, Diagnostic, Diagnostics, Label, SarifDriver, SarifLog, Sources, Span, Suggestion};
+use serde_json::Value;
+
+
+/***** CONSTANTS *****/
+ast_toolkit_2::codes! {
+    static CODES;
+
+    E0001 {
+        title: "Duplicate definition",
+        explanation: "Something was defined twice.",
+    }
+    E0002 {
+        title: "Unused name",
+        explanation: "Something was never used.",
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Serializes some diagnostics to SARIF.
+///
+/// # Returns
+/// The parsed JSON [`Value`] of the log.
+fn log() -> Value {
+    let mut sources = Sources::new();
+    sources.add("src/main.lang", "import lib;\nlet x = 1;\nlet x = 2;\n");
+    sources.add("src/lib.lang", "let y = 3;\n");
+
+    let mut diags = Diagnostics::new();
+    diags.emit(
+        Diagnostic::error("Duplicate definition of 'x'", Span::new("src/main.lang", 16..17))
+            .with_code("E0001")
+            .with_label(Label::with_message(Span::new("src/main.lang", 27..28), "defined again here"))
+            .with_label(Label::with_message(Span::new("src/lib.lang", 4..5), "unrelated"))
+            .with_note("Names must be unique")
+            .with_help("Rename one of them")
+            .with_suggestion(Suggestion::new("Rename it", Span::new("src/main.lang", 27..28), "x2", Applicability::MaybeIncorrect)),
+    );
+    diags.emit(Diagnostic::warning("Unused 'y'", Span::new("src/lib.lang", 4..5)).with_code("E0002"));
+    diags.emit(Diagnostic::note("Just saying", Span::new("src/lib.lang", 0..3)));
+
+    let mut buf: Vec<u8> = Vec::new();
+    diags.render_sarif(SarifDriver::new("langc").with_version("1.0.0").with_registry(&CODES), &sources, &mut buf).unwrap();
+    serde_json::from_slice(&buf).unwrap()
+}
+
+/// Finds a result in a log by its message.
+///
+/// # Arguments
+/// - `log`: The log to search.
+/// - `message`: The message of the result.
+///
+/// # Returns
+/// The result's JSON [`Value`].
+fn result<'l>(log: &'l Value, message: &str) -> &'l Value {
+    log["runs"][0]["results"].as_array().unwrap().iter().find(|result| result["message"]["text"] == message).unwrap()
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn toplevel() {
+    let log: Value = log();
+    assert_eq!(log["$schema"], SARIF_SCHEMA);
+    assert_eq!(log["version"], SARIF_VERSION);
+    assert_eq!(log["runs"].as_array().unwrap().len(), 1);
+
+    let driver = &log["runs"][0]["tool"]["driver"];
+    assert_eq!(driver["name"], "langc");
+    assert_eq!(driver["version"], "1.0.0");
+    let rules: Vec<&str> = driver["rules"].as_array().unwrap().iter().map(|rule| rule["id"].as_str().unwrap()).collect();
+    assert_eq!(rules, ["E0001", "E0002"]);
+    assert_eq!(driver["rules"][0]["shortDescription"]["text"], "Duplicate definition");
+
+    // Both files are artifacts
+    let artifacts: Vec<&str> = log["runs"][0]["artifacts"].as_array().unwrap().iter().map(|a| a["location"]["uri"].as_str().unwrap()).collect();
+    assert_eq!(artifacts.len(), 2);
+    assert!(artifacts.contains(&"src/main.lang") && artifacts.contains(&"src/lib.lang"));
+}
+
+#[test]
+fn rules_agree() {
+    let log: Value = log();
+    let rules = log["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap();
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    for result in results {
+        match result.get("ruleId") {
+            Some(id) => assert_eq!(rules[result["ruleIndex"].as_u64().unwrap() as usize]["id"], *id),
+            None => assert!(result.get("ruleIndex").is_none()),
+        }
+    }
+    assert_eq!(result(&log, "Duplicate definition of 'x'")["level"], "error");
+    assert_eq!(result(&log, "Unused 'y'")["level"], "warning");
+    assert_eq!(result(&log, "Just saying")["level"], "note");
+}
+
+#[test]
+fn locations() {
+    let log: Value = log();
+    let result: &Value = result(&log, "Duplicate definition of 'x'");
+
+    // The primary location, with 1-based lines and columns
+    let loc = &result["locations"][0]["physicalLocation"];
+    assert_eq!(loc["artifactLocation"]["uri"], "src/main.lang");
+    assert_eq!(loc["region"]["startLine"], 2);
+    assert_eq!(loc["region"]["startColumn"], 5);
+    assert_eq!(loc["region"]["endLine"], 2);
+    assert_eq!(loc["region"]["endColumn"], 6);
+
+    // Secondary labels, also in other files, are related locations
+    let related = result["relatedLocations"].as_array().unwrap();
+    assert_eq!(related.len(), 2);
+    assert_eq!(related[0]["physicalLocation"]["region"]["startLine"], 3);
+    assert_eq!(related[0]["message"]["text"], "defined again here");
+    assert_eq!(related[1]["physicalLocation"]["artifactLocation"]["uri"], "src/lib.lang");
+    assert_eq!(related[1]["physicalLocation"]["region"]["startLine"], 1);
+    assert_eq!(related[1]["physicalLocation"]["region"]["startColumn"], 5);
+    let ids: Vec<u64> = related.iter().map(|loc| loc["id"].as_u64().unwrap()).collect();
+    assert_eq!(ids, [0, 1]);
+}
+
+#[test]
+fn notes_and_fixes() {
+    let log: Value = log();
+    let result: &Value = result(&log, "Duplicate definition of 'x'");
+    assert_eq!(result["properties"]["notes"][0], "Names must be unique");
+    assert_eq!(result["properties"]["helps"][0], "Rename one of them");
+
+    let fix = &result["fixes"][0];
+    assert_eq!(fix["description"]["text"], "Rename it");
+    let change = &fix["artifactChanges"][0];
+    assert_eq!(change["artifactLocation"]["uri"], "src/main.lang");
+    assert_eq!(change["replacements"][0]["deletedRegion"]["charOffset"], 27);
+    assert_eq!(change["replacements"][0]["deletedRegion"]["charLength"], 1);
+    assert_eq!(change["replacements"][0]["insertedContent"]["text"], "x2");
+
+    // And the log can be read back
+    let _: SarifLog = serde_json::from_value(log).unwrap();
+}
+
+#[test]
+fn artifacts() {
+    let mut sources = Sources::new();
+    sources.add("src\\my file#1.lang", "let é = 1;\n");
+    let diags: Vec<Diagnostic> = vec![Diagnostic::error("Oops", Span::new("src\\my file#1.lang", 4..5)).with_suggestion(Suggestion::new(
+        "Rename it",
+        Span::new("src\\my file#1.lang", 4..5),
+        "e",
+        Applicability::MaybeIncorrect,
+    ))];
+    let log: Value = serde_json::to_value(SarifLog::new(SarifDriver::new("langc"), &diags, &sources)).unwrap();
+    let run = &log["runs"][0];
+
+    // URIs are percent-encoded, and lengths are in bytes
+    assert_eq!(run["artifacts"][0]["location"]["uri"], "src/my%20file%231.lang");
+    assert_eq!(run["artifacts"][0]["length"], 12);
+    assert_eq!(run["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "src/my%20file%231.lang");
+    assert_eq!(run["results"][0]["fixes"][0]["artifactChanges"][0]["artifactLocation"]["uri"], "src/my%20file%231.lang");
+}
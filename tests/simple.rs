@@ -0,0 +1,62 @@
+//  SIMPLE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:57:41
+//  Last edited:
+//    18 Oct 2026, 21:57:41
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests reporting chumsky's [`Simple`] parse errors.
+//
+
+#![cfg(all(feature = "parser", feature = "report"))]
+
+use ast_toolkit_2::parser::chumsky::Error as _;
+use ast_toolkit_2::parser::chumsky::error::Simple;
+use ast_toolkit_2::report::{Label, Report, Span};
+
+
+/***** TESTS *****/
+#[test]
+fn expected() {
+    // Expected tokens are deduplicated and sorted, with the end of the input last
+    let err: Simple<char, Span> = Simple::expected_input_found(Span::new("a", 3..4), [None, Some('b'), Some('a'), Some('c'), Some('a')], Some('d'));
+    assert_eq!(err.message(), "expected one of `a`, `b`, `c` or end of input, found `d`");
+    assert_eq!(err.primary(), Label::with_message(Span::new("a", 3..4), "unexpected `d`"));
+    assert!(err.labels().is_empty());
+    assert!(err.notes().is_empty());
+
+    let err: Simple<char, Span> = Simple::expected_input_found(Span::empty("a", 3), [Some(')'), Some(',')], None);
+    assert_eq!(err.message(), "expected `)` or `,`, found end of input");
+    let err: Simple<char, Span> = Simple::expected_input_found(Span::new("a", 3..4), [], Some('d'));
+    assert_eq!(err.message(), "found unexpected `d`");
+}
+
+#[test]
+fn labelled() {
+    // The label summarizes the expected tokens, which are then listed at the primary label
+    let err: Simple<char, Span> = Simple::expected_input_found(Span::new("a", 3..4), [Some('1'), Some('2')], Some('x')).with_label("a number");
+    assert_eq!(err.message(), "expected a number, found `x`");
+    assert_eq!(err.primary(), Label::with_message(Span::new("a", 3..4), "expected `1` or `2`"));
+    assert!(err.notes().is_empty());
+}
+
+#[test]
+fn unclosed() {
+    let err: Simple<char, Span> = Simple::unclosed_delimiter(Span::new("a", 0..1), '(', Span::empty("a", 5), ')', None).with_label("an argument list");
+    assert_eq!(err.message(), "unclosed delimiter `(`");
+    assert_eq!(err.primary(), Label::with_message(Span::new("a", 0..1), "unclosed delimiter"));
+    assert_eq!(err.labels(), vec![Label::with_message(Span::empty("a", 5), "expected `)`, found end of input")]);
+    assert_eq!(err.notes(), vec!["while parsing an argument list".to_string()]);
+}
+
+#[test]
+fn custom() {
+    let err: Simple<char, Span> = Simple::custom(Span::new("a", 2..6), "integer literal too large");
+    assert_eq!(err.message(), "integer literal too large");
+    assert_eq!(err.primary(), Label::new(Span::new("a", 2..6)));
+    assert!(err.labels().is_empty());
+}
@@ -0,0 +1,86 @@
+//  SINK.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:52:37
+//  Last edited:
+//    18 Oct 2026, 21:52:37
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests the [`Diagnostics`]-sink.
+//
+
+#![cfg(feature = "report")]
+
+use ast_toolkit_2::report::{Diagnostic, Diagnostics, Severity, Span};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Renders only the summary of some diagnostics.
+///
+/// # Arguments
+/// - `diags`: The [`Diagnostics`] to summarize.
+///
+/// # Returns
+/// The rendered summary, without colours.
+fn summary(diags: &Diagnostics) -> String {
+    let mut buf: Vec<u8> = Vec::new();
+    diags.render_summary(&mut buf).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn dedup() {
+    let mut diags = Diagnostics::new();
+    assert!(diags.emit(Diagnostic::error("Oops", Span::new("a", 0..1))));
+    assert!(!diags.emit(Diagnostic::error("Oops", Span::new("a", 0..1))));
+    assert!(diags.emit(Diagnostic::error("Oops", Span::new("a", 1..2))));
+    assert!(diags.emit(Diagnostic::warning("Oops", Span::new("a", 0..1))));
+    assert_eq!(diags.len(), 3);
+    assert_eq!(diags.count(Severity::Error), 2);
+    assert_eq!(diags.count(Severity::Warning), 1);
+}
+
+#[test]
+fn sorted() {
+    let mut diags = Diagnostics::new();
+    diags.emit(Diagnostic::error("Third", Span::new("b", 0..1)));
+    diags.emit(Diagnostic::error("Second", Span::new("a", 5..6)));
+    diags.emit(Diagnostic::error("First", Span::new("a", 0..1)));
+    diags.emit(Diagnostic::warning("Also second", Span::new("a", 5..6)));
+    let messages: Vec<&str> = diags.sorted().into_iter().map(|diag| diag.message.as_str()).collect();
+    assert_eq!(messages, ["First", "Second", "Also second", "Third"]);
+}
+
+#[test]
+fn max_errors() {
+    let mut diags = Diagnostics::new().with_max_errors(2);
+    for i in 0..4 {
+        diags.emit(Diagnostic::error("Oops", Span::new("a", i..i + 1)));
+    }
+    diags.emit(Diagnostic::warning("Hmm", Span::new("a", 0..1)));
+    assert_eq!(diags.len(), 3);
+    assert_eq!(diags.count(Severity::Error), 4);
+    assert_eq!(diags.suppressed(), 2);
+    assert_eq!(summary(&diags), "error: too many errors emitted (2 more were not shown)\nerror: aborting due to 4 previous errors; 1 warning emitted\n");
+}
+
+#[test]
+fn max_errors_dedup() {
+    // Suppressed errors are still deduplicated
+    let mut diags = Diagnostics::new().with_max_errors(1);
+    diags.emit(Diagnostic::error("Oops", Span::new("a", 0..1)));
+    for _ in 0..3 {
+        assert!(!diags.emit(Diagnostic::error("Oops", Span::new("a", 1..2))));
+    }
+    assert_eq!(diags.count(Severity::Error), 2);
+    assert_eq!(diags.suppressed(), 1);
+    assert_eq!(summary(&diags), "error: too many errors emitted (1 more was not shown)\nerror: aborting due to 2 previous errors\n");
+}
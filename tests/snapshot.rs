@@ -0,0 +1,128 @@
+//  SNAPSHOT.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:58:43
+//  Last edited:
+//    18 Oct 2026, 21:58:43
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests the snapshot [`Harness`] for rendered diagnostics.
+//
+
+#![cfg(feature = "report")]
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use ast_toolkit_2::report::snapshot::{Failure, Kind};
+use ast_toolkit_2::report::{Diagnostic, Diagnostics, Harness, RustcRenderer, Sources, Span};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Creates a fresh directory with input files.
+///
+/// # Arguments
+/// - `name`: Some name unique to the test, to keep tests apart.
+/// - `files`: The relative paths and contents of the files to create.
+///
+/// # Returns
+/// The path to the directory.
+fn dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir: PathBuf = std::env::temp_dir().join(format!("ast-toolkit-snapshot-{}-{name}", std::process::id()));
+    if dir.exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    for (path, text) in files {
+        let path: PathBuf = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, text).unwrap();
+    }
+    dir
+}
+
+/// A toy compiler that errors on every `bad` and warns on every `meh`.
+///
+/// # Arguments
+/// - `sources`: The [`Sources`] with the input file.
+/// - `name`: The name of the input file in there.
+/// - `diags`: The [`Diagnostics`] to emit to.
+fn compile(sources: &Sources, name: &Arc<str>, diags: &mut Diagnostics) {
+    let text: &str = sources.get(name).unwrap();
+    let chars: Vec<char> = text.chars().collect();
+    for i in 0..chars.len() {
+        let word: String = chars[i..].iter().take(3).collect();
+        if word == "bad" {
+            diags.emit(Diagnostic::error("Bad word", Span::new(name.clone(), i..i + 3)).with_help("Say something nice"));
+        } else if word == "meh" {
+            diags.emit(Diagnostic::warning("Meh word", Span::new(name.clone(), i..i + 3)));
+        }
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn bless() {
+    let dir: PathBuf = dir("bless", &[("a.lang", "ok\nbad //~ ERROR Bad word\n"), ("sub/b.lang", "ok\n"), ("c.txt", "bad\n")]);
+    let harness = Harness::new(&dir, "lang").with_bless(false).with_renderer(RustcRenderer);
+
+    // Without golden files, only files without diagnostics pass
+    let failures: Vec<Failure> = harness.check(compile).unwrap();
+    assert_eq!(failures.len(), 1);
+    assert!(matches!(&failures[0], Failure::Mismatch { path, expected, .. } if path.ends_with("a.lang") && expected.is_empty()));
+
+    // Blessing writes them, with paths relative to the directory
+    assert!(harness.clone().with_bless(true).check(compile).unwrap().is_empty());
+    assert_eq!(
+        fs::read_to_string(dir.join("a.stderr")).unwrap(),
+        "error: Bad word\n --> a.lang:2:1\n  |\n2 | bad //~ ERROR Bad word\n  | ^^^\n  |\n  = help: Say something nice\n\nerror: aborting due to 1 previous \
+         error\n"
+    );
+    assert!(!dir.join("sub/b.stderr").exists());
+    assert!(!dir.join("c.stderr").exists());
+    harness.run(compile);
+
+    // Changing the output makes the test fail with a diff
+    fs::write(dir.join("a.lang"), "bad //~ ERROR Bad word\nok\n").unwrap();
+    let failures: Vec<Failure> = harness.check(compile).unwrap();
+    assert_eq!(failures.len(), 1);
+    let text: String = failures[0].to_string();
+    assert!(text.contains("\n- 2 | bad //~ ERROR Bad word\n") && text.contains("\n+ 1 | bad //~ ERROR Bad word\n"), "{text}");
+    assert!(text.contains("\n  error: aborting due to 1 previous error\n"), "{text}");
+
+    // Blessing removes golden files that are no longer needed
+    fs::write(dir.join("a.lang"), "ok\n").unwrap();
+    assert!(harness.clone().with_bless(true).check(compile).unwrap().is_empty());
+    assert!(!dir.join("a.stderr").exists());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn annotations() {
+    let dir: PathBuf = dir(
+        "annotations",
+        &[("a.lang", "bad meh\n//~^ ERROR Bad\n//~| WARN Meh\n//~| HELP nice\nbad bad //~ ERROR Bad\n//~^ ERROR Bad word\nok //~ NOTE Nothing\nmeh\n")],
+    );
+    let failures: Vec<Failure> = Harness::new(&dir, "lang").with_bless(true).check(compile).unwrap();
+    let failures: Vec<String> = failures
+        .iter()
+        .map(|failure| match failure {
+            Failure::Missing { line, kind, message, .. } => format!("missing {kind} {message} at {line}"),
+            Failure::Unexpected { line, severity, message, .. } => format!("unexpected {severity} {message} at {line}"),
+            Failure::Mismatch { .. } => panic!("Unexpected mismatch in bless mode"),
+        })
+        .collect();
+    assert_eq!(failures, ["missing NOTE Nothing at 7", "unexpected warning Meh word at 8"]);
+    assert_eq!(Kind::Warning.to_string(), "WARN");
+
+    // Annotations can be turned off
+    assert!(Harness::new(&dir, "lang").with_bless(true).with_annotations(false).check(compile).unwrap().is_empty());
+    fs::remove_dir_all(dir).unwrap();
+}
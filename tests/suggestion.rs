@@ -0,0 +1,115 @@
+//  SUGGESTION.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 21:56:04
+//  Last edited:
+//    18 Oct 2026, 21:56:04
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests fix-it [`Suggestion`]s, both how they are rendered and how they
+//!   are applied.
+//
+
+#![cfg(feature = "report")]
+
+use ast_toolkit_2::report::suggestion::Error;
+use ast_toolkit_2::report::{Applicability, Diagnostic, RenderConfig, Renderer, RustcRenderer, Sources, Span, Suggestion, apply_suggestions};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns some sources to apply suggestions to.
+///
+/// # Returns
+/// [`Sources`] with a file `a` with a multi-byte character in it, and a file `b`.
+fn sources() -> Sources {
+    let mut sources = Sources::new();
+    sources.add("a", "let é = lenght(x);\n");
+    sources.add("b", "fn main() {}\n");
+    sources
+}
+
+/// Creates a diagnostic with a single suggestion.
+///
+/// # Arguments
+/// - `span`: The [`Span`] to replace.
+/// - `replacement`: The text to replace it with.
+/// - `applicability`: How confident we are in the suggestion.
+///
+/// # Returns
+/// A new [`Diagnostic`].
+fn suggest(span: Span, replacement: &str, applicability: Applicability) -> Diagnostic {
+    Diagnostic::error("Oops", span.clone()).with_suggestion(Suggestion::new("Try this", span, replacement, applicability))
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn apply() {
+    let diags: [Diagnostic; 3] = [
+        suggest(Span::new("a", 8..14), "length", Applicability::MachineApplicable),
+        // Offsets are in characters, so this is after the `é`
+        suggest(Span::empty("a", 5), ": u8", Applicability::MachineApplicable),
+        suggest(Span::new("b", 3..7), "start", Applicability::MachineApplicable),
+    ];
+    let res: Sources = apply_suggestions(&sources(), &diags, Applicability::MachineApplicable).unwrap();
+    assert_eq!(&**res.get("a").unwrap(), "let é: u8 = length(x);\n");
+    assert_eq!(&**res.get("b").unwrap(), "fn start() {}\n");
+}
+
+#[test]
+fn applicability() {
+    let diags: [Diagnostic; 2] =
+        [suggest(Span::new("a", 8..14), "length", Applicability::MachineApplicable), suggest(Span::new("a", 15..16), "<expr>", Applicability::HasPlaceholders)];
+    let res: Sources = apply_suggestions(&sources(), &diags, Applicability::MaybeIncorrect).unwrap();
+    assert_eq!(&**res.get("a").unwrap(), "let é = length(x);\n");
+    let res: Sources = apply_suggestions(&sources(), &diags, Applicability::Unspecified).unwrap();
+    assert_eq!(&**res.get("a").unwrap(), "let é = length(<expr>);\n");
+}
+
+#[test]
+fn overlap() {
+    // Overlapping edits are refused...
+    let diags: [Diagnostic; 2] =
+        [suggest(Span::new("a", 8..14), "length", Applicability::MachineApplicable), suggest(Span::new("a", 12..17), "th", Applicability::MachineApplicable)];
+    assert!(matches!(
+        apply_suggestions(&sources(), &diags, Applicability::MachineApplicable),
+        Err(Error::Overlap { first, second }) if first == Span::new("a", 8..14) && second == Span::new("a", 12..17)
+    ));
+
+    // ...as are two different insertions at the same position...
+    let diags: [Diagnostic; 2] =
+        [suggest(Span::empty("a", 5), ": u8", Applicability::MachineApplicable), suggest(Span::empty("a", 5), ": u16", Applicability::MachineApplicable)];
+    assert!(matches!(apply_suggestions(&sources(), &diags, Applicability::MachineApplicable), Err(Error::Overlap { .. })));
+
+    // ...but adjacent and identical edits are fine
+    let diags: [Diagnostic; 3] = [
+        suggest(Span::new("a", 8..14), "length", Applicability::MachineApplicable),
+        suggest(Span::new("a", 14..15), "[", Applicability::MachineApplicable),
+        suggest(Span::new("a", 8..14), "length", Applicability::MachineApplicable),
+    ];
+    let res: Sources = apply_suggestions(&sources(), &diags, Applicability::MachineApplicable).unwrap();
+    assert_eq!(&**res.get("a").unwrap(), "let é = length[x);\n");
+}
+
+#[test]
+fn invalid() {
+    let diags: [Diagnostic; 1] = [suggest(Span::new("c", 0..1), "x", Applicability::MachineApplicable)];
+    assert!(matches!(apply_suggestions(&sources(), &diags, Applicability::MachineApplicable), Err(Error::UnknownSource { .. })));
+    let diags: [Diagnostic; 1] = [suggest(Span::new("b", 10..20), "x", Applicability::MachineApplicable)];
+    assert!(matches!(apply_suggestions(&sources(), &diags, Applicability::MachineApplicable), Err(Error::OutOfBounds { .. })));
+}
+
+#[test]
+fn render() {
+    let diag: Diagnostic = suggest(Span::new("a", 8..14), "length", Applicability::MachineApplicable);
+    let mut buf: Vec<u8> = Vec::new();
+    RustcRenderer.render(&diag, &sources(), &RenderConfig { color: false, width: None }, &mut buf).unwrap();
+    let text: String = String::from_utf8(buf).unwrap();
+    assert!(text.ends_with("help: Try this\n  |\n1 | let é = length(x);\n  |         ~~~~~~\n\n"), "{text}");
+}
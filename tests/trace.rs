@@ -0,0 +1,217 @@
+//  TRACE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 22:44:35
+//  Last edited:
+//    18 Oct 2026, 23:27:53
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests recording traces of the derived parsers.
+//
+
+#![cfg(feature = "trace")]
+
+use ast_toolkit_2::parser::chumsky::prelude::*;
+use ast_toolkit_2::parser::chumsky::text;
+use ast_toolkit_2::parser::trace::{self, Event, Outcome, Trace};
+use ast_toolkit_2::parser::{Error, Parse, Span};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses an identifier.
+///
+/// # Returns
+/// A [`Parser`] producing the identifier's text.
+fn ident() -> impl Parser<char, String, Error = Error> + Clone { text::ident() }
+
+/// Parses a decimal integer.
+///
+/// # Returns
+/// A [`Parser`] producing the integer's value.
+fn int() -> impl Parser<char, u64, Error = Error> + Clone { text::int(10).from_str().unwrapped() }
+
+/// Summarizes the events of a trace.
+///
+/// # Arguments
+/// - `trace`: The [`Trace`] to summarize.
+///
+/// # Returns
+/// The name, depth, positions and outcome of every event.
+fn summary(trace: &Trace) -> Vec<(&'static str, usize, usize, usize, Outcome)> {
+    trace.events().iter().map(|event| (event.name, event.depth, event.start, event.end, event.outcome)).collect()
+}
+
+/// Checks that the outcomes of a trace are consistent with each other.
+///
+/// # Arguments
+/// - `trace`: The [`Trace`] to check.
+fn check(trace: &Trace) {
+    let events: &[Event] = trace.events();
+    for (i, event) in events.iter().enumerate() {
+        assert!(event.entered <= event.exited, "{trace}");
+        assert_eq!(event.error.is_some(), event.outcome != Outcome::Matched, "{trace}");
+        let Some(parent) = event.parent else {
+            assert_eq!(event.depth, 0, "{trace}");
+            continue;
+        };
+        assert!(parent < i && events[parent].depth + 1 == event.depth, "{trace}");
+        // Only failed parsers can have failed children
+        if events[parent].outcome != Outcome::Failed {
+            assert_ne!(event.outcome, Outcome::Failed, "Event {i} failed under a parent that didn't in\n{trace}");
+        }
+    }
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// A list of statements.
+#[derive(Debug, Parse, PartialEq)]
+struct Program(Vec<Stmt>);
+
+/// A single statement.
+#[derive(Debug, Parse, PartialEq)]
+enum Stmt {
+    #[parse(keyword = "let")]
+    Let(#[parse(with = ident)] String, #[parse(punct = "=")] Span, Expr, #[parse(punct = ";")] Span),
+    Expr(Expr, #[parse(punct = ";")] Span),
+}
+
+/// An expression.
+#[derive(Debug, Parse, PartialEq)]
+enum Expr {
+    #[parse(infix, punct = "+", prec = 1)]
+    Add(Box<Expr>, Box<Expr>),
+    Call(#[parse(with = ident)] String, #[parse(delim("(", ")"), sep = ",")] Vec<Expr>),
+    Var(#[parse(with = ident)] String),
+    Int(#[parse(with = int)] u64),
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn events() {
+    let (res, trace) = trace::record(|| Stmt::parse_str("a", "f(x);"));
+    assert!(res.is_ok());
+    assert_eq!(
+        summary(&trace),
+        [
+            ("Stmt", 0, 0, 5, Outcome::Matched),
+            ("Stmt::Let", 1, 0, 0, Outcome::Backtracked),
+            ("Stmt::Expr", 1, 0, 5, Outcome::Matched),
+            ("Expr", 2, 0, 4, Outcome::Matched),
+            ("Expr::Call", 3, 0, 4, Outcome::Matched),
+            ("Expr", 4, 2, 3, Outcome::Matched),
+            ("Expr::Call", 5, 2, 3, Outcome::Backtracked),
+            ("Expr::Var", 5, 2, 3, Outcome::Matched),
+        ]
+    );
+    assert_eq!(trace.events()[6].error.as_deref(), Some("expected '(', found ')'"));
+    assert_eq!(trace.events()[2].parent, Some(0));
+    check(&trace);
+}
+
+#[test]
+fn outcomes() {
+    // A failed parse has a tree of failures to the errors of every alternative
+    let (res, trace) = trace::record(|| Stmt::parse_str("a", "let x = ;"));
+    assert!(res.is_err());
+    let failures: Vec<(&str, usize, usize)> = trace.failures().map(|event| (event.name, event.start, event.end)).collect();
+    assert_eq!(
+        failures,
+        [("Stmt", 0, 8), ("Stmt::Let", 0, 8), ("Expr", 8, 8), ("Expr::Call", 8, 8), ("Expr::Var", 8, 8), ("Expr::Int", 8, 8), ("Stmt::Expr", 0, 4)]
+    );
+    check(&trace);
+
+    // Failures in parsers that were backtracked were backtracked themselves, however deep
+    for text in ["let x = ;", "let x = 1;  f(a, 2);", "x + f(1) + 2;", "f(g(h(;", ""] {
+        let (res, trace) = trace::record(|| Program::parse_str("a", text));
+        check(&trace);
+        if res.is_ok() {
+            assert_eq!(trace.failures().count(), 0, "{text:?}:\n{trace}");
+        }
+    }
+    let (_, trace) = trace::record(|| Program::parse_str("a", "let x = ;"));
+    let let_stmt: &Event = trace.events().iter().find(|event| event.name == "Stmt::Let").unwrap();
+    assert_eq!(let_stmt.outcome, Outcome::Backtracked);
+    assert_eq!(let_stmt.error.as_deref(), Some("expected '0', found ';'"));
+}
+
+#[test]
+fn display() {
+    let (_, trace) = trace::record(|| Stmt::parse_str("a", "x;"));
+    assert_eq!(
+        trace.to_string(),
+        "Stmt @ 0..2: matched
+  Stmt::Let @ 0..0: backtracked (unexpected end of input)
+  Stmt::Expr @ 0..2: matched
+    Expr @ 0..1: matched
+      Expr::Call @ 0..1: backtracked (expected '(', found ';')
+      Expr::Var @ 0..1: matched
+"
+    );
+}
+
+#[test]
+fn recording() {
+    // Nothing is recorded outside of recording
+    assert!(Stmt::parse_str("a", "x;").is_ok());
+    let ((inner, outer), trace) = trace::record(|| {
+        let outer = Expr::parse_str("a", "1").is_ok();
+        // Nested recordings don't end up in the outer one
+        let (inner, trace) = trace::record(|| Stmt::parse_str("a", "x;").is_ok());
+        (inner && trace.events().len() == 6, outer)
+    });
+    assert!(inner && outer);
+    assert_eq!(
+        summary(&trace),
+        [
+            ("Expr", 0, 0, 1, Outcome::Matched),
+            ("Expr::Call", 1, 0, 0, Outcome::Backtracked),
+            ("Expr::Var", 1, 0, 0, Outcome::Backtracked),
+            ("Expr::Int", 1, 0, 1, Outcome::Matched),
+        ]
+    );
+    // Neither are other threads
+    let (_, trace) = trace::record(|| std::thread::spawn(|| Stmt::parse_str("a", "x;").is_ok()).join().unwrap());
+    assert!(trace.events().is_empty());
+
+    // Recordings that panicked stop all the same
+    let (_, trace) = trace::record(|| {
+        let res = std::panic::catch_unwind(|| {
+            trace::record(|| {
+                assert!(Stmt::parse_str("a", "x;").is_ok());
+                panic!("Oops")
+            })
+        });
+        assert!(res.is_err());
+        Expr::parse_str("a", "1").is_ok()
+    });
+    assert_eq!(trace.events().len(), 4, "{trace}");
+    assert_eq!(trace.events()[0].name, "Expr");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json() {
+    use serde_json::Value;
+
+    let (_, trace) = trace::record(|| Stmt::parse_str("a", "x;"));
+    let mut buf: Vec<u8> = Vec::new();
+    trace.write_json(&mut buf).unwrap();
+    let json: Value = serde_json::from_slice(&buf).unwrap();
+    let events: &Vec<Value> = json["traceEvents"].as_array().unwrap();
+    assert_eq!(events.len(), trace.events().len());
+    assert_eq!((&events[0]["name"], &events[0]["ph"], &events[0]["args"]["end"]), (&Value::from("Stmt"), &Value::from("X"), &Value::from(2)));
+    assert_eq!(events[1]["args"]["outcome"], "backtracked");
+    assert_eq!(events[1]["args"]["error"], "unexpected end of input");
+    assert!(events[2]["args"].get("error").is_none());
+}
@@ -0,0 +1,142 @@
+//  UNPARSE.rs
+//    by Lut99
+//
+//  Created:
+//    18 Oct 2026, 22:06:51
+//  Last edited:
+//    18 Oct 2026, 23:32:31
+//  Auto updated?
+//    Yes
+//
+//  Description:
+//!   Tests laying out [`Doc`]s and printing nodes with the [`Unparse`]
+//!   derive.
+//
+
+#![cfg(feature = "parser")]
+
+use ast_toolkit_2::parser::chumsky::prelude::*;
+use ast_toolkit_2::parser::chumsky::text;
+use ast_toolkit_2::parser::doc::Doc;
+use ast_toolkit_2::parser::{Error, Parse, Unparse};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses an identifier.
+///
+/// # Returns
+/// A [`Parser`] producing the identifier's text.
+fn ident() -> impl Parser<char, String, Error = Error> + Clone { text::ident() }
+
+/// Shorthand for a variable.
+///
+/// # Arguments
+/// - `name`: The name of the variable.
+///
+/// # Returns
+/// A boxed [`Expr::Var`].
+fn var(name: &str) -> Box<Expr> { Box::new(Expr::Var(name.into())) }
+
+/// Shorthand for a list of words separated by lines.
+///
+/// # Arguments
+/// - `words`: The words to print.
+///
+/// # Returns
+/// A [`Doc`] printing them.
+fn words(words: &[&'static str]) -> Doc { Doc::join(words.iter().map(|word| Doc::text(*word)), Doc::line()) }
+
+
+
+
+
+/***** HELPERS *****/
+/// An expression with operators that need parentheses.
+#[derive(Debug, Parse, Unparse)]
+enum Expr {
+    #[parse(infix, punct = "+", prec = 1)]
+    Add(Box<Expr>, Box<Expr>),
+    #[parse(infix, punct = "*", prec = 2)]
+    Mul(Box<Expr>, Box<Expr>),
+    #[parse(infix, punct = "^", prec = 3, assoc = right)]
+    Pow(Box<Expr>, Box<Expr>),
+    #[parse(prefix, punct = "-", prec = 4)]
+    Neg(Box<Expr>),
+    Paren(#[parse(delim("(", ")"))] Box<Expr>),
+    Call(#[parse(with = ident)] String, #[parse(delim("(", ")"), sep = ",")] Vec<Expr>),
+    Var(#[parse(with = ident)] String),
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn groups() {
+    let doc: Doc = words(&["aaa", "bbb", "ccc"]).group();
+    assert_eq!(doc.pretty(11), "aaa bbb ccc");
+    assert_eq!(doc.pretty(10), "aaa\nbbb\nccc");
+
+    // Inner groups are kept flat if they fit after breaking the outer one
+    let doc: Doc = Doc::join([words(&["aaa", "bbb"]).group(), words(&["ccc", "ddd"]).group()], Doc::line()).group();
+    assert_eq!(doc.pretty(7), "aaa bbb\nccc ddd");
+    assert_eq!(doc.pretty(6), "aaa\nbbb\nccc\nddd");
+
+    // Hardlines break every group around them
+    let doc: Doc = Doc::concat([Doc::text("a"), Doc::line(), Doc::text("b"), Doc::Hardline, Doc::text("c")]).group();
+    assert_eq!(doc.pretty(80), "a\nb\nc");
+}
+
+#[test]
+fn delimited() {
+    let doc: Doc = Doc::delimited("(", ")", Doc::join([Doc::text("a"), Doc::text("b")], Doc::concat([Doc::Punct(","), Doc::line()])));
+    assert_eq!(doc.pretty(6), "(a, b)");
+    assert_eq!(doc.pretty(5), "(\n    a,\n    b\n)");
+
+    // Braces are spaced from their contents
+    let doc: Doc = Doc::delimited("{", "}", Doc::text("x"));
+    assert_eq!(doc.pretty(80), "{ x }");
+    assert_eq!(doc.pretty(4), "{\n    x\n}");
+}
+
+#[test]
+fn spaces() {
+    let doc: Doc = Doc::join([Doc::Keyword("let"), Doc::text("f"), Doc::Punct("("), Doc::text("x"), Doc::Punct(")"), Doc::Punct(";")], Doc::Space);
+    assert_eq!(doc.pretty(80), "let f(x);");
+    assert_eq!(doc.pretty(1), "let f(x);");
+    assert_eq!(Doc::join([Doc::Keyword("return"), Doc::Punct("(")], Doc::Space).to_string(), "return (");
+
+    // Glued spaces take no room when deciding whether a group fits
+    let call: Doc = Doc::join([Doc::text("f"), Doc::Punct("("), Doc::text("x"), Doc::Punct(")")], Doc::Space);
+    let doc: Doc = Doc::Group(Box::new(Doc::Concat(vec![call, Doc::Line(" "), Doc::text("y")])));
+    assert_eq!(doc.pretty(6), "f(x) y");
+    assert_eq!(doc.pretty(5), "f(x)\ny");
+}
+
+#[test]
+fn operators() {
+    // (a + b) * (c + d) ^ -e
+    let expr = Expr::Mul(Box::new(Expr::Add(var("a"), var("b"))), Box::new(Expr::Pow(Box::new(Expr::Add(var("c"), var("d"))), Box::new(Expr::Neg(var("e"))))));
+    assert_eq!(expr.unparse_to_string(80), "(a + b) * (c + d) ^ -e");
+    // Associativity decides on which side parentheses are needed
+    assert_eq!(Expr::Add(var("a"), Box::new(Expr::Add(var("b"), var("c")))).unparse_to_string(80), "a + (b + c)");
+    assert_eq!(Expr::Add(Box::new(Expr::Add(var("a"), var("b"))), var("c")).unparse_to_string(80), "a + b + c");
+    assert_eq!(Expr::Pow(Box::new(Expr::Pow(var("a"), var("b"))), var("c")).unparse_to_string(80), "(a ^ b) ^ c");
+    assert_eq!(Expr::Pow(var("a"), Box::new(Expr::Pow(var("b"), var("c")))).unparse_to_string(80), "a ^ b ^ c");
+}
+
+#[test]
+fn stable() {
+    // Added parentheses break like parsed ones, so printing is stable at every width
+    let long = |name: &str| var(&name.repeat(6));
+    let expr = Expr::Mul(
+        Box::new(Expr::Add(long("a"), Box::new(Expr::Call("f".into(), vec![*long("b"), *long("c")])))),
+        Box::new(Expr::Add(long("d"), Box::new(Expr::Paren(Box::new(Expr::Add(long("e"), long("f"))))))),
+    );
+    for width in 1..80 {
+        let text: String = expr.unparse_to_string(width);
+        let reparsed: Expr = Expr::parse_str("a", &text).unwrap_or_else(|errs| panic!("Failed to reparse {text:?}: {errs:?}"));
+        assert_eq!(reparsed.unparse_to_string(width), text, "Printing at width {width} is unstable");
+    }
+}